@@ -18,6 +18,14 @@ impl Callback {
             .then(Self::ext(env::current_account_id()).handle_callbacks())
     }
 
+    /// Call function c once for each value asynchronously and count the successful results with
+    /// `handle_callback_results`.
+    pub fn call_many(values: Vec<u8>) -> Promise {
+        require!(!values.is_empty(), "Expected at least one value");
+        let calls = values.into_iter().map(|value| Self::ext(env::current_account_id()).c(value));
+        Promise::join(calls).then(Self::ext(env::current_account_id()).handle_callback_results())
+    }
+
     /// Calls function c with a value that will always succeed
     pub fn a() -> Promise {
         Self::ext(env::current_account_id()).c(A_VALUE)
@@ -59,6 +67,16 @@ impl Callback {
         }
         (b.is_err(), c.is_err(), d.is_err())
     }
+
+    /// Receives the results of all promises, some of which may have failed, and returns the
+    /// number of successful results along with their sum.
+    #[private]
+    pub fn handle_callback_results(
+        #[callback_results] results: Vec<Result<u8, PromiseError>>,
+    ) -> (usize, u32) {
+        let succeeded: Vec<u8> = results.into_iter().flatten().collect();
+        (succeeded.len(), succeeded.iter().map(|&x| x as u32).sum())
+    }
 }
 
 #[cfg(all(test, not(target_arch = "wasm32")))]
@@ -120,6 +138,23 @@ mod tests {
             .await?;
         assert_eq!(res.json::<(bool, bool, bool)>()?, (true, true, true));
 
+        // Following tests the function call where a batch of promises partially fails and all the
+        // results are handled by a single `#[callback_results]` argument.
+
+        // 3 of 5 calls succeed
+        let res = contract
+            .call("call_many")
+            .args_json((vec![1u8, 0, 2, 0, 3],))
+            .max_gas()
+            .transact()
+            .await?;
+        assert_eq!(res.json::<(usize, u32)>()?, (3, 6));
+
+        // All calls fail
+        let res =
+            contract.call("call_many").args_json((vec![0u8, 0],)).max_gas().transact().await?;
+        assert_eq!(res.json::<(usize, u32)>()?, (0, 0));
+
         Ok(())
     }
 }
@@ -1,6 +1,11 @@
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use near_sdk::env;
-use near_sdk::{log, near_bindgen, PromiseOrValue};
+use near_sdk::{log, near_bindgen, Gas, GasWeight, Promise, PromiseError, PromiseOrValue};
+
+/// Static gas attached to each attempt of `flaky`.
+const FLAKY_GAS: Gas = Gas(5_000_000_000_000);
+/// Gas the retry callback needs to schedule another attempt along with its own callback.
+const RETRY_GAS: Gas = Gas(30_000_000_000_000);
 
 #[near_bindgen]
 #[derive(Default, BorshDeserialize, BorshSerialize)]
@@ -30,4 +35,55 @@ impl CrossContract {
         log!("Multiplied {:?}", result.clone());
         result
     }
+
+    /// Calls `flaky` until it succeeds, retrying from the callback up to `max_attempts` times
+    /// as long as the callback received enough gas to schedule another attempt.
+    pub fn call_with_retry(&self, fail_until: u32, max_attempts: u32) -> Promise {
+        Self::schedule_attempt(1, fail_until, max_attempts)
+    }
+
+    /// Schedules an attempt of `flaky` with only static gas, leaving all unused gas to the
+    /// callback so that it can afford to retry.
+    fn schedule_attempt(attempt: u32, fail_until: u32, max_attempts: u32) -> Promise {
+        let account_id = env::current_account_id();
+        Self::ext(account_id.clone())
+            .with_static_gas(FLAKY_GAS)
+            .flaky(attempt, fail_until)
+            .with_unused_gas_ratio(GasWeight(0))
+            .then(
+                Self::ext(account_id)
+                    .with_static_gas(RETRY_GAS)
+                    .retry_callback(attempt, fail_until, max_attempts)
+                    .with_unused_gas_ratio(GasWeight(1)),
+            )
+    }
+
+    /// Fails for every attempt up to `fail_until` and returns the attempt number otherwise.
+    #[private]
+    pub fn flaky(&self, attempt: u32, fail_until: u32) -> u32 {
+        if attempt <= fail_until {
+            env::panic_str("Attempt failed");
+        }
+        attempt
+    }
+
+    /// Returns the attempt that succeeded, or schedules another attempt if the last one failed.
+    #[private]
+    pub fn retry_callback(
+        &self,
+        attempt: u32,
+        fail_until: u32,
+        max_attempts: u32,
+        #[callback_result] result: Result<u32, PromiseError>,
+    ) -> PromiseOrValue<u32> {
+        if let Ok(attempt) = result {
+            return PromiseOrValue::Value(attempt);
+        }
+        let remaining_gas = env::prepaid_gas() - env::used_gas();
+        if attempt >= max_attempts || remaining_gas < FLAKY_GAS + RETRY_GAS {
+            env::panic_str(&format!("Call failed after {} attempts", attempt));
+        }
+        log!("Attempt {} failed, retrying", attempt);
+        Self::schedule_attempt(attempt + 1, fail_until, max_attempts).into()
+    }
 }
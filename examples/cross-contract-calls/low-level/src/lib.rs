@@ -1,6 +1,6 @@
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use near_sdk::serde_json;
-use near_sdk::{env, near_bindgen, require, Gas, PromiseResult};
+use near_sdk::{env, near_bindgen, require, Gas, GasWeight, Promise, PromiseResult};
 
 // Prepaid gas for a single (not inclusive of recursion) `factorial` call.
 const FACTORIAL_CALL_GAS: Gas = Gas(20_000_000_000_000);
@@ -39,6 +39,35 @@ impl CrossContract {
         env::promise_return(promise1);
     }
 
+    /// Same as `factorial`, but lets the runtime distribute the unused gas between the recursive
+    /// call and the callback instead of computing the gas to attach upfront. The recursive call
+    /// gets three quarters of the unused gas, while the callback is guaranteed its static gas and
+    /// receives the remaining quarter.
+    pub fn factorial_weighted(&self, n: u32) {
+        if n <= 1 {
+            env::value_return(&serde_json::to_vec(&1u32).unwrap());
+            return;
+        }
+        let account_id = env::current_account_id();
+        Promise::new(account_id.clone())
+            .function_call_weight(
+                "factorial_weighted".to_string(),
+                serde_json::to_vec(&(n - 1,)).unwrap(),
+                0,
+                Gas(0),
+                GasWeight(3),
+            )
+            .then_weight(
+                account_id,
+                "factorial_mult".to_string(),
+                serde_json::to_vec(&(n,)).unwrap(),
+                0,
+                FACTORIAL_MULT_CALL_GAS,
+                GasWeight(1),
+            )
+            .as_return();
+    }
+
     /// Used for callbacks only. Multiplies current factorial result by the next value. Panics if
     /// it is not called by the contract itself.
     pub fn factorial_mult(&self, n: u32) {
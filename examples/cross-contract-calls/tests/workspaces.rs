@@ -28,3 +28,33 @@ async fn test_factorial(contract_name: &str) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_factorial_weighted() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await?;
+    let contract = worker.dev_deploy(&std::fs::read("res/cross_contract_low_level.wasm")?).await?;
+
+    let n = 5;
+    let res = contract.call("factorial_weighted").args_json((n,)).max_gas().transact().await?;
+    assert!(res.is_success());
+    assert_eq!(res.json::<u32>()?, (1..n + 1).product::<u32>());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_call_with_retry() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await?;
+    let contract = worker.dev_deploy(&std::fs::read("res/cross_contract_high_level.wasm")?).await?;
+
+    // Succeeds on the third attempt.
+    let res = contract.call("call_with_retry").args_json((2, 3)).max_gas().transact().await?;
+    assert!(res.is_success());
+    assert_eq!(res.json::<u32>()?, 3);
+
+    // Gives up after the maximum number of attempts.
+    let res = contract.call("call_with_retry").args_json((3, 3)).max_gas().transact().await?;
+    assert!(res.is_failure());
+
+    Ok(())
+}
@@ -92,9 +92,23 @@ impl Contract {
     fn on_tokens_burned(&mut self, account_id: AccountId, amount: Balance) {
         log!("Account @{} burned {}", account_id, amount);
     }
+
+    fn on_transfer_unused(
+        &mut self,
+        sender_id: AccountId,
+        receiver_id: AccountId,
+        amount: Balance,
+    ) {
+        log!("Account @{} refunded {} to @{}", receiver_id, amount, sender_id);
+    }
 }
 
-near_contract_standards::impl_fungible_token_core!(Contract, token, on_tokens_burned);
+near_contract_standards::impl_fungible_token_core!(
+    Contract,
+    token,
+    on_tokens_burned = on_tokens_burned,
+    on_transfer_unused = on_transfer_unused
+);
 near_contract_standards::impl_fungible_token_storage!(Contract, token, on_account_closed);
 
 #[near_bindgen]
@@ -106,8 +120,8 @@ impl FungibleTokenMetadataProvider for Contract {
 
 #[cfg(all(test, not(target_arch = "wasm32")))]
 mod tests {
-    use near_sdk::test_utils::{accounts, VMContextBuilder};
-    use near_sdk::{testing_env, Balance};
+    use near_sdk::test_utils::{accounts, get_logs, VMContextBuilder};
+    use near_sdk::{testing_env, Balance, PromiseResult};
 
     use super::*;
 
@@ -163,11 +177,45 @@ mod tests {
 
         testing_env!(context
             .storage_usage(env::storage_usage())
-            .account_balance(env::account_balance())
+            .account_balance(env::account_balance().as_yoctonear())
             .is_view(true)
             .attached_deposit(0)
             .build());
         assert_eq!(contract.ft_balance_of(accounts(2)).0, (TOTAL_SUPPLY - transfer_amount));
         assert_eq!(contract.ft_balance_of(accounts(1)).0, transfer_amount);
     }
+
+    #[test]
+    fn test_resolve_transfer_partially_unused() {
+        let mut context = get_context(accounts(2));
+        testing_env!(context.build());
+        let mut contract = Contract::new_default_meta(accounts(2), TOTAL_SUPPLY.into());
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(contract.storage_balance_bounds().min.into())
+            .predecessor_account_id(accounts(1))
+            .build());
+        contract.storage_deposit(None, None);
+
+        // The receiver already got the tokens and only used 60 of them.
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .predecessor_account_id(accounts(2))
+            .build());
+        contract.ft_transfer(accounts(1), 100.into(), None);
+        testing_env!(
+            context.attached_deposit(0).predecessor_account_id(accounts(0)).build(),
+            near_sdk::VMConfig::test(),
+            near_sdk::RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Successful(b"\"40\"".to_vec())],
+        );
+        let used = contract.ft_resolve_transfer(accounts(2), accounts(1), 100.into());
+
+        assert_eq!(used.0, 60);
+        assert_eq!(contract.ft_balance_of(accounts(1)).0, 60);
+        assert_eq!(contract.ft_balance_of(accounts(2)).0, TOTAL_SUPPLY - 60);
+        assert_eq!(get_logs().last().unwrap(), "Account @bob refunded 40 to @charlie");
+    }
 }
@@ -0,0 +1,7 @@
+#!/bin/bash
+TARGET="${CARGO_TARGET_DIR:-../../target}"
+set -e
+cd "$(dirname $0)"
+cargo build --target wasm32-unknown-unknown --release
+cp $TARGET/wasm32-unknown-unknown/release/multi_token.wasm ./res/
+#wasm-opt -Oz --output ./res/multi_token.wasm ./res/multi_token.wasm
@@ -0,0 +1,214 @@
+/*!
+Multi Token implementation with JSON serialization.
+NOTES:
+  - The maximum balance value is limited by U128 (2**128 - 1).
+  - JSON calls should pass U128 as a base-10 string. E.g. "100".
+  - Only the owner of the contract can mint new tokens or increase the supply of existing ones.
+  - Approval Management is not implemented, so tokens can only be transferred by their holders.
+  - To prevent the deployed contract from being modified or deleted, it should not have any access
+    keys on its account.
+*/
+use near_contract_standards::multi_token::metadata::{
+    MTContractMetadata, MultiTokenMetadataProvider, TokenMetadata, MT_METADATA_SPEC,
+};
+use near_contract_standards::multi_token::MultiToken;
+use near_contract_standards::multi_token::{Token, TokenId};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::LazyOption;
+use near_sdk::json_types::U128;
+use near_sdk::{
+    env, near_bindgen, require, AccountId, BorshStorageKey, PanicOnDefault, PromiseOrValue,
+};
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
+pub struct Contract {
+    tokens: MultiToken,
+    metadata: LazyOption<MTContractMetadata>,
+}
+
+#[derive(BorshSerialize, BorshStorageKey)]
+enum StorageKey {
+    TotalSupply,
+    Balances,
+    Metadata,
+    TokenMetadata,
+    Enumeration,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Initializes the contract owned by `owner_id` with
+    /// default metadata (for example purposes only).
+    #[init]
+    pub fn new_default_meta(owner_id: AccountId) -> Self {
+        Self::new(
+            owner_id,
+            MTContractMetadata {
+                spec: MT_METADATA_SPEC.to_string(),
+                name: "Example NEAR multi token".to_string(),
+            },
+        )
+    }
+
+    #[init]
+    pub fn new(owner_id: AccountId, metadata: MTContractMetadata) -> Self {
+        require!(!env::state_exists(), "Already initialized");
+        metadata.assert_valid();
+        Self {
+            tokens: MultiToken::new(
+                StorageKey::TotalSupply,
+                StorageKey::Balances,
+                owner_id,
+                Some(StorageKey::TokenMetadata),
+                Some(StorageKey::Enumeration),
+            ),
+            metadata: LazyOption::new(StorageKey::Metadata, Some(&metadata)),
+        }
+    }
+
+    /// Mint `amount` of the token with ID=`token_id` to `token_owner_id`.
+    ///
+    /// Since this example implements metadata, `token_metadata` is required the first time a
+    /// token is minted and ignored afterwards.
+    pub fn mt_mint(
+        &mut self,
+        token_id: TokenId,
+        token_owner_id: AccountId,
+        amount: U128,
+        token_metadata: Option<TokenMetadata>,
+    ) -> Token {
+        assert_eq!(env::predecessor_account_id(), self.tokens.owner_id, "Unauthorized");
+        if let Some(token_metadata) = &token_metadata {
+            token_metadata.assert_valid();
+        }
+        self.tokens.internal_mint(token_id, token_owner_id, amount.into(), token_metadata)
+    }
+
+    /// Burn `amount` of the token with ID=`token_id` held by the caller.
+    #[payable]
+    pub fn mt_burn(&mut self, token_id: TokenId, amount: U128, memo: Option<String>) {
+        near_sdk::assert_one_yocto();
+        self.tokens.internal_burn(&token_id, &env::predecessor_account_id(), amount.into(), memo)
+    }
+}
+
+near_contract_standards::impl_multi_token_core!(Contract, tokens);
+near_contract_standards::impl_multi_token_enumeration!(Contract, tokens);
+
+#[near_bindgen]
+impl MultiTokenMetadataProvider for Contract {
+    fn mt_metadata_contract(&self) -> MTContractMetadata {
+        self.metadata.get().unwrap()
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::testing_env;
+
+    use super::*;
+
+    fn get_context(predecessor_account_id: AccountId) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .current_account_id(accounts(0))
+            .signer_account_id(predecessor_account_id.clone())
+            .predecessor_account_id(predecessor_account_id);
+        builder
+    }
+
+    fn sample_token_metadata() -> TokenMetadata {
+        TokenMetadata {
+            title: Some("Gold".into()),
+            description: Some("Shiny in-game currency".into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let contract = Contract::new_default_meta(accounts(1));
+        testing_env!(context.is_view(true).build());
+        assert_eq!(contract.mt_token(vec!["1".to_string()]), vec![None]);
+        assert_eq!(contract.mt_metadata_contract().spec, MT_METADATA_SPEC);
+    }
+
+    #[test]
+    fn test_mint() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = Contract::new_default_meta(accounts(0));
+
+        let token = contract.mt_mint(
+            "gold".to_string(),
+            accounts(1),
+            U128(100),
+            Some(sample_token_metadata()),
+        );
+        assert_eq!(token.supply, U128(100));
+        assert_eq!(token.metadata.unwrap(), sample_token_metadata());
+
+        let token = contract.mt_mint("gold".to_string(), accounts(2), U128(10), None);
+        assert_eq!(token.supply, U128(110));
+        assert_eq!(contract.mt_balance_of(accounts(2), "gold".to_string()), U128(10));
+    }
+
+    #[test]
+    #[should_panic(expected = "Unauthorized")]
+    fn test_mint_unauthorized() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = Contract::new_default_meta(accounts(1));
+        contract.mt_mint("gold".to_string(), accounts(1), U128(100), Some(sample_token_metadata()));
+    }
+
+    #[test]
+    fn test_batch_transfer() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = Contract::new_default_meta(accounts(0));
+        contract.mt_mint("gold".to_string(), accounts(1), U128(100), Some(sample_token_metadata()));
+        contract.mt_mint(
+            "silver".to_string(),
+            accounts(1),
+            U128(50),
+            Some(sample_token_metadata()),
+        );
+
+        testing_env!(context.attached_deposit(1).predecessor_account_id(accounts(1)).build());
+        contract.mt_batch_transfer(
+            accounts(2),
+            vec!["gold".to_string(), "silver".to_string()],
+            vec![U128(30), U128(50)],
+            None,
+            None,
+        );
+
+        testing_env!(context.attached_deposit(0).is_view(true).build());
+        let token_ids = vec!["gold".to_string(), "silver".to_string()];
+        assert_eq!(
+            contract.mt_batch_balance_of(accounts(1), token_ids.clone()),
+            vec![U128(70), U128(0)]
+        );
+        assert_eq!(contract.mt_batch_balance_of(accounts(2), token_ids), vec![U128(30), U128(50)]);
+        assert_eq!(contract.mt_tokens_for_owner(accounts(1), None, None).len(), 1);
+        assert_eq!(contract.mt_tokens_for_owner(accounts(2), None, None).len(), 2);
+    }
+
+    #[test]
+    fn test_burn() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = Contract::new_default_meta(accounts(0));
+        contract.mt_mint("gold".to_string(), accounts(1), U128(100), Some(sample_token_metadata()));
+
+        testing_env!(context.attached_deposit(1).predecessor_account_id(accounts(1)).build());
+        contract.mt_burn("gold".to_string(), U128(40), None);
+        assert_eq!(contract.mt_supply("gold".to_string()), Some(U128(60)));
+        assert_eq!(contract.mt_balance_of(accounts(1), "gold".to_string()), U128(60));
+    }
+}
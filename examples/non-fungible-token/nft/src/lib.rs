@@ -207,7 +207,7 @@ mod tests {
 
         testing_env!(context
             .storage_usage(env::storage_usage())
-            .account_balance(env::account_balance())
+            .account_balance(env::account_balance().as_yoctonear())
             .is_view(true)
             .attached_deposit(0)
             .build());
@@ -245,7 +245,7 @@ mod tests {
 
         testing_env!(context
             .storage_usage(env::storage_usage())
-            .account_balance(env::account_balance())
+            .account_balance(env::account_balance().as_yoctonear())
             .is_view(true)
             .attached_deposit(0)
             .build());
@@ -283,7 +283,7 @@ mod tests {
         contract.nft_revoke(token_id.clone(), accounts(1));
         testing_env!(context
             .storage_usage(env::storage_usage())
-            .account_balance(env::account_balance())
+            .account_balance(env::account_balance().as_yoctonear())
             .is_view(true)
             .attached_deposit(0)
             .build());
@@ -321,7 +321,7 @@ mod tests {
         contract.nft_revoke_all(token_id.clone());
         testing_env!(context
             .storage_usage(env::storage_usage())
-            .account_balance(env::account_balance())
+            .account_balance(env::account_balance().as_yoctonear())
             .is_view(true)
             .attached_deposit(0)
             .build());
@@ -1,5 +1,5 @@
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::{env, near_bindgen};
+use near_sdk::{env, near_bindgen, AccountId};
 
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize)]
@@ -18,6 +18,7 @@ impl TestContract {
         Self {}
     }
 
+    #[private]
     #[init(ignore_state)]
     pub fn migrate_state() -> Self {
         #[derive(BorshDeserialize)]
@@ -30,6 +31,12 @@ impl TestContract {
         Self {}
     }
 
+    /// Callable by the contract itself and by a fixed set of accounts.
+    #[private(allow = ["alice.near", "bob.near"])]
+    pub fn allow_listed_callback(&self) -> AccountId {
+        env::predecessor_account_id()
+    }
+
     pub fn test_panic_macro(&mut self) {
         panic!("PANIC!");
     }
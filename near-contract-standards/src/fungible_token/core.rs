@@ -53,4 +53,8 @@ pub trait FungibleTokenCore {
 
     /// Returns the balance of the account. If the account doesn't exist must returns `"0"`.
     fn ft_balance_of(&self, account_id: AccountId) -> U128;
+
+    /// Returns the balances of the given accounts in the same order, so clients can query many
+    /// accounts in one call. Accounts that don't exist have a balance of `"0"`.
+    fn ft_balance_of_batch(&self, account_ids: Vec<AccountId>) -> Vec<U128>;
 }
@@ -1,5 +1,5 @@
 use crate::fungible_token::core::FungibleTokenCore;
-use crate::fungible_token::events::{FtBurn, FtTransfer};
+use crate::fungible_token::events::{FtBurn, FtMint, FtTransfer};
 use crate::fungible_token::receiver::ext_ft_receiver;
 use crate::fungible_token::resolver::{ext_ft_resolver, FungibleTokenResolver};
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
@@ -10,11 +10,47 @@ use near_sdk::{
     PromiseResult, StorageUsage,
 };
 
-const GAS_FOR_RESOLVE_TRANSFER: Gas = Gas(5_000_000_000_000);
-const GAS_FOR_FT_TRANSFER_CALL: Gas = Gas(25_000_000_000_000 + GAS_FOR_RESOLVE_TRANSFER.0);
+/// Gas attached to `ft_resolve_transfer` by default.
+pub const GAS_FOR_RESOLVE_TRANSFER: Gas = Gas(5_000_000_000_000);
+/// Gas attached to the receiver's `ft_on_transfer` by default with
+/// [`FungibleToken::ft_transfer_call_with_gas`].
+pub const GAS_FOR_FT_ON_TRANSFER: Gas = Gas(10_000_000_000_000);
+/// Gas needed by `ft_transfer_call` itself, besides the gas attached to the calls it schedules.
+const GAS_FOR_FT_TRANSFER_CALL_EXECUTION: Gas = Gas(25_000_000_000_000);
+const GAS_FOR_FT_TRANSFER_CALL: Gas =
+    Gas(GAS_FOR_FT_TRANSFER_CALL_EXECUTION.0 + GAS_FOR_RESOLVE_TRANSFER.0);
 
 const ERR_TOTAL_SUPPLY_OVERFLOW: &str = "Total supply overflow";
 
+/// Gas split between the calls scheduled by [`FungibleToken::ft_transfer_call_with_gas`].
+///
+/// Each call gets its static gas, and the gas left once the transfer is done is distributed
+/// between them in proportion to their weights. By default, the receiver's `ft_on_transfer` gets
+/// [`GAS_FOR_FT_ON_TRANSFER`] and all of the gas left, and `ft_resolve_transfer` gets
+/// [`GAS_FOR_RESOLVE_TRANSFER`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransferCallGas {
+    /// Static gas attached to the receiver's `ft_on_transfer`.
+    pub on_transfer: Gas,
+    /// Weight of `ft_on_transfer` in the distribution of the gas left.
+    pub on_transfer_weight: u64,
+    /// Static gas attached to `ft_resolve_transfer`.
+    pub resolve_transfer: Gas,
+    /// Weight of `ft_resolve_transfer` in the distribution of the gas left.
+    pub resolve_transfer_weight: u64,
+}
+
+impl Default for TransferCallGas {
+    fn default() -> Self {
+        Self {
+            on_transfer: GAS_FOR_FT_ON_TRANSFER,
+            on_transfer_weight: 1,
+            resolve_transfer: GAS_FOR_RESOLVE_TRANSFER,
+            resolve_transfer_weight: 0,
+        }
+    }
+}
+
 /// Implementation of a FungibleToken standard.
 /// Allows to include NEP-141 compatible token to any contract.
 /// There are next traits that any contract may implement:
@@ -110,11 +146,81 @@ impl FungibleToken {
         .emit();
     }
 
+    /// Mints `amount` of new tokens to the registered `account_id`, increasing the total supply,
+    /// and emits an [`FtMint`] event.
+    pub fn internal_mint(&mut self, account_id: &AccountId, amount: Balance, memo: Option<String>) {
+        require!(amount > 0, "The amount should be a positive number");
+        self.internal_deposit(account_id, amount);
+        FtMint { owner_id: account_id, amount: &U128(amount), memo: memo.as_deref() }.emit();
+    }
+
+    /// Burns `amount` of tokens from the registered `account_id`, decreasing the total supply,
+    /// and emits an [`FtBurn`] event.
+    ///
+    /// Panics if the account balance is lower than `amount`.
+    pub fn internal_burn(&mut self, account_id: &AccountId, amount: Balance, memo: Option<String>) {
+        require!(amount > 0, "The amount should be a positive number");
+        require!(
+            self.internal_unwrap_balance_of(account_id) >= amount,
+            "The account doesn't have enough balance to burn"
+        );
+        self.internal_withdraw(account_id, amount);
+        FtBurn { owner_id: account_id, amount: &U128(amount), memo: memo.as_deref() }.emit();
+    }
+
     pub fn internal_register_account(&mut self, account_id: &AccountId) {
         if self.accounts.insert(account_id, &0).is_some() {
             env::panic_str("The account is already registered");
         }
     }
+
+    /// Same as [`FungibleTokenCore::ft_transfer_call`], with the gas attached to the receiver's
+    /// `ft_on_transfer` and to `ft_resolve_transfer` set by `gas` instead of giving all of the
+    /// prepaid gas to the receiver, e.g. to leave more gas to the resolution.
+    ///
+    /// Panics if the prepaid gas doesn't cover the static gas of both calls and the execution of
+    /// the transfer.
+    pub fn ft_transfer_call_with_gas(
+        &mut self,
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+        msg: String,
+        gas: TransferCallGas,
+    ) -> PromiseOrValue<U128> {
+        assert_one_yocto();
+        let required_gas = GAS_FOR_FT_TRANSFER_CALL_EXECUTION
+            .0
+            .checked_add(gas.on_transfer.0)
+            .and_then(|required| required.checked_add(gas.resolve_transfer.0))
+            .unwrap_or_else(|| env::panic_str("Prepaid gas overflow"));
+        require!(env::prepaid_gas().0 >= required_gas, "More gas is required");
+        self.internal_transfer_call(receiver_id, amount.into(), memo, msg, gas)
+    }
+
+    fn internal_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        amount: Balance,
+        memo: Option<String>,
+        msg: String,
+        gas: TransferCallGas,
+    ) -> PromiseOrValue<U128> {
+        let sender_id = env::predecessor_account_id();
+        self.internal_transfer(&sender_id, &receiver_id, amount, memo);
+        // Initiating receiver's call and the callback
+        ext_ft_receiver::ext(receiver_id.clone())
+            .with_static_gas(gas.on_transfer)
+            .with_unused_gas_weight(gas.on_transfer_weight)
+            .ft_on_transfer(sender_id.clone(), amount.into(), msg)
+            .then(
+                ext_ft_resolver::ext(env::current_account_id())
+                    .with_static_gas(gas.resolve_transfer)
+                    .with_unused_gas_weight(gas.resolve_transfer_weight)
+                    .ft_resolve_transfer(sender_id, receiver_id, amount.into()),
+            )
+            .into()
+    }
 }
 
 impl FungibleTokenCore for FungibleToken {
@@ -134,23 +240,17 @@ impl FungibleTokenCore for FungibleToken {
     ) -> PromiseOrValue<U128> {
         assert_one_yocto();
         require!(env::prepaid_gas() > GAS_FOR_FT_TRANSFER_CALL, "More gas is required");
-        let sender_id = env::predecessor_account_id();
-        let amount: Balance = amount.into();
-        self.internal_transfer(&sender_id, &receiver_id, amount, memo);
         let receiver_gas = env::prepaid_gas()
             .0
             .checked_sub(GAS_FOR_FT_TRANSFER_CALL.0)
             .unwrap_or_else(|| env::panic_str("Prepaid gas overflow"));
-        // Initiating receiver's call and the callback
-        ext_ft_receiver::ext(receiver_id.clone())
-            .with_static_gas(receiver_gas.into())
-            .ft_on_transfer(sender_id.clone(), amount.into(), msg)
-            .then(
-                ext_ft_resolver::ext(env::current_account_id())
-                    .with_static_gas(GAS_FOR_RESOLVE_TRANSFER)
-                    .ft_resolve_transfer(sender_id, receiver_id, amount.into()),
-            )
-            .into()
+        let gas = TransferCallGas {
+            on_transfer: receiver_gas.into(),
+            on_transfer_weight: 1,
+            resolve_transfer: GAS_FOR_RESOLVE_TRANSFER,
+            resolve_transfer_weight: 1,
+        };
+        self.internal_transfer_call(receiver_id, amount.into(), memo, msg, gas)
     }
 
     fn ft_total_supply(&self) -> U128 {
@@ -160,6 +260,10 @@ impl FungibleTokenCore for FungibleToken {
     fn ft_balance_of(&self, account_id: AccountId) -> U128 {
         self.accounts.get(&account_id).unwrap_or(0).into()
     }
+
+    fn ft_balance_of_batch(&self, account_ids: Vec<AccountId>) -> Vec<U128> {
+        account_ids.into_iter().map(|account_id| self.ft_balance_of(account_id)).collect()
+    }
 }
 
 impl FungibleToken {
@@ -245,3 +349,163 @@ impl FungibleTokenResolver for FungibleToken {
         self.internal_ft_resolve_transfer(&sender_id, receiver_id, amount).0.into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::{get_created_receipts, get_logs, VMContextBuilder};
+    use near_sdk::testing_env;
+
+    fn alice() -> AccountId {
+        "alice".parse().unwrap()
+    }
+
+    fn bob() -> AccountId {
+        "bob".parse().unwrap()
+    }
+
+    fn setup() -> FungibleToken {
+        testing_env!(VMContextBuilder::new().build());
+        let mut token = FungibleToken::new(b"t");
+        token.internal_register_account(&alice());
+        token.internal_register_account(&bob());
+        token
+    }
+
+    #[test]
+    fn internal_mint() {
+        let mut token = setup();
+        token.internal_mint(&alice(), 100, None);
+        token.internal_mint(&bob(), 50, Some("airdrop".to_string()));
+        assert_eq!(token.internal_unwrap_balance_of(&alice()), 100);
+        assert_eq!(token.internal_unwrap_balance_of(&bob()), 50);
+        assert_eq!(token.total_supply, 150);
+        assert_eq!(
+            get_logs(),
+            [
+                r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_mint","data":[{"owner_id":"alice","amount":"100"}]}"#,
+                r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_mint","data":[{"owner_id":"bob","amount":"50","memo":"airdrop"}]}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn internal_burn() {
+        let mut token = setup();
+        token.internal_mint(&alice(), 100, None);
+        token.internal_mint(&bob(), 50, None);
+        token.internal_burn(&alice(), 30, Some("redeem".to_string()));
+        token.internal_burn(&bob(), 50, None);
+        assert_eq!(token.internal_unwrap_balance_of(&alice()), 70);
+        assert_eq!(token.internal_unwrap_balance_of(&bob()), 0);
+        assert_eq!(token.total_supply, 70);
+        assert_eq!(
+            get_logs()[2..],
+            [
+                r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_burn","data":[{"owner_id":"alice","amount":"30","memo":"redeem"}]}"#,
+                r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_burn","data":[{"owner_id":"bob","amount":"50"}]}"#,
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "The account doesn't have enough balance to burn")]
+    fn internal_burn_exceeds_balance() {
+        let mut token = setup();
+        token.internal_mint(&alice(), 100, None);
+        token.internal_burn(&alice(), 101, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Total supply overflow")]
+    fn internal_mint_total_supply_overflow() {
+        let mut token = setup();
+        token.internal_mint(&alice(), Balance::MAX, None);
+        token.internal_mint(&bob(), 1, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "The account bob.near is not registered")]
+    fn internal_mint_unregistered() {
+        let mut token = setup();
+        token.internal_mint(&"bob.near".parse().unwrap(), 1, None);
+    }
+
+    fn transfer_call_receipts(gas: Option<TransferCallGas>) -> Vec<(String, Gas)> {
+        let mut token = setup();
+        token.internal_mint(&alice(), 100, None);
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .attached_deposit(1)
+            .prepaid_gas(Gas(100_000_000_000_000))
+            .build());
+        let _ = match gas {
+            Some(gas) => token.ft_transfer_call_with_gas(bob(), 10.into(), None, "".into(), gas),
+            None => token.ft_transfer_call(bob(), 10.into(), None, "".into()),
+        };
+        assert_eq!(token.internal_unwrap_balance_of(&bob()), 10);
+        get_created_receipts()
+            .iter()
+            .flat_map(|receipt| &receipt.actions)
+            .map(|action| (action.function_name().unwrap().to_string(), action.gas().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn transfer_call_gives_remaining_gas_to_receiver() {
+        assert_eq!(
+            transfer_call_receipts(None),
+            [
+                ("ft_on_transfer".to_string(), Gas(70_000_000_000_000)),
+                ("ft_resolve_transfer".to_string(), GAS_FOR_RESOLVE_TRANSFER),
+            ]
+        );
+    }
+
+    #[test]
+    fn transfer_call_with_default_gas() {
+        assert_eq!(
+            transfer_call_receipts(Some(TransferCallGas::default())),
+            [
+                ("ft_on_transfer".to_string(), GAS_FOR_FT_ON_TRANSFER),
+                ("ft_resolve_transfer".to_string(), GAS_FOR_RESOLVE_TRANSFER),
+            ]
+        );
+    }
+
+    #[test]
+    fn transfer_call_with_custom_gas() {
+        let gas = TransferCallGas {
+            on_transfer: Gas(50_000_000_000_000),
+            resolve_transfer: Gas(20_000_000_000_000),
+            ..Default::default()
+        };
+        assert_eq!(
+            transfer_call_receipts(Some(gas)),
+            [
+                ("ft_on_transfer".to_string(), gas.on_transfer),
+                ("ft_resolve_transfer".to_string(), gas.resolve_transfer),
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "More gas is required")]
+    fn transfer_call_gas_exceeds_prepaid() {
+        let gas = TransferCallGas { on_transfer: Gas(75_000_000_000_000), ..Default::default() };
+        transfer_call_receipts(Some(gas));
+    }
+
+    #[test]
+    fn ft_balance_of_batch() {
+        let mut token = setup();
+        token.internal_mint(&alice(), 100, None);
+        let carol: AccountId = "carol".parse().unwrap();
+
+        assert_eq!(
+            token.ft_balance_of_batch(vec![bob(), alice(), carol, alice()]),
+            [U128(0), U128(100), U128(0), U128(100)]
+        );
+        assert!(token.ft_balance_of_batch(vec![]).is_empty());
+    }
+}
@@ -1,8 +1,20 @@
 /// The core methods for a basic fungible token. Extension standards may be
 /// added in addition to this macro.
+/// Takes name of the Contract struct, the inner field for the token and optional method names to
+/// call:
+/// - `on_tokens_burned = method`, with `(sender_id, burned_amount)` when tokens are burned because
+/// the sender's account was closed. It can also be passed without the name of the argument.
+/// - `on_transfer_unused = method`, with `(sender_id, receiver_id, unused_amount)` when the receiver
+/// of an `ft_transfer_call` didn't use all of the tokens and the unused amount was refunded.
 #[macro_export]
 macro_rules! impl_fungible_token_core {
-    ($contract: ident, $token: ident $(, $on_tokens_burned_fn:ident)?) => {
+    (@receiver_id $receiver_id:ident) => {
+        $receiver_id
+    };
+    (@receiver_id $receiver_id:ident, $on_transfer_unused_fn:ident) => {
+        $receiver_id.clone()
+    };
+    ($contract: ident, $token: ident $(, on_tokens_burned = $on_tokens_burned_fn:ident)? $(, on_transfer_unused = $on_transfer_unused_fn:ident)? $(,)?) => {
         use $crate::fungible_token::core::FungibleTokenCore;
         use $crate::fungible_token::resolver::FungibleTokenResolver;
 
@@ -36,6 +48,10 @@ macro_rules! impl_fungible_token_core {
             fn ft_balance_of(&self, account_id: AccountId) -> U128 {
                 self.$token.ft_balance_of(account_id)
             }
+
+            fn ft_balance_of_batch(&self, account_ids: Vec<AccountId>) -> Vec<U128> {
+                self.$token.ft_balance_of_batch(account_ids)
+            }
         }
 
         #[near_bindgen]
@@ -47,15 +63,27 @@ macro_rules! impl_fungible_token_core {
                 receiver_id: AccountId,
                 amount: U128,
             ) -> U128 {
-                let (used_amount, burned_amount) =
-                    self.$token.internal_ft_resolve_transfer(&sender_id, receiver_id, amount);
+                let (used_amount, burned_amount) = self.$token.internal_ft_resolve_transfer(
+                    &sender_id,
+                    $crate::impl_fungible_token_core!(@receiver_id receiver_id $(, $on_transfer_unused_fn)?),
+                    amount,
+                );
                 if burned_amount > 0 {
                     $(self.$on_tokens_burned_fn(sender_id, burned_amount);)?
-                }
+                } $(else {
+                    // Tokens are only refunded to the sender if none were burned.
+                    let unused_amount = amount.0 - used_amount;
+                    if unused_amount > 0 {
+                        self.$on_transfer_unused_fn(sender_id, receiver_id, unused_amount);
+                    }
+                })?
                 used_amount.into()
             }
         }
     };
+    ($contract: ident, $token: ident, $on_tokens_burned_fn:ident $(,)?) => {
+        $crate::impl_fungible_token_core!($contract, $token, on_tokens_burned = $on_tokens_burned_fn);
+    };
 }
 
 /// Ensures that when fungible token storage grows by collections adding entries,
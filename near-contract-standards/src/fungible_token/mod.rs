@@ -7,5 +7,5 @@ pub mod receiver;
 pub mod resolver;
 pub mod storage_impl;
 
-pub use core_impl::FungibleToken;
+pub use core_impl::{FungibleToken, TransferCallGas};
 pub use macros::*;
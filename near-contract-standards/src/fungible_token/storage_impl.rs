@@ -40,8 +40,13 @@ impl FungibleToken {
 }
 
 impl StorageManagement for FungibleToken {
-    // `registration_only` doesn't affect the implementation for vanilla fungible token.
-    #[allow(unused_variables)]
+    /// Registers `account_id` (or the predecessor) and refunds the part of the attached deposit
+    /// that is not needed to cover the storage of the account.
+    ///
+    /// With `registration_only` set to `true`, only the minimum storage balance is charged.
+    /// Otherwise up to the maximum storage balance is charged, which for the vanilla fungible
+    /// token is the same amount, as the storage used by an account never changes.
+    /// If the account is already registered, the whole deposit is refunded.
     fn storage_deposit(
         &mut self,
         account_id: Option<AccountId>,
@@ -55,13 +60,19 @@ impl StorageManagement for FungibleToken {
                 Promise::new(env::predecessor_account_id()).transfer(amount);
             }
         } else {
-            let min_balance = self.storage_balance_bounds().min.0;
+            let bounds = self.storage_balance_bounds();
+            let min_balance = bounds.min.0;
             if amount < min_balance {
                 env::panic_str("The attached deposit is less than the minimum storage balance");
             }
 
+            let charge = if registration_only.unwrap_or(false) {
+                min_balance
+            } else {
+                bounds.max.map_or(amount, |max| std::cmp::min(amount, max.0))
+            };
             self.internal_register_account(&account_id);
-            let refund = amount - min_balance;
+            let refund = amount - charge;
             if refund > 0 {
                 Promise::new(env::predecessor_account_id()).transfer(refund);
             }
@@ -109,3 +120,78 @@ impl StorageManagement for FungibleToken {
         self.internal_storage_balance_of(&account_id)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::mock::VmAction;
+    use near_sdk::test_utils::{accounts, get_created_receipts, VMContextBuilder};
+    use near_sdk::testing_env;
+
+    fn setup() -> (FungibleToken, Balance) {
+        testing_env!(VMContextBuilder::new().build());
+        let token = FungibleToken::new(b"t".to_vec());
+        let min = token.storage_balance_bounds().min.0;
+        (token, min)
+    }
+
+    fn deposit(token: &mut FungibleToken, amount: Balance, registration_only: Option<bool>) {
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(accounts(1))
+            .attached_deposit(amount)
+            .build());
+        let balance = token.storage_deposit(Some(accounts(2)), registration_only);
+        assert_eq!(balance.total, token.storage_balance_bounds().min);
+        assert_eq!(balance.available.0, 0);
+    }
+
+    fn refunds() -> Vec<(AccountId, Balance)> {
+        get_created_receipts()
+            .into_iter()
+            .flat_map(|receipt| {
+                let receiver_id = receipt.receiver_id;
+                receipt.actions.into_iter().filter_map(move |action| match action {
+                    VmAction::Transfer { deposit } => Some((receiver_id.clone(), deposit)),
+                    _ => None,
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn storage_deposit_exact() {
+        let (mut token, min) = setup();
+        deposit(&mut token, min, None);
+        assert!(token.accounts.contains_key(&accounts(2)));
+        assert!(refunds().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "The attached deposit is less than the minimum storage balance")]
+    fn storage_deposit_under() {
+        let (mut token, min) = setup();
+        deposit(&mut token, min - 1, None);
+    }
+
+    #[test]
+    fn storage_deposit_over_refunds_excess() {
+        let (mut token, min) = setup();
+        deposit(&mut token, min + 100, None);
+        assert_eq!(refunds(), vec![(accounts(1), 100)]);
+    }
+
+    #[test]
+    fn storage_deposit_registration_only_charges_min() {
+        let (mut token, min) = setup();
+        deposit(&mut token, min * 2, Some(true));
+        assert_eq!(refunds(), vec![(accounts(1), min)]);
+    }
+
+    #[test]
+    fn storage_deposit_registered_refunds_all() {
+        let (mut token, min) = setup();
+        deposit(&mut token, min, None);
+        deposit(&mut token, 42, None);
+        assert_eq!(refunds(), vec![(accounts(1), 42)]);
+    }
+}
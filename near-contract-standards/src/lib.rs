@@ -1,5 +1,7 @@
 /// Fungible tokens as described in [by the spec](https://nomicon.io/Standards/FungibleToken/README.html).
 pub mod fungible_token;
+/// Multi tokens as described in [by the spec](https://nomicon.io/Standards/Tokens/MultiToken/Core).
+pub mod multi_token;
 /// Non-fungible tokens as described in [by the spec](https://nomicon.io/Standards/NonFungibleToken/README.html).
 pub mod non_fungible_token;
 /// Storage management deals with handling [state storage](https://docs.near.org/docs/concepts/storage-staking) on NEAR. This follows the [storage management standard](https://nomicon.io/Standards/StorageManagement.html).
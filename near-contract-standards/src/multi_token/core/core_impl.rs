@@ -0,0 +1,583 @@
+use super::resolver::MultiTokenResolver;
+use crate::multi_token::core::receiver::ext_mt_receiver;
+use crate::multi_token::core::resolver::ext_mt_resolver;
+use crate::multi_token::core::MultiTokenCore;
+use crate::multi_token::events::{MtBurn, MtMint, MtTransfer};
+use crate::multi_token::metadata::TokenMetadata;
+use crate::multi_token::token::{Token, TokenId};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::{LookupMap, TreeMap, UnorderedSet};
+use near_sdk::json_types::U128;
+use near_sdk::{
+    assert_one_yocto, env, require, AccountId, Balance, BorshStorageKey, Gas, IntoStorageKey,
+    PromiseOrValue, PromiseResult,
+};
+
+const GAS_FOR_RESOLVE_TRANSFER: Gas = Gas(5_000_000_000_000);
+const GAS_FOR_MT_TRANSFER_CALL: Gas = Gas(25_000_000_000_000 + GAS_FOR_RESOLVE_TRANSFER.0);
+
+const ERR_TOTAL_SUPPLY_OVERFLOW: &str = "Total supply overflow";
+
+/// Implementation of the multi token standard.
+/// Allows to include NEP-245 compatible tokens to any contract.
+/// There are next traits that any contract may implement:
+///     - MultiTokenCore -- interface with mt_transfer methods. MultiToken provides methods for it.
+///     - MultiTokenEnumeration -- interface for getting lists of tokens. MultiToken provides methods for it.
+///     - MultiTokenMetadataProvider -- return metadata for the contract, up to contract to implement.
+///
+/// For example usage, see examples/multi-token/src/lib.rs.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct MultiToken {
+    // owner of contract
+    pub owner_id: AccountId,
+
+    // always required, total supply of each token
+    pub total_supply: TreeMap<TokenId, Balance>,
+
+    // always required, balance of each (token, account) pair
+    pub balances: LookupMap<(TokenId, AccountId), Balance>,
+
+    // required by metadata extension
+    pub token_metadata_by_id: Option<LookupMap<TokenId, TokenMetadata>>,
+
+    // required by enumeration extension
+    pub tokens_per_owner: Option<LookupMap<AccountId, UnorderedSet<TokenId>>>,
+}
+
+#[derive(BorshStorageKey, BorshSerialize)]
+pub enum StorageKey {
+    TokensPerOwner { account_hash: Vec<u8> },
+}
+
+impl MultiToken {
+    pub fn new<Q, R, S, T>(
+        total_supply_prefix: Q,
+        balances_prefix: R,
+        owner_id: AccountId,
+        token_metadata_prefix: Option<S>,
+        enumeration_prefix: Option<T>,
+    ) -> Self
+    where
+        Q: IntoStorageKey,
+        R: IntoStorageKey,
+        S: IntoStorageKey,
+        T: IntoStorageKey,
+    {
+        Self {
+            owner_id,
+            total_supply: TreeMap::new(total_supply_prefix),
+            balances: LookupMap::new(balances_prefix),
+            token_metadata_by_id: token_metadata_prefix.map(LookupMap::new),
+            tokens_per_owner: enumeration_prefix.map(LookupMap::new),
+        }
+    }
+
+    fn tokens_per_owner_key(account_id: &AccountId) -> StorageKey {
+        // Domain-separate the hash so the sets never collide with the ones of an NFT sharing
+        // the same contract.
+        StorageKey::TokensPerOwner {
+            account_hash: env::sha256([b"mt:", account_id.as_bytes()].concat().as_slice()),
+        }
+    }
+
+    /// Returns the balance of `account_id` for `token_id`, or 0 if the account holds none.
+    pub fn internal_balance_of(&self, account_id: &AccountId, token_id: &str) -> Balance {
+        self.balances.get(&(token_id.to_string(), account_id.clone())).unwrap_or(0)
+    }
+
+    /// Adds `amount` of `token_id` to the balance of `account_id`, without changing the total
+    /// supply.
+    pub fn internal_deposit(&mut self, account_id: &AccountId, token_id: &str, amount: Balance) {
+        let balance = self.internal_balance_of(account_id, token_id);
+        let new_balance =
+            balance.checked_add(amount).unwrap_or_else(|| env::panic_str("Balance overflow"));
+        self.balances.insert(&(token_id.to_string(), account_id.clone()), &new_balance);
+
+        // Enumeration extension: Record tokens_per_owner for use with enumeration view methods.
+        if balance == 0 {
+            if let Some(tokens_per_owner) = &mut self.tokens_per_owner {
+                let mut token_ids = tokens_per_owner
+                    .get(account_id)
+                    .unwrap_or_else(|| UnorderedSet::new(Self::tokens_per_owner_key(account_id)));
+                token_ids.insert(&token_id.to_string());
+                tokens_per_owner.insert(account_id, &token_ids);
+            }
+        }
+    }
+
+    /// Removes `amount` of `token_id` from the balance of `account_id`, without changing the
+    /// total supply.
+    pub fn internal_withdraw(&mut self, account_id: &AccountId, token_id: &str, amount: Balance) {
+        let balance = self.internal_balance_of(account_id, token_id);
+        let new_balance = balance
+            .checked_sub(amount)
+            .unwrap_or_else(|| env::panic_str("The account doesn't have enough balance"));
+        let key = (token_id.to_string(), account_id.clone());
+        if new_balance == 0 {
+            self.balances.remove(&key);
+            if let Some(tokens_per_owner) = &mut self.tokens_per_owner {
+                if let Some(mut token_ids) = tokens_per_owner.get(account_id) {
+                    token_ids.remove(&key.0);
+                    if token_ids.is_empty() {
+                        tokens_per_owner.remove(account_id);
+                    } else {
+                        tokens_per_owner.insert(account_id, &token_ids);
+                    }
+                }
+            }
+        } else {
+            self.balances.insert(&key, &new_balance);
+        }
+    }
+
+    /// Mint `amount` of `token_id` to `owner_id`. Not part of official standard, but needed in
+    /// most situations. Consuming contract expected to wrap this with an `mt_mint` function.
+    ///
+    /// If the token does not exist yet and the contract is using the Metadata extension,
+    /// `token_metadata` must be given. Metadata given for an existing token is ignored.
+    ///
+    /// Returns the minted token and emits the mint event.
+    pub fn internal_mint(
+        &mut self,
+        token_id: TokenId,
+        owner_id: AccountId,
+        amount: Balance,
+        token_metadata: Option<TokenMetadata>,
+    ) -> Token {
+        require!(amount > 0, "The amount should be a positive number");
+        let supply = match self.total_supply.get(&token_id) {
+            Some(supply) => supply,
+            None => {
+                if let Some(token_metadata_by_id) = &mut self.token_metadata_by_id {
+                    let metadata = token_metadata
+                        .as_ref()
+                        .unwrap_or_else(|| env::panic_str("Must provide metadata"));
+                    token_metadata_by_id.insert(&token_id, metadata);
+                }
+                0
+            }
+        };
+        let supply =
+            supply.checked_add(amount).unwrap_or_else(|| env::panic_str(ERR_TOTAL_SUPPLY_OVERFLOW));
+        self.total_supply.insert(&token_id, &supply);
+        self.internal_deposit(&owner_id, &token_id, amount);
+
+        MtMint {
+            owner_id: &owner_id,
+            token_ids: &[&token_id],
+            amounts: &[U128(amount)],
+            memo: None,
+        }
+        .emit();
+
+        let metadata = self.token_metadata_by_id.as_ref().and_then(|by_id| by_id.get(&token_id));
+        Token { token_id, supply: U128(supply), metadata }
+    }
+
+    /// Burn `amount` of `token_id` from `owner_id`, decreasing the total supply, and emit the
+    /// burn event.
+    ///
+    /// Panics if the account balance is lower than `amount`.
+    pub fn internal_burn(
+        &mut self,
+        #[allow(clippy::ptr_arg)] token_id: &TokenId,
+        owner_id: &AccountId,
+        amount: Balance,
+        memo: Option<String>,
+    ) {
+        require!(amount > 0, "The amount should be a positive number");
+        self.internal_withdraw(owner_id, token_id, amount);
+        let supply = self
+            .total_supply
+            .get(token_id)
+            .and_then(|supply| supply.checked_sub(amount))
+            .unwrap_or_else(|| env::panic_str(ERR_TOTAL_SUPPLY_OVERFLOW));
+        self.total_supply.insert(token_id, &supply);
+
+        MtBurn {
+            owner_id,
+            token_ids: &[token_id],
+            amounts: &[U128(amount)],
+            authorized_id: None,
+            memo: memo.as_deref(),
+        }
+        .emit();
+    }
+
+    /// Transfer `amounts` of `token_ids` from `sender_id` to `receiver_id` and emit a single
+    /// transfer event for the whole batch.
+    ///
+    /// Panics if the lengths of `token_ids` and `amounts` differ, if any amount is zero or if the
+    /// sender does not have enough balance of any of the tokens.
+    pub fn internal_batch_transfer(
+        &mut self,
+        sender_id: &AccountId,
+        receiver_id: &AccountId,
+        token_ids: &[TokenId],
+        amounts: &[Balance],
+        memo: Option<String>,
+    ) {
+        require!(sender_id != receiver_id, "Sender and receiver should be different");
+        require!(!token_ids.is_empty(), "At least one token should be transferred");
+        require!(
+            token_ids.len() == amounts.len(),
+            "Number of token ids and amounts should be equal"
+        );
+        for (token_id, &amount) in token_ids.iter().zip(amounts) {
+            require!(amount > 0, "The amount should be a positive number");
+            require!(self.total_supply.get(token_id).is_some(), "Token not found");
+            self.internal_withdraw(sender_id, token_id, amount);
+            self.internal_deposit(receiver_id, token_id, amount);
+        }
+
+        let token_ids: Vec<&str> = token_ids.iter().map(String::as_str).collect();
+        let amounts: Vec<U128> = amounts.iter().map(|&amount| U128(amount)).collect();
+        MtTransfer {
+            old_owner_id: sender_id,
+            new_owner_id: receiver_id,
+            token_ids: &token_ids,
+            amounts: &amounts,
+            authorized_id: None,
+            memo: memo.as_deref(),
+        }
+        .emit();
+    }
+
+    fn assert_no_approvals<T>(approvals: Option<T>) {
+        require!(approvals.is_none(), "Approval extension is not supported");
+    }
+
+    fn internal_batch_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<U128>,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<Vec<U128>> {
+        require!(env::prepaid_gas() > GAS_FOR_MT_TRANSFER_CALL, "More gas is required");
+        let sender_id = env::predecessor_account_id();
+        let balances: Vec<Balance> = amounts.iter().map(|amount| amount.0).collect();
+        self.internal_batch_transfer(&sender_id, &receiver_id, &token_ids, &balances, memo);
+        let receiver_gas = env::prepaid_gas()
+            .0
+            .checked_sub(GAS_FOR_MT_TRANSFER_CALL.0)
+            .unwrap_or_else(|| env::panic_str("Prepaid gas overflow"));
+        let previous_owner_ids = vec![sender_id.clone(); token_ids.len()];
+        // Initiating receiver's call and the callback
+        ext_mt_receiver::ext(receiver_id.clone())
+            .with_static_gas(receiver_gas.into())
+            .mt_on_transfer(
+                sender_id,
+                previous_owner_ids.clone(),
+                token_ids.clone(),
+                amounts.clone(),
+                msg,
+            )
+            .then(
+                ext_mt_resolver::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_RESOLVE_TRANSFER)
+                    .mt_resolve_transfer(previous_owner_ids, receiver_id, token_ids, amounts),
+            )
+            .into()
+    }
+
+    fn internal_token(&self, token_id: TokenId) -> Option<Token> {
+        let supply = self.total_supply.get(&token_id)?;
+        let metadata = self.token_metadata_by_id.as_ref().and_then(|by_id| by_id.get(&token_id));
+        Some(Token { token_id, supply: U128(supply), metadata })
+    }
+}
+
+impl MultiTokenCore for MultiToken {
+    fn mt_transfer(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        amount: U128,
+        approval: Option<(AccountId, u64)>,
+        memo: Option<String>,
+    ) {
+        assert_one_yocto();
+        Self::assert_no_approvals(approval);
+        let sender_id = env::predecessor_account_id();
+        self.internal_batch_transfer(&sender_id, &receiver_id, &[token_id], &[amount.0], memo);
+    }
+
+    fn mt_batch_transfer(
+        &mut self,
+        receiver_id: AccountId,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<U128>,
+        approvals: Option<Vec<Option<(AccountId, u64)>>>,
+        memo: Option<String>,
+    ) {
+        assert_one_yocto();
+        Self::assert_no_approvals(approvals);
+        let sender_id = env::predecessor_account_id();
+        let amounts: Vec<Balance> = amounts.into_iter().map(|amount| amount.0).collect();
+        self.internal_batch_transfer(&sender_id, &receiver_id, &token_ids, &amounts, memo);
+    }
+
+    fn mt_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        amount: U128,
+        approval: Option<(AccountId, u64)>,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<Vec<U128>> {
+        assert_one_yocto();
+        Self::assert_no_approvals(approval);
+        self.internal_batch_transfer_call(receiver_id, vec![token_id], vec![amount], memo, msg)
+    }
+
+    fn mt_batch_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<U128>,
+        approvals: Option<Vec<Option<(AccountId, u64)>>>,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<Vec<U128>> {
+        assert_one_yocto();
+        Self::assert_no_approvals(approvals);
+        self.internal_batch_transfer_call(receiver_id, token_ids, amounts, memo, msg)
+    }
+
+    fn mt_token(&self, token_ids: Vec<TokenId>) -> Vec<Option<Token>> {
+        token_ids.into_iter().map(|token_id| self.internal_token(token_id)).collect()
+    }
+
+    fn mt_balance_of(&self, account_id: AccountId, token_id: TokenId) -> U128 {
+        self.internal_balance_of(&account_id, &token_id).into()
+    }
+
+    fn mt_batch_balance_of(&self, account_id: AccountId, token_ids: Vec<TokenId>) -> Vec<U128> {
+        token_ids
+            .iter()
+            .map(|token_id| self.internal_balance_of(&account_id, token_id).into())
+            .collect()
+    }
+
+    fn mt_supply(&self, token_id: TokenId) -> Option<U128> {
+        self.total_supply.get(&token_id).map(U128)
+    }
+
+    fn mt_batch_supply(&self, token_ids: Vec<TokenId>) -> Vec<Option<U128>> {
+        token_ids.iter().map(|token_id| self.total_supply.get(token_id).map(U128)).collect()
+    }
+}
+
+impl MultiToken {
+    /// Internal method that refunds the unused amounts of an `mt_transfer_call` or
+    /// `mt_batch_transfer_call` back to the previous owners.
+    /// Returns the used amount of each token.
+    pub fn internal_mt_resolve_transfer(
+        &mut self,
+        previous_owner_ids: &[AccountId],
+        receiver_id: AccountId,
+        token_ids: &[TokenId],
+        amounts: Vec<U128>,
+    ) -> Vec<U128> {
+        // Get the unused amounts from the `mt_on_transfer` call result. A failed call or a
+        // malformed result means none of the tokens were used.
+        let unused_amounts = match env::promise_result(0) {
+            PromiseResult::Successful(value) => {
+                match near_sdk::serde_json::from_slice::<Vec<U128>>(&value) {
+                    Ok(unused) if unused.len() == amounts.len() => unused
+                        .into_iter()
+                        .zip(&amounts)
+                        .map(|(unused, amount)| std::cmp::min(unused.0, amount.0))
+                        .collect(),
+                    _ => amounts.iter().map(|amount| amount.0).collect(),
+                }
+            }
+            PromiseResult::Failed => amounts.iter().map(|amount| amount.0).collect::<Vec<_>>(),
+        };
+
+        let mut used_amounts = Vec::with_capacity(amounts.len());
+        for (i, (amount, unused_amount)) in amounts.iter().zip(unused_amounts).enumerate() {
+            let token_id = &token_ids[i];
+            let previous_owner_id = &previous_owner_ids[i];
+            let receiver_balance = self.internal_balance_of(&receiver_id, token_id);
+            let refund_amount = std::cmp::min(receiver_balance, unused_amount);
+            if refund_amount > 0 {
+                self.internal_withdraw(&receiver_id, token_id, refund_amount);
+                self.internal_deposit(previous_owner_id, token_id, refund_amount);
+                MtTransfer {
+                    old_owner_id: &receiver_id,
+                    new_owner_id: previous_owner_id,
+                    token_ids: &[token_id],
+                    amounts: &[U128(refund_amount)],
+                    authorized_id: None,
+                    memo: Some("refund"),
+                }
+                .emit();
+            }
+            used_amounts.push(U128(amount.0 - refund_amount));
+        }
+        used_amounts
+    }
+}
+
+impl MultiTokenResolver for MultiToken {
+    fn mt_resolve_transfer(
+        &mut self,
+        previous_owner_ids: Vec<AccountId>,
+        receiver_id: AccountId,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<U128>,
+    ) -> Vec<U128> {
+        self.internal_mt_resolve_transfer(&previous_owner_ids, receiver_id, &token_ids, amounts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::{testing_env, RuntimeFeesConfig, VMConfig};
+
+    fn setup() -> MultiToken {
+        testing_env!(VMContextBuilder::new().attached_deposit(1).build());
+        let mut mt = MultiToken::new(b"s", b"b", accounts(0), None::<&[u8]>, Some(b"e"));
+        mt.internal_mint("gold".to_string(), accounts(1), 100, None);
+        mt.internal_mint("silver".to_string(), accounts(1), 50, None);
+        mt
+    }
+
+    fn set_promise_result(result: PromiseResult) {
+        testing_env!(
+            VMContextBuilder::new().build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![result],
+        );
+    }
+
+    #[test]
+    fn mint_and_burn_update_supply() {
+        let mut mt = setup();
+        mt.internal_mint("gold".to_string(), accounts(2), 20, None);
+        assert_eq!(mt.mt_supply("gold".to_string()), Some(U128(120)));
+        assert_eq!(mt.mt_balance_of(accounts(2), "gold".to_string()), U128(20));
+
+        mt.internal_burn(&"gold".to_string(), &accounts(1), 30, None);
+        assert_eq!(mt.mt_supply("gold".to_string()), Some(U128(90)));
+        assert_eq!(mt.mt_balance_of(accounts(1), "gold".to_string()), U128(70));
+        assert_eq!(mt.mt_supply("bronze".to_string()), None);
+    }
+
+    #[test]
+    fn batch_transfer_moves_balances() {
+        let mut mt = setup();
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(accounts(1))
+            .attached_deposit(1)
+            .build());
+        mt.mt_batch_transfer(
+            accounts(2),
+            vec!["gold".to_string(), "silver".to_string()],
+            vec![U128(40), U128(50)],
+            None,
+            None,
+        );
+
+        assert_eq!(
+            mt.mt_batch_balance_of(accounts(1), vec!["gold".to_string(), "silver".to_string()]),
+            vec![U128(60), U128(0)]
+        );
+        assert_eq!(
+            mt.mt_batch_balance_of(accounts(2), vec!["gold".to_string(), "silver".to_string()]),
+            vec![U128(40), U128(50)]
+        );
+        // Transfers never change the total supply.
+        assert_eq!(
+            mt.mt_batch_supply(vec!["gold".to_string(), "silver".to_string()]),
+            vec![Some(U128(100)), Some(U128(50))]
+        );
+
+        let tokens_per_owner = mt.tokens_per_owner.as_ref().unwrap();
+        assert_eq!(tokens_per_owner.get(&accounts(1)).unwrap().to_vec(), vec!["gold"]);
+        assert_eq!(tokens_per_owner.get(&accounts(2)).unwrap().len(), 2);
+
+        assert_eq!(
+            near_sdk::test_utils::get_logs().last().unwrap(),
+            r#"EVENT_JSON:{"standard":"nep245","version":"1.0.0","event":"mt_transfer","data":[{"old_owner_id":"bob","new_owner_id":"charlie","token_ids":["gold","silver"],"amounts":["40","50"]}]}"#
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "The account doesn't have enough balance")]
+    fn batch_transfer_insufficient_balance() {
+        let mut mt = setup();
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(accounts(1))
+            .attached_deposit(1)
+            .build());
+        mt.mt_batch_transfer(
+            accounts(2),
+            vec!["gold".to_string(), "silver".to_string()],
+            vec![U128(10), U128(51)],
+            None,
+            None,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Number of token ids and amounts should be equal")]
+    fn batch_transfer_length_mismatch() {
+        let mut mt = setup();
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(accounts(1))
+            .attached_deposit(1)
+            .build());
+        mt.mt_batch_transfer(accounts(2), vec!["gold".to_string()], vec![], None, None);
+    }
+
+    #[test]
+    fn resolve_transfer_refunds_unused() {
+        let mut mt = setup();
+        mt.internal_batch_transfer(
+            &accounts(1),
+            &accounts(2),
+            &["gold".to_string(), "silver".to_string()],
+            &[40, 20],
+            None,
+        );
+
+        set_promise_result(PromiseResult::Successful(b"[\"10\",\"0\"]".to_vec()));
+        let used = mt.mt_resolve_transfer(
+            vec![accounts(1), accounts(1)],
+            accounts(2),
+            vec!["gold".to_string(), "silver".to_string()],
+            vec![U128(40), U128(20)],
+        );
+        assert_eq!(used, vec![U128(30), U128(20)]);
+        assert_eq!(mt.mt_balance_of(accounts(1), "gold".to_string()), U128(70));
+        assert_eq!(mt.mt_balance_of(accounts(2), "gold".to_string()), U128(30));
+        assert_eq!(mt.mt_balance_of(accounts(2), "silver".to_string()), U128(20));
+    }
+
+    #[test]
+    fn resolve_failed_transfer_refunds_all() {
+        let mut mt = setup();
+        mt.internal_batch_transfer(&accounts(1), &accounts(2), &["gold".to_string()], &[40], None);
+
+        set_promise_result(PromiseResult::Failed);
+        let used = mt.mt_resolve_transfer(
+            vec![accounts(1)],
+            accounts(2),
+            vec!["gold".to_string()],
+            vec![U128(40)],
+        );
+        assert_eq!(used, vec![U128(0)]);
+        assert_eq!(mt.mt_balance_of(accounts(1), "gold".to_string()), U128(100));
+        assert_eq!(mt.mt_balance_of(accounts(2), "gold".to_string()), U128(0));
+        assert!(mt.tokens_per_owner.as_ref().unwrap().get(&accounts(2)).is_none());
+    }
+}
@@ -0,0 +1,146 @@
+mod core_impl;
+
+mod receiver;
+mod resolver;
+
+pub use self::core_impl::*;
+
+pub use self::receiver::MultiTokenReceiver;
+pub use self::resolver::MultiTokenResolver;
+
+use crate::multi_token::token::{Token, TokenId};
+use near_sdk::json_types::U128;
+use near_sdk::AccountId;
+use near_sdk::PromiseOrValue;
+
+/// Used for all multi tokens. The specification for the
+/// [core multi token standard] lays out the reasoning for each method.
+/// It's important to check out [MultiTokenReceiver](crate::multi_token::core::MultiTokenReceiver)
+/// and [MultiTokenResolver](crate::multi_token::core::MultiTokenResolver) to
+/// understand how the cross-contract call work.
+///
+/// The `approval` arguments are for use with the Approval Management extension, which is not
+/// implemented by [`MultiToken`]. It panics if an approval is given.
+///
+/// [core multi token standard]: <https://nomicon.io/Standards/Tokens/MultiToken/Core>
+pub trait MultiTokenCore {
+    /// Simple transfer. Transfer a given `amount` of `token_id` from the caller to
+    /// `receiver_id`.
+    ///
+    /// Requirements
+    /// * Caller of the method must attach a deposit of 1 yoctoⓃ for security purposes
+    /// * Caller must have a balance of at least `amount` of `token_id`
+    /// * Contract MUST panic if called by someone other than token owner or,
+    ///   if using Approval Management, one of the approved accounts
+    ///
+    /// Arguments:
+    /// * `receiver_id`: the valid NEAR account receiving the token
+    /// * `token_id`: the token to transfer
+    /// * `amount`: the number of tokens to transfer, wrapped in quotes and treated
+    ///    like a string, although the number will be stored as an unsigned integer
+    ///    with 128 bits.
+    /// * `approval`: expected approval account and ID, for use with Approval Management
+    /// * `memo` (optional): for use cases that may benefit from indexing or
+    ///    providing information for a transfer
+    fn mt_transfer(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        amount: U128,
+        approval: Option<(AccountId, u64)>,
+        memo: Option<String>,
+    );
+
+    /// Simple batch transfer. Transfer the given `amounts` of `token_ids` from the caller to
+    /// `receiver_id`.
+    ///
+    /// Requirements
+    /// * Caller of the method must attach a deposit of 1 yoctoⓃ for security purposes
+    /// * `token_ids` and `amounts` (and `approvals`, if given) must have the same length
+    /// * Caller must have a balance of at least the given amount of each token
+    /// * Contract MUST panic if called by someone other than token owner or,
+    ///   if using Approval Management, one of the approved accounts
+    ///
+    /// Arguments:
+    /// * `receiver_id`: the valid NEAR account receiving the tokens
+    /// * `token_ids`: the tokens to transfer
+    /// * `amounts`: the number of tokens to transfer for each token id
+    /// * `approvals`: expected approval account and ID for each token, for use with Approval
+    ///    Management
+    /// * `memo` (optional): for use cases that may benefit from indexing or
+    ///    providing information for a transfer
+    fn mt_batch_transfer(
+        &mut self,
+        receiver_id: AccountId,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<U128>,
+        approvals: Option<Vec<Option<(AccountId, u64)>>>,
+        memo: Option<String>,
+    );
+
+    /// Transfer tokens and call a method on a receiver contract. A successful
+    /// workflow will end in a success execution outcome to the callback on the MT
+    /// contract at the method `mt_resolve_transfer`.
+    ///
+    /// Requirements:
+    /// * Caller of the method must attach a deposit of 1 yoctoⓃ for security
+    ///   purposes
+    /// * The receiving contract must implement `mt_on_transfer` according to the
+    ///   standard. If it does not, MT contract's `mt_resolve_transfer` MUST deal
+    ///   with the resulting failed cross-contract call and roll back the transfer.
+    /// * Contract MUST implement the behavior described in `mt_resolve_transfer`
+    ///
+    /// Arguments:
+    /// * `receiver_id`: the valid NEAR account receiving the token.
+    /// * `token_id`: the token to send.
+    /// * `amount`: the number of tokens to transfer.
+    /// * `approval`: expected approval account and ID, for use with Approval Management
+    /// * `memo` (optional): for use cases that may benefit from indexing or
+    ///    providing information for a transfer.
+    /// * `msg`: specifies information needed by the receiving contract in
+    ///    order to properly handle the transfer. Can indicate both a function to
+    ///    call and the parameters to pass to that function.
+    ///
+    /// Returns the amount of each token that was used by the receiver.
+    fn mt_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        amount: U128,
+        approval: Option<(AccountId, u64)>,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<Vec<U128>>;
+
+    /// Transfer a batch of tokens and call a method on a receiver contract. Works the same as
+    /// [`mt_transfer_call`](MultiTokenCore::mt_transfer_call) for each of the `token_ids`, with
+    /// `amounts` (and `approvals`, if given) required to have the same length as `token_ids`.
+    ///
+    /// Returns the amount of each token that was used by the receiver.
+    fn mt_batch_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<U128>,
+        approvals: Option<Vec<Option<(AccountId, u64)>>>,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<Vec<U128>>;
+
+    /// Returns the tokens with the given `token_ids`, with `null` for each token that does not
+    /// exist.
+    fn mt_token(&self, token_ids: Vec<TokenId>) -> Vec<Option<Token>>;
+
+    /// Returns the balance of `account_id` for the given `token_id`.
+    fn mt_balance_of(&self, account_id: AccountId, token_id: TokenId) -> U128;
+
+    /// Returns the balances of `account_id` for each of the given `token_ids`.
+    fn mt_batch_balance_of(&self, account_id: AccountId, token_ids: Vec<TokenId>) -> Vec<U128>;
+
+    /// Returns the total supply of the given `token_id` or `null` if no such token.
+    fn mt_supply(&self, token_id: TokenId) -> Option<U128>;
+
+    /// Returns the total supply of each of the given `token_ids`, with `null` for each token
+    /// that does not exist.
+    fn mt_batch_supply(&self, token_ids: Vec<TokenId>) -> Vec<Option<U128>>;
+}
@@ -0,0 +1,97 @@
+use super::MultiTokenEnumeration;
+use crate::multi_token::token::{Token, TokenId};
+use crate::multi_token::MultiToken;
+use near_sdk::json_types::U128;
+use near_sdk::{env, require, AccountId, Balance};
+
+impl MultiToken {
+    /// Helper function used by a enumerations methods
+    /// Note: this method is not exposed publicly to end users
+    fn enum_get_token(&self, token_id: TokenId, supply: Balance) -> Token {
+        let metadata = self.token_metadata_by_id.as_ref().and_then(|m| m.get(&token_id));
+        Token { token_id, supply: U128(supply), metadata }
+    }
+}
+
+impl MultiTokenEnumeration for MultiToken {
+    fn mt_tokens(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Token> {
+        // Get starting index, whether or not it was explicitly given.
+        // Defaults to 0 based on the spec:
+        // https://nomicon.io/Standards/Tokens/MultiToken/Enumeration#interface
+        let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+        require!(
+            (self.total_supply.len() as u128) >= start_index,
+            "Out of bounds, please use a smaller from_index."
+        );
+        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+        require!(limit != 0, "Cannot provide limit of 0.");
+        self.total_supply
+            .iter()
+            .skip(start_index as usize)
+            .take(limit)
+            .map(|(token_id, supply)| self.enum_get_token(token_id, supply))
+            .collect()
+    }
+
+    fn mt_tokens_for_owner(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<Token> {
+        let tokens_per_owner = self.tokens_per_owner.as_ref().unwrap_or_else(|| {
+            env::panic_str(
+                "Could not find tokens_per_owner when calling a method on the \
+                enumeration standard.",
+            )
+        });
+        let token_set = if let Some(token_set) = tokens_per_owner.get(&account_id) {
+            token_set
+        } else {
+            return vec![];
+        };
+
+        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+        require!(limit != 0, "Cannot provide limit of 0.");
+        let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+        require!(
+            token_set.len() as u128 > start_index,
+            "Out of bounds, please use a smaller from_index."
+        );
+        token_set
+            .iter()
+            .skip(start_index as usize)
+            .take(limit)
+            .map(|token_id| {
+                let supply = self.total_supply.get(&token_id).unwrap_or_default();
+                self.enum_get_token(token_id, supply)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::testing_env;
+
+    #[test]
+    fn tokens_and_tokens_for_owner() {
+        testing_env!(VMContextBuilder::new().build());
+        let mut mt = MultiToken::new(b"s", b"b", accounts(0), None::<&[u8]>, Some(b"e"));
+        mt.internal_mint("a".to_string(), accounts(1), 10, None);
+        mt.internal_mint("b".to_string(), accounts(2), 5, None);
+        mt.internal_mint("c".to_string(), accounts(1), 1, None);
+
+        let ids: Vec<_> =
+            mt.mt_tokens(Some(U128(1)), None).into_iter().map(|t| t.token_id).collect();
+        assert_eq!(ids, vec!["b", "c"]);
+
+        let tokens = mt.mt_tokens_for_owner(accounts(1), None, Some(1));
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].token_id, "a");
+        assert_eq!(tokens[0].supply, U128(10));
+        assert!(mt.mt_tokens_for_owner(accounts(3), None, None).is_empty());
+    }
+}
@@ -0,0 +1,61 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::Base64VecU8;
+use near_sdk::require;
+use near_sdk::serde::{Deserialize, Serialize};
+
+/// This spec can be treated like a version of the standard.
+pub const MT_METADATA_SPEC: &str = "mt-1.0.0";
+
+/// Metadata for the multi token contract itself.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "abi", derive(schemars::JsonSchema))]
+#[serde(crate = "near_sdk::serde")]
+pub struct MTContractMetadata {
+    pub spec: String, // required, essentially a version like "mt-1.0.0"
+    pub name: String, // required, ex. "Zoink's Digital Sword Collection"
+}
+
+/// Metadata on the individual token level.
+#[derive(
+    Debug, Clone, Serialize, Deserialize, PartialEq, Eq, BorshDeserialize, BorshSerialize, Default,
+)]
+#[cfg_attr(feature = "abi", derive(schemars::JsonSchema))]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenMetadata {
+    pub title: Option<String>, // ex. "Arch Nemesis: Mail Carrier" or "Parcel #5055"
+    pub description: Option<String>, // free-form description
+    pub media: Option<String>, // URL to associated media, preferably to decentralized, content-addressed storage
+    pub media_hash: Option<Base64VecU8>, // Base64-encoded sha256 hash of content referenced by the `media` field. Required if `media` is included.
+    pub issued_at: Option<String>,       // ISO 8601 datetime when token was issued or minted
+    pub expires_at: Option<String>,      // ISO 8601 datetime when token expires
+    pub starts_at: Option<String>,       // ISO 8601 datetime when token starts being valid
+    pub updated_at: Option<String>,      // ISO 8601 datetime when token was last updated
+    pub extra: Option<String>, // anything extra the MT wants to store on-chain. Can be stringified JSON.
+    pub reference: Option<String>, // URL to an off-chain JSON file with more info.
+    pub reference_hash: Option<Base64VecU8>, // Base64-encoded sha256 hash of JSON from reference field. Required if `reference` is included.
+}
+
+/// Offers details on the contract-level metadata.
+pub trait MultiTokenMetadataProvider {
+    fn mt_metadata_contract(&self) -> MTContractMetadata;
+}
+
+impl MTContractMetadata {
+    pub fn assert_valid(&self) {
+        require!(self.spec == MT_METADATA_SPEC, "Spec is not MT metadata");
+    }
+}
+
+impl TokenMetadata {
+    pub fn assert_valid(&self) {
+        require!(self.media.is_some() == self.media_hash.is_some());
+        if let Some(media_hash) = &self.media_hash {
+            require!(media_hash.0.len() == 32, "Media hash has to be 32 bytes");
+        }
+
+        require!(self.reference.is_some() == self.reference_hash.is_some());
+        if let Some(reference_hash) = &self.reference_hash {
+            require!(reference_hash.0.len() == 32, "Reference hash has to be 32 bytes");
+        }
+    }
+}
@@ -0,0 +1,19 @@
+/// The [core multi token standard](https://nomicon.io/Standards/Tokens/MultiToken/Core). This can be though of as the base standard, with the others being extension standards.
+pub mod core;
+/// Trait for the [MT enumeration standard](https://nomicon.io/Standards/Tokens/MultiToken/Enumeration).
+/// This provides useful view-only methods returning all tokens and tokens by owner.
+pub mod enumeration;
+/// Macros typically used by a contract wanting to take advantage of the multi
+/// token NEAR contract standard approach.
+mod macros;
+/// Metadata traits and implementation according to the [MT metadata standard](https://nomicon.io/Standards/Tokens/MultiToken/Metadata).
+/// This covers both the contract metadata and the individual token metadata.
+pub mod metadata;
+/// The Token struct for the multi token.
+mod token;
+pub use self::token::{Token, TokenId};
+
+pub use self::core::MultiToken;
+pub use macros::*;
+
+pub mod events;
@@ -0,0 +1,18 @@
+use crate::multi_token::metadata::TokenMetadata;
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+
+/// Note that token IDs for multi tokens are strings on NEAR. It's still fine to use autoincrementing numbers as unique IDs if desired, but they should be stringified.
+pub type TokenId = String;
+
+/// In this implementation, the Token struct takes the metadata extension standard as an optional field.
+/// Unlike NFTs, a multi token can be owned by many accounts at once, so the total supply of the
+/// token is returned instead of its owner.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "abi", derive(schemars::JsonSchema))]
+#[serde(crate = "near_sdk::serde")]
+pub struct Token {
+    pub token_id: TokenId,
+    pub supply: U128,
+    pub metadata: Option<TokenMetadata>,
+}
@@ -2,13 +2,17 @@ use crate::non_fungible_token::approval::ext_nft_approval_receiver;
 /// Common implementation of the [approval management standard](https://nomicon.io/Standards/NonFungibleToken/ApprovalManagement.html) for NFTs.
 /// on the contract/account that has just been approved. This is not required to implement.
 use crate::non_fungible_token::approval::NonFungibleTokenApproval;
+#[cfg(feature = "nft-approval-events")]
+use crate::non_fungible_token::events::{NftApprove, NftRevoke};
 use crate::non_fungible_token::token::TokenId;
 use crate::non_fungible_token::utils::{
-    assert_at_least_one_yocto, bytes_for_approved_account_id, refund_approved_account_ids,
-    refund_approved_account_ids_iter, refund_deposit,
+    bytes_for_approved_account_id, refund_approved_account_ids, refund_approved_account_ids_iter,
+    refund_deposit,
 };
 use crate::non_fungible_token::NonFungibleToken;
-use near_sdk::{assert_one_yocto, env, require, AccountId, Gas, Promise};
+use near_sdk::{
+    assert_at_least_one_yocto, assert_one_yocto, env, require, AccountId, Gas, Promise,
+};
 
 const GAS_FOR_NFT_APPROVE: Gas = Gas(10_000_000_000_000);
 
@@ -56,6 +60,15 @@ impl NonFungibleTokenApproval for NonFungibleToken {
             if old_approval_id.is_none() { bytes_for_approved_account_id(&account_id) } else { 0 };
         refund_deposit(storage_used);
 
+        #[cfg(feature = "nft-approval-events")]
+        NftApprove {
+            token_id: &token_id,
+            owner_id: &owner_id,
+            account_id: &account_id,
+            approval_id,
+        }
+        .emit();
+
         // if given `msg`, schedule call to `nft_on_approve` and return it. Else, return None.
         msg.map(|msg| {
             ext_nft_approval_receiver::ext(account_id)
@@ -90,6 +103,14 @@ impl NonFungibleTokenApproval for NonFungibleToken {
                     // otherwise, update approvals_by_id with updated HashMap
                     approvals_by_id.insert(&token_id, approved_account_ids);
                 }
+
+                #[cfg(feature = "nft-approval-events")]
+                NftRevoke {
+                    token_id: &token_id,
+                    owner_id: &owner_id,
+                    account_id: Some(&account_id),
+                }
+                .emit();
             }
         }
     }
@@ -111,6 +132,9 @@ impl NonFungibleTokenApproval for NonFungibleToken {
             refund_approved_account_ids(predecessor_account_id, approved_account_ids);
             // ...and remove whole HashMap of approvals
             approvals_by_id.remove(&token_id);
+
+            #[cfg(feature = "nft-approval-events")]
+            NftRevoke { token_id: &token_id, owner_id: &owner_id, account_id: None }.emit();
         }
     }
 
@@ -151,3 +175,79 @@ impl NonFungibleTokenApproval for NonFungibleToken {
         }
     }
 }
+
+#[cfg(all(test, feature = "nft-approval-events"))]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::{get_logs, VMContextBuilder};
+    use near_sdk::{testing_env, ONE_NEAR};
+
+    fn setup() -> NonFungibleToken {
+        let owner_id: AccountId = "bob".parse().unwrap();
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner_id.clone())
+            .attached_deposit(ONE_NEAR)
+            .build());
+        let mut token = NonFungibleToken::new(
+            b"o",
+            owner_id.clone(),
+            None::<Vec<u8>>,
+            None::<Vec<u8>>,
+            Some(b"a"),
+        );
+        token.internal_mint_with_refund("0".to_string(), owner_id, None, None);
+        token
+    }
+
+    #[test]
+    fn nft_approve_emits_event() {
+        let mut token = setup();
+        token.nft_approve("0".to_string(), "alice".parse().unwrap(), None);
+        token.nft_approve("0".to_string(), "carol".parse().unwrap(), None);
+        assert_eq!(
+            get_logs(),
+            [
+                r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_approve","data":[{"token_id":"0","owner_id":"bob","account_id":"alice","approval_id":1}]}"#,
+                r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_approve","data":[{"token_id":"0","owner_id":"bob","account_id":"carol","approval_id":2}]}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn nft_revoke_emits_event() {
+        let mut token = setup();
+        token.nft_approve("0".to_string(), "alice".parse().unwrap(), None);
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id("bob".parse().unwrap())
+            .attached_deposit(1)
+            .build());
+        token.nft_revoke("0".to_string(), "alice".parse().unwrap());
+        // Revoking an account that is not approved does not emit an event.
+        token.nft_revoke("0".to_string(), "alice".parse().unwrap());
+        assert_eq!(
+            get_logs(),
+            [
+                r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_revoke","data":[{"token_id":"0","owner_id":"bob","account_id":"alice"}]}"#
+            ]
+        );
+    }
+
+    #[test]
+    fn nft_revoke_all_emits_event() {
+        let mut token = setup();
+        token.nft_approve("0".to_string(), "alice".parse().unwrap(), None);
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id("bob".parse().unwrap())
+            .attached_deposit(1)
+            .build());
+        token.nft_revoke_all("0".to_string());
+        // Revoking all approvals of a token without approvals does not emit an event.
+        token.nft_revoke_all("0".to_string());
+        assert_eq!(
+            get_logs(),
+            [
+                r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_revoke","data":[{"token_id":"0","owner_id":"bob"}]}"#
+            ]
+        );
+    }
+}
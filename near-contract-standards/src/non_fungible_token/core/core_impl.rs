@@ -2,7 +2,7 @@ use super::resolver::NonFungibleTokenResolver;
 use crate::non_fungible_token::core::receiver::ext_nft_receiver;
 use crate::non_fungible_token::core::resolver::ext_nft_resolver;
 use crate::non_fungible_token::core::NonFungibleTokenCore;
-use crate::non_fungible_token::events::{NftMint, NftTransfer};
+use crate::non_fungible_token::events::{NftBurn, NftMint, NftTransfer};
 use crate::non_fungible_token::metadata::TokenMetadata;
 use crate::non_fungible_token::token::{Token, TokenId};
 use crate::non_fungible_token::utils::{refund_approved_account_ids, refund_deposit_to_account};
@@ -157,7 +157,8 @@ impl NonFungibleToken {
 
     /// Transfer token_id from `from` to `to`
     ///
-    /// Do not perform any safety checks or do any logging
+    /// Do not perform any safety checks or do any logging. Approvals are kept, see
+    /// [`Self::internal_transfer_unguarded_with_event`] to clear them and emit the transfer event.
     pub fn internal_transfer_unguarded(
         &mut self,
         #[allow(clippy::ptr_arg)] token_id: &TokenId,
@@ -190,6 +191,37 @@ impl NonFungibleToken {
         }
     }
 
+    /// Transfer token_id from its current owner to `to`, clearing its approvals and emitting an
+    /// `nft_transfer` event, like [`Self::internal_transfer`] does but without checking that
+    /// anyone is allowed to transfer the token.
+    ///
+    /// This is dangerous: it is meant for tokens the contract fully controls, e.g. to release a
+    /// token held in escrow, and the calling method must do the authorization itself. Panics if
+    /// the token doesn't exist or if `from` isn't its owner.
+    ///
+    /// Returns the cleared approvals, if the approval extension is being used.
+    pub fn internal_transfer_unguarded_with_event(
+        &mut self,
+        #[allow(clippy::ptr_arg)] token_id: &TokenId,
+        from: &AccountId,
+        to: &AccountId,
+        memo: Option<String>,
+    ) -> Option<HashMap<AccountId, u64>> {
+        let owner_id =
+            self.owner_by_id.get(token_id).unwrap_or_else(|| env::panic_str("Token not found"));
+        require!(&owner_id == from, "The token is not owned by the given account");
+        require!(from != to, "Current and next owner must differ");
+
+        let approved_account_ids =
+            self.approvals_by_id.as_mut().map(|by_id| by_id.remove(token_id).unwrap_or_default());
+
+        self.internal_transfer_unguarded(token_id, from, to);
+
+        NonFungibleToken::emit_transfer(from, to, token_id, None, memo);
+
+        approved_account_ids
+    }
+
     /// Transfer from current owner to receiver_id, checking that sender is allowed to transfer.
     /// Clear approvals, if approval extension being used.
     /// Return previous owner and approvals.
@@ -367,6 +399,52 @@ impl NonFungibleToken {
 
         Token { token_id, owner_id, metadata: token_metadata, approved_account_ids }
     }
+
+    /// Burn a token without checking whether the caller is allowed to, removing it from all the
+    /// extensions in use, and emit the burn event. Not part of official standard, consuming
+    /// contracts are expected to check the caller and to refund the released storage.
+    ///
+    /// Returns the owner of the burned token.
+    pub fn internal_burn(
+        &mut self,
+        #[allow(clippy::ptr_arg)] token_id: &TokenId,
+        memo: Option<String>,
+    ) -> AccountId {
+        let owner_id =
+            self.owner_by_id.remove(token_id).unwrap_or_else(|| env::panic_str("Token not found"));
+
+        if let Some(token_metadata_by_id) = &mut self.token_metadata_by_id {
+            token_metadata_by_id.remove(token_id);
+        }
+
+        if let Some(tokens_per_owner) = &mut self.tokens_per_owner {
+            let mut owner_tokens = tokens_per_owner.get(&owner_id).unwrap_or_else(|| {
+                env::panic_str("Unable to access tokens per owner in unguarded call.")
+            });
+            owner_tokens.remove(token_id);
+            if owner_tokens.is_empty() {
+                tokens_per_owner.remove(&owner_id);
+            } else {
+                tokens_per_owner.insert(&owner_id, &owner_tokens);
+            }
+        }
+
+        if let Some(approvals_by_id) = &mut self.approvals_by_id {
+            approvals_by_id.remove(token_id);
+        }
+        if let Some(next_approval_id_by_id) = &mut self.next_approval_id_by_id {
+            next_approval_id_by_id.remove(token_id);
+        }
+
+        NftBurn {
+            owner_id: &owner_id,
+            token_ids: &[token_id],
+            authorized_id: None,
+            memo: memo.as_deref(),
+        }
+        .emit();
+        owner_id
+    }
 }
 
 impl NonFungibleTokenCore for NonFungibleToken {
@@ -478,3 +556,58 @@ impl NonFungibleTokenResolver for NonFungibleToken {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::non_fungible_token::test_token;
+    use near_sdk::test_utils::{accounts, get_logs, VMContextBuilder};
+    use near_sdk::testing_env;
+
+    fn setup() -> NonFungibleToken {
+        let mut token = test_token();
+        token.internal_mint_with_refund("0".to_string(), accounts(1), None, None);
+        token
+    }
+
+    #[test]
+    fn transfer_unguarded_with_event() {
+        let mut token = setup();
+        let token_id = "0".to_string();
+        let approvals = [(accounts(3), 1)].into_iter().collect();
+        token.approvals_by_id.as_mut().unwrap().insert(&token_id, &approvals);
+        testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(4)).build());
+
+        let cleared = token.internal_transfer_unguarded_with_event(
+            &token_id,
+            &accounts(1),
+            &accounts(2),
+            Some("escrow".to_string()),
+        );
+        assert_eq!(cleared, Some(approvals));
+
+        assert_eq!(token.owner_by_id.get(&token_id), Some(accounts(2)));
+        let tokens_per_owner = token.tokens_per_owner.as_ref().unwrap();
+        assert!(tokens_per_owner.get(&accounts(1)).is_none());
+        assert!(tokens_per_owner.get(&accounts(2)).unwrap().contains(&token_id));
+        assert!(token.approvals_by_id.as_ref().unwrap().get(&token_id).is_none());
+        assert_eq!(
+            get_logs(),
+            vec![
+                r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_transfer","data":[{"old_owner_id":"bob","new_owner_id":"charlie","token_ids":["0"],"memo":"escrow"}]}"#
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "The token is not owned by the given account")]
+    fn transfer_unguarded_with_event_wrong_owner() {
+        let mut token = setup();
+        token.internal_transfer_unguarded_with_event(
+            &"0".to_string(),
+            &accounts(2),
+            &accounts(3),
+            None,
+        );
+    }
+}
@@ -46,6 +46,8 @@ impl NonFungibleTokenEnumeration for NonFungibleToken {
     }
 
     fn nft_supply_for_owner(&self, account_id: AccountId) -> U128 {
+        // The length of a token set is stored with it, so the count is a single read, kept up to
+        // date by minting, transfers and burns.
         let tokens_per_owner = self.tokens_per_owner.as_ref().unwrap_or_else(|| {
             env::panic_str(
                 "Could not find tokens_per_owner when calling a method on the \
@@ -95,3 +97,67 @@ impl NonFungibleTokenEnumeration for NonFungibleToken {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::non_fungible_token::test_token;
+    use near_sdk::test_utils::{accounts, gas_profile, VMContextBuilder};
+    use near_sdk::testing_env;
+
+    fn mint(token: &mut NonFungibleToken, token_id: &str, owner_id: AccountId) {
+        token.internal_mint_with_refund(token_id.to_string(), owner_id, None, None);
+    }
+
+    fn supply(token: &NonFungibleToken, account_id: AccountId) -> u128 {
+        token.nft_supply_for_owner(account_id).0
+    }
+
+    #[test]
+    fn supply_for_owner_follows_mutations() {
+        let mut token = test_token();
+        for token_id in ["0", "1", "2"] {
+            mint(&mut token, token_id, accounts(1));
+        }
+        assert_eq!(supply(&token, accounts(1)), 3);
+        assert_eq!(supply(&token, accounts(2)), 0);
+
+        token.internal_transfer(&accounts(1), &accounts(2), &"0".to_string(), None, None);
+        assert_eq!(supply(&token, accounts(1)), 2);
+        assert_eq!(supply(&token, accounts(2)), 1);
+
+        assert_eq!(token.internal_burn(&"1".to_string(), None), accounts(1));
+        assert_eq!(supply(&token, accounts(1)), 1);
+        assert_eq!(token.nft_total_supply().0, 2);
+
+        token.internal_burn(&"2".to_string(), None);
+        token.internal_burn(&"0".to_string(), Some("memo".to_string()));
+        assert_eq!(supply(&token, accounts(1)), 0);
+        assert_eq!(supply(&token, accounts(2)), 0);
+        assert_eq!(token.nft_total_supply().0, 0);
+        assert!(token.nft_tokens_for_owner(accounts(1), None, None).is_empty());
+    }
+
+    #[test]
+    fn supply_for_owner_reads_do_not_depend_on_count() {
+        let mut token = test_token();
+        mint(&mut token, "single", accounts(1));
+        for i in 0..20 {
+            mint(&mut token, &i.to_string(), accounts(2));
+        }
+
+        let reads = |account_id| {
+            testing_env!(VMContextBuilder::new().build());
+            assert!(supply(&token, account_id) > 0);
+            gas_profile()["storage_read_base"]
+        };
+        assert_eq!(reads(accounts(1)), reads(accounts(2)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Token not found")]
+    fn burn_missing_token() {
+        let mut token = test_token();
+        token.internal_burn(&"0".to_string(), None);
+    }
+}
@@ -6,6 +6,10 @@ use near_sdk::AccountId;
 
 /// Offers methods helpful in determining account ownership of NFTs and provides a way to page through NFTs per owner, determine total supply, etc.
 pub trait NonFungibleTokenEnumeration {
+    /// The maximum number of tokens returned by [`nft_tokens_page`](Self::nft_tokens_page).
+    /// Larger page sizes are clamped to this value.
+    const MAX_PAGE_SIZE: u32 = 100;
+
     /// Returns the total supply of non-fungible tokens as a string representing an
     /// unsigned 128-bit integer to avoid JSON number limit of 2^53.
     fn nft_total_supply(&self) -> U128;
@@ -24,6 +28,23 @@ pub trait NonFungibleTokenEnumeration {
         limit: Option<u64>,       // default: unlimited (could fail due to gas limit)
     ) -> Vec<Token>;
 
+    /// Get a page of all tokens
+    ///
+    /// Arguments:
+    /// * `page`: the zero-based index of the page to return
+    /// * `page_size`: the number of tokens per page, clamped to [`MAX_PAGE_SIZE`](Self::MAX_PAGE_SIZE)
+    ///
+    /// Returns an array of Token objects, which is empty if the page is past the last token or
+    /// `page_size` is zero. The last page may contain fewer than `page_size` tokens.
+    fn nft_tokens_page(&self, page: u32, page_size: u32) -> Vec<Token> {
+        let page_size = page_size.min(Self::MAX_PAGE_SIZE);
+        let from_index = page as u128 * page_size as u128;
+        if page_size == 0 || from_index >= self.nft_total_supply().0 {
+            return vec![];
+        }
+        self.nft_tokens(Some(from_index.into()), Some(page_size.into()))
+    }
+
     /// Get number of tokens owned by a given account
     ///
     /// Arguments:
@@ -50,3 +71,94 @@ pub trait NonFungibleTokenEnumeration {
         limit: Option<u64>,       // default: unlimited (could fail due to gas limit)
     ) -> Vec<Token>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Tokens(Vec<Token>);
+
+    impl Tokens {
+        fn new(count: usize) -> Self {
+            let owner_id: AccountId = "bob.near".parse().unwrap();
+            Self(
+                (0..count)
+                    .map(|i| Token {
+                        token_id: i.to_string(),
+                        owner_id: owner_id.clone(),
+                        metadata: None,
+                        approved_account_ids: None,
+                    })
+                    .collect(),
+            )
+        }
+    }
+
+    impl NonFungibleTokenEnumeration for Tokens {
+        const MAX_PAGE_SIZE: u32 = 4;
+
+        fn nft_total_supply(&self) -> U128 {
+            U128(self.0.len() as u128)
+        }
+
+        fn nft_tokens(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Token> {
+            let start = from_index.map_or(0, |i| i.0 as usize);
+            assert!(start <= self.0.len(), "Out of bounds, please use a smaller from_index.");
+            let limit = limit.map_or(usize::MAX, |l| l as usize);
+            assert!(limit != 0, "Cannot provide limit of 0.");
+            self.0[start..].iter().take(limit).cloned().collect()
+        }
+
+        fn nft_supply_for_owner(&self, account_id: AccountId) -> U128 {
+            U128(self.0.iter().filter(|token| token.owner_id == account_id).count() as u128)
+        }
+
+        fn nft_tokens_for_owner(
+            &self,
+            account_id: AccountId,
+            from_index: Option<U128>,
+            limit: Option<u64>,
+        ) -> Vec<Token> {
+            let start = from_index.map_or(0, |i| i.0 as usize);
+            let limit = limit.map_or(usize::MAX, |l| l as usize);
+            self.0
+                .iter()
+                .filter(|token| token.owner_id == account_id)
+                .skip(start)
+                .take(limit)
+                .cloned()
+                .collect()
+        }
+    }
+
+    fn token_ids(tokens: Vec<Token>) -> Vec<String> {
+        tokens.into_iter().map(|token| token.token_id).collect()
+    }
+
+    #[test]
+    fn nft_tokens_page() {
+        let tokens = Tokens::new(7);
+        assert_eq!(token_ids(tokens.nft_tokens_page(0, 3)), ["0", "1", "2"]);
+        assert_eq!(token_ids(tokens.nft_tokens_page(1, 3)), ["3", "4", "5"]);
+        // Last partial page.
+        assert_eq!(token_ids(tokens.nft_tokens_page(2, 3)), ["6"]);
+        assert_eq!(token_ids(tokens.nft_tokens_page(3, 1)), ["3"]);
+    }
+
+    #[test]
+    fn nft_tokens_page_out_of_range() {
+        let tokens = Tokens::new(7);
+        assert!(tokens.nft_tokens_page(3, 3).is_empty());
+        assert!(tokens.nft_tokens_page(7, 1).is_empty());
+        assert!(tokens.nft_tokens_page(u32::MAX, u32::MAX).is_empty());
+        assert!(tokens.nft_tokens_page(0, 0).is_empty());
+        assert!(Tokens::new(0).nft_tokens_page(0, 3).is_empty());
+    }
+
+    #[test]
+    fn nft_tokens_page_clamps_page_size() {
+        let tokens = Tokens::new(7);
+        assert_eq!(token_ids(tokens.nft_tokens_page(0, 10)), ["0", "1", "2", "3"]);
+        assert_eq!(token_ids(tokens.nft_tokens_page(1, 10)), ["4", "5", "6"]);
+    }
+}
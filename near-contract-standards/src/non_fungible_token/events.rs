@@ -8,10 +8,11 @@
 //! <https://github.com/near/NEPs/blob/master/specs/Standards/EventsFormat.md>
 //!
 //! The three events in this standard are [`NftMint`], [`NftTransfer`], and [`NftBurn`].
+//! Additionally, changes to approvals are logged as [`NftApprove`] and [`NftRevoke`] events.
 //!
 //! These events can be logged by calling `.emit()` on them if a single event, or calling
-//! [`NftMint::emit_many`], [`NftTransfer::emit_many`],
-//! or [`NftBurn::emit_many`] respectively.
+//! [`NftMint::emit_many`], [`NftTransfer::emit_many`], [`NftBurn::emit_many`],
+//! [`NftApprove::emit_many`] or [`NftRevoke::emit_many`] respectively.
 
 use crate::event::NearEvent;
 use near_sdk::AccountId;
@@ -95,6 +96,56 @@ impl NftBurn<'_> {
     }
 }
 
+/// Data to log for an NFT approve event. To log this event, call [`.emit()`](NftApprove::emit).
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct NftApprove<'a> {
+    pub token_id: &'a str,
+    pub owner_id: &'a AccountId,
+    pub account_id: &'a AccountId,
+    pub approval_id: u64,
+}
+
+impl NftApprove<'_> {
+    /// Logs the event to the host. This is required to ensure that the event is triggered
+    /// and to consume the event.
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    /// Emits an nft approve event, through [`env::log_str`](near_sdk::env::log_str),
+    /// where each [`NftApprove`] represents the data of each approval.
+    pub fn emit_many(data: &[NftApprove<'_>]) {
+        new_171_v1(Nep171EventKind::NftApprove(data)).emit()
+    }
+}
+
+/// Data to log for an NFT revoke event. To log this event, call [`.emit()`](NftRevoke::emit).
+///
+/// `account_id` is omitted when all approvals of the token are revoked.
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct NftRevoke<'a> {
+    pub token_id: &'a str,
+    pub owner_id: &'a AccountId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<&'a AccountId>,
+}
+
+impl NftRevoke<'_> {
+    /// Logs the event to the host. This is required to ensure that the event is triggered
+    /// and to consume the event.
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    /// Emits an nft revoke event, through [`env::log_str`](near_sdk::env::log_str),
+    /// where each [`NftRevoke`] represents the data of each revoked approval.
+    pub fn emit_many(data: &[NftRevoke<'_>]) {
+        new_171_v1(Nep171EventKind::NftRevoke(data)).emit()
+    }
+}
+
 #[derive(Serialize, Debug)]
 pub(crate) struct Nep171Event<'a> {
     version: &'static str,
@@ -110,6 +161,8 @@ enum Nep171EventKind<'a> {
     NftMint(&'a [NftMint<'a>]),
     NftTransfer(&'a [NftTransfer<'a>]),
     NftBurn(&'a [NftBurn<'a>]),
+    NftApprove(&'a [NftApprove<'a>]),
+    NftRevoke(&'a [NftRevoke<'a>]),
 }
 
 fn new_171<'a>(version: &'static str, event_kind: Nep171EventKind<'a>) -> NearEvent<'a> {
@@ -222,4 +275,46 @@ mod tests {
             r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_transfer","data":[{"old_owner_id":"alice","new_owner_id":"bob","token_ids":["2","3"],"authorized_id":"bob","memo":"has memo"},{"old_owner_id":"bob","new_owner_id":"alice","token_ids":["0","1"]}]}"#
         );
     }
+
+    #[test]
+    fn nft_approve() {
+        NftApprove { token_id: "0", owner_id: &bob(), account_id: &alice(), approval_id: 1 }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_approve","data":[{"token_id":"0","owner_id":"bob","account_id":"alice","approval_id":1}]}"#
+        );
+    }
+
+    #[test]
+    fn nft_approves() {
+        NftApprove::emit_many(&[
+            NftApprove { token_id: "0", owner_id: &bob(), account_id: &alice(), approval_id: 1 },
+            NftApprove { token_id: "1", owner_id: &alice(), account_id: &bob(), approval_id: 7 },
+        ]);
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_approve","data":[{"token_id":"0","owner_id":"bob","account_id":"alice","approval_id":1},{"token_id":"1","owner_id":"alice","account_id":"bob","approval_id":7}]}"#
+        );
+    }
+
+    #[test]
+    fn nft_revoke() {
+        NftRevoke { token_id: "0", owner_id: &bob(), account_id: Some(&alice()) }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_revoke","data":[{"token_id":"0","owner_id":"bob","account_id":"alice"}]}"#
+        );
+    }
+
+    #[test]
+    fn nft_revokes() {
+        NftRevoke::emit_many(&[
+            NftRevoke { token_id: "0", owner_id: &bob(), account_id: Some(&alice()) },
+            NftRevoke { token_id: "1", owner_id: &bob(), account_id: None },
+        ]);
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_revoke","data":[{"token_id":"0","owner_id":"bob","account_id":"alice"},{"token_id":"1","owner_id":"bob"}]}"#
+        );
+    }
 }
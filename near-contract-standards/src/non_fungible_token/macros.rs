@@ -119,6 +119,10 @@ macro_rules! impl_non_fungible_token_enumeration {
                 self.$token.nft_tokens(from_index, limit)
             }
 
+            fn nft_tokens_page(&self, page: u32, page_size: u32) -> Vec<Token> {
+                self.$token.nft_tokens_page(page, page_size)
+            }
+
             fn nft_supply_for_owner(&self, account_id: AccountId) -> near_sdk::json_types::U128 {
                 self.$token.nft_supply_for_owner(account_id)
             }
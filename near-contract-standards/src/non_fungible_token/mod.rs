@@ -23,4 +23,20 @@ pub use utils::*;
 pub use self::core::NonFungibleToken;
 pub use macros::*;
 
+/// Sets up a testing environment and creates a token owned by `accounts(0)` which keeps
+/// enumeration and approval data.
+#[cfg(test)]
+pub(crate) fn test_token() -> NonFungibleToken {
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+
+    near_sdk::testing_env!(VMContextBuilder::new().build());
+    NonFungibleToken::new(
+        b"o".to_vec(),
+        accounts(0),
+        None::<Vec<u8>>,
+        Some(b"e".to_vec()),
+        Some(b"a".to_vec()),
+    )
+}
+
 pub mod events;
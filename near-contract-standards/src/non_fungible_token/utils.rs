@@ -45,8 +45,3 @@ pub fn refund_deposit_to_account(storage_used: u64, account_id: AccountId) {
 pub fn refund_deposit(storage_used: u64) {
     refund_deposit_to_account(storage_used, env::predecessor_account_id())
 }
-
-/// Assert that at least 1 yoctoNEAR was attached.
-pub(crate) fn assert_at_least_one_yocto() {
-    require!(env::attached_deposit() >= 1, "Requires attached deposit of at least 1 yoctoNEAR")
-}
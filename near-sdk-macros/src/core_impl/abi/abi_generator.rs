@@ -147,16 +147,27 @@ impl ImplItemMethodInfo {
                     };
                     callbacks.push(generate_abi_type(typ, &arg.serializer_ty));
                 }
-                BindgenArgType::CallbackArgVec => {
+                BindgenArgType::CallbackArgVec | BindgenArgType::CallbackResultArgVec => {
                     if callback_vec.is_none() {
-                        let typ = if let Some(vec_type) = utils::extract_vec_type(typ) {
-                            vec_type
-                        } else {
+                        let typ = match arg.bindgen_ty {
+                            BindgenArgType::CallbackArgVec => utils::extract_vec_type(typ),
+                            _ => utils::extract_vec_type(typ).and_then(utils::extract_ok_type),
+                        };
+                        let typ = if let Some(typ) = typ {
+                            typ
+                        } else if let BindgenArgType::CallbackArgVec = arg.bindgen_ty {
                             return syn::Error::new_spanned(
                                 &arg.ty,
                                 "Function parameters marked with #[callback_vec] should have type Vec<T>",
                             )
                             .into_compile_error();
+                        } else {
+                            return syn::Error::new_spanned(
+                                &arg.ty,
+                                "Function parameters marked with \
+                                    #[callback_results] should have type Vec<Result<T, PromiseError>>",
+                            )
+                            .into_compile_error();
                         };
 
                         let abi_type =
@@ -165,7 +176,7 @@ impl ImplItemMethodInfo {
                     } else {
                         return syn::Error::new(
                             Span::call_site(),
-                            "A function can only have one #[callback_vec] parameter.",
+                            "A function can only have one #[callback_vec] or #[callback_results] parameter.",
                         )
                         .to_compile_error();
                     }
@@ -0,0 +1,118 @@
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2, TokenTree};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_quote, Error, Generics, Ident, ItemEnum, ItemStruct, LitStr, Token, WhereClause};
+
+/// Arguments of `#[near_bindgen(eq = "borsh", hash = "borsh")]`, which implement the comparison
+/// traits of a value on its Borsh serialization.
+pub(crate) struct BorshComparisons {
+    eq: bool,
+    hash: bool,
+}
+
+impl BorshComparisons {
+    /// Returns `true` if any of the arguments of `near_bindgen` is `eq` or `hash`.
+    pub(crate) fn is_requested(attr: &TokenStream) -> bool {
+        let mut at_arg_start = true;
+        TokenStream2::from(attr.clone()).into_iter().any(|token| {
+            let requested = at_arg_start
+                && matches!(&token, TokenTree::Ident(ident) if ident == "eq" || ident == "hash");
+            at_arg_start = matches!(&token, TokenTree::Punct(punct) if punct.as_char() == ',');
+            requested
+        })
+    }
+
+    /// Generates `PartialEq` and `Eq` if `eq` is set, and `Hash` if `hash` is set, for the type
+    /// `name`.
+    fn generate(&self, name: &Ident, generics: &Generics) -> TokenStream2 {
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let predicate = parse_quote!(#name #ty_generics: ::near_sdk::borsh::BorshSerialize);
+        let where_clause: WhereClause = if let Some(mut w) = where_clause.cloned() {
+            w.predicates.push(predicate);
+            w
+        } else {
+            parse_quote!(where #predicate)
+        };
+        let eq = self.eq.then(|| {
+            quote! {
+                impl #impl_generics ::core::cmp::PartialEq for #name #ty_generics #where_clause {
+                    fn eq(&self, other: &Self) -> bool {
+                        ::near_sdk::__private::borsh_bytes(self)
+                            == ::near_sdk::__private::borsh_bytes(other)
+                    }
+                }
+
+                impl #impl_generics ::core::cmp::Eq for #name #ty_generics #where_clause {}
+            }
+        });
+        let hash = self.hash.then(|| {
+            quote! {
+                impl #impl_generics ::core::hash::Hash for #name #ty_generics #where_clause {
+                    fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+                        ::core::hash::Hash::hash(&::near_sdk::__private::borsh_bytes(self), state)
+                    }
+                }
+            }
+        });
+        quote! {
+            #eq
+            #hash
+        }
+    }
+}
+
+impl Parse for BorshComparisons {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut comparisons = Self { eq: false, hash: false };
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+            if key == "eq" {
+                comparisons.eq = true;
+            } else if key == "hash" {
+                comparisons.hash = true;
+            } else {
+                return Err(Error::new(
+                    key.span(),
+                    "Expected `eq` or `hash`, which can't be combined with other arguments.",
+                ));
+            }
+            input.parse::<Token![=]>()?;
+            let value: LitStr = input.parse()?;
+            if value.value() != "borsh" {
+                return Err(Error::new(value.span(), "Only `\"borsh\"` is supported."));
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(comparisons)
+    }
+}
+
+/// Implements the traits requested by `#[near_bindgen(eq = "borsh", hash = "borsh")]` on the
+/// struct or enum, which is otherwise left unchanged.
+pub(crate) fn near_borsh_comparisons(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let comparisons = match syn::parse::<BorshComparisons>(attr) {
+        Ok(comparisons) => comparisons,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let generated = if let Ok(input) = syn::parse::<ItemEnum>(item.clone()) {
+        comparisons.generate(&input.ident, &input.generics)
+    } else if let Ok(input) = syn::parse::<ItemStruct>(item.clone()) {
+        comparisons.generate(&input.ident, &input.generics)
+    } else {
+        return TokenStream::from(
+            syn::Error::new(
+                Span::call_site(),
+                "`#[near_bindgen(eq = \"borsh\", hash = \"borsh\")]` can only be used on structs or enums.",
+            )
+            .to_compile_error(),
+        );
+    };
+    let item = TokenStream2::from(item);
+    TokenStream::from(quote! {
+        #item
+        #generated
+    })
+}
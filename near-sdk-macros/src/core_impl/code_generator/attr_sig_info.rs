@@ -58,6 +58,10 @@ impl AttrSigInfo {
             "Can only generate input struct for when input args are specified"
         );
         let attribute = match &self.input_serializer {
+            SerializerType::JSON if self.input_auto => quote! {
+                #[derive(near_sdk::serde::Deserialize, near_sdk::borsh::BorshDeserialize)]
+                #[serde(crate = "near_sdk::serde")]
+            },
             SerializerType::JSON => quote! {
                 #[derive(near_sdk::serde::Deserialize)]
                 #[serde(crate = "near_sdk::serde")]
@@ -211,29 +215,7 @@ impl AttrSigInfo {
                             return syn::Error::new_spanned(ty, "Function parameters marked with \
                                 #[callback_result] should have type Result<T, PromiseError>").into_compile_error()
                         };
-                        let deserialize = deserialize_data(serializer_ty);
-                        let deserialization_branch = match ok_type {
-                            // The unit type in this context is a bit special because functions
-                            // without an explicit return type do not serialize their response.
-                            // But when someone tries to refer to their callback result with
-                            // `#[callback_result]` they specify the callback type as
-                            // `Result<(), PromiseError>` which cannot be correctly deserialized from
-                            // an empty byte array.
-                            //
-                            // So instead of going through serde, we consider deserialization to be
-                            // successful if the byte array is empty or try the normal
-                            // deserialization otherwise.
-                            syn::Type::Tuple(type_tuple) if type_tuple.elems.is_empty() =>
-                                quote! {
-                                    near_sdk::PromiseResult::Successful(data) if data.is_empty() =>
-                                        Ok(()),
-                                    near_sdk::PromiseResult::Successful(data) => Ok(#deserialize)
-                                },
-                            _ =>
-                                quote! {
-                                    near_sdk::PromiseResult::Successful(data) => Ok(#deserialize)
-                                }
-                        };
+                        let deserialization_branch = callback_result_branch(ok_type, serializer_ty);
                         let result = quote! {
                             match near_sdk::env::promise_result(#idx) {
                                 #deserialization_branch,
@@ -250,23 +232,53 @@ impl AttrSigInfo {
             })
     }
 
-    /// Create code that deserializes arguments that were decorated with `#[callback_vec]`.
+    /// Create code that deserializes arguments that were decorated with `#[callback_vec]` or
+    /// `#[callback_results]`.
     pub fn callback_vec_deserialization(&self) -> TokenStream2 {
         self
             .args
             .iter()
-            .filter(|arg| matches!(arg.bindgen_ty, BindgenArgType::CallbackArgVec))
+            .filter(|arg| {
+                matches!(
+                    arg.bindgen_ty,
+                    BindgenArgType::CallbackArgVec | BindgenArgType::CallbackResultArgVec
+                )
+            })
             .fold(TokenStream2::new(), |acc, arg| {
-                let ArgInfo { mutability, ident, ty, .. } = arg;
-                let invocation = deserialize_data(&arg.serializer_ty);
+                let ArgInfo { mutability, ident, ty, bindgen_ty, serializer_ty, .. } = arg;
+                let invocation = match bindgen_ty {
+                    BindgenArgType::CallbackArgVec => {
+                        let deserialize = deserialize_data(serializer_ty);
+                        quote! {
+                            let data: Vec<u8> = match near_sdk::env::promise_result(i) {
+                                near_sdk::PromiseResult::Successful(x) => x,
+                                _ => near_sdk::env::panic_str(&format!("Callback computation {} was not successful", i)),
+                            };
+                            #deserialize
+                        }
+                    }
+                    _ => {
+                        let ok_type = if let Some(ok_type) =
+                            utils::extract_vec_type(ty).and_then(utils::extract_ok_type)
+                        {
+                            ok_type
+                        } else {
+                            return syn::Error::new_spanned(ty, "Function parameters marked with \
+                                #[callback_results] should have type Vec<Result<T, PromiseError>>").into_compile_error()
+                        };
+                        let deserialization_branch = callback_result_branch(ok_type, serializer_ty);
+                        quote! {
+                            match near_sdk::env::promise_result(i) {
+                                #deserialization_branch,
+                                near_sdk::PromiseResult::Failed => Err(near_sdk::PromiseError::Failed),
+                            }
+                        }
+                    }
+                };
                 quote! {
                 #acc
                 let #mutability #ident: #ty = (0..near_sdk::env::promise_results_count())
                 .map(|i| {
-                    let data: Vec<u8> = match near_sdk::env::promise_result(i) {
-                        near_sdk::PromiseResult::Successful(x) => x,
-                        _ => near_sdk::env::panic_str(&format!("Callback computation {} was not successful", i)),
-                    };
                     #invocation
                 }).collect();
             }
@@ -274,6 +286,32 @@ impl AttrSigInfo {
     }
 }
 
+/// Create the match arms that deserialize a successful promise result into `Ok` for arguments
+/// decorated with `#[callback_result]` or `#[callback_results]`.
+fn callback_result_branch(ok_type: &syn::Type, serializer_ty: &SerializerType) -> TokenStream2 {
+    let deserialize = deserialize_data(serializer_ty);
+    match ok_type {
+        // The unit type in this context is a bit special because functions
+        // without an explicit return type do not serialize their response.
+        // But when someone tries to refer to their callback result with
+        // `#[callback_result]` they specify the callback type as
+        // `Result<(), PromiseError>` which cannot be correctly deserialized from
+        // an empty byte array.
+        //
+        // So instead of going through serde, we consider deserialization to be
+        // successful if the byte array is empty or try the normal
+        // deserialization otherwise.
+        syn::Type::Tuple(type_tuple) if type_tuple.elems.is_empty() => quote! {
+            near_sdk::PromiseResult::Successful(data) if data.is_empty() =>
+                Ok(()),
+            near_sdk::PromiseResult::Successful(data) => Ok(#deserialize)
+        },
+        _ => quote! {
+            near_sdk::PromiseResult::Successful(data) => Ok(#deserialize)
+        },
+    }
+}
+
 pub fn deserialize_data(ty: &SerializerType) -> TokenStream2 {
     match ty {
         SerializerType::JSON => quote! {
@@ -24,8 +24,9 @@ pub(crate) fn generate_ext_structs(
     };
     if let Some(generics) = generic_details {
         // If ext generation is on struct, make ext function associated with struct not module
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
         ext_code = quote! {
-            impl #generics #ident #generics {
+            impl #impl_generics #ident #ty_generics #where_clause {
                 #ext_code
             }
         };
@@ -191,6 +192,28 @@ mod tests {
         assert_eq!(expected.to_string(), actual.to_string());
     }
 
+    #[test]
+    fn ext_gen_generic() {
+        let st: ItemStruct = parse_quote! {
+            struct Test<T: BorshSerialize> where T: Clone { a: T }
+        };
+        let actual = generate_ext_structs(&st.ident, Some(&st.generics));
+        let expected = quote!(
+            impl<T: BorshSerialize> Test<T> where T: Clone {
+                /// API for calling this contract's functions in a subsequent execution.
+                pub fn ext(account_id: near_sdk::AccountId) -> TestExt {
+                    TestExt {
+                        account_id,
+                        deposit: 0,
+                        static_gas: near_sdk::Gas(0),
+                        gas_weight: near_sdk::GasWeight::default(),
+                    }
+                }
+            }
+        );
+        assert!(actual.to_string().ends_with(&expected.to_string()));
+    }
+
     #[test]
     fn module_ext_gen() {
         let ident: Ident = parse_quote! { Test };
@@ -1,5 +1,5 @@
 use crate::core_impl::info_extractor::{
-    AttrSigInfo, ImplItemMethodInfo, MethodType, SerializerType,
+    ArgInfo, AttrSigInfo, ImplItemMethodInfo, MethodType, PrivateAllow, SerializerType,
 };
 use crate::core_impl::utils;
 use proc_macro2::TokenStream as TokenStream2;
@@ -10,19 +10,61 @@ use syn::ReturnType;
 impl ImplItemMethodInfo {
     /// Generate wrapper method for the given method of the contract.
     pub fn method_wrapper(&self) -> TokenStream2 {
+        let body = match self.wrapper_body() {
+            Ok(body) => body,
+            Err(err) => return err,
+        };
+        let AttrSigInfo { non_bindgen_attrs, ident, .. } = &self.attr_signature_info;
+        quote! {
+            #(#non_bindgen_attrs)*
+            #[cfg(target_arch = "wasm32")]
+            #[no_mangle]
+            pub extern "C" fn #ident() {
+                near_sdk::env::setup_panic_hook();
+                #body
+            }
+        }
+    }
+
+    /// Generate the wrapper of the given method for the unit tests of the contract. It runs the
+    /// same checks, deserialization and state handling as the exported wrapper, so that tests can
+    /// call the method with the input and context set by `testing_env!`.
+    ///
+    /// The panic hook isn't set up, to keep the panics of the test harness. Errors are left to
+    /// the exported wrapper to report.
+    pub fn test_method_wrapper(&self) -> TokenStream2 {
+        let body = match self.wrapper_body() {
+            Ok(body) => body,
+            Err(_) => return TokenStream2::new(),
+        };
+        let AttrSigInfo { non_bindgen_attrs, ident, .. } = &self.attr_signature_info;
+        quote! {
+            #(#non_bindgen_attrs)*
+            #[cfg(all(test, not(target_arch = "wasm32")))]
+            #[allow(dead_code)]
+            pub fn #ident() {
+                #body
+            }
+        }
+    }
+
+    /// Generate the body of the wrapper of the given method, or the compile error to emit instead.
+    fn wrapper_body(&self) -> Result<TokenStream2, TokenStream2> {
         let ImplItemMethodInfo { attr_signature_info, struct_type, .. } = self;
         // Args provided by `env::input()`.
         let has_input_args = attr_signature_info.input_args().next().is_some();
 
-        let panic_hook = quote! {
-            near_sdk::env::setup_panic_hook();
-        };
         let arg_struct;
         let arg_parsing;
         if has_input_args {
             arg_struct = attr_signature_info.input_struct_deser();
             let decomposition = attr_signature_info.decomposition_pattern();
             let serializer_invocation = match attr_signature_info.input_serializer {
+                SerializerType::JSON if attr_signature_info.input_auto => quote! {
+                    near_sdk::__private::deserialize_input_auto(
+                        &near_sdk::env::input().expect("Expected input since method has arguments.")
+                    )
+                },
                 SerializerType::JSON => quote! {
                     near_sdk::serde_json::from_slice(
                         &near_sdk::env::input().expect("Expected input since method has arguments.")
@@ -37,6 +79,16 @@ impl ImplItemMethodInfo {
             arg_parsing = quote! {
                 let #decomposition : Input = #serializer_invocation ;
             };
+        } else if attr_signature_info.deny_args {
+            let error = format!("Method {} doesn't accept arguments", attr_signature_info.ident);
+            arg_struct = TokenStream2::new();
+            arg_parsing = quote! {
+                if let Some(input) = near_sdk::env::input() {
+                    if !near_sdk::__private::is_empty_input(&input) {
+                        near_sdk::env::panic_str(#error);
+                    }
+                }
+            };
         } else {
             arg_struct = TokenStream2::new();
             arg_parsing = TokenStream2::new();
@@ -47,18 +99,33 @@ impl ImplItemMethodInfo {
 
         let arg_list = attr_signature_info.arg_list();
         let AttrSigInfo {
-            non_bindgen_attrs,
             ident,
             receiver,
             returns,
             result_serializer,
+            result_skip_none,
+            result_sorted_keys,
             method_type,
             is_payable,
+            min_deposit,
             is_private,
+            private_allow,
             is_handles_result,
             ..
         } = attr_signature_info;
-        let deposit_check = if *is_payable || matches!(method_type, &MethodType::View) {
+        let deposit_check = if let Some(min_deposit) = min_deposit {
+            // Payable method requiring a minimum deposit
+            let error = format!(
+                "Method {} requires an attached deposit of at least {} yoctoNEAR",
+                ident, min_deposit
+            );
+            let min_deposit = proc_macro2::Literal::u128_unsuffixed(*min_deposit);
+            quote! {
+                if near_sdk::env::attached_deposit() < #min_deposit {
+                    near_sdk::env::panic_str(#error);
+                }
+            }
+        } else if *is_payable || matches!(method_type, &MethodType::View) {
             // No check if the method is payable or a view method
             quote! {}
         } else {
@@ -70,25 +137,55 @@ impl ImplItemMethodInfo {
                 }
             }
         };
-        let is_private_check = if *is_private {
-            let error = format!("Method {} is private", ident);
-            quote! {
+        let private_error = format!("Method {} is private", ident);
+        let is_private_check = match private_allow {
+            _ if !*is_private => quote! {},
+            Some(PrivateAllow::Accounts(accounts)) if !accounts.is_empty() => quote! {
+                {
+                    let predecessor = near_sdk::env::predecessor_account_id();
+                    if near_sdk::env::current_account_id() != predecessor
+                        && ![#(#accounts),*].contains(&predecessor.as_str())
+                    {
+                        near_sdk::env::panic_str(#private_error);
+                    }
+                }
+            },
+            None | Some(PrivateAllow::Accounts(_)) => quote! {
                 if near_sdk::env::current_account_id() != near_sdk::env::predecessor_account_id() {
-                    near_sdk::env::panic_str(#error);
+                    near_sdk::env::panic_str(#private_error);
                 }
-            }
-        } else {
-            quote! {}
+            },
+            // Checked once the contract state is loaded.
+            Some(PrivateAllow::Field(_) | PrivateAllow::Method(_)) => quote! {},
         };
+        let allowed_member_check = match private_allow {
+            Some(PrivateAllow::Field(field)) if *is_private => Some(quote! { contract.#field }),
+            Some(PrivateAllow::Method(method)) if *is_private => {
+                Some(quote! { contract.#method() })
+            }
+            _ => None,
+        }
+        .map(|allowed| {
+            quote! {
+                {
+                    let predecessor = near_sdk::env::predecessor_account_id();
+                    if near_sdk::env::current_account_id() != predecessor
+                        && predecessor.as_str() != ::core::convert::AsRef::<str>::as_ref(&#allowed)
+                    {
+                        near_sdk::env::panic_str(#private_error);
+                    }
+                }
+            }
+        });
         let body = if matches!(method_type, &MethodType::Init) {
             match init_method_wrapper(self, true) {
                 Ok(wrapper) => wrapper,
-                Err(err) => return err.to_compile_error(),
+                Err(err) => return Err(err.to_compile_error()),
             }
         } else if matches!(method_type, &MethodType::InitIgnoreState) {
             match init_method_wrapper(self, false) {
                 Ok(wrapper) => wrapper,
-                Err(err) => return err.to_compile_error(),
+                Err(err) => return Err(err.to_compile_error()),
             }
         } else {
             let contract_deser;
@@ -98,6 +195,7 @@ impl ImplItemMethodInfo {
                 let mutability = &receiver.mutability;
                 contract_deser = quote! {
                     let #mutability contract: #struct_type = near_sdk::env::state_read().unwrap_or_default();
+                    #allowed_member_check
                 };
                 method_invocation = quote! {
                     contract.#ident(#arg_list)
@@ -112,7 +210,7 @@ impl ImplItemMethodInfo {
             } else {
                 contract_deser = TokenStream2::new();
                 method_invocation = quote! {
-                    #struct_type::#ident(#arg_list)
+                    <#struct_type>::#ident(#arg_list)
                 };
                 contract_ser = TokenStream2::new();
             }
@@ -125,21 +223,14 @@ impl ImplItemMethodInfo {
                 ReturnType::Type(_, return_type)
                     if utils::type_is_result(return_type) && *is_handles_result =>
                 {
-                    let value_ser = match result_serializer {
-                        SerializerType::JSON => quote! {
-                            let result = near_sdk::serde_json::to_vec(&result).expect("Failed to serialize the return value using JSON.");
-                        },
-                        SerializerType::Borsh => quote! {
-                            let result = near_sdk::borsh::BorshSerialize::try_to_vec(&result).expect("Failed to serialize the return value using Borsh.");
-                        },
-                    };
+                    let value_ser = value_ser(result_serializer, *result_sorted_keys);
+                    let value_return = value_return(value_ser, *result_skip_none);
                     quote! {
                         #contract_deser
                         let result = #method_invocation;
                         match result {
                             Ok(result) => {
-                                #value_ser
-                                near_sdk::env::value_return(&result);
+                                #value_return
                                 #contract_ser
                             }
                             Err(err) => near_sdk::FunctionError::panic(&err)
@@ -147,56 +238,37 @@ impl ImplItemMethodInfo {
                     }
                 }
                 ReturnType::Type(_, return_type) if *is_handles_result => {
-                    return syn::Error::new(
+                    return Err(syn::Error::new(
                         return_type.span(),
                         "Method marked with #[handle_result] should return Result<T, E> (where E implements FunctionError).",
                     )
-                    .to_compile_error();
+                    .to_compile_error());
                 }
                 ReturnType::Type(_, return_type) if utils::type_is_result(return_type) => {
-                    return syn::Error::new(return_type.span(), RESULT_DEPRECATED_MESSAGE)
-                        .to_compile_error();
+                    return Err(syn::Error::new(return_type.span(), RESULT_DEPRECATED_MESSAGE)
+                        .to_compile_error());
                 }
                 ReturnType::Type(_, _) => {
-                    let value_ser = match result_serializer {
-                        SerializerType::JSON => quote! {
-                            let result = near_sdk::serde_json::to_vec(&result).expect("Failed to serialize the return value using JSON.");
-                        },
-                        SerializerType::Borsh => quote! {
-                            let result = near_sdk::borsh::BorshSerialize::try_to_vec(&result).expect("Failed to serialize the return value using Borsh.");
-                        },
-                    };
+                    let value_ser = value_ser(result_serializer, *result_sorted_keys);
+                    let value_return = value_return(value_ser, *result_skip_none);
                     quote! {
                         #contract_deser
                         let result = #method_invocation;
-                        #value_ser
-                        near_sdk::env::value_return(&result);
+                        #value_return
                         #contract_ser
                     }
                 }
             }
         };
-        let non_bindgen_attrs = non_bindgen_attrs.iter().fold(TokenStream2::new(), |acc, value| {
-            quote! {
-                #acc
-                #value
-            }
-        });
-        quote! {
-            #non_bindgen_attrs
-            #[cfg(target_arch = "wasm32")]
-            #[no_mangle]
-            pub extern "C" fn #ident() {
-                #panic_hook
-                #is_private_check
-                #deposit_check
-                #arg_struct
-                #arg_parsing
-                #callback_deser
-                #callback_vec_deser
-                #body
-            }
-        }
+        Ok(quote! {
+            #is_private_check
+            #deposit_check
+            #arg_struct
+            #arg_parsing
+            #callback_deser
+            #callback_vec_deser
+            #body
+        })
     }
 }
 
@@ -205,17 +277,33 @@ fn init_method_wrapper(
     check_state: bool,
 ) -> Result<TokenStream2, syn::Error> {
     let ImplItemMethodInfo { attr_signature_info, struct_type, .. } = method_info;
-    let arg_list = attr_signature_info.arg_list();
-    let AttrSigInfo { ident, returns, is_handles_result, .. } = attr_signature_info;
+    let mut arg_list = attr_signature_info.arg_list();
+    let AttrSigInfo { ident, returns, is_handles_result, migrate_state, run_once, .. } =
+        attr_signature_info;
     let state_check = if check_state {
         quote! {
             if near_sdk::env::state_exists() {
                 near_sdk::env::panic_str("The contract has already been initialized");
             }
         }
+    } else if *run_once {
+        let method_name = ident.to_string();
+        quote! {
+            near_sdk::__private::init_once(#method_name);
+        }
     } else {
         quote! {}
     };
+    // `#[migrate]` methods get the old state as their first argument.
+    let state_check = if let Some(ArgInfo { ident: state_ident, ty, .. }) = migrate_state {
+        arg_list = quote! { #state_ident, #arg_list };
+        quote! {
+            #state_check
+            let #state_ident: #ty = near_sdk::__private::read_state_to_migrate();
+        }
+    } else {
+        state_check
+    };
     match returns {
         ReturnType::Default => {
             Err(syn::Error::new(ident.span(), "Init methods must return the contract state"))
@@ -225,9 +313,9 @@ fn init_method_wrapper(
         {
             Ok(quote! {
                 #state_check
-                let result = #struct_type::#ident(#arg_list);
+                let result = <#struct_type>::#ident(#arg_list);
                 match result {
-                    Ok(contract) => near_sdk::env::state_write(&contract),
+                    Ok(contract) => near_sdk::env::state_write::<#struct_type>(&contract),
                     Err(err) => near_sdk::FunctionError::panic(&err)
                 }
             })
@@ -244,12 +332,44 @@ fn init_method_wrapper(
         }
         ReturnType::Type(_, _) => Ok(quote! {
             #state_check
-            let contract = #struct_type::#ident(#arg_list);
-            near_sdk::env::state_write(&contract);
+            let contract = <#struct_type>::#ident(#arg_list);
+            near_sdk::env::state_write::<#struct_type>(&contract);
         }),
     }
 }
 
+/// Serializes `result` into the bytes returned by the method.
+fn value_ser(result_serializer: &SerializerType, sorted_keys: bool) -> TokenStream2 {
+    match result_serializer {
+        SerializerType::JSON if sorted_keys => quote! {
+            let result = near_sdk::__private::to_json_vec_sorted_keys(&result).expect("Failed to serialize the return value using JSON.");
+        },
+        SerializerType::JSON => quote! {
+            let result = near_sdk::serde_json::to_vec(&result).expect("Failed to serialize the return value using JSON.");
+        },
+        SerializerType::Borsh => quote! {
+            let result = near_sdk::borsh::BorshSerialize::try_to_vec(&result).expect("Failed to serialize the return value using Borsh.");
+        },
+    }
+}
+
+/// Returns the serialized `result`, or nothing if it is `None` and `skip_none` is set.
+fn value_return(value_ser: TokenStream2, skip_none: bool) -> TokenStream2 {
+    let value_return = quote! {
+        #value_ser
+        near_sdk::env::value_return(&result);
+    };
+    if skip_none {
+        quote! {
+            if let ::core::option::Option::Some(result) = &result {
+                #value_return
+            }
+        }
+    } else {
+        value_return
+    }
+}
+
 static RESULT_DEPRECATED_MESSAGE: &str = "\
 Serializing Result<T, E> has been deprecated. Consider marking your method \
 with #[handle_result] if the second generic represents a panicable error or \
@@ -1,8 +1,9 @@
 use crate::core_impl::ext::generate_ext_function_wrappers;
+use crate::core_impl::BindgenArgType;
 use crate::ItemImplInfo;
 use proc_macro2::TokenStream as TokenStream2;
-use quote::ToTokens;
-use syn::{spanned::Spanned, Ident};
+use quote::quote_spanned;
+use syn::{spanned::Spanned, Error, Type};
 
 impl ItemImplInfo {
     /// Generate the code that wraps
@@ -10,17 +11,68 @@ impl ItemImplInfo {
         let mut res = TokenStream2::new();
         for method in &self.methods {
             res.extend(method.method_wrapper());
+            res.extend(method.test_method_wrapper());
         }
         res
     }
 
+    /// Generate the checks that the callbacks linked with `#[callback_link]` exist. Callbacks
+    /// defined in the same `impl` block must also read a promise result.
+    ///
+    /// The checks are generic over the generics of the `impl`, so they can name its type.
+    pub fn callback_link_checks(&self) -> syn::Result<TokenStream2> {
+        let ty = &self.ty;
+        let (impl_generics, _, where_clause) = self.generics.split_for_impl();
+        let mut res = TokenStream2::new();
+        for callback in
+            self.methods.iter().filter_map(|m| m.attr_signature_info.callback_link.as_ref())
+        {
+            let linked = self.methods.iter().find(|m| &m.attr_signature_info.ident == callback);
+            if let Some(linked) = linked {
+                let reads_promise_result = linked
+                    .attr_signature_info
+                    .args
+                    .iter()
+                    .any(|arg| !matches!(arg.bindgen_ty, BindgenArgType::Regular));
+                if !reads_promise_result {
+                    return Err(Error::new(
+                        callback.span(),
+                        format!(
+                            "The linked callback `{}` must read the promise result, e.g. with a `#[callback_unwrap]` or `#[callback_result]` argument.",
+                            callback
+                        ),
+                    ));
+                }
+            }
+            res.extend(quote_spanned! {callback.span()=>
+                const _: () = {
+                    #[allow(dead_code)]
+                    fn callback_link_check #impl_generics () #where_clause {
+                        let _ = <#ty>::#callback;
+                    }
+                };
+            });
+        }
+        Ok(res)
+    }
+
     pub fn generate_ext_wrapper_code(&self) -> TokenStream2 {
-        match syn::parse::<Ident>(self.ty.to_token_stream().into()) {
-            Ok(n) => generate_ext_function_wrappers(
-                &n,
+        // The ext struct of a generic contract isn't generic, so `Contract<u64>` uses
+        // `ContractExt` too.
+        let ident = match &self.ty {
+            Type::Path(type_path) if type_path.qself.is_none() => {
+                type_path.path.segments.last().map(|segment| &segment.ident)
+            }
+            _ => None,
+        };
+        match ident {
+            Some(ident) => generate_ext_function_wrappers(
+                ident,
                 self.methods.iter().map(|m| &m.attr_signature_info),
             ),
-            Err(e) => syn::Error::new(self.ty.span(), e).to_compile_error(),
+            None => {
+                syn::Error::new(self.ty.span(), "expected the contract type").to_compile_error()
+            }
         }
     }
 }
@@ -454,6 +506,37 @@ mod tests {
         assert_eq!(expected.to_string(), actual.to_string());
     }
 
+    #[test]
+    fn callback_args_results_vec() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[private] pub fn method(&self, #[callback_results] x: Vec<Result<String, PromiseError>>) { }
+        };
+        let method_info = ImplItemMethodInfo::new(&mut method, false, impl_type).unwrap().unwrap();
+        let actual = method_info.method_wrapper();
+        let expected = quote!(
+            #[cfg(target_arch = "wasm32")]
+            #[no_mangle]
+            pub extern "C" fn method() {
+                near_sdk::env::setup_panic_hook();
+                if near_sdk::env::current_account_id() != near_sdk::env::predecessor_account_id() {
+                    near_sdk::env::panic_str("Method method is private");
+                }
+                let x: Vec<Result<String, PromiseError> > = (0..near_sdk::env::promise_results_count())
+                    .map(|i| {
+                        match near_sdk::env::promise_result(i) {
+                            near_sdk::PromiseResult::Successful(data) => Ok(near_sdk::serde_json::from_slice(&data).expect("Failed to deserialize callback using JSON")),
+                            near_sdk::PromiseResult::Failed => Err(near_sdk::PromiseError::Failed),
+                        }
+                    })
+                    .collect();
+                let contract: Hello = near_sdk::env::state_read().unwrap_or_default();
+                contract.method(x, );
+            }
+        );
+        assert_eq!(expected.to_string(), actual.to_string());
+    }
+
     #[test]
     fn simple_init() {
         let impl_type: Type = syn::parse_str("Hello").unwrap();
@@ -483,8 +566,8 @@ mod tests {
                 if near_sdk::env::state_exists() {
                     near_sdk::env::panic_str("The contract has already been initialized");
                 }
-                let contract = Hello::method(&mut k,);
-                near_sdk::env::state_write(&contract);
+                let contract = <Hello>::method(&mut k,);
+                near_sdk::env::state_write::<Hello>(&contract);
             }
         );
         assert_eq!(expected.to_string(), actual.to_string());
@@ -528,13 +611,184 @@ mod tests {
                     &near_sdk::env::input().expect("Expected input since method has arguments.")
                 )
                 .expect("Failed to deserialize input from JSON.");
-                let contract = Hello::method(&mut k,);
+                near_sdk::__private::init_once("method");
+                let contract = <Hello>::method(&mut k,);
+                near_sdk::env::state_write::<Hello>(&contract);
+            }
+        );
+        assert_eq!(expected.to_string(), actual.to_string());
+    }
+
+    #[test]
+    fn init_ignore_state_private() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[private]
+            #[init(ignore_state)]
+            pub fn method() -> Self { }
+        };
+        let method_info = ImplItemMethodInfo::new(&mut method, false, impl_type).unwrap().unwrap();
+        let actual = method_info.method_wrapper();
+        let expected = quote!(
+            #[cfg(target_arch = "wasm32")]
+            #[no_mangle]
+            pub extern "C" fn method() {
+                near_sdk::env::setup_panic_hook();
+                if near_sdk::env::current_account_id() != near_sdk::env::predecessor_account_id() {
+                    near_sdk::env::panic_str("Method method is private");
+                }
+                if near_sdk::env::attached_deposit() != 0 {
+                    near_sdk::env::panic_str("Method method doesn't accept deposit");
+                }
+                near_sdk::__private::init_once("method");
+                let contract = <Hello>::method();
+                near_sdk::env::state_write::<Hello>(&contract);
+            }
+        );
+        assert_eq!(expected.to_string(), actual.to_string());
+    }
+
+    #[test]
+    fn migrate() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[migrate(from = OldHello)]
+            pub fn migrate(old: OldHello, k: u64) -> Self { }
+        };
+        let method_info = ImplItemMethodInfo::new(&mut method, false, impl_type).unwrap().unwrap();
+        let actual = method_info.method_wrapper();
+        let expected = quote!(
+            #[cfg(target_arch = "wasm32")]
+            #[no_mangle]
+            pub extern "C" fn migrate() {
+                near_sdk::env::setup_panic_hook();
+                if near_sdk::env::current_account_id() != near_sdk::env::predecessor_account_id() {
+                    near_sdk::env::panic_str("Method migrate is private");
+                }
+                if near_sdk::env::attached_deposit() != 0 {
+                    near_sdk::env::panic_str("Method migrate doesn't accept deposit");
+                }
+                #[derive(near_sdk :: serde :: Deserialize)]
+                #[serde(crate = "near_sdk::serde")]
+                struct Input {
+                    k: u64,
+                }
+                let Input { k, }: Input = near_sdk::serde_json::from_slice(
+                    &near_sdk::env::input().expect("Expected input since method has arguments.")
+                )
+                .expect("Failed to deserialize input from JSON.");
+                let old: OldHello = near_sdk::__private::read_state_to_migrate();
+                let contract = <Hello>::migrate(old, k,);
+                near_sdk::env::state_write::<Hello>(&contract);
+            }
+        );
+        assert_eq!(expected.to_string(), actual.to_string());
+    }
+
+    #[test]
+    fn migrate_without_old_state() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[migrate(from = OldHello)]
+            pub fn migrate() -> Self { }
+        };
+        let error = ImplItemMethodInfo::new(&mut method, false, impl_type).map(|_| ()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The first argument of a `#[migrate]` function must be the old state, e.g. `old: OldHello`."
+        );
+    }
+
+    #[test]
+    fn init_ignore_state_private_allow_repeat() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[private]
+            #[init(ignore_state, allow_repeat)]
+            pub fn method() -> Self { }
+        };
+        let method_info = ImplItemMethodInfo::new(&mut method, false, impl_type).unwrap().unwrap();
+        let actual = method_info.method_wrapper();
+        let expected = quote!(
+            #[cfg(target_arch = "wasm32")]
+            #[no_mangle]
+            pub extern "C" fn method() {
+                near_sdk::env::setup_panic_hook();
+                if near_sdk::env::current_account_id() != near_sdk::env::predecessor_account_id() {
+                    near_sdk::env::panic_str("Method method is private");
+                }
+                if near_sdk::env::attached_deposit() != 0 {
+                    near_sdk::env::panic_str("Method method doesn't accept deposit");
+                }
+                let contract = <Hello>::method();
+                near_sdk::env::state_write::<Hello>(&contract);
+            }
+        );
+        assert_eq!(expected.to_string(), actual.to_string());
+    }
+
+    #[test]
+    fn init_allow_repeat_without_ignore_state() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[init(allow_repeat)]
+            pub fn method() -> Self { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, false, impl_type).map(|_| ()).unwrap_err();
+        let expected = "`allow_repeat` can only be used together with `ignore_state`.";
+        assert_eq!(expected, actual.to_string());
+    }
+
+    #[test]
+    fn init_private_without_ignore_state() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[private]
+            #[init]
+            pub fn method() -> Self { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, false, impl_type).map(|_| ()).unwrap_err();
+        let expected = "Init function can't be private unless it is `#[init(ignore_state)]`.";
+        assert_eq!(expected, actual.to_string());
+    }
+
+    #[test]
+    fn payable_min_deposit() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[payable(min = "1.5 NEAR")]
+            pub fn method(&mut self) { }
+        };
+        let method_info = ImplItemMethodInfo::new(&mut method, false, impl_type).unwrap().unwrap();
+        let actual = method_info.method_wrapper();
+        let expected = quote!(
+            #[cfg(target_arch = "wasm32")]
+            #[no_mangle]
+            pub extern "C" fn method() {
+                near_sdk::env::setup_panic_hook();
+                if near_sdk::env::attached_deposit() < 1500000000000000000000000 {
+                    near_sdk::env::panic_str("Method method requires an attached deposit of at least 1500000000000000000000000 yoctoNEAR");
+                }
+                let mut contract: Hello = near_sdk::env::state_read().unwrap_or_default();
+                contract.method();
                 near_sdk::env::state_write(&contract);
             }
         );
         assert_eq!(expected.to_string(), actual.to_string());
     }
 
+    #[test]
+    fn payable_invalid_min_deposit() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[payable(min = "1 near")]
+            pub fn method(&mut self) { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, false, impl_type).map(|_| ()).unwrap_err();
+        let expected = "Invalid minimum deposit. Expected an amount of NEAR, mNEAR or yoctoNEAR, e.g. \"1 NEAR\" or \"0.5 mNEAR\".";
+        assert_eq!(expected, actual.to_string());
+    }
+
     #[test]
     fn init_payable() {
         let impl_type: Type = syn::parse_str("Hello").unwrap();
@@ -562,8 +816,8 @@ mod tests {
                 if near_sdk::env::state_exists() {
                     near_sdk::env::panic_str("The contract has already been initialized");
                 }
-                let contract = Hello::method(&mut k,);
-                near_sdk::env::state_write(&contract);
+                let contract = <Hello>::method(&mut k,);
+                near_sdk::env::state_write::<Hello>(&contract);
             }
         );
         assert_eq!(expected.to_string(), actual.to_string());
@@ -606,6 +860,245 @@ mod tests {
         assert_eq!(expected.to_string(), actual.to_string());
     }
 
+    #[test]
+    fn method_serializer_borsh() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[serializer(borsh)]
+            pub fn method(&mut self, k: u64, m: Bar) { }
+        };
+        let method_info = ImplItemMethodInfo::new(&mut method, false, impl_type).unwrap().unwrap();
+        let actual = method_info.method_wrapper();
+        let expected = quote!(
+            #[cfg(target_arch = "wasm32")]
+            #[no_mangle]
+            pub extern "C" fn method() {
+                near_sdk::env::setup_panic_hook();
+                if near_sdk::env::attached_deposit() != 0 {
+                    near_sdk::env::panic_str("Method method doesn't accept deposit");
+                }
+                #[derive(near_sdk :: borsh :: BorshDeserialize)]
+                struct Input {
+                    k: u64,
+                    m: Bar,
+                }
+                let Input { k, m, }: Input = near_sdk::borsh::BorshDeserialize::try_from_slice(
+                    &near_sdk::env::input().expect("Expected input since method has arguments.")
+                )
+                .expect("Failed to deserialize input from Borsh.");
+                let mut contract: Hello = near_sdk::env::state_read().unwrap_or_default();
+                contract.method(k, m, );
+                near_sdk::env::state_write(&contract);
+            }
+        );
+        assert_eq!(expected.to_string(), actual.to_string());
+    }
+
+    #[test]
+    fn method_serializer_auto() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[serializer(auto)]
+            pub fn method(&mut self, k: u64, m: Bar) { }
+        };
+        let method_info = ImplItemMethodInfo::new(&mut method, false, impl_type).unwrap().unwrap();
+        let actual = method_info.method_wrapper();
+        let expected = quote!(
+            #[cfg(target_arch = "wasm32")]
+            #[no_mangle]
+            pub extern "C" fn method() {
+                near_sdk::env::setup_panic_hook();
+                if near_sdk::env::attached_deposit() != 0 {
+                    near_sdk::env::panic_str("Method method doesn't accept deposit");
+                }
+                #[derive(near_sdk :: serde :: Deserialize, near_sdk :: borsh :: BorshDeserialize)]
+                #[serde(crate = "near_sdk::serde")]
+                struct Input {
+                    k: u64,
+                    m: Bar,
+                }
+                let Input { k, m, }: Input = near_sdk::__private::deserialize_input_auto(
+                    &near_sdk::env::input().expect("Expected input since method has arguments.")
+                );
+                let mut contract: Hello = near_sdk::env::state_read().unwrap_or_default();
+                contract.method(k, m, );
+                near_sdk::env::state_write(&contract);
+            }
+        );
+        assert_eq!(expected.to_string(), actual.to_string());
+    }
+
+    #[test]
+    fn method_serializer_auto_with_borsh_arg() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[serializer(auto)]
+            pub fn method(&mut self, #[serializer(borsh)] m: Bar) { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, false, impl_type).map(|_| ()).unwrap_err();
+        let expected = "Input arguments should be all of the same serialization type.";
+        assert_eq!(expected, actual.to_string());
+    }
+
+    #[test]
+    fn result_serializer_auto() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[result_serializer(auto)]
+            pub fn method(&self) -> u64 { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, false, impl_type).map(|_| ()).unwrap_err();
+        let expected = "The `auto` serializer can only be used for method arguments.";
+        assert_eq!(expected, actual.to_string());
+    }
+
+    #[test]
+    fn result_serializer_skip_none() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[result_serializer(json, skip_none)]
+            pub fn method(&self) -> Option<u64> { }
+        };
+        let method_info = ImplItemMethodInfo::new(&mut method, false, impl_type).unwrap().unwrap();
+        let actual = method_info.method_wrapper();
+        let expected = quote!(
+            #[cfg(target_arch = "wasm32")]
+            #[no_mangle]
+            pub extern "C" fn method() {
+                near_sdk::env::setup_panic_hook();
+                let contract: Hello = near_sdk::env::state_read().unwrap_or_default();
+                let result = contract.method();
+                if let ::core::option::Option::Some(result) = &result {
+                    let result =
+                        near_sdk::serde_json::to_vec(&result).expect("Failed to serialize the return value using JSON.");
+                    near_sdk::env::value_return(&result);
+                }
+            }
+        );
+        assert_eq!(expected.to_string(), actual.to_string());
+    }
+
+    #[test]
+    fn result_serializer_skip_none_handle_result() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[handle_result]
+            #[result_serializer(json, skip_none)]
+            pub fn method(&mut self) -> Result<Option<u64>, &'static str> { }
+        };
+        let method_info = ImplItemMethodInfo::new(&mut method, false, impl_type).unwrap().unwrap();
+        let actual = method_info.method_wrapper();
+        let expected = quote!(
+            #[cfg(target_arch = "wasm32")]
+            #[no_mangle]
+            pub extern "C" fn method() {
+                near_sdk::env::setup_panic_hook();
+                if near_sdk::env::attached_deposit() != 0 {
+                    near_sdk::env::panic_str("Method method doesn't accept deposit");
+                }
+                let mut contract: Hello = near_sdk::env::state_read().unwrap_or_default();
+                let result = contract.method();
+                match result {
+                    Ok(result) => {
+                        if let ::core::option::Option::Some(result) = &result {
+                            let result =
+                                near_sdk::serde_json::to_vec(&result).expect("Failed to serialize the return value using JSON.");
+                            near_sdk::env::value_return(&result);
+                        }
+                        near_sdk::env::state_write(&contract);
+                    }
+                    Err(err) => near_sdk::FunctionError::panic(&err)
+                }
+            }
+        );
+        assert_eq!(expected.to_string(), actual.to_string());
+    }
+
+    #[test]
+    fn result_serializer_skip_none_without_option() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[result_serializer(json, skip_none)]
+            pub fn method(&self) -> u64 { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, false, impl_type).map(|_| ()).unwrap_err();
+        let expected = "The `skip_none` option can only be used on methods returning `Option<T>`.";
+        assert_eq!(expected, actual.to_string());
+    }
+
+    #[test]
+    fn result_serializer_skip_none_borsh() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[result_serializer(borsh, skip_none)]
+            pub fn method(&self) -> Option<u64> { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, false, impl_type).map(|_| ()).unwrap_err();
+        let expected = "The `skip_none` option can only be used with the `json` serializer.";
+        assert_eq!(expected, actual.to_string());
+    }
+
+    #[test]
+    fn serializer_skip_none_argument() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            pub fn method(&self, #[serializer(json, skip_none)] a: Option<u64>) { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, false, impl_type).map(|_| ()).unwrap_err();
+        let expected = "The `skip_none` option can only be used with `#[result_serializer]`.";
+        assert_eq!(expected, actual.to_string());
+    }
+
+    #[test]
+    fn result_serializer_sorted_keys() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[result_serializer(json, skip_none, sorted_keys)]
+            pub fn method(&self) -> Option<Info> { }
+        };
+        let method_info = ImplItemMethodInfo::new(&mut method, false, impl_type).unwrap().unwrap();
+        let actual = method_info.method_wrapper();
+        let expected = quote!(
+            #[cfg(target_arch = "wasm32")]
+            #[no_mangle]
+            pub extern "C" fn method() {
+                near_sdk::env::setup_panic_hook();
+                let contract: Hello = near_sdk::env::state_read().unwrap_or_default();
+                let result = contract.method();
+                if let ::core::option::Option::Some(result) = &result {
+                    let result = near_sdk::__private::to_json_vec_sorted_keys(&result)
+                        .expect("Failed to serialize the return value using JSON.");
+                    near_sdk::env::value_return(&result);
+                }
+            }
+        );
+        assert_eq!(expected.to_string(), actual.to_string());
+    }
+
+    #[test]
+    fn result_serializer_sorted_keys_borsh() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[result_serializer(borsh, sorted_keys)]
+            pub fn method(&self) -> Info { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, false, impl_type).map(|_| ()).unwrap_err();
+        let expected = "The `sorted_keys` option can only be used with the `json` serializer.";
+        assert_eq!(expected, actual.to_string());
+    }
+
+    #[test]
+    fn method_serializer_mixed_args() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[serializer(json)]
+            pub fn method(&mut self, k: u64, #[serializer(borsh)] m: Bar) { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, false, impl_type).map(|_| ()).unwrap_err();
+        let expected = "Input arguments should be all of the same serialization type.";
+        assert_eq!(expected, actual.to_string());
+    }
+
     #[test]
     fn callback_args_mixed_serialization() {
         let impl_type: Type = syn::parse_str("Hello").unwrap();
@@ -693,6 +1186,105 @@ mod tests {
         assert_eq!(expected.to_string(), actual.to_string());
     }
 
+    #[test]
+    fn private_method_allow_accounts() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[private(allow = ["alice.near", "bob.near"])]
+            pub fn private_method(&mut self) { }
+        };
+        let method_info = ImplItemMethodInfo::new(&mut method, false, impl_type).unwrap().unwrap();
+        let actual = method_info.method_wrapper();
+        let expected = quote!(
+            #[cfg(target_arch = "wasm32")]
+            #[no_mangle]
+            pub extern "C" fn private_method() {
+                near_sdk::env::setup_panic_hook();
+                {
+                    let predecessor = near_sdk::env::predecessor_account_id();
+                    if near_sdk::env::current_account_id() != predecessor
+                        && !["alice.near", "bob.near"].contains(&predecessor.as_str())
+                    {
+                        near_sdk::env::panic_str("Method private_method is private");
+                    }
+                }
+                if near_sdk::env::attached_deposit() != 0 {
+                    near_sdk::env::panic_str("Method private_method doesn't accept deposit");
+                }
+                let mut contract: Hello = near_sdk::env::state_read().unwrap_or_default();
+                contract.private_method();
+                near_sdk::env::state_write(&contract);
+            }
+        );
+        assert_eq!(expected.to_string(), actual.to_string());
+    }
+
+    #[test]
+    fn private_method_allow_field() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[private(allow = "owner")]
+            pub fn private_method(&self) { }
+        };
+        let method_info = ImplItemMethodInfo::new(&mut method, false, impl_type).unwrap().unwrap();
+        let actual = method_info.method_wrapper();
+        let expected = quote!(
+            #[cfg(target_arch = "wasm32")]
+            #[no_mangle]
+            pub extern "C" fn private_method() {
+                near_sdk::env::setup_panic_hook();
+                let contract: Hello = near_sdk::env::state_read().unwrap_or_default();
+                {
+                    let predecessor = near_sdk::env::predecessor_account_id();
+                    if near_sdk::env::current_account_id() != predecessor
+                        && predecessor.as_str() != ::core::convert::AsRef::<str>::as_ref(&contract.owner)
+                    {
+                        near_sdk::env::panic_str("Method private_method is private");
+                    }
+                }
+                contract.private_method();
+            }
+        );
+        assert_eq!(expected.to_string(), actual.to_string());
+    }
+
+    #[test]
+    fn private_method_allow_method() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[private(allow = "owner()")]
+            pub fn private_method(&self) { }
+        };
+        let method_info = ImplItemMethodInfo::new(&mut method, false, impl_type).unwrap().unwrap();
+        let actual = method_info.method_wrapper().to_string();
+        let expected = quote!(::core::convert::AsRef::<str>::as_ref(&contract.owner()));
+        assert!(actual.contains(&expected.to_string()));
+    }
+
+    #[test]
+    fn private_method_allow_field_without_self() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[private(allow = "owner")]
+            pub fn private_method() { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, false, impl_type).map(|_| ()).unwrap_err();
+        let expected = "Allowing the callers of a private method with a field or a method of the contract requires a `self` receiver.";
+        assert_eq!(expected, actual.to_string());
+    }
+
+    #[test]
+    fn private_method_allow_invalid_member() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[private(allow = "owner(1)")]
+            pub fn private_method(&self) { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, false, impl_type).map(|_| ()).unwrap_err();
+        let expected = "Expected the name of a field, e.g. \"owner\", or of a method without arguments, e.g. \"owner()\".";
+        assert_eq!(expected, actual.to_string());
+    }
+
     #[test]
     fn handle_result_json() {
         let impl_type: Type = syn::parse_str("Hello").unwrap();
@@ -773,9 +1365,9 @@ mod tests {
                 if near_sdk::env::state_exists() {
                     near_sdk::env::panic_str("The contract has already been initialized");
                 }
-                let result = Hello::new();
+                let result = <Hello>::new();
                 match result {
-                    Ok(contract) => near_sdk::env::state_write(&contract),
+                    Ok(contract) => near_sdk::env::state_write::<Hello>(&contract),
                     Err(err) => near_sdk::FunctionError::panic(&err)
                 }
             }
@@ -801,9 +1393,10 @@ mod tests {
                 if near_sdk::env::attached_deposit() != 0 {
                     near_sdk::env::panic_str("Method new doesn't accept deposit");
                 }
-                let result = Hello::new();
+                near_sdk::__private::init_once("new");
+                let result = <Hello>::new();
                 match result {
-                    Ok(contract) => near_sdk::env::state_write(&contract),
+                    Ok(contract) => near_sdk::env::state_write::<Hello>(&contract),
                     Err(err) => near_sdk::FunctionError::panic(&err)
                 }
             }
@@ -822,9 +1415,130 @@ mod tests {
             #[no_mangle]
             pub extern "C" fn method() {
                 near_sdk::env::setup_panic_hook();
-                Hello::method();
+                <Hello>::method();
             }
         );
         assert_eq!(expected.to_string(), actual.to_string());
     }
+
+    #[test]
+    fn callback_link_check() {
+        let mut item: syn::ItemImpl = parse_quote! {
+            impl Hello {
+                #[callback_link(method = "on_value")]
+                pub fn fetch(&self) -> Promise { }
+            }
+        };
+        let impl_info = crate::ItemImplInfo::new(&mut item).unwrap();
+        let actual = impl_info.callback_link_checks().unwrap();
+        let expected = quote!(
+            const _: () = {
+                #[allow(dead_code)]
+                fn callback_link_check() {
+                    let _ = <Hello>::on_value;
+                }
+            };
+        );
+        assert_eq!(expected.to_string(), actual.to_string());
+    }
+
+    #[test]
+    fn callback_link_check_where_clause() {
+        let mut item: syn::ItemImpl = parse_quote! {
+            impl Hello<u64> where u64: Copy {
+                #[callback_link(method = "on_value")]
+                pub fn fetch(&self) -> Promise { }
+            }
+        };
+        let impl_info = crate::ItemImplInfo::new(&mut item).unwrap();
+        let actual = impl_info.callback_link_checks().unwrap();
+        let expected = quote!(
+            const _: () = {
+                #[allow(dead_code)]
+                fn callback_link_check() where u64: Copy {
+                    let _ = <Hello<u64> >::on_value;
+                }
+            };
+        );
+        assert_eq!(expected.to_string(), actual.to_string());
+    }
+
+    #[test]
+    fn callback_link_without_callback_arg() {
+        let mut item: syn::ItemImpl = parse_quote! {
+            impl Hello {
+                #[callback_link(method = "on_value")]
+                pub fn fetch(&self) -> Promise { }
+
+                pub fn on_value(&mut self, value: u64) { }
+            }
+        };
+        let impl_info = crate::ItemImplInfo::new(&mut item).unwrap();
+        let actual = impl_info.callback_link_checks().unwrap_err();
+        let expected = "The linked callback `on_value` must read the promise result, e.g. with a `#[callback_unwrap]` or `#[callback_result]` argument.";
+        assert_eq!(expected, actual.to_string());
+    }
+
+    #[test]
+    fn callback_link_init() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[init]
+            #[callback_link(method = "on_value")]
+            pub fn new() -> Self { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, false, impl_type).map(|_| ()).unwrap_err();
+        let expected = "Init methods can't link a callback.";
+        assert_eq!(expected, actual.to_string());
+    }
+
+    #[test]
+    fn callback_link_invalid_method() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[callback_link(method = "on value")]
+            pub fn fetch(&self) -> Promise { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, false, impl_type).map(|_| ()).unwrap_err();
+        let expected = "Expected the name of a method, e.g. \"on_transfer\".";
+        assert_eq!(expected, actual.to_string());
+    }
+
+    #[test]
+    fn deny_empty_args() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[deny_empty_args]
+            pub fn method(&self) { }
+        };
+        let method_info = ImplItemMethodInfo::new(&mut method, false, impl_type).unwrap().unwrap();
+        let actual = method_info.method_wrapper();
+        let expected = quote!(
+            #[cfg(target_arch = "wasm32")]
+            #[no_mangle]
+            pub extern "C" fn method() {
+                near_sdk::env::setup_panic_hook();
+                if let Some(input) = near_sdk::env::input() {
+                    if !near_sdk::__private::is_empty_input(&input) {
+                        near_sdk::env::panic_str("Method method doesn't accept arguments");
+                    }
+                }
+                let contract: Hello = near_sdk::env::state_read().unwrap_or_default();
+                contract.method();
+            }
+        );
+        assert_eq!(expected.to_string(), actual.to_string());
+    }
+
+    #[test]
+    fn deny_empty_args_with_args() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[deny_empty_args]
+            pub fn method(&self, k: u64) { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, false, impl_type).map(|_| ()).unwrap_err();
+        let expected = "`#[deny_empty_args]` can only be used on methods without arguments.";
+        assert_eq!(expected, actual.to_string());
+    }
 }
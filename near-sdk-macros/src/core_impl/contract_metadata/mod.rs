@@ -0,0 +1,181 @@
+use proc_macro2::{Ident, TokenStream as TokenStream2};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Error, Generics, LitStr, Token};
+
+/// Version of NEP-330 that the generated metadata follows.
+const NEP330_VERSION: &str = "1.1.0";
+/// Version of a standard listed without one, e.g. `"nep141"`.
+const DEFAULT_STANDARD_VERSION: &str = "1.0.0";
+
+/// Arguments of `#[near_bindgen(contract_metadata(...))]`, the NEP-330 source metadata of a
+/// contract.
+pub(crate) struct ContractMetadata {
+    version: Option<String>,
+    link: Option<String>,
+    /// Pairs of standard and version.
+    standards: Vec<(String, String)>,
+}
+
+impl Parse for ContractMetadata {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident: Ident = input.parse()?;
+        if ident != "contract_metadata" {
+            return Err(Error::new(ident.span(), "Expected `contract_metadata(...)`."));
+        }
+        let content;
+        let _paren_token = syn::parenthesized!(content in input);
+        let mut metadata = Self { version: None, link: None, standards: Vec::new() };
+        while !content.is_empty() {
+            let key: Ident = content.parse()?;
+            content.parse::<Token![=]>()?;
+            if key == "version" {
+                metadata.version = Some(content.parse::<LitStr>()?.value());
+            } else if key == "link" {
+                metadata.link = Some(content.parse::<LitStr>()?.value());
+            } else if key == "standards" {
+                let list;
+                let _bracket_token = syn::bracketed!(list in content);
+                let standards: Punctuated<LitStr, Token![,]> =
+                    list.parse_terminated(<LitStr as Parse>::parse)?;
+                for standard in standards {
+                    metadata.standards.push(parse_standard(&standard)?);
+                }
+            } else {
+                return Err(Error::new(
+                    key.span(),
+                    "Unsupported contract metadata field. Expected `version`, `link` or `standards`.",
+                ));
+            }
+            if !content.is_empty() {
+                content.parse::<Token![,]>()?;
+            }
+        }
+        if !input.is_empty() {
+            return Err(input.error("Unexpected arguments after `contract_metadata(...)`."));
+        }
+        if !metadata.standards.iter().any(|(standard, _)| standard == "nep330") {
+            metadata.standards.insert(0, ("nep330".to_string(), NEP330_VERSION.to_string()));
+        }
+        Ok(metadata)
+    }
+}
+
+/// Parses a standard with an optional version, e.g. `"nep141"` or `"nep171:1.1.0"`.
+fn parse_standard(lit: &LitStr) -> syn::Result<(String, String)> {
+    let value = lit.value();
+    let (standard, version) = match value.split_once(':') {
+        Some((standard, version)) => (standard, version),
+        None => (value.as_str(), DEFAULT_STANDARD_VERSION),
+    };
+    if standard.is_empty() || version.is_empty() {
+        return Err(Error::new(
+            lit.span(),
+            "Expected a standard with an optional version, e.g. \"nep141\" or \"nep171:1.1.0\".",
+        ));
+    }
+    Ok((standard.to_string(), version.to_string()))
+}
+
+impl ContractMetadata {
+    /// Serializes the metadata into the JSON object defined by NEP-330.
+    fn to_json(&self) -> String {
+        let optional = |value: &Option<String>| value.as_deref().map_or("null".into(), json_string);
+        let standards: Vec<String> = self
+            .standards
+            .iter()
+            .map(|(standard, version)| {
+                format!(
+                    "{{\"standard\":{},\"version\":{}}}",
+                    json_string(standard),
+                    json_string(version)
+                )
+            })
+            .collect();
+        format!(
+            "{{\"version\":{},\"link\":{},\"standards\":[{}]}}",
+            optional(&self.version),
+            optional(&self.link),
+            standards.join(",")
+        )
+    }
+
+    /// Generates the `contract_source_metadata` view method of the contract `ident`, along with
+    /// a `CONTRACT_SOURCE_METADATA` constant holding the returned JSON.
+    pub(crate) fn generate(&self, ident: &Ident, generics: &Generics) -> TokenStream2 {
+        let json = self.to_json();
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        quote! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                /// NEP-330 source metadata of the contract, returned by the
+                /// `contract_source_metadata` view method.
+                pub const CONTRACT_SOURCE_METADATA: &'static str = #json;
+            }
+
+            #[cfg(target_arch = "wasm32")]
+            #[no_mangle]
+            pub extern "C" fn contract_source_metadata() {
+                near_sdk::env::value_return(#json.as_bytes());
+            }
+        }
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ContractMetadata;
+
+    #[test]
+    fn metadata_json() {
+        let metadata: ContractMetadata = syn::parse_str(
+            r#"contract_metadata(version = "1.0.0", link = "https://a.b/\"c\"", standards = ["nep141", "nep171:1.1.0"])"#,
+        )
+        .unwrap();
+        assert_eq!(
+            metadata.to_json(),
+            r#"{"version":"1.0.0","link":"https://a.b/\"c\"","standards":[{"standard":"nep330","version":"1.1.0"},{"standard":"nep141","version":"1.0.0"},{"standard":"nep171","version":"1.1.0"}]}"#
+        );
+
+        let metadata: ContractMetadata =
+            syn::parse_str(r#"contract_metadata(standards = ["nep330:1.0.0"])"#).unwrap();
+        assert_eq!(
+            metadata.to_json(),
+            r#"{"version":null,"link":null,"standards":[{"standard":"nep330","version":"1.0.0"}]}"#
+        );
+    }
+
+    #[test]
+    fn invalid_metadata() {
+        let err = syn::parse_str::<ContractMetadata>(r#"contract_metadata(name = "a")"#)
+            .map(|_| ())
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unsupported contract metadata field. Expected `version`, `link` or `standards`."
+        );
+        let err =
+            syn::parse_str::<ContractMetadata>(r#"contract_metadata(standards = [":1.0.0"])"#)
+                .map(|_| ())
+                .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Expected a standard with an optional version, e.g. \"nep141\" or \"nep171:1.1.0\"."
+        );
+    }
+}
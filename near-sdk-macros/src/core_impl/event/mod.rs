@@ -5,10 +5,11 @@ use syn::{parse_quote, ItemEnum, LitStr};
 
 /// this function is used to inject serialization macros and the `near_sdk::EventMetadata` macro.
 /// In addition, this function extracts the event's `standard` value and injects it as a constant to be used by
-/// the `near_sdk::EventMetadata` derive macro
+/// the `near_sdk::EventMetadata` derive macro. An optional `version` argument sets the version of
+/// every variant that does not override it with `#[event_version("x.x.x")]`.
 pub(crate) fn near_events(attr: TokenStream, item: TokenStream) -> TokenStream {
-    // get standard from attr args
-    let standard = get_standard_arg(&syn::parse_macro_input!(attr as syn::AttributeArgs));
+    // get standard and default version from attr args
+    let (standard, version) = get_event_args(&syn::parse_macro_input!(attr as syn::AttributeArgs));
     if standard.is_none() {
         return TokenStream::from(
             syn::Error::new(
@@ -23,13 +24,59 @@ pub(crate) fn near_events(attr: TokenStream, item: TokenStream) -> TokenStream {
         let name = &input.ident;
         let standard_name = format!("{}_event_standard", name);
         let standard_ident = syn::Ident::new(&standard_name, Span::call_site());
-        // NearEvent Macro handles implementation
-        input
-            .attrs
-            .push(parse_quote! (#[derive(near_sdk::serde::Serialize, near_sdk::EventMetadata)]));
-        input.attrs.push(parse_quote! (#[serde(crate="near_sdk::serde")]));
+        if let Some(version) = &version {
+            if let Err(err) = validate_version(version) {
+                return TokenStream::from(err.to_compile_error());
+            }
+        }
+        for var in input.variants.iter_mut() {
+            match get_event_version(var) {
+                Some(var_version) => {
+                    if let Err(err) = validate_version(&var_version) {
+                        return TokenStream::from(err.to_compile_error());
+                    }
+                }
+                // Variants without their own version fall back to the enum level one.
+                None => {
+                    if let Some(version) = &version {
+                        var.attrs.push(parse_quote!(#[event_version(#version)]));
+                    }
+                }
+            }
+        }
+        // Serde container attributes set on the enum take precedence over the defaults below, which
+        // would otherwise be rejected by serde as duplicates. The `tag` and `content` of the NEP-297
+        // envelope can't be changed.
+        let serde_args = serde_attr_args(&input.attrs);
+        if let Some(arg) =
+            serde_args.iter().find(|arg| arg.is_ident("tag") || arg.is_ident("content"))
+        {
+            return TokenStream::from(
+                syn::Error::new_spanned(
+                    arg,
+                    "Near events are serialized with `tag = \"event\", content = \"data\"`, which can't be overridden.",
+                )
+                .to_compile_error(),
+            );
+        }
+        let has_serde_arg = |arg: &str| serde_args.iter().any(|existing| existing.is_ident(arg));
+        // NearEvent Macro handles implementation. The derive goes first so that the `serde`
+        // attributes of the enum come after it.
+        if derives_serialize(&input.attrs) {
+            input.attrs.insert(0, parse_quote! (#[derive(near_sdk::EventMetadata)]));
+        } else {
+            input.attrs.insert(
+                0,
+                parse_quote! (#[derive(near_sdk::serde::Serialize, near_sdk::EventMetadata)]),
+            );
+        }
+        if !has_serde_arg("crate") {
+            input.attrs.push(parse_quote! (#[serde(crate="near_sdk::serde")]));
+        }
         input.attrs.push(parse_quote! (#[serde(tag = "event", content = "data")]));
-        input.attrs.push(parse_quote! (#[serde(rename_all = "snake_case")]));
+        if !has_serde_arg("rename_all") {
+            input.attrs.push(parse_quote! (#[serde(rename_all = "snake_case")]));
+        }
 
         TokenStream::from(quote! {
             const #standard_ident: &'static str = #standard;
@@ -57,9 +104,58 @@ pub(crate) fn get_event_version(var: &syn::Variant) -> Option<LitStr> {
     None
 }
 
-/// this function returns the `standard` value from `#[near_bindgen(event_json(standard = "nepXXX"))]`
-fn get_standard_arg(args: &[syn::NestedMeta]) -> Option<LitStr> {
+/// Returns the paths of the arguments of all `#[serde(...)]` attributes, e.g. `rename_all` for
+/// `#[serde(rename_all = "camelCase")]`.
+fn serde_attr_args(attrs: &[syn::Attribute]) -> Vec<syn::Path> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("serde"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|nested| match nested {
+            syn::NestedMeta::Meta(meta) => Some(meta.path().clone()),
+            syn::NestedMeta::Lit(_) => None,
+        })
+        .collect()
+}
+
+/// Returns `true` if `Serialize` is already derived by one of the `#[derive(...)]` attributes.
+fn derives_serialize(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().filter(|attr| attr.path.is_ident("derive")).any(|attr| match attr.parse_meta() {
+        Ok(syn::Meta::List(list)) => list.nested.iter().any(|nested| match nested {
+            syn::NestedMeta::Meta(meta) => {
+                matches!(meta.path().segments.last(), Some(segment) if segment.ident == "Serialize")
+            }
+            syn::NestedMeta::Lit(_) => false,
+        }),
+        _ => false,
+    })
+}
+
+/// Checks that an event version has the `MAJOR.MINOR.PATCH` format required by NEP-297.
+fn validate_version(version: &LitStr) -> syn::Result<()> {
+    let value = version.value();
+    let parts: Vec<&str> = value.split('.').collect();
+    if parts.len() == 3
+        && parts.iter().all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()))
+    {
+        Ok(())
+    } else {
+        Err(syn::Error::new(
+            version.span(),
+            "Near event versions must follow the \"MAJOR.MINOR.PATCH\" format, e.g. \"1.0.0\".",
+        ))
+    }
+}
+
+/// this function returns the `standard` and `version` values from
+/// `#[near_bindgen(event_json(standard = "nepXXX", version = "x.x.x"))]`
+fn get_event_args(args: &[syn::NestedMeta]) -> (Option<LitStr>, Option<LitStr>) {
     let mut standard: Option<LitStr> = None;
+    let mut version: Option<LitStr> = None;
     for arg in args.iter() {
         if let syn::NestedMeta::Meta(syn::Meta::List(syn::MetaList { path, nested, .. })) = arg {
             if path.is_ident("event_json") {
@@ -72,12 +168,13 @@ fn get_standard_arg(args: &[syn::NestedMeta]) -> Option<LitStr> {
                     {
                         if path.is_ident("standard") {
                             standard = Some(value.to_owned());
-                            break;
+                        } else if path.is_ident("version") {
+                            version = Some(value.to_owned());
                         }
                     }
                 }
             }
         }
     }
-    standard
+    (standard, version)
 }
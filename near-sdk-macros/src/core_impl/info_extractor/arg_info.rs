@@ -13,6 +13,8 @@ pub enum BindgenArgType {
     CallbackResultArg,
     /// An argument that we read from all `env::promise_result()`.
     CallbackArgVec,
+    /// An argument that we read from all `env::promise_result()` which handles the errors.
+    CallbackResultArgVec,
 }
 
 /// A single argument of a function after it was processed by the bindgen.
@@ -35,6 +37,8 @@ pub struct ArgInfo {
     pub bindgen_ty: BindgenArgType,
     /// Type of serializer that we use for this argument.
     pub serializer_ty: SerializerType,
+    /// Whether the serializer was set with `#[serializer(...)]` on the argument itself.
+    pub explicit_serializer: bool,
     /// The original `PatType` of the argument.
     pub original: PatType,
 }
@@ -65,6 +69,7 @@ impl ArgInfo {
         let mut bindgen_ty = BindgenArgType::Regular;
         // In the absence of serialization attributes this is a JSON serialization.
         let mut serializer_ty = SerializerType::JSON;
+        let mut explicit_serializer = false;
         for attr in &original.attrs {
             let attr_str = attr.path.to_token_stream().to_string();
             match attr_str.as_str() {
@@ -77,9 +82,28 @@ impl ArgInfo {
                 "callback_vec" => {
                     bindgen_ty = BindgenArgType::CallbackArgVec;
                 }
+                "callback_results" => {
+                    bindgen_ty = BindgenArgType::CallbackResultArgVec;
+                }
                 "serializer" => {
                     let serializer: SerializerAttr = syn::parse2(attr.tokens.clone())?;
+                    if serializer.is_auto {
+                        return Err(Error::new(
+                            attr.span(),
+                            "The `auto` serializer can only be used on methods.",
+                        ));
+                    }
+                    if let Some(option) = serializer.result_option() {
+                        return Err(Error::new(
+                            attr.span(),
+                            format!(
+                                "The `{}` option can only be used with `#[result_serializer]`.",
+                                option
+                            ),
+                        ));
+                    }
                     serializer_ty = serializer.serializer_type;
+                    explicit_serializer = true;
                 }
                 _ => {
                     non_bindgen_attrs.push((*attr).clone());
@@ -94,6 +118,7 @@ impl ArgInfo {
                 && attr_str != "serializer"
                 && attr_str != "callback_result"
                 && attr_str != "callback_unwrap"
+                && attr_str != "callback_results"
         });
 
         Ok(Self {
@@ -106,6 +131,7 @@ impl ArgInfo {
             ty,
             bindgen_ty,
             serializer_ty,
+            explicit_serializer,
             original: original.clone(),
         })
     }
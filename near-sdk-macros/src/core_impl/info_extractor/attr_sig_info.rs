@@ -1,6 +1,7 @@
 use super::visitor::Visitor;
 use super::{
-    ArgInfo, BindgenArgType, InitAttr, MethodKind, MethodType, ReturnKind, SerializerAttr,
+    ArgInfo, BindgenArgType, CallbackLinkAttr, InitAttr, InitMethod, MethodKind, MethodType,
+    MigrateAttr, PayableAttr, PrivateAllow, PrivateAttr, ReturnKind, SerializerAttr,
     SerializerType,
 };
 use crate::core_impl::utils;
@@ -21,6 +22,8 @@ pub struct AttrSigInfoV2 {
     pub method_kind: MethodKind,
     /// The serializer that we use for `env::input()`.
     pub input_serializer: SerializerType,
+    /// Whether `env::input()` can also be Borsh, with `#[serializer(auto)]`.
+    pub input_auto: bool,
     /// The original method signature.
     pub original_sig: Signature,
 }
@@ -37,14 +40,33 @@ pub struct AttrSigInfo {
     pub method_type: MethodType,
     /// Whether method accepting $NEAR.
     pub is_payable: bool,
+    /// Minimum attached deposit in yoctoNEAR required by a payable method.
+    pub min_deposit: Option<u128>,
     /// Whether method can accept calls from self (current account)
     pub is_private: bool,
+    /// Callers allowed in addition to the current account if the method is private.
+    pub private_allow: Option<PrivateAllow>,
     /// Whether method returns Result type where only Ok type is serialized
     pub is_handles_result: bool,
     /// The serializer that we use for `env::input()`.
     pub input_serializer: SerializerType,
+    /// Whether `env::input()` can also be Borsh, with `#[serializer(auto)]`.
+    pub input_auto: bool,
     /// The serializer that we use for the return type.
     pub result_serializer: SerializerType,
+    /// Whether a `None` result is returned as an empty body instead of `null`.
+    pub result_skip_none: bool,
+    /// Whether the keys of JSON objects in the result are sorted.
+    pub result_sorted_keys: bool,
+    /// The callback linked with `#[callback_link]` to the promise returned by the method.
+    pub callback_link: Option<Ident>,
+    /// Whether the method panics if called with arguments, with `#[deny_empty_args]`.
+    pub deny_args: bool,
+    /// The argument that receives the old contract state of a `#[migrate(from = ...)]` method.
+    /// It is not part of `args`, as it isn't read from the input.
+    pub migrate_state: Option<ArgInfo>,
+    /// Whether the init method panics when it is called again, see [`InitMethod::run_once`].
+    pub run_once: bool,
     /// The receiver, like `mut self`, `self`, `&mut self`, `&self`, or `None`.
     pub receiver: Option<Receiver>,
     /// What this function returns.
@@ -64,13 +86,22 @@ impl From<AttrSigInfoV2> for AttrSigInfo {
                 args: info.args,
                 method_type: MethodType::Regular,
                 is_payable: call_method.is_payable,
+                min_deposit: call_method.min_deposit,
                 is_private: call_method.is_private,
+                private_allow: call_method.private_allow,
                 is_handles_result: matches!(
                     call_method.returns.kind,
                     ReturnKind::HandlesResult { .. }
                 ),
                 input_serializer: info.input_serializer,
+                input_auto: info.input_auto,
                 result_serializer: call_method.result_serializer,
+                result_skip_none: call_method.result_skip_none,
+                result_sorted_keys: call_method.result_sorted_keys,
+                callback_link: None,
+                deny_args: false,
+                migrate_state: None,
+                run_once: false,
                 receiver: call_method.receiver,
                 returns: call_method.returns.original,
                 original_sig: info.original_sig,
@@ -81,13 +112,22 @@ impl From<AttrSigInfoV2> for AttrSigInfo {
                 args: info.args,
                 method_type: MethodType::View,
                 is_payable: false,
+                min_deposit: None,
                 is_private: view_method.is_private,
+                private_allow: view_method.private_allow,
                 is_handles_result: matches!(
                     view_method.returns.kind,
                     ReturnKind::HandlesResult { .. }
                 ),
                 input_serializer: info.input_serializer,
+                input_auto: info.input_auto,
                 result_serializer: view_method.result_serializer,
+                result_skip_none: view_method.result_skip_none,
+                result_sorted_keys: view_method.result_sorted_keys,
+                callback_link: None,
+                deny_args: false,
+                migrate_state: None,
+                run_once: false,
                 receiver: view_method.receiver,
                 returns: view_method.returns.original,
                 original_sig: info.original_sig,
@@ -102,13 +142,22 @@ impl From<AttrSigInfoV2> for AttrSigInfo {
                     MethodType::Init
                 },
                 is_payable: init_method.is_payable,
-                is_private: false,
+                min_deposit: init_method.min_deposit,
+                is_private: init_method.is_private,
+                private_allow: None,
                 is_handles_result: matches!(
                     init_method.returns.kind,
                     ReturnKind::HandlesResult { .. }
                 ),
                 input_serializer: info.input_serializer,
+                input_auto: info.input_auto,
                 result_serializer: SerializerType::JSON,
+                result_skip_none: false,
+                result_sorted_keys: false,
+                callback_link: None,
+                deny_args: false,
+                migrate_state: None,
+                run_once: init_method.run_once,
                 receiver: None,
                 returns: init_method.returns.original,
                 original_sig: info.original_sig,
@@ -170,6 +219,10 @@ impl AttrSigInfo {
 
         let ident = original_sig.ident.clone();
         let mut non_bindgen_attrs = vec![];
+        let mut method_serializer = None;
+        let mut callback_link = None;
+        let mut deny_args = None;
+        let mut migrate_from = None;
 
         // Visit attributes
         for attr in original_attrs.iter() {
@@ -179,19 +232,52 @@ impl AttrSigInfo {
                     let init_attr: InitAttr = syn::parse2(attr.tokens.clone())?;
                     visitor.visit_init_attr(attr, &init_attr)?;
                 }
+                "migrate" => {
+                    let migrate_attr: MigrateAttr = syn::parse2(attr.tokens.clone())?;
+                    visitor.visit_migrate_attr(attr);
+                    migrate_from = Some((attr.span(), migrate_attr.from));
+                }
                 "payable" => {
-                    visitor.visit_payable_attr(attr)?;
+                    let payable_attr: PayableAttr = syn::parse2(attr.tokens.clone())?;
+                    visitor.visit_payable_attr(attr, &payable_attr)?;
                 }
                 "private" => {
-                    visitor.visit_private_attr(attr)?;
+                    let private_attr: PrivateAttr = syn::parse2(attr.tokens.clone())?;
+                    visitor.visit_private_attr(attr, &private_attr)?;
                 }
                 "result_serializer" => {
                     let serializer: SerializerAttr = syn::parse2(attr.tokens.clone())?;
                     visitor.visit_result_serializer_attr(attr, &serializer)?;
                 }
+                "serializer" => {
+                    let serializer: SerializerAttr = syn::parse2(attr.tokens.clone())?;
+                    if let Some(option) = serializer.result_option() {
+                        return Err(Error::new(
+                            attr.span(),
+                            format!(
+                                "The `{}` option can only be used with `#[result_serializer]`.",
+                                option
+                            ),
+                        ));
+                    }
+                    method_serializer = Some(serializer);
+                }
                 "handle_result" => {
                     visitor.visit_handle_result_attr();
                 }
+                "callback_link" => {
+                    let link: CallbackLinkAttr = syn::parse2(attr.tokens.clone())?;
+                    callback_link = Some((attr.span(), link.method));
+                }
+                "deny_empty_args" => {
+                    if !attr.tokens.is_empty() {
+                        return Err(Error::new(
+                            attr.tokens.span(),
+                            "`#[deny_empty_args]` doesn't take arguments.",
+                        ));
+                    }
+                    deny_args = Some(attr.span());
+                }
                 _ => {
                     non_bindgen_attrs.push((*attr).clone());
                 }
@@ -209,7 +295,62 @@ impl AttrSigInfo {
             }
         }
 
+        // The old state is passed as the first argument of a `#[migrate]` method. It is read as
+        // the `from` type, so the compiler checks that this is the type of the argument.
+        let migrate_state = match migrate_from {
+            Some((span, from)) => match args.first() {
+                Some(arg)
+                    if arg.reference.is_none()
+                        && matches!(arg.bindgen_ty, BindgenArgType::Regular) =>
+                {
+                    let mut state = args.remove(0);
+                    state.ty = from;
+                    Some(state)
+                }
+                first => {
+                    let message = format!(
+                        "The first argument of a `#[migrate]` function must be the old state, e.g. `old: {}`.",
+                        from.to_token_stream()
+                    );
+                    return Err(Error::new(first.map_or(span, |arg| arg.original.span()), message));
+                }
+            },
+            None => None,
+        };
+
+        // A `#[serializer(borsh)]` on the method applies to all of its input arguments that don't
+        // set their own serializer.
+        let input_auto = matches!(&method_serializer, Some(s) if s.is_auto);
+        if method_serializer.map(|s| s.serializer_type) == Some(SerializerType::Borsh) {
+            for arg in args.iter_mut() {
+                if matches!(arg.bindgen_ty, BindgenArgType::Regular) && !arg.explicit_serializer {
+                    arg.serializer_ty = SerializerType::Borsh;
+                }
+            }
+        }
+
         let method_kind = visitor.build()?;
+        if let (Some((span, _)), MethodKind::Init(_)) = (&callback_link, &method_kind) {
+            return Err(Error::new(*span, "Init methods can't link a callback."));
+        }
+        if let MethodKind::Init(InitMethod { returns, .. }) = &method_kind {
+            let returns_self = match &returns.kind {
+                ReturnKind::General(ty) | ReturnKind::HandlesResult { ok_type: ty } => {
+                    utils::type_is_self(ty, source_type)
+                }
+                ReturnKind::Default => false,
+            };
+            if !returns_self {
+                let span = match &original_sig.output {
+                    ReturnType::Type(_, ty) => ty.span(),
+                    ReturnType::Default => original_sig.ident.span(),
+                };
+                return Err(Error::new(
+                    span,
+                    "Init function must return `Self`, or `Result<Self, E>` with `#[handle_result]`.",
+                ));
+            }
+        }
 
         *original_attrs = non_bindgen_attrs.clone();
 
@@ -219,6 +360,7 @@ impl AttrSigInfo {
             args,
             method_kind,
             input_serializer: SerializerType::JSON,
+            input_auto,
             original_sig: original_sig.clone(),
         }
         .into();
@@ -226,7 +368,9 @@ impl AttrSigInfo {
         let input_serializer =
             if result.input_args().all(|arg: &ArgInfo| arg.serializer_ty == SerializerType::JSON) {
                 SerializerType::JSON
-            } else if result.input_args().all(|arg| arg.serializer_ty == SerializerType::Borsh) {
+            } else if !input_auto
+                && result.input_args().all(|arg| arg.serializer_ty == SerializerType::Borsh)
+            {
                 SerializerType::Borsh
             } else {
                 return Err(Error::new(
@@ -234,7 +378,16 @@ impl AttrSigInfo {
                     "Input arguments should be all of the same serialization type.",
                 ));
             };
+        if let (Some(span), Some(_)) = (deny_args, result.input_args().next()) {
+            return Err(Error::new(
+                span,
+                "`#[deny_empty_args]` can only be used on methods without arguments.",
+            ));
+        }
         result.input_serializer = input_serializer;
+        result.deny_args = deny_args.is_some();
+        result.migrate_state = migrate_state;
+        result.callback_link = callback_link.map(|(_, method)| method);
         Ok(result)
     }
 
@@ -0,0 +1,33 @@
+use proc_macro2::Ident;
+use syn::parse::{Parse, ParseStream};
+use syn::{Error, LitStr, Token};
+
+/// Arguments of `#[callback_link]`, e.g. `#[callback_link(method = "on_transfer")]`.
+pub struct CallbackLinkAttr {
+    /// The callback method scheduled with `.then()` on the returned promise. Its span is the span
+    /// of the string literal, so that errors about the callback point at the attribute.
+    pub method: Ident,
+}
+
+impl Parse for CallbackLinkAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let error = || {
+            Error::new(
+                input.span(),
+                "Expected the name of the callback method, e.g. `#[callback_link(method = \"on_transfer\")]`.",
+            )
+        };
+        let content;
+        let _paren_token = syn::parenthesized!(content in input);
+        let ident: Ident = content.parse().map_err(|_| error())?;
+        if ident != "method" {
+            return Err(Error::new(ident.span(), "Unsupported callback_link attribute."));
+        }
+        content.parse::<Token![=]>()?;
+        let method: LitStr = content.parse()?;
+        let method = method.parse::<Ident>().map_err(|_| {
+            Error::new(method.span(), "Expected the name of a method, e.g. \"on_transfer\".")
+        })?;
+        Ok(Self { method })
+    }
+}
@@ -62,6 +62,65 @@ mod tests {
         assert!(matches!(actual, ReturnType::Type(_, ty) if ty.as_ref() == &expected));
     }
 
+    #[test]
+    fn init_wrong_return() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[init]
+            pub fn method(k: &mut u64) -> u64 { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, false, impl_type).map(|_| ()).unwrap_err();
+        let expected = "Init function must return `Self`, or `Result<Self, E>` with `#[handle_result]`.";
+        assert_eq!(expected, actual.to_string());
+    }
+
+    #[test]
+    fn init_wrong_result_return() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[init]
+            #[handle_result]
+            pub fn method(k: &mut u64) -> Result<u64, Error> { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, false, impl_type).map(|_| ()).unwrap_err();
+        let expected = "Init function must return `Self`, or `Result<Self, E>` with `#[handle_result]`.";
+        assert_eq!(expected, actual.to_string());
+    }
+
+    #[test]
+    fn init_contract_type_return() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[init]
+            pub fn method(k: &mut u64) -> crate::Hello { }
+        };
+        assert!(ImplItemMethodInfo::new(&mut method, false, impl_type).is_ok());
+    }
+
+    #[test]
+    fn init_option_return() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[init]
+            pub fn method(k: &mut u64) -> Option<Self> { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, false, impl_type).map(|_| ()).unwrap_err();
+        let expected = "Init function must return `Self`, or `Result<Self, E>` with `#[handle_result]`.";
+        assert_eq!(expected, actual.to_string());
+    }
+
+    #[test]
+    fn method_serializer_keeps_arg_serializer() {
+        let impl_type: Type = syn::parse_str("Hello").unwrap();
+        let mut method: ImplItemMethod = parse_quote! {
+            #[serializer(borsh)]
+            pub fn method(&mut self, k: u64, #[serializer(json)] m: u64) { }
+        };
+        let actual = ImplItemMethodInfo::new(&mut method, false, impl_type).map(|_| ()).unwrap_err();
+        let expected = "Input arguments should be all of the same serialization type.";
+        assert_eq!(expected, actual.to_string());
+    }
+
     #[test]
     fn handle_result_incorrect_return_type() {
         let impl_type: Type = syn::parse_str("Hello").unwrap();
@@ -1,25 +1,40 @@
 use proc_macro2::Ident;
 use syn::parse::{Parse, ParseStream};
-use syn::token::Paren;
+use syn::punctuated::Punctuated;
+use syn::token::{Comma, Paren};
 use syn::Error;
 
 pub struct InitAttr {
     pub ignore_state: bool,
+    /// Whether a private init function that ignores state can be called more than once.
+    pub allow_repeat: bool,
 }
 
 impl Parse for InitAttr {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let ignore_state = if input.peek(Paren) {
+        let mut ignore_state = false;
+        let mut allow_repeat = false;
+        if input.peek(Paren) {
             let content;
             let _paren_token = syn::parenthesized!(content in input);
-            let ident: Ident = content.parse()?;
-            match ident.to_string().as_str() {
-                "ignore_state" => true,
-                _ => return Err(Error::new(input.span(), "Unsupported init attribute.")),
+            let idents = Punctuated::<Ident, Comma>::parse_separated_nonempty(&content)?;
+            for ident in idents {
+                match ident.to_string().as_str() {
+                    "ignore_state" => ignore_state = true,
+                    "allow_repeat" => allow_repeat = true,
+                    _ => return Err(Error::new(input.span(), "Unsupported init attribute.")),
+                }
             }
-        } else {
-            false
-        };
-        Ok(Self { ignore_state })
+            if !content.is_empty() {
+                return Err(Error::new(input.span(), "Unsupported init attribute."));
+            }
+            if allow_repeat && !ignore_state {
+                return Err(Error::new(
+                    input.span(),
+                    "`allow_repeat` can only be used together with `ignore_state`.",
+                ));
+            }
+        }
+        Ok(Self { ignore_state, allow_repeat })
     }
 }
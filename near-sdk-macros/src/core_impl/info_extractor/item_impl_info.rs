@@ -1,11 +1,13 @@
 use crate::ImplItemMethodInfo;
 use syn::spanned::Spanned;
-use syn::{Error, ImplItem, ItemImpl, Type};
+use syn::{Error, Generics, ImplItem, ItemImpl, Type};
 
 /// Information extracted from `impl` section.
 pub struct ItemImplInfo {
     /// The type for which this `impl` is written.
     pub ty: Type,
+    /// The generics and where clause of the `impl`.
+    pub generics: Generics,
     /// Info extracted for each public method.
     pub methods: Vec<ImplItemMethodInfo>,
 }
@@ -29,6 +31,6 @@ impl ItemImplInfo {
                 }
             }
         }
-        Ok(Self { ty, methods })
+        Ok(Self { ty, generics: original.generics.clone(), methods })
     }
 }
@@ -0,0 +1,29 @@
+use proc_macro2::Ident;
+use syn::parse::{Parse, ParseStream};
+use syn::{Error, Token, Type};
+
+/// Arguments of `#[migrate]`, e.g. `#[migrate(from = OldState)]`.
+pub struct MigrateAttr {
+    /// The type of the state stored before the migration.
+    pub from: Type,
+}
+
+impl Parse for MigrateAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.is_empty() {
+            return Err(Error::new(
+                input.span(),
+                "Expected the type of the old state, e.g. `#[migrate(from = OldState)]`.",
+            ));
+        }
+        let content;
+        let _paren_token = syn::parenthesized!(content in input);
+        let ident: Ident = content.parse()?;
+        if ident != "from" {
+            return Err(Error::new(ident.span(), "Unsupported migrate attribute."));
+        }
+        content.parse::<Token![=]>()?;
+        let from: Type = content.parse()?;
+        Ok(Self { from })
+    }
+}
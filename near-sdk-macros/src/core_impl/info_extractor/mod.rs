@@ -23,6 +23,18 @@ mod item_impl_info;
 mod init_attr;
 pub use init_attr::InitAttr;
 
+mod callback_link_attr;
+pub use callback_link_attr::CallbackLinkAttr;
+
+mod migrate_attr;
+pub use migrate_attr::MigrateAttr;
+
+mod payable_attr;
+pub use payable_attr::PayableAttr;
+
+mod private_attr;
+pub use private_attr::{PrivateAllow, PrivateAttr};
+
 mod visitor;
 
 pub use item_impl_info::ItemImplInfo;
@@ -55,10 +67,18 @@ pub enum MethodKind {
 pub struct CallMethod {
     /// Whether method accepting $NEAR.
     pub is_payable: bool,
+    /// Minimum attached deposit in yoctoNEAR required by a payable method.
+    pub min_deposit: Option<u128>,
     /// Whether method can accept calls from self (current account)
     pub is_private: bool,
+    /// Callers allowed in addition to the current account if the method is private.
+    pub private_allow: Option<PrivateAllow>,
     /// The serializer that we use for the return type.
     pub result_serializer: SerializerType,
+    /// Whether a `None` result is returned as an empty body instead of `null`.
+    pub result_skip_none: bool,
+    /// Whether the keys of JSON objects in the result are sorted.
+    pub result_sorted_keys: bool,
     /// What this function returns.
     pub returns: Returns,
     /// The receiver, like `mut self`, `self`, `&mut self`, `&self`, or `None`.
@@ -69,8 +89,14 @@ pub struct CallMethod {
 pub struct ViewMethod {
     /// Whether method can accept calls from self (current account)
     pub is_private: bool,
+    /// Callers allowed in addition to the current account if the method is private.
+    pub private_allow: Option<PrivateAllow>,
     /// The serializer that we use for the return type.
     pub result_serializer: SerializerType,
+    /// Whether a `None` result is returned as an empty body instead of `null`.
+    pub result_skip_none: bool,
+    /// Whether the keys of JSON objects in the result are sorted.
+    pub result_sorted_keys: bool,
     /// What this function returns.
     pub returns: Returns,
     /// The receiver, like `mut self`, `self`, `&mut self`, `&self`, or `None`.
@@ -81,8 +107,16 @@ pub struct ViewMethod {
 pub struct InitMethod {
     /// Whether method accepting $NEAR.
     pub is_payable: bool,
+    /// Minimum attached deposit in yoctoNEAR required by a payable method.
+    pub min_deposit: Option<u128>,
+    /// Whether method can only be called by the contract itself. Only allowed when the init
+    /// method ignores state.
+    pub is_private: bool,
     /// Whether init method ignores state
     pub ignores_state: bool,
+    /// Whether an init method that ignores state panics when it is called again, unless it is
+    /// `#[init(ignore_state, allow_repeat)]`.
+    pub run_once: bool,
     /// What this function returns.
     pub returns: Returns,
 }
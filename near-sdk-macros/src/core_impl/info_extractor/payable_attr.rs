@@ -0,0 +1,83 @@
+use proc_macro2::Ident;
+use syn::parse::{Parse, ParseStream};
+use syn::token::Paren;
+use syn::{Error, LitStr, Token};
+
+/// Number of decimals of 1 NEAR in yoctoNEAR.
+const NEAR_DECIMALS: usize = 24;
+/// Number of decimals of 1 mNEAR in yoctoNEAR.
+const MILLINEAR_DECIMALS: usize = 21;
+
+/// Arguments of `#[payable]`, e.g. `#[payable(min = "1 NEAR")]`.
+pub struct PayableAttr {
+    /// Minimum attached deposit in yoctoNEAR.
+    pub min_deposit: Option<u128>,
+}
+
+impl Parse for PayableAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let min_deposit = if input.peek(Paren) {
+            let content;
+            let _paren_token = syn::parenthesized!(content in input);
+            let ident: Ident = content.parse()?;
+            if ident != "min" {
+                return Err(Error::new(ident.span(), "Unsupported payable attribute."));
+            }
+            content.parse::<Token![=]>()?;
+            let amount: LitStr = content.parse()?;
+            let min_deposit = parse_near_amount(&amount.value()).ok_or_else(|| {
+                Error::new(
+                    amount.span(),
+                    "Invalid minimum deposit. Expected an amount of NEAR, mNEAR or yoctoNEAR, e.g. \"1 NEAR\" or \"0.5 mNEAR\".",
+                )
+            })?;
+            Some(min_deposit)
+        } else {
+            None
+        };
+        Ok(Self { min_deposit })
+    }
+}
+
+/// Parses an amount with a unit into yoctoNEAR, following `near_sdk::NearToken`'s `FromStr`.
+fn parse_near_amount(s: &str) -> Option<u128> {
+    let s = s.trim();
+    let unit_start = s.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(s.len());
+    let (amount, unit) = s.split_at(unit_start);
+    let decimals = match unit.trim_start() {
+        "" | "yoctoNEAR" => 0,
+        "mNEAR" => MILLINEAR_DECIMALS,
+        "NEAR" => NEAR_DECIMALS,
+        _ => return None,
+    };
+    let (whole, fraction) = match amount.split_once('.') {
+        Some((whole, fraction)) if !whole.is_empty() && !fraction.is_empty() => (whole, fraction),
+        None if !amount.is_empty() => (amount, ""),
+        _ => return None,
+    };
+    if !fraction.bytes().all(|b| b.is_ascii_digit()) || fraction.len() > decimals {
+        return None;
+    }
+    let whole: u128 = whole.parse().ok()?;
+    let fraction: u128 = format!("{:0<width$}", fraction, width = decimals).parse().unwrap_or(0);
+    whole.checked_mul(10u128.pow(decimals as u32))?.checked_add(fraction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_near_amount;
+
+    #[test]
+    fn parse_amounts() {
+        assert_eq!(parse_near_amount("1 NEAR"), Some(10u128.pow(24)));
+        assert_eq!(parse_near_amount("0.5 mNEAR"), Some(5 * 10u128.pow(20)));
+        assert_eq!(parse_near_amount("10 yoctoNEAR"), Some(10));
+        assert_eq!(parse_near_amount("10"), Some(10));
+        assert_eq!(parse_near_amount("1.5NEAR"), Some(15 * 10u128.pow(23)));
+        assert_eq!(parse_near_amount("1 near"), None);
+        assert_eq!(parse_near_amount("1.5"), None);
+        assert_eq!(parse_near_amount(".5 NEAR"), None);
+        assert_eq!(parse_near_amount("-1 NEAR"), None);
+        assert_eq!(parse_near_amount("1000000000000000 NEAR"), None);
+    }
+}
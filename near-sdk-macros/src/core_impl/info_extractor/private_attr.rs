@@ -0,0 +1,70 @@
+use proc_macro2::Ident;
+use syn::parse::{Parse, ParseStream};
+use syn::token::{Bracket, Paren};
+use syn::{Error, Expr, LitStr, Token};
+
+/// Arguments of `#[private]`, e.g. `#[private(allow = "owner")]`.
+pub struct PrivateAttr {
+    /// Callers allowed in addition to the contract itself.
+    pub allow: Option<PrivateAllow>,
+}
+
+/// Callers allowed to call a `#[private]` method in addition to the contract itself.
+#[derive(Clone, PartialEq, Eq)]
+pub enum PrivateAllow {
+    /// A list of account IDs, e.g. `#[private(allow = ["alice.near", "bob.near"])]`.
+    Accounts(Vec<String>),
+    /// A field of the contract holding the allowed account, e.g. `#[private(allow = "owner")]`.
+    Field(Ident),
+    /// A method of the contract returning the allowed account, e.g.
+    /// `#[private(allow = "owner()")]`.
+    Method(Ident),
+}
+
+impl Parse for PrivateAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let allow = if input.peek(Paren) {
+            let content;
+            let _paren_token = syn::parenthesized!(content in input);
+            let ident: Ident = content.parse()?;
+            if ident != "allow" {
+                return Err(Error::new(ident.span(), "Unsupported private attribute."));
+            }
+            content.parse::<Token![=]>()?;
+            if content.peek(Bracket) {
+                let accounts;
+                let _bracket_token = syn::bracketed!(accounts in content);
+                let accounts =
+                    accounts.parse_terminated::<LitStr, Token![,]>(<LitStr as Parse>::parse)?;
+                Some(PrivateAllow::Accounts(accounts.iter().map(LitStr::value).collect()))
+            } else {
+                let member: LitStr = content.parse()?;
+                Some(parse_member(&member)?)
+            }
+        } else {
+            None
+        };
+        Ok(Self { allow })
+    }
+}
+
+fn parse_member(member: &LitStr) -> syn::Result<PrivateAllow> {
+    let error = || {
+        Error::new(
+            member.span(),
+            "Expected the name of a field, e.g. \"owner\", or of a method without arguments, e.g. \"owner()\".",
+        )
+    };
+    match member.parse::<Expr>().map_err(|_| error())? {
+        Expr::Path(path) if path.qself.is_none() => {
+            path.path.get_ident().cloned().map(PrivateAllow::Field).ok_or_else(error)
+        }
+        Expr::Call(call) if call.args.is_empty() => match *call.func {
+            Expr::Path(path) if path.qself.is_none() => {
+                path.path.get_ident().cloned().map(PrivateAllow::Method).ok_or_else(error)
+            }
+            _ => Err(error()),
+        },
+        _ => Err(error()),
+    }
+}
@@ -1,12 +1,21 @@
 use super::SerializerType;
 use proc_macro2::Ident;
 use syn::parse::{Parse, ParseStream};
-use syn::{parenthesized, Error};
+use syn::{parenthesized, Error, Token};
 
 pub struct SerializerAttr {
     #[allow(dead_code)]
     paren_token: syn::token::Paren,
     pub serializer_type: SerializerType,
+    /// Whether the input is accepted in either Borsh or JSON with `#[serializer(auto)]`. The
+    /// `serializer_type` is then the one used by clients generated by the SDK.
+    pub is_auto: bool,
+    /// Whether a `None` result is returned as an empty body instead of `null`, with
+    /// `#[result_serializer(json, skip_none)]`.
+    pub skip_none: bool,
+    /// Whether the keys of JSON objects in the result are sorted lexicographically, with
+    /// `#[result_serializer(json, sorted_keys)]`.
+    pub sorted_keys: bool,
 }
 
 impl Parse for SerializerAttr {
@@ -14,11 +23,43 @@ impl Parse for SerializerAttr {
         let content;
         let paren_token = parenthesized!(content in input);
         let ident: Ident = content.parse()?;
-        let serializer_type = match ident.to_string().as_str() {
-            "borsh" => SerializerType::Borsh,
-            "json" => SerializerType::JSON,
+        let (serializer_type, is_auto) = match ident.to_string().as_str() {
+            "borsh" => (SerializerType::Borsh, false),
+            "json" => (SerializerType::JSON, false),
+            "auto" => (SerializerType::JSON, true),
             _ => return Err(Error::new(input.span(), "Unsupported serializer type.")),
         };
-        Ok(Self { paren_token, serializer_type })
+        let mut skip_none = false;
+        let mut sorted_keys = false;
+        while content.peek(Token![,]) {
+            content.parse::<Token![,]>()?;
+            let option: Ident = content.parse()?;
+            let name = option.to_string();
+            match name.as_str() {
+                "skip_none" => skip_none = true,
+                "sorted_keys" => sorted_keys = true,
+                _ => return Err(Error::new(option.span(), "Unsupported serializer option.")),
+            }
+            if serializer_type != SerializerType::JSON || is_auto {
+                return Err(Error::new(
+                    option.span(),
+                    format!("The `{}` option can only be used with the `json` serializer.", name),
+                ));
+            }
+        }
+        Ok(Self { paren_token, serializer_type, is_auto, skip_none, sorted_keys })
+    }
+}
+
+impl SerializerAttr {
+    /// Returns the name of the first option that only applies to `#[result_serializer]`.
+    pub fn result_option(&self) -> Option<&'static str> {
+        if self.skip_none {
+            Some("skip_none")
+        } else if self.sorted_keys {
+            Some("sorted_keys")
+        } else {
+            None
+        }
     }
 }
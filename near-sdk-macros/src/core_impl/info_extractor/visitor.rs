@@ -1,5 +1,8 @@
-use super::{InitAttr, MethodKind, ReturnKind, SerializerAttr};
+use super::{
+    InitAttr, MethodKind, PayableAttr, PrivateAllow, PrivateAttr, ReturnKind, SerializerAttr,
+};
 use crate::core_impl::{utils, CallMethod, InitMethod, Returns, SerializerType, ViewMethod};
+use proc_macro2::Span;
 use quote::ToTokens;
 use syn::{spanned::Spanned, Attribute, Error, FnArg, Receiver, ReturnType, Signature, Type};
 
@@ -13,9 +16,26 @@ pub struct Visitor {
 struct ParsedData {
     handles_result: bool,
     is_payable: bool,
+    min_deposit: Option<u128>,
     is_private: bool,
+    private_allow: Option<PrivateAllow>,
+    /// Span of the `#[private]` attribute that allows other callers.
+    private_allow_span: Option<Span>,
+    /// Span of the `#[private]` attribute of an init function, which is only allowed if it
+    /// ignores state.
+    private_init_span: Option<Span>,
+    /// Span of the `#[init]` attribute, if present.
+    init_span: Option<Span>,
+    /// Span of the `#[migrate]` attribute, which makes the function an init function.
+    migrate_span: Option<Span>,
     ignores_state: bool,
+    /// Whether a private init function that ignores state can be called more than once.
+    allow_repeat: bool,
     result_serializer: SerializerType,
+    /// Span of `#[result_serializer(json, skip_none)]`, if present.
+    result_skip_none_span: Option<Span>,
+    /// Whether `#[result_serializer(json, sorted_keys)]` is present.
+    result_sorted_keys: bool,
     receiver: Option<Receiver>,
 }
 
@@ -24,9 +44,18 @@ impl Default for ParsedData {
         Self {
             handles_result: Default::default(),
             is_payable: Default::default(),
+            min_deposit: Default::default(),
             is_private: Default::default(),
+            private_allow: Default::default(),
+            private_allow_span: Default::default(),
+            private_init_span: Default::default(),
+            init_span: Default::default(),
+            migrate_span: Default::default(),
             ignores_state: Default::default(),
+            allow_repeat: Default::default(),
             result_serializer: SerializerType::JSON,
+            result_skip_none_span: Default::default(),
+            result_sorted_keys: Default::default(),
             receiver: Default::default(),
         }
     }
@@ -61,6 +90,8 @@ impl Visitor {
         match self.kind {
             Init => {
                 self.parsed_data.ignores_state = init_attr.ignore_state;
+                self.parsed_data.allow_repeat = init_attr.allow_repeat;
+                self.parsed_data.init_span = Some(attr.span());
                 Ok(())
             }
             Call | View => {
@@ -71,12 +102,25 @@ impl Visitor {
         }
     }
 
-    pub fn visit_payable_attr(&mut self, attr: &Attribute) -> syn::Result<()> {
+    /// A `#[migrate]` function is a private init function that ignores the existing state, as it
+    /// reads the old state itself.
+    pub fn visit_migrate_attr(&mut self, attr: &Attribute) {
+        self.parsed_data.ignores_state = true;
+        self.parsed_data.is_private = true;
+        self.parsed_data.migrate_span = Some(attr.span());
+    }
+
+    pub fn visit_payable_attr(
+        &mut self,
+        attr: &Attribute,
+        payable_attr: &PayableAttr,
+    ) -> syn::Result<()> {
         use VisitorKind::*;
 
         match self.kind {
             Call | Init => {
                 self.parsed_data.is_payable = true;
+                self.parsed_data.min_deposit = payable_attr.min_deposit;
                 Ok(())
             }
             View => {
@@ -86,18 +130,30 @@ impl Visitor {
         }
     }
 
-    pub fn visit_private_attr(&mut self, attr: &Attribute) -> syn::Result<()> {
+    pub fn visit_private_attr(
+        &mut self,
+        attr: &Attribute,
+        private_attr: &PrivateAttr,
+    ) -> syn::Result<()> {
         use VisitorKind::*;
 
         match self.kind {
             Call | View => {
                 self.parsed_data.is_private = true;
+                self.parsed_data.private_allow = private_attr.allow.clone();
+                self.parsed_data.private_allow_span = Some(attr.span());
                 Ok(())
             }
-            Init => {
-                let message = format!("{} function can't be private.", self.kind);
+            Init if private_attr.allow.is_some() => {
+                let message = format!("{} function can't allow other callers.", self.kind);
                 Err(Error::new(attr.span(), message))
             }
+            Init => {
+                // Checked in `build` as the `init` attribute may be visited after this one.
+                self.parsed_data.is_private = true;
+                self.parsed_data.private_init_span = Some(attr.span());
+                Ok(())
+            }
         }
     }
 
@@ -108,9 +164,18 @@ impl Visitor {
     ) -> syn::Result<()> {
         use VisitorKind::*;
 
+        if result_serializer_attr.is_auto {
+            let message = "The `auto` serializer can only be used for method arguments.";
+            return Err(Error::new(attr.span(), message));
+        }
+
         match self.kind {
             Call | View => {
                 self.parsed_data.result_serializer = result_serializer_attr.serializer_type.clone();
+                if result_serializer_attr.skip_none {
+                    self.parsed_data.result_skip_none_span = Some(attr.span());
+                }
+                self.parsed_data.result_sorted_keys = result_serializer_attr.sorted_keys;
                 Ok(())
             }
             Init => {
@@ -171,21 +236,89 @@ impl Visitor {
         let Visitor { kind, parsed_data, .. } = self;
 
         let ParsedData {
-            is_payable, is_private, ignores_state, result_serializer, receiver, ..
+            is_payable,
+            min_deposit,
+            is_private,
+            private_allow,
+            private_allow_span,
+            private_init_span,
+            init_span,
+            migrate_span,
+            ignores_state,
+            allow_repeat,
+            result_serializer,
+            result_skip_none_span,
+            result_sorted_keys,
+            receiver,
+            ..
         } = parsed_data;
 
+        if let (Some(span), Some(_)) = (init_span, migrate_span) {
+            let message = "A `#[migrate]` function is an init function already, remove `#[init]`.";
+            return Err(Error::new(span, message));
+        }
+
+        if let Some(span) = private_init_span {
+            if !ignores_state {
+                let message = format!(
+                    "{} function can't be private unless it is `#[init(ignore_state)]`.",
+                    kind
+                );
+                return Err(Error::new(span, message));
+            }
+        }
+
+        if matches!(private_allow, Some(PrivateAllow::Field(_) | PrivateAllow::Method(_)))
+            && receiver.is_none()
+        {
+            let message = "Allowing the callers of a private method with a field or a method of the contract requires a `self` receiver.";
+            return Err(Error::new(private_allow_span.unwrap_or_else(Span::call_site), message));
+        }
+
+        let result_skip_none = match (result_skip_none_span, &returns.kind) {
+            (None, _) => false,
+            (Some(_), ReturnKind::General(ty) | ReturnKind::HandlesResult { ok_type: ty })
+                if utils::type_is_option(ty) =>
+            {
+                true
+            }
+            (Some(span), _) => {
+                let message =
+                    "The `skip_none` option can only be used on methods returning `Option<T>`.";
+                return Err(Error::new(span, message));
+            }
+        };
+
         let result = match kind {
             Call => MethodKind::Call(CallMethod {
                 is_payable,
+                min_deposit,
                 is_private,
+                private_allow,
                 result_serializer,
+                result_skip_none,
+                result_sorted_keys,
                 returns,
                 receiver,
             }),
-            Init => MethodKind::Init(InitMethod { is_payable, ignores_state, returns }),
-            View => {
-                MethodKind::View(ViewMethod { is_private, result_serializer, receiver, returns })
-            }
+            Init => MethodKind::Init(InitMethod {
+                is_payable,
+                min_deposit,
+                is_private,
+                ignores_state,
+                // `#[migrate]` functions fail on a second call as the state was migrated already.
+                run_once: ignores_state && !allow_repeat && migrate_span.is_none(),
+                returns,
+            }),
+            View => MethodKind::View(ViewMethod {
+                is_private,
+                private_allow,
+                result_serializer,
+                result_skip_none,
+                result_sorted_keys,
+                receiver,
+                returns,
+            }),
         };
 
         Ok(result)
@@ -193,7 +326,10 @@ impl Visitor {
 }
 
 fn is_init(attributes: &[Attribute]) -> bool {
-    attributes.iter().any(|a| a.path.to_token_stream().to_string() == "init")
+    attributes.iter().any(|a| {
+        let path = a.path.to_token_stream().to_string();
+        path == "init" || path == "migrate"
+    })
 }
 
 fn is_view(sig: &Signature) -> bool {
@@ -1,11 +1,15 @@
 #[cfg(feature = "abi")]
 pub(crate) mod abi;
+mod borsh_comparisons;
 mod code_generator;
+mod contract_metadata;
 mod event;
 mod info_extractor;
 mod metadata;
 mod utils;
+pub(crate) use borsh_comparisons::{near_borsh_comparisons, BorshComparisons};
 pub(crate) use code_generator::*;
+pub(crate) use contract_metadata::ContractMetadata;
 pub(crate) use event::{get_event_version, near_events};
 pub(crate) use info_extractor::*;
 pub(crate) use metadata::metadata_visitor::MetadataVisitor;
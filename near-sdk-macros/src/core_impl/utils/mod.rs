@@ -2,7 +2,7 @@ use proc_macro2::{Group, Span, TokenStream as TokenStream2, TokenTree};
 use quote::quote;
 use syn::spanned::Spanned;
 use syn::token::{And, Mut};
-use syn::{GenericArgument, Path, PathArguments, Signature, Type};
+use syn::{GenericArgument, Ident, Path, PathArguments, Signature, Type};
 
 /// Checks whether the given path is literally "Result".
 /// Note that it won't match a fully qualified name `core::result::Result` or a type alias like
@@ -21,6 +21,42 @@ pub(crate) fn type_is_result(ty: &Type) -> bool {
     }
 }
 
+/// Checks whether the given type is literally `Option<T>`, with the same limitations as
+/// `path_is_result`.
+pub(crate) fn type_is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) if type_path.qself.is_none() => {
+            let path = &type_path.path;
+            path.leading_colon.is_none()
+                && path.segments.len() == 1
+                && path.segments.iter().next().unwrap().ident == "Option"
+        }
+        _ => false,
+    }
+}
+
+/// Checks whether the given type is `Self` or the contract type `self_ty`, which `Self` is
+/// replaced with. Only the last segments of the paths are compared, so `crate::Contract` matches
+/// `Contract`, but a type alias of the contract doesn't.
+pub(crate) fn type_is_self(ty: &Type, self_ty: &TokenStream2) -> bool {
+    fn last_ident(ty: &Type) -> Option<&Ident> {
+        match ty {
+            Type::Path(type_path) if type_path.qself.is_none() => {
+                type_path.path.segments.last().map(|segment| &segment.ident)
+            }
+            Type::Group(group) => last_ident(&group.elem),
+            Type::Paren(paren) => last_ident(&paren.elem),
+            _ => None,
+        }
+    }
+
+    let self_ty = syn::parse2::<Type>(self_ty.clone()).ok();
+    match last_ident(ty) {
+        Some(ident) => ident == "Self" || self_ty.as_ref().and_then(last_ident) == Some(ident),
+        None => false,
+    }
+}
+
 /// Extracts the Ok type from a `Result` type.
 ///
 /// For example, given `Result<String, u8>` type it will return `String` type.
@@ -47,7 +83,6 @@ pub(crate) fn extract_ok_type(ty: &Type) -> Option<&Type> {
 /// Checks whether the given path is literally "Vec".
 /// Note that it won't match a fully qualified name `std::vec::Vec` or a type alias like
 /// `type MyVec = Vec<String>`.
-#[cfg(feature = "__abi-generate")]
 fn path_is_vec(path: &Path) -> bool {
     path.leading_colon.is_none()
         && path.segments.len() == 1
@@ -57,7 +92,6 @@ fn path_is_vec(path: &Path) -> bool {
 /// Extracts the inner generic type from a `Vec<_>` type.
 ///
 /// For example, given `Vec<String>` this function will return `String`.
-#[cfg(feature = "__abi-generate")]
 pub(crate) fn extract_vec_type(ty: &Type) -> Option<&Type> {
     match ty {
         Type::Path(type_path) if type_path.qself.is_none() && path_is_vec(&type_path.path) => {
@@ -125,7 +159,9 @@ fn _sanitize_self(typ: TokenStream2, replace_with: &TokenStream2) -> TokenStream
             .collect::<TokenStream2>(),
         TokenTree::Group(group) => {
             let stream = _sanitize_self(group.stream(), replace_with);
-            TokenTree::Group(Group::new(group.delimiter(), stream)).into()
+            let mut sanitized = Group::new(group.delimiter(), stream);
+            sanitized.set_span(group.span());
+            TokenTree::Group(sanitized).into()
         }
         rest => rest.into(),
     });
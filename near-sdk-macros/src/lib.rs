@@ -9,6 +9,7 @@ use proc_macro::TokenStream;
 use self::core_impl::*;
 use proc_macro2::Span;
 use quote::{quote, ToTokens};
+use syn::spanned::Spanned;
 use syn::visit::Visit;
 use syn::{parse_quote, File, ItemEnum, ItemImpl, ItemStruct, ItemTrait, WhereClause};
 
@@ -19,14 +20,52 @@ use syn::{parse_quote, File, ItemEnum, ItemImpl, ItemStruct, ItemTrait, WhereCla
 /// This macro will generate code to load and deserialize state if the `self` parameter is included
 /// as well as saving it back to state if `&mut self` is used.
 ///
+/// The contract struct can be generic, e.g. `Contract<T: BorshSerialize + BorshDeserialize>`, as
+/// long as the `impl` blocks exposing its methods are for a concrete type, like
+/// `impl Contract<u64>`, as the state is deserialized into that type.
+///
 /// For parameter serialization, this macro will generate a struct with all of the parameters as
 /// fields and derive deserialization for it. By default this will be JSON deserialized with `serde`
-/// but can be overwritten by using `#[serializer(borsh)]`.
+/// but can be overwritten by using `#[serializer(borsh)]`, either on each of the parameters or on
+/// the method itself to apply it to all of its parameters. With `#[serializer(auto)]` on the
+/// method, the input is accepted as either JSON or Borsh, e.g. while clients migrate from one
+/// format to the other. The parameters then have to implement both `Deserialize` and
+/// `BorshDeserialize`.
 ///
 /// `#[near_bindgen]` will also handle serializing and setting the return value of the
 /// function execution based on what type is returned by the function. By default, this will be
 /// done through `serde` serialized as JSON, but this can be overwritten using
-/// `#[result_serializer(borsh)]`.
+/// `#[result_serializer(borsh)]`. Methods returning `Option<T>` can use
+/// `#[result_serializer(json, skip_none)]` to return an empty body instead of `null` for `None`.
+/// With `#[result_serializer(json, sorted_keys)]`, the keys of the JSON objects in the result are
+/// sorted lexicographically instead of following the order of the struct fields, which gives
+/// clients a deterministic output to compare or hash. The options can be combined.
+///
+/// Methods marked with `#[private]` can only be called by the contract account itself. Other
+/// callers can be allowed with `#[private(allow = "owner")]`, where `owner` is a field of the
+/// contract, `#[private(allow = "owner()")]`, where `owner` is a method of the contract, or
+/// `#[private(allow = ["alice.near", "bob.near"])]`. The field or the return value of the method
+/// has to implement `AsRef<str>`, e.g. be an `AccountId`.
+///
+/// Methods returning a promise can name the callback they schedule with `.then()` using
+/// `#[callback_link(method = "on_transfer")]`. The build then fails if the contract has no such
+/// method, and if the callback is defined in the same `impl` block, it must read the promise
+/// result, e.g. with a `#[callback_unwrap]` or `#[callback_result]` argument.
+///
+/// Methods without arguments ignore their input by default. With `#[deny_empty_args]`, they
+/// panic instead if called with anything but an empty input or an empty JSON object `{}`.
+///
+/// State migrations can use `#[migrate(from = OldState)]` on a static method taking the old state
+/// as its first argument and returning the new state, e.g.
+/// `pub fn migrate(old: OldState) -> Self`. The old state is read from storage as `OldState` and
+/// the returned state replaces it. Like `#[private] #[init(ignore_state)]`, the method can only be
+/// called by the contract account itself. Calling it again fails only if the migrated state can't
+/// be deserialized as `OldState`, so a migration between two layouts with a compatible Borsh
+/// encoding must guard against being run twice itself.
+///
+/// In unit tests, `#[near_bindgen]` also generates a function named after each method that runs
+/// the same checks and (de)serialization as the exported method, reading the input from the
+/// context given to `testing_env!`, e.g. `migrate()` for the `migrate` method.
 ///
 /// # Examples
 ///
@@ -44,6 +83,51 @@ use syn::{parse_quote, File, ItemEnum, ItemImpl, ItemStruct, ItemTrait, WhereCla
 /// }
 /// ```
 ///
+/// Contract Source Metadata Standard:
+///
+/// By passing `contract_metadata` as an argument on the contract struct or enum, `near_bindgen`
+/// generates the `contract_source_metadata` view method defined by NEP-330. The `standards` are
+/// listed as `"nepXXX"` or `"nepXXX:MAJOR.MINOR.PATCH"`, defaulting to version `1.0.0`, and
+/// NEP-330 itself is added if it is missing. The returned JSON is also available in the
+/// `CONTRACT_SOURCE_METADATA` associated constant of the contract.
+///
+/// ```ignore
+/// use near_sdk::near_bindgen;
+///
+/// #[near_bindgen(contract_metadata(
+///     version = "1.0.0",
+///     link = "https://github.com/near/near-sdk-rs",
+///     standards = ["nep141", "nep145"]
+/// ))]
+/// pub struct Contract {}
+/// ```
+///
+/// Borsh Comparisons:
+///
+/// By passing `eq = "borsh"` and `hash = "borsh"` as arguments on a struct or enum,
+/// `near_bindgen` implements `PartialEq` and `Eq`, and `Hash` respectively, on the Borsh
+/// serialization of the value rather than field by field. Values are then equal exactly when
+/// they are stored under the same key, e.g. in a `LookupMap`. The type has to implement
+/// `BorshSerialize`, and each comparison serializes both values. No contract code is generated
+/// for the type, so the arguments can't be combined with `contract_metadata`.
+///
+/// ```
+/// use near_sdk::borsh::{self, BorshSerialize};
+/// use near_sdk::{near_bindgen, AccountId};
+/// use std::collections::HashSet;
+///
+/// #[near_bindgen(eq = "borsh", hash = "borsh")]
+/// #[derive(BorshSerialize)]
+/// pub struct Pair {
+///     account_id: AccountId,
+///     nonce: u64,
+/// }
+///
+/// let pair = || Pair { account_id: "alice.near".parse().unwrap(), nonce: 1 };
+/// assert!(pair() == pair());
+/// assert_eq!(HashSet::from([pair(), pair()]).len(), 1);
+/// ```
+///
 /// Events Standard:
 ///
 /// By passing `event_json` as an argument `near_bindgen` will generate the relevant code to format events
@@ -52,7 +136,12 @@ use syn::{parse_quote, File, ItemEnum, ItemImpl, ItemStruct, ItemTrait, WhereCla
 /// For parameter serialization, this macro will generate a wrapper struct to include the NEP-297 standard fields `standard` and `version
 /// as well as include serialization reformatting to include the `event` and `data` fields automatically.
 /// The `standard` and `version` values must be included in the enum and variant declaration (see example below).
-/// By default this will be JSON deserialized with `serde`
+/// A default `version` can be set next to the `standard`, in which case `#[event_version]` is only needed on
+/// the variants that override it. Versions must follow the `MAJOR.MINOR.PATCH` format.
+/// By default this will be JSON deserialized with `serde`, with snake case event names. Serde
+/// container attributes placed after `near_bindgen`, e.g. `#[serde(rename_all = "camelCase")]`,
+/// replace the defaults instead of conflicting with them, and an existing `Serialize` derive is
+/// reused. The `tag` and `content` of the NEP-297 format can't be overridden.
 ///
 ///
 /// # Examples
@@ -72,6 +161,15 @@ use syn::{parse_quote, File, ItemEnum, ItemImpl, ItemStruct, ItemTrait, WhereCla
 ///    EmptyEvent
 /// }
 ///
+/// #[near_bindgen(event_json(standard = "nepYYY", version = "1.2.0"))]
+/// pub enum MyVersionedEvents {
+///    // Emitted with version "1.2.0".
+///    Deposit { amount: u128 },
+///
+///    #[event_version("2.0.0")]
+///    Withdraw { amount: u128 },
+/// }
+///
 /// #[near_bindgen]
 /// impl Contract {
 ///     pub fn some_function(&self) {
@@ -88,8 +186,22 @@ pub fn near_bindgen(attr: TokenStream, item: TokenStream) -> TokenStream {
         return core_impl::near_events(attr, item);
     }
 
+    if BorshComparisons::is_requested(&attr) {
+        return core_impl::near_borsh_comparisons(attr, item);
+    }
+
+    let contract_metadata = if attr.to_string().contains("contract_metadata") {
+        match syn::parse::<ContractMetadata>(attr) {
+            Ok(metadata) => Some(metadata),
+            Err(err) => return err.to_compile_error().into(),
+        }
+    } else {
+        None
+    };
+
     if let Ok(input) = syn::parse::<ItemStruct>(item.clone()) {
         let ext_gen = generate_ext_structs(&input.ident, Some(&input.generics));
+        let metadata_gen = contract_metadata.map(|m| m.generate(&input.ident, &input.generics));
         #[cfg(feature = "__abi-embed-checked")]
         let abi_embedded = abi::embed();
         #[cfg(not(feature = "__abi-embed-checked"))]
@@ -97,10 +209,12 @@ pub fn near_bindgen(attr: TokenStream, item: TokenStream) -> TokenStream {
         TokenStream::from(quote! {
             #input
             #ext_gen
+            #metadata_gen
             #abi_embedded
         })
     } else if let Ok(input) = syn::parse::<ItemEnum>(item.clone()) {
         let ext_gen = generate_ext_structs(&input.ident, Some(&input.generics));
+        let metadata_gen = contract_metadata.map(|m| m.generate(&input.ident, &input.generics));
         #[cfg(feature = "__abi-embed-checked")]
         let abi_embedded = abi::embed();
         #[cfg(not(feature = "__abi-embed-checked"))]
@@ -108,8 +222,17 @@ pub fn near_bindgen(attr: TokenStream, item: TokenStream) -> TokenStream {
         TokenStream::from(quote! {
             #input
             #ext_gen
+            #metadata_gen
             #abi_embedded
         })
+    } else if contract_metadata.is_some() {
+        TokenStream::from(
+            syn::Error::new(
+                Span::call_site(),
+                "`#[near_bindgen(contract_metadata(...))]` can only be used on the contract struct or enum definition.",
+            )
+            .to_compile_error(),
+        )
     } else if let Ok(mut input) = syn::parse::<ItemImpl>(item) {
         let item_impl_info = match ItemImplInfo::new(&mut input) {
             Ok(x) => x,
@@ -136,6 +259,12 @@ pub fn near_bindgen(attr: TokenStream, item: TokenStream) -> TokenStream {
         }
 
         let generated_code = item_impl_info.wrapper_code();
+        let callback_link_checks = match item_impl_info.callback_link_checks() {
+            Ok(x) => x,
+            Err(err) => {
+                return err.to_compile_error().into();
+            }
+        };
 
         // Add wrapper methods for ext call API
         let ext_generated_code = item_impl_info.generate_ext_wrapper_code();
@@ -143,6 +272,7 @@ pub fn near_bindgen(attr: TokenStream, item: TokenStream) -> TokenStream {
             #ext_generated_code
             #input
             #generated_code
+            #callback_link_checks
             #abi_generated
         })
     } else {
@@ -505,6 +635,75 @@ pub fn borsh_storage_key(item: TokenStream) -> TokenStream {
     })
 }
 
+/// `IntoStorageKey` generates implementation for the `IntoStorageKey` trait of an enum without
+/// fields, using the index of the variant as a single byte key. It allows short prefixes for
+/// persistent collections without implementing or deriving `BorshSerialize`.
+///
+/// The keys are the same as the Borsh serialization of the enum used by `BorshStorageKey`, so
+/// an enum without fields can switch from one derive to the other without migrating the state.
+/// As with `BorshStorageKey`, reordering, inserting or removing variants other than at the end
+/// changes the prefixes of the existing collections, which then have to be migrated.
+#[proc_macro_derive(IntoStorageKey)]
+pub fn into_storage_key(item: TokenStream) -> TokenStream {
+    let input = match syn::parse::<ItemEnum>(item) {
+        Ok(input) => input,
+        Err(_) => {
+            return TokenStream::from(
+                syn::Error::new(
+                    Span::call_site(),
+                    "IntoStorageKey can only be used as a derive on enums.",
+                )
+                .to_compile_error(),
+            );
+        }
+    };
+    if input.variants.len() > 256 {
+        return TokenStream::from(
+            syn::Error::new(
+                input.ident.span(),
+                "IntoStorageKey supports at most 256 variants, as each key is a single byte.",
+            )
+            .to_compile_error(),
+        );
+    }
+    let mut arms = vec![];
+    for (index, variant) in input.variants.iter().enumerate() {
+        if !matches!(variant.fields, syn::Fields::Unit) {
+            return TokenStream::from(
+                syn::Error::new(
+                    variant.fields.span(),
+                    "IntoStorageKey only supports variants without fields. Use BorshStorageKey instead.",
+                )
+                .to_compile_error(),
+            );
+        }
+        if let Some((_, discriminant)) = &variant.discriminant {
+            return TokenStream::from(
+                syn::Error::new(
+                    discriminant.span(),
+                    "IntoStorageKey uses the index of the variant, explicit discriminants are not supported.",
+                )
+                .to_compile_error(),
+            );
+        }
+        let ident = &variant.ident;
+        let index = index as u8;
+        arms.push(quote! { Self::#ident => #index });
+    }
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    TokenStream::from(quote! {
+        impl #impl_generics near_sdk::IntoStorageKey for #name #ty_generics #where_clause {
+            fn into_storage_key(self) -> ::std::vec::Vec<u8> {
+                let index: u8 = match self {
+                    #(#arms,)*
+                };
+                ::std::vec![index]
+            }
+        }
+    })
+}
+
 /// `FunctionError` generates implementation for `near_sdk::FunctionError` trait.
 /// It allows contract runtime to panic with the type using its `ToString` implementation
 /// as the message.
@@ -565,7 +764,7 @@ pub fn derive_event_attributes(item: TokenStream) -> TokenStream {
                 return TokenStream::from(
                     syn::Error::new(
                         Span::call_site(),
-                        "Near events must have `event_version`. Must have a single string literal value.",
+                        "Near events must have `event_version` or a default `version` in `event_json`. Must have a single string literal value.",
                     )
                     .to_compile_error(),
                 );
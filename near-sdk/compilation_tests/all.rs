@@ -5,6 +5,22 @@ fn compilation_tests() {
     t.compile_fail("compilation_tests/invalid_arg_pat.rs");
     t.pass("compilation_tests/regular.rs");
     t.pass("compilation_tests/private.rs");
+    t.pass("compilation_tests/private_allow.rs");
+    t.compile_fail("compilation_tests/private_allow_static.rs");
+    t.pass("compilation_tests/contract_metadata.rs");
+    t.compile_fail("compilation_tests/contract_metadata_invalid.rs");
+    t.pass("compilation_tests/result_serializer_skip_none.rs");
+    t.pass("compilation_tests/store_into_iter.rs");
+    t.pass("compilation_tests/callback_link.rs");
+    t.compile_fail("compilation_tests/callback_link_missing.rs");
+    t.pass("compilation_tests/deny_empty_args.rs");
+    t.compile_fail("compilation_tests/deny_empty_args_with_args.rs");
+    t.compile_fail("compilation_tests/into_storage_key_fields.rs");
+    t.pass("compilation_tests/migrate.rs");
+    t.compile_fail("compilation_tests/migrate_without_old_state.rs");
+    t.pass("compilation_tests/init_return_self.rs");
+    t.compile_fail("compilation_tests/init_wrong_return.rs");
+    t.pass("compilation_tests/generic_contract.rs");
     t.pass("compilation_tests/trait_impl.rs");
     t.compile_fail("compilation_tests/bad_argument.rs");
     t.pass("compilation_tests/complex.rs");
@@ -12,17 +28,29 @@ fn compilation_tests() {
     t.pass("compilation_tests/references.rs");
     t.pass("compilation_tests/init_function.rs");
     t.pass("compilation_tests/init_ignore_state.rs");
+    t.compile_fail("compilation_tests/init_private.rs");
     t.pass("compilation_tests/no_default.rs");
     t.pass("compilation_tests/lifetime_method.rs");
     t.pass("compilation_tests/cond_compilation.rs");
     t.compile_fail("compilation_tests/payable_view.rs");
+    t.pass("compilation_tests/payable_min.rs");
+    t.compile_fail("compilation_tests/payable_min_view.rs");
     t.pass("compilation_tests/borsh_storage_key.rs");
     t.pass("compilation_tests/borsh_storage_key_generics.rs");
     t.pass("compilation_tests/function_error.rs");
+    t.pass("compilation_tests/function_error_custom.rs");
     t.pass("compilation_tests/enum_near_bindgen.rs");
     t.pass("compilation_tests/schema_derive.rs");
     t.compile_fail("compilation_tests/schema_derive_invalids.rs");
     t.compile_fail("compilation_tests/generic_function.rs");
     t.compile_fail("compilation_tests/generic_const_function.rs");
     t.pass("compilation_tests/self_support.rs");
+    t.pass("compilation_tests/callback_results.rs");
+    t.pass("compilation_tests/method_serializer.rs");
+    t.compile_fail("compilation_tests/method_serializer_mixed.rs");
+    t.pass("compilation_tests/method_serializer_auto.rs");
+    t.compile_fail("compilation_tests/method_serializer_auto_arg.rs");
+    t.compile_fail("compilation_tests/event_invalid_version.rs");
+    t.compile_fail("compilation_tests/event_serde_tag.rs");
+    t.compile_fail("compilation_tests/borsh_comparisons_invalid.rs");
 }
@@ -0,0 +1,18 @@
+//! Invalid arguments of `#[near_bindgen(eq = "borsh", hash = "borsh")]`.
+
+use borsh::BorshSerialize;
+use near_sdk::near_bindgen;
+
+#[near_bindgen(eq = "json")]
+#[derive(BorshSerialize)]
+pub struct JsonEq {
+    value: u64,
+}
+
+#[near_bindgen(contract_metadata(version = "1.0.0"), hash = "borsh")]
+#[derive(BorshSerialize)]
+pub struct WithMetadata {
+    value: u64,
+}
+
+fn main() {}
@@ -0,0 +1,76 @@
+//! Methods returning promises linked to their callbacks.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::{env, near_bindgen, Gas, Promise, PromiseError};
+
+#[near_bindgen]
+#[derive(Default, BorshDeserialize, BorshSerialize)]
+struct Callback {
+    total: u64,
+}
+
+#[near_bindgen]
+impl Callback {
+    #[callback_link(method = "on_value")]
+    pub fn fetch_value(&self) -> Promise {
+        Promise::new("other.near".parse().unwrap())
+            .function_call("get_value".to_string(), vec![], 0, Gas::from_tgas(5))
+            .then(Promise::new(env::current_account_id()).function_call(
+                "on_value".to_string(),
+                vec![],
+                0,
+                Gas::from_tgas(5),
+            ))
+    }
+
+    #[private]
+    pub fn on_value(&mut self, #[callback_unwrap] value: u64) {
+        self.total += value;
+    }
+
+    #[callback_link(method = "on_result")]
+    pub fn fetch_result(&self) -> Promise {
+        Promise::new("other.near".parse().unwrap())
+    }
+
+    #[callback_link(method = "on_elsewhere")]
+    pub fn fetch_elsewhere(&self) -> Promise {
+        Promise::new("other.near".parse().unwrap())
+    }
+}
+
+#[near_bindgen]
+impl Callback {
+    #[private]
+    pub fn on_result(&mut self, #[callback_result] value: Result<u64, PromiseError>) {
+        self.total += value.unwrap_or_default();
+    }
+}
+
+impl Callback {
+    fn on_elsewhere(&mut self) {}
+}
+
+#[near_bindgen]
+#[derive(Default, BorshDeserialize, BorshSerialize)]
+struct GenericCallback<T: BorshSerialize + BorshDeserialize> {
+    total: T,
+}
+
+#[near_bindgen]
+impl GenericCallback<u64>
+where
+    u64: Copy,
+{
+    #[callback_link(method = "on_value")]
+    pub fn fetch_value(&self) -> Promise {
+        Promise::new("other.near".parse().unwrap())
+    }
+
+    #[private]
+    pub fn on_value(&mut self, #[callback_unwrap] value: u64) {
+        self.total += value;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+//! A promise linked to a callback that doesn't exist.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::{near_bindgen, Promise};
+
+#[near_bindgen]
+#[derive(Default, BorshDeserialize, BorshSerialize)]
+struct Callback {}
+
+#[near_bindgen]
+impl Callback {
+    #[callback_link(method = "on_vlaue")]
+    pub fn fetch_value(&self) -> Promise {
+        Promise::new("other.near".parse().unwrap())
+    }
+
+    #[private]
+    pub fn on_value(&mut self, #[callback_unwrap] value: u64) -> u64 {
+        value
+    }
+}
+
+fn main() {}
@@ -0,0 +1,38 @@
+//! Callback methods receiving all promise results with their errors.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::{near_bindgen, PromiseError};
+
+#[near_bindgen]
+#[derive(Default, BorshDeserialize, BorshSerialize)]
+struct Callback {}
+
+#[near_bindgen]
+impl Callback {
+    #[private]
+    pub fn count_succeeded(#[callback_results] results: Vec<Result<u8, PromiseError>>) -> usize {
+        results.iter().filter(|result| result.is_ok()).count()
+    }
+
+    #[private]
+    pub fn all_units(
+        &mut self,
+        #[callback_results] mut results: Vec<Result<(), PromiseError>>,
+    ) -> bool {
+        results.retain(|result| result.is_ok());
+        results.is_empty()
+    }
+
+    #[private]
+    pub fn with_arg(
+        &self,
+        factor: u64,
+        #[serializer(borsh)]
+        #[callback_results]
+        results: Vec<Result<u64, PromiseError>>,
+    ) -> u64 {
+        results.into_iter().flatten().map(|x| x * factor).sum()
+    }
+}
+
+fn main() {}
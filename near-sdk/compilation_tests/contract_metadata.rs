@@ -0,0 +1,25 @@
+//! Contract with NEP-330 source metadata.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::near_bindgen;
+
+#[near_bindgen(contract_metadata(
+    version = "1.0.0",
+    link = "https://github.com/near/near-sdk-rs",
+    standards = ["nep141", "nep145"]
+))]
+#[derive(Default, BorshDeserialize, BorshSerialize)]
+struct Contract {
+    value: u32,
+}
+
+#[near_bindgen]
+impl Contract {
+    pub fn get_value(&self) -> u32 {
+        self.value
+    }
+}
+
+fn main() {
+    let _: &str = Contract::CONTRACT_SOURCE_METADATA;
+}
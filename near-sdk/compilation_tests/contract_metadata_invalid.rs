@@ -0,0 +1,15 @@
+//! Contract metadata only supports the fields defined by NEP-330.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::near_bindgen;
+
+#[near_bindgen(contract_metadata(version = "1.0.0", name = "contract"))]
+#[derive(Default, BorshDeserialize, BorshSerialize)]
+struct Contract {}
+
+#[near_bindgen(contract_metadata(version = "1.0.0"))]
+impl Contract {
+    pub fn ping(&self) {}
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+//! Methods without arguments can reject a non-empty input.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::near_bindgen;
+
+#[near_bindgen]
+#[derive(Default, BorshDeserialize, BorshSerialize)]
+struct Counter {
+    value: u64,
+}
+
+#[near_bindgen]
+impl Counter {
+    #[deny_empty_args]
+    pub fn get(&self) -> u64 {
+        self.value
+    }
+
+    #[deny_empty_args]
+    pub fn increment(&mut self) {
+        self.value += 1;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+//! `#[deny_empty_args]` is only meaningful for methods without arguments.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::near_bindgen;
+
+#[near_bindgen]
+#[derive(Default, BorshDeserialize, BorshSerialize)]
+struct Counter {
+    value: u64,
+}
+
+#[near_bindgen]
+impl Counter {
+    #[deny_empty_args]
+    pub fn add(&mut self, value: u64) {
+        self.value += value;
+    }
+}
+
+fn main() {}
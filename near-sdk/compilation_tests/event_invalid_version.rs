@@ -0,0 +1,16 @@
+//! Event versions must follow the `MAJOR.MINOR.PATCH` format.
+
+use near_sdk::near_bindgen;
+
+#[near_bindgen(event_json(standard = "test_standard", version = "1.0"))]
+pub enum DefaultVersionEvents {
+    Test,
+}
+
+#[near_bindgen(event_json(standard = "test_standard"))]
+pub enum VariantVersionEvents {
+    #[event_version("v1.0.0")]
+    Test,
+}
+
+fn main() {}
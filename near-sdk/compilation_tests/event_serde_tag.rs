@@ -0,0 +1,17 @@
+//! The `tag` and `content` of the event envelope can't be overridden.
+
+use near_sdk::near_bindgen;
+
+#[near_bindgen(event_json(standard = "test_standard"))]
+#[serde(tag = "kind")]
+pub enum TagEvents {
+    Test,
+}
+
+#[near_bindgen(event_json(standard = "test_standard"))]
+#[serde(content = "payload")]
+pub enum ContentEvents {
+    Test,
+}
+
+fn main() {}
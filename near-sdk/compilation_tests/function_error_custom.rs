@@ -0,0 +1,47 @@
+//! Testing `#[handle_result]` with error types that implement `FunctionError` themselves.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::serde::Serialize;
+use near_sdk::{env, near_bindgen, FunctionError, JsonError};
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct CodedError {
+    code: u32,
+    message: String,
+}
+
+impl FunctionError for CodedError {
+    fn panic(&self) -> ! {
+        env::abort_with(self)
+    }
+}
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+struct Contract {}
+
+#[near_bindgen]
+impl Contract {
+    #[handle_result]
+    pub fn string_error(&self) -> Result<u32, String> {
+        Err("not found".to_string())
+    }
+
+    #[handle_result]
+    pub fn str_error(&self) -> Result<u32, &'static str> {
+        Err("not found")
+    }
+
+    #[handle_result]
+    pub fn coded_error(&self) -> Result<u32, CodedError> {
+        Err(CodedError { code: 404, message: "not found".to_string() })
+    }
+
+    #[handle_result]
+    pub fn json_error(&self) -> Result<u32, JsonError<u32>> {
+        Err(JsonError(404))
+    }
+}
+
+fn main() {}
@@ -0,0 +1,38 @@
+//! Contracts can be generic, as long as the `impl` block uses a concrete type.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::{env, near_bindgen, Promise};
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+struct Contract<T: BorshSerialize + BorshDeserialize> {
+    value: T,
+    history: Vec<T>,
+}
+
+#[near_bindgen]
+impl Contract<u64> {
+    #[init]
+    pub fn new(value: u64) -> Self {
+        Self { value, history: vec![] }
+    }
+
+    pub fn set(&mut self, value: u64) {
+        self.history.push(self.value);
+        self.value = value;
+    }
+
+    pub fn get(&self) -> u64 {
+        self.value
+    }
+
+    pub fn default_value() -> u64 {
+        0
+    }
+
+    pub fn get_remote(&self) -> Promise {
+        Self::ext(env::current_account_id()).get()
+    }
+}
+
+fn main() {}
@@ -18,6 +18,16 @@ impl Incrementer {
     pub fn new(starting_value: u32) -> Self {
         Self { value: starting_value }
     }
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        Self { value: 0 }
+    }
+    #[private]
+    #[init(ignore_state, allow_repeat)]
+    pub fn reset() -> Self {
+        Self { value: 0 }
+    }
 }
 
 fn main() {}
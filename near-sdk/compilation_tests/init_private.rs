@@ -0,0 +1,21 @@
+//! Init functions can only be private if they ignore state.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::near_bindgen;
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize)]
+struct Incrementer {
+    value: u32,
+}
+
+#[near_bindgen]
+impl Incrementer {
+    #[private]
+    #[init]
+    pub fn new(starting_value: u32) -> Self {
+        Self { value: starting_value }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,35 @@
+//! Init functions can return `Self`, the contract type, or `Result<Self, E>` with
+//! `#[handle_result]`.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::near_bindgen;
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize)]
+struct Incrementer {
+    value: u32,
+}
+
+#[near_bindgen]
+impl Incrementer {
+    #[init]
+    pub fn new(starting_value: u32) -> Self {
+        Self { value: starting_value }
+    }
+
+    #[init]
+    pub fn zero() -> Incrementer {
+        Incrementer { value: 0 }
+    }
+
+    #[init]
+    #[handle_result]
+    pub fn try_new(starting_value: u32) -> Result<Self, &'static str> {
+        if starting_value > 100 {
+            return Err("Starting value is too large");
+        }
+        Ok(Self { value: starting_value })
+    }
+}
+
+fn main() {}
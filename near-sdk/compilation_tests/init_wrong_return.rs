@@ -0,0 +1,35 @@
+//! Init functions must return the contract state.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::near_bindgen;
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize)]
+struct Incrementer {
+    value: u32,
+}
+
+#[near_bindgen]
+impl Incrementer {
+    #[init]
+    pub fn new(starting_value: u32) -> u32 {
+        starting_value
+    }
+}
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize)]
+struct Counter {
+    value: u32,
+}
+
+#[near_bindgen]
+impl Counter {
+    #[init]
+    #[handle_result]
+    pub fn new(starting_value: u32) -> Result<u32, &'static str> {
+        Ok(starting_value)
+    }
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+//! IntoStorageKey can't be derived for variants with fields, which need more than one byte.
+
+use near_sdk::IntoStorageKey;
+
+#[derive(IntoStorageKey)]
+enum StorageKey {
+    Accounts,
+    SubAccounts { account_id: String },
+}
+
+fn main() {}
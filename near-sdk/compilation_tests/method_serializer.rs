@@ -0,0 +1,35 @@
+//! Contract mixing JSON and Borsh serialized method arguments.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::near_bindgen;
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Payload {
+    data: Vec<u8>,
+}
+
+#[near_bindgen]
+#[derive(Default, BorshDeserialize, BorshSerialize)]
+struct Storage {
+    total: u64,
+}
+
+#[near_bindgen]
+impl Storage {
+    pub fn add_json(&mut self, amount: u64) {
+        self.total += amount;
+    }
+
+    #[serializer(borsh)]
+    pub fn add_borsh(&mut self, amount: u64, payload: Payload) {
+        self.total += amount + payload.data.len() as u64;
+    }
+
+    #[serializer(borsh)]
+    #[result_serializer(borsh)]
+    pub fn get_borsh(&self, offset: u64) -> u64 {
+        self.total + offset
+    }
+}
+
+fn main() {}
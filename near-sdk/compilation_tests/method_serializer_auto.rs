@@ -0,0 +1,33 @@
+//! Method accepting its arguments in either JSON or Borsh.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::near_bindgen;
+use near_sdk::serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Payload {
+    data: Vec<u8>,
+}
+
+#[near_bindgen]
+#[derive(Default, BorshDeserialize, BorshSerialize)]
+struct Storage {
+    total: u64,
+}
+
+#[near_bindgen]
+impl Storage {
+    #[serializer(auto)]
+    pub fn add(&mut self, amount: u64, payload: Payload) {
+        self.total += amount + payload.data.len() as u64;
+    }
+
+    #[serializer(auto)]
+    #[result_serializer(borsh)]
+    pub fn get(&self, offset: u64) -> u64 {
+        self.total + offset
+    }
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+//! The `auto` serializer is not supported on individual arguments.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::near_bindgen;
+
+#[near_bindgen]
+#[derive(Default, BorshDeserialize, BorshSerialize)]
+struct Storage {}
+
+#[near_bindgen]
+impl Storage {
+    pub fn insert(&mut self, #[serializer(auto)] value: u64) {}
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+//! Method-level serializer conflicting with the serializer of an argument.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::near_bindgen;
+
+#[near_bindgen]
+#[derive(Default, BorshDeserialize, BorshSerialize)]
+struct Storage {}
+
+#[near_bindgen]
+impl Storage {
+    #[serializer(json)]
+    pub fn insert(&mut self, key: u64, #[serializer(borsh)] value: u64) {}
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+//! Smart contract migrating its state from an older layout.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::near_bindgen;
+
+#[derive(BorshDeserialize, BorshSerialize)]
+struct OldIncrementer {
+    value: u32,
+}
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+struct Incrementer {
+    value: u64,
+    step: u64,
+}
+
+#[near_bindgen]
+impl Incrementer {
+    #[migrate(from = OldIncrementer)]
+    pub fn migrate(old: OldIncrementer, step: u64) -> Self {
+        Self { value: u64::from(old.value), step }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+//! The old state must be the first argument of a `#[migrate]` method.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::near_bindgen;
+
+#[derive(BorshDeserialize, BorshSerialize)]
+struct OldIncrementer {
+    value: u32,
+}
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+struct Incrementer {
+    value: u64,
+}
+
+#[near_bindgen]
+impl Incrementer {
+    #[migrate(from = OldIncrementer)]
+    pub fn migrate() -> Self {
+        Self { value: 0 }
+    }
+}
+
+fn main() {}
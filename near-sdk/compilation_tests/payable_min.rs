@@ -0,0 +1,37 @@
+//! Payable methods can require a minimum attached deposit.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::near_bindgen;
+
+#[near_bindgen]
+#[derive(Default, BorshDeserialize, BorshSerialize)]
+struct Test {
+    deposits: u32,
+}
+
+#[near_bindgen]
+impl Test {
+    #[init]
+    #[payable(min = "0.01 NEAR")]
+    pub fn new() -> Self {
+        Self { deposits: 0 }
+    }
+
+    #[payable(min = "1 NEAR")]
+    pub fn deposit(&mut self) {
+        self.deposits += 1;
+    }
+
+    #[payable(min = "500 mNEAR")]
+    pub fn half_deposit(&mut self) {
+        self.deposits += 1;
+    }
+
+    #[payable(min = "1")]
+    pub fn yocto_deposit(&mut self) {}
+
+    #[payable]
+    pub fn any_deposit(&mut self) {}
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+//! A minimum deposit can't be required by view methods, which are not payable.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::near_bindgen;
+
+#[near_bindgen]
+#[derive(Default, BorshDeserialize, BorshSerialize)]
+struct Test {}
+
+#[near_bindgen]
+impl Test {
+    #[payable(min = "1 NEAR")]
+    pub fn pay(&self) {}
+}
+
+fn main() {}
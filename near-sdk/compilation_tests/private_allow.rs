@@ -0,0 +1,51 @@
+//! Private methods that can also be called by other accounts.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::{near_bindgen, AccountId};
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize)]
+struct Incrementer {
+    value: u32,
+    owner: AccountId,
+}
+
+impl Default for Incrementer {
+    fn default() -> Self {
+        Self { value: 0, owner: "owner.near".parse().unwrap() }
+    }
+}
+
+#[near_bindgen]
+impl Incrementer {
+    #[private(allow = "owner")]
+    pub fn inc_by_owner(&mut self, by: u32) {
+        self.value += by;
+    }
+
+    #[private(allow = "operator()")]
+    pub fn inc_by_operator(&mut self, by: u32) {
+        self.value += by;
+    }
+
+    #[private(allow = ["alice.near", "bob.near"])]
+    pub fn inc_by_allowed(&mut self, by: u32) {
+        self.value += by;
+    }
+
+    #[private(allow = [])]
+    pub fn inc_by_self(&mut self, by: u32) {
+        self.value += by;
+    }
+
+    #[private(allow = "owner")]
+    pub fn get_value(&self) -> u32 {
+        self.value
+    }
+
+    pub fn operator(&self) -> String {
+        format!("operator.{}", self.owner)
+    }
+}
+
+fn main() {}
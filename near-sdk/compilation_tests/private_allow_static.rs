@@ -0,0 +1,18 @@
+//! Private methods can't allow callers through the contract state without a receiver.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::{near_bindgen, AccountId};
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize)]
+struct Incrementer {
+    owner: AccountId,
+}
+
+#[near_bindgen]
+impl Incrementer {
+    #[private(allow = "owner")]
+    pub fn ping() {}
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+//! Methods returning an empty body for `None`.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::near_bindgen;
+
+#[near_bindgen]
+#[derive(Default, BorshDeserialize, BorshSerialize)]
+struct Storage {
+    value: Option<u64>,
+}
+
+#[near_bindgen]
+impl Storage {
+    #[result_serializer(json, skip_none)]
+    pub fn get_value(&self) -> Option<u64> {
+        self.value
+    }
+
+    #[handle_result]
+    #[result_serializer(json, skip_none)]
+    pub fn take_value(&mut self) -> Result<Option<u64>, &'static str> {
+        Ok(self.value.take())
+    }
+}
+
+fn main() {}
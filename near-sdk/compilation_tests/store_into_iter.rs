@@ -0,0 +1,54 @@
+//! Iterating over borrowed store collections with `for` loops.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::near_bindgen;
+use near_sdk::store::{TreeMap, UnorderedMap, Vector};
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize)]
+struct Storage {
+    balances: UnorderedMap<String, u64>,
+    sorted: TreeMap<String, u64>,
+    history: Vector<u64>,
+}
+
+impl Default for Storage {
+    fn default() -> Self {
+        Self {
+            balances: UnorderedMap::new(b"b"),
+            sorted: TreeMap::new(b"s"),
+            history: Vector::new(b"h"),
+        }
+    }
+}
+
+#[near_bindgen]
+impl Storage {
+    pub fn total(&self) -> u64 {
+        let mut total = 0;
+        for (_, balance) in &self.balances {
+            total += balance;
+        }
+        for (_, balance) in &self.sorted {
+            total += balance;
+        }
+        for amount in &self.history {
+            total += amount;
+        }
+        total
+    }
+
+    pub fn double(&mut self) {
+        for (_, balance) in &mut self.balances {
+            *balance *= 2;
+        }
+        for (_, balance) in &mut self.sorted {
+            *balance *= 2;
+        }
+        for amount in &mut self.history {
+            *amount *= 2;
+        }
+    }
+}
+
+fn main() {}
@@ -12,7 +12,8 @@ use std::{convert::TryFrom, mem::MaybeUninit};
 use crate::mock::MockedBlockchain;
 use crate::promise::Allowance;
 use crate::types::{
-    AccountId, Balance, BlockHeight, Gas, PromiseIndex, PromiseResult, PublicKey, StorageUsage,
+    AccountId, Balance, BlockHeight, Gas, NearToken, PromiseIndex, PromiseResult, PublicKey,
+    StorageUsage,
 };
 use crate::{GasWeight, PromiseError};
 use near_sys as sys;
@@ -26,15 +27,24 @@ const REGISTER_EXPECTED_ERR: &str =
 const ATOMIC_OP_REGISTER: u64 = std::u64::MAX - 2;
 /// Register used to record evicted values from the storage.
 const EVICTED_REGISTER: u64 = std::u64::MAX - 1;
+/// Register used to keep the input of the contract call for [`input_register`]. No other function
+/// of the environment writes to it, so the input is not clobbered by other host calls.
+const INPUT_REGISTER: u64 = std::u64::MAX - 3;
 
 /// Key used to store the state of the contract.
-const STATE_KEY: &[u8] = b"STATE";
+pub(crate) const STATE_KEY: &[u8] = b"STATE";
 
 /// The minimum length of a valid account ID.
 const MIN_ACCOUNT_ID_LEN: u64 = 2;
 /// The maximum length of a valid account ID.
 const MAX_ACCOUNT_ID_LEN: u64 = 64;
 
+thread_local! {
+    /// The id of the current account, which is constant for a contract execution, so it is only
+    /// read from the host once. Reset with the mocked blockchain in unit tests.
+    static CURRENT_ACCOUNT_ID: std::cell::RefCell<Option<AccountId>> = std::cell::RefCell::new(None);
+}
+
 fn expect_register<T>(option: Option<T>) -> T {
     option.unwrap_or_else(|| panic_str(REGISTER_EXPECTED_ERR))
 }
@@ -100,7 +110,8 @@ pub(crate) unsafe fn read_register_fixed_64(register_id: u64) -> [u8; 64] {
 pub fn set_blockchain_interface(blockchain_interface: MockedBlockchain) {
     crate::mock::with_mocked_blockchain(|b| {
         *b = blockchain_interface;
-    })
+    });
+    CURRENT_ACCOUNT_ID.with(|id| id.borrow_mut().take());
 }
 
 /// Implements panic hook that converts `PanicInfo` into a string and provides it through the
@@ -150,8 +161,17 @@ pub fn register_len(register_id: u64) -> Option<u64> {
 // # Context API #
 // ###############
 /// The id of the account that owns the current contract.
+///
+/// The id is only read from the host on the first call of a contract execution; later calls
+/// return a copy of it.
 pub fn current_account_id() -> AccountId {
-    assert_valid_account_id(method_into_register!(current_account_id))
+    CURRENT_ACCOUNT_ID.with(|id| {
+        id.borrow_mut()
+            .get_or_insert_with(|| {
+                assert_valid_account_id(method_into_register!(current_account_id))
+            })
+            .clone()
+    })
 }
 
 /// The id of the account that either signed the original transaction or issued the initial
@@ -171,6 +191,37 @@ pub fn predecessor_account_id() -> AccountId {
     assert_valid_account_id(method_into_register!(predecessor_account_id))
 }
 
+/// Returns whether the [`predecessor_account_id`] is `expected`. The predecessor is compared
+/// in place, without allocating an [`AccountId`] for it.
+pub fn is_predecessor(expected: &AccountId) -> bool {
+    let expected = expected.as_bytes();
+    unsafe { sys::predecessor_account_id(ATOMIC_OP_REGISTER) };
+    let len = expect_register(register_len(ATOMIC_OP_REGISTER));
+    if len != expected.len() as u64 || len > MAX_ACCOUNT_ID_LEN {
+        return false;
+    }
+    let mut predecessor = [0u8; MAX_ACCOUNT_ID_LEN as usize];
+    //* SAFETY: The register holds `len` bytes, which was checked above to fit into the buffer.
+    unsafe { sys::read_register(ATOMIC_OP_REGISTER, predecessor.as_mut_ptr() as u64) };
+    &predecessor[..expected.len()] == expected
+}
+
+/// Panics unless the [`predecessor_account_id`] is `expected`, e.g. to restrict a method to
+/// the owner of the contract.
+///
+/// # Examples
+/// ```
+/// use near_sdk::{env, AccountId};
+///
+/// let owner: AccountId = env::predecessor_account_id();
+/// env::assert_predecessor(&owner);
+/// ```
+pub fn assert_predecessor(expected: &AccountId) {
+    if !is_predecessor(expected) {
+        panic_str(&format!("Method can only be called by {}", expected))
+    }
+}
+
 /// Helper function to convert and check the account ID from bytes from the runtime.
 fn assert_valid_account_id(bytes: Vec<u8>) -> AccountId {
     String::from_utf8(bytes)
@@ -184,6 +235,71 @@ pub fn input() -> Option<Vec<u8>> {
     try_method_into_register!(input)
 }
 
+/// Loads the input to the contract call into a register without copying it into the contract
+/// memory. A call without input yields an empty [`InputRegister`], see
+/// [`InputRegister::is_empty`].
+///
+/// The returned [`InputRegister`] can copy the input into a reused buffer, which avoids the
+/// allocation [`input`] makes on every call for contracts reading large inputs.
+///
+/// # Examples
+///
+/// ```
+/// # use borsh::BorshSerialize;
+/// # use near_sdk::{env, testing_env, test_utils::VMContextBuilder};
+/// # let mut context = VMContextBuilder::new().build();
+/// # context.input = vec![1u64, 2, 3].try_to_vec().unwrap();
+/// # testing_env!(context);
+/// let mut buf = Vec::new();
+/// let input = env::input_register().unwrap();
+/// let values: Vec<u64> = borsh::BorshDeserialize::try_from_slice(input.read_into(&mut buf)).unwrap();
+/// assert_eq!(values, [1, 2, 3]);
+/// ```
+pub fn input_register() -> Option<InputRegister> {
+    unsafe { sys::input(INPUT_REGISTER) };
+    register_len(INPUT_REGISTER).map(|len| InputRegister { len })
+}
+
+/// The input to the contract call loaded into a register by [`input_register`].
+#[derive(Debug)]
+pub struct InputRegister {
+    len: u64,
+}
+
+impl InputRegister {
+    /// Returns the length of the input in bytes.
+    pub fn len(&self) -> usize {
+        self.len.try_into().unwrap_or_else(|_| abort())
+    }
+
+    /// Returns `true` if the input is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Copies the input into `buf`, replacing its contents, and returns it as a slice. The
+    /// allocation of `buf` is reused when it already has enough capacity for the input.
+    pub fn read_into<'a>(&self, buf: &'a mut Vec<u8>) -> &'a [u8] {
+        let len = self.len();
+        buf.clear();
+        buf.reserve(len);
+        //* SAFETY: The buffer has capacity for the length of the register, which is written
+        //*         completely before the length of the buffer is updated.
+        unsafe {
+            sys::read_register(INPUT_REGISTER, buf.as_mut_ptr() as u64);
+            buf.set_len(len);
+        }
+        buf
+    }
+
+    /// Copies the input into a new vector.
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.read_into(&mut buf);
+        buf
+    }
+}
+
 /// Current block index.
 #[deprecated(since = "4.0.0", note = "Use block_height instead")]
 pub fn block_index() -> BlockHeight {
@@ -192,7 +308,7 @@ pub fn block_index() -> BlockHeight {
 
 /// Returns the height of the block the transaction is being executed in.
 pub fn block_height() -> BlockHeight {
-    unsafe { sys::block_height() }
+    BlockHeight(unsafe { sys::block_height() })
 }
 
 /// Current block timestamp, i.e, number of non-leap-nanoseconds since January 1, 1970 0:00:00 UTC.
@@ -201,10 +317,25 @@ pub fn block_timestamp() -> u64 {
 }
 
 /// Current block timestamp, i.e, number of non-leap-milliseconds since January 1, 1970 0:00:00 UTC.
+///
+/// The nanoseconds of [`block_timestamp`] are truncated, not rounded.
 pub fn block_timestamp_ms() -> u64 {
     block_timestamp() / 1_000_000
 }
 
+/// Current block timestamp, i.e, number of non-leap-seconds since January 1, 1970 0:00:00 UTC.
+///
+/// The nanoseconds of [`block_timestamp`] are truncated, not rounded.
+pub fn block_timestamp_s() -> u64 {
+    block_timestamp() / 1_000_000_000
+}
+
+/// Current block timestamp as the [`Duration`](core::time::Duration) since January 1, 1970
+/// 0:00:00 UTC, without losing the precision of [`block_timestamp`].
+pub fn block_timestamp_duration() -> core::time::Duration {
+    core::time::Duration::from_nanos(block_timestamp())
+}
+
 /// Current epoch height.
 pub fn epoch_height() -> u64 {
     unsafe { sys::epoch_height() }
@@ -220,17 +351,45 @@ pub fn storage_usage() -> StorageUsage {
 // #################
 /// The balance attached to the given account. This includes the attached_deposit that was
 /// attached to the transaction
-pub fn account_balance() -> Balance {
+pub fn account_balance() -> NearToken {
     let data = [0u8; size_of::<Balance>()];
     unsafe { sys::account_balance(data.as_ptr() as u64) };
-    Balance::from_le_bytes(data)
+    NearToken::from_yoctonear(Balance::from_le_bytes(data))
 }
 
-/// The balance locked for potential validator staking.
-pub fn account_locked_balance() -> Balance {
+/// The balance locked for potential validator staking. It is not included in
+/// [`account_balance`].
+pub fn account_locked_balance() -> NearToken {
     let data = [0u8; size_of::<Balance>()];
     unsafe { sys::account_locked_balance(data.as_ptr() as u64) };
-    Balance::from_le_bytes(data)
+    NearToken::from_yoctonear(Balance::from_le_bytes(data))
+}
+
+/// The part of [`account_balance`] that can be spent or transferred without breaking the
+/// storage staking of the account.
+///
+/// The cost of the [`storage_usage`] of the account is covered by the locked balance first, and
+/// only the rest of it by the account balance. This is `0` if the account can't cover its storage.
+///
+/// # Examples
+///
+/// ```
+/// use near_sdk::{env, testing_env, test_utils::VMContextBuilder, NearToken};
+///
+/// testing_env!(VMContextBuilder::new()
+///     .account_balance(NearToken::from_near(10).as_yoctonear())
+///     .storage_usage(100_000)
+///     .build());
+/// // 100 kB of storage cost 1 NEAR.
+/// assert_eq!(env::account_available_balance(), NearToken::from_near(9));
+/// ```
+pub fn account_available_balance() -> NearToken {
+    let storage_cost = storage_cost_for_bytes(storage_usage()).as_yoctonear();
+    let unlocked_storage_cost =
+        storage_cost.saturating_sub(account_locked_balance().as_yoctonear());
+    NearToken::from_yoctonear(
+        account_balance().as_yoctonear().saturating_sub(unlocked_storage_cost),
+    )
 }
 
 /// The balance that was attached to the call that will be immediately deposited before the
@@ -251,6 +410,29 @@ pub fn used_gas() -> Gas {
     Gas(unsafe { sys::used_gas() })
 }
 
+/// The gas that can still be used by the contract execution, i.e. [`prepaid_gas`] minus
+/// [`used_gas`]. Gas attached to promises created so far counts as used.
+pub fn remaining_gas() -> Gas {
+    prepaid_gas().saturating_sub(used_gas())
+}
+
+/// Returns whether at least `min` gas remains, e.g. to stop processing a batch before running
+/// out of gas, while there is still enough left to save the progress.
+///
+/// # Examples
+/// ```
+/// use near_sdk::{env, Gas};
+///
+/// let mut processed = 0;
+/// while processed < 10 && env::gas_guard(Gas::from_tgas(5)) {
+///     processed += 1;
+/// }
+/// assert_eq!(processed, 10);
+/// ```
+pub fn gas_guard(min: Gas) -> bool {
+    remaining_gas() >= min
+}
+
 // ############
 // # Math API #
 // ############
@@ -275,6 +457,117 @@ pub fn random_seed_array() -> [u8; 32] {
     }
 }
 
+/// Returns a pseudo-random number generator seeded from [`random_seed_array`].
+///
+/// The generator is deterministic: every call within the same block starts from the same seed
+/// and produces the same sequence, so create it once and reuse it to draw several values.
+///
+/// **Note:** the output is only as unpredictable as the block random seed, which is known to the
+/// validators producing the block and to anyone once the block is published. Do not use it
+/// where a validator or a front-running user could profit from predicting the outcome.
+///
+/// # Examples
+///
+/// ```
+/// use near_sdk::env;
+///
+/// let mut rng = env::rng();
+/// let dice = rng.gen_range(1..=6);
+/// assert!((1..=6).contains(&dice));
+/// ```
+pub fn rng() -> Rng {
+    Rng::from_seed(random_seed_array())
+}
+
+/// Small pseudo-random number generator, based on xoshiro256**. See [`rng`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: [u64; 4],
+}
+
+impl Rng {
+    /// Creates a generator from a 32 byte seed, e.g. the result of [`random_seed_array`].
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        // The seed is mixed into the state with SplitMix64, so seeds with repeated words, like
+        // the all-zero default seed of the mocked blockchain, still produce a well-mixed state.
+        let mut state = [0u64; 4];
+        let mut x = 0u64;
+        for (word, chunk) in state.iter_mut().zip(seed.chunks_exact(8)) {
+            x = x
+                .wrapping_add(u64::from_le_bytes(chunk.try_into().unwrap()))
+                .wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = x;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            *word = z ^ (z >> 31);
+        }
+        Self { state }
+    }
+
+    /// Returns the next pseudo-random `u64`.
+    pub fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    /// Returns a pseudo-random value uniformly distributed over `range`, e.g. `0..10` or `1..=6`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty.
+    pub fn gen_range<R: std::ops::RangeBounds<u64>>(&mut self, range: R) -> u64 {
+        use std::ops::Bound;
+
+        let low = match range.start_bound() {
+            Bound::Included(&start) => Some(start),
+            Bound::Excluded(&start) => start.checked_add(1),
+            Bound::Unbounded => Some(0),
+        };
+        let high = match range.end_bound() {
+            Bound::Included(&end) => Some(end),
+            Bound::Excluded(&end) => end.checked_sub(1),
+            Bound::Unbounded => Some(u64::MAX),
+        };
+        let (low, high) = match (low, high) {
+            (Some(low), Some(high)) if low <= high => (low, high),
+            _ => panic_str("Cannot generate a value in an empty range"),
+        };
+        let span = match (high - low).checked_add(1) {
+            Some(span) => span,
+            None => return self.next_u64(),
+        };
+        // Rejects the values of the last incomplete multiple of `span` to avoid modulo bias.
+        let zone = u64::MAX - (u64::MAX - span + 1) % span;
+        loop {
+            let value = self.next_u64();
+            if value <= zone {
+                return low + value % span;
+            }
+        }
+    }
+
+    /// Fills `dest` with pseudo-random bytes.
+    pub fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let mut chunks = dest.chunks_exact_mut(8);
+        for chunk in &mut chunks {
+            chunk.copy_from_slice(&self.next_u64().to_le_bytes());
+        }
+        let remainder = chunks.into_remainder();
+        if !remainder.is_empty() {
+            let bytes = self.next_u64().to_le_bytes();
+            remainder.copy_from_slice(&bytes[..remainder.len()]);
+        }
+    }
+}
+
 /// Hashes the random sequence of bytes using sha256.
 ///
 /// # Examples
@@ -360,6 +653,61 @@ pub fn ripemd160_array(value: &[u8]) -> [u8; 20] {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HashAlgorithm {
+    Sha256,
+    Keccak256,
+}
+
+/// Hasher which can be fed data in chunks before producing a 32 byte hash with either
+/// [`sha256_array`] or [`keccak256_array`].
+///
+/// The runtime does not expose incremental hashing host functions, so data passed to
+/// [`update`](Hasher::update) is accumulated and hashed with a single host function call
+/// when calling [`finalize`](Hasher::finalize).
+///
+/// # Examples
+///
+/// ```
+/// use near_sdk::env;
+///
+/// let mut hasher = env::Hasher::sha256();
+/// hasher.update(b"The phrase ");
+/// hasher.update(b"that will be hashed");
+///
+/// assert_eq!(hasher.finalize(), env::sha256_array(b"The phrase that will be hashed"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Hasher {
+    algorithm: HashAlgorithm,
+    buffer: Vec<u8>,
+}
+
+impl Hasher {
+    /// Creates a hasher which produces a SHA-256 hash on [`finalize`](Self::finalize).
+    pub fn sha256() -> Self {
+        Self { algorithm: HashAlgorithm::Sha256, buffer: Vec::new() }
+    }
+
+    /// Creates a hasher which produces a Keccak-256 hash on [`finalize`](Self::finalize).
+    pub fn keccak256() -> Self {
+        Self { algorithm: HashAlgorithm::Keccak256, buffer: Vec::new() }
+    }
+
+    /// Feeds `data` into the hasher.
+    pub fn update(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+    }
+
+    /// Consumes the hasher and returns the hash of all data passed to [`update`](Self::update).
+    pub fn finalize(self) -> [u8; 32] {
+        match self.algorithm {
+            HashAlgorithm::Sha256 => sha256_array(&self.buffer),
+            HashAlgorithm::Keccak256 => keccak256_array(&self.buffer),
+        }
+    }
+}
+
 /// Recovers an ECDSA signer address from a 32-byte message `hash` and a corresponding `signature`
 /// along with `v` recovery byte.
 ///
@@ -392,6 +740,24 @@ pub fn ecrecover(
     }
 }
 
+/// Recovers the secp256k1 [`PublicKey`] that signed the 32-byte message `hash` with the given
+/// `signature` and `v` recovery byte, like Ethereum's `ecrecover`.
+///
+/// This is [`ecrecover`] with the recovered 64 bytes wrapped in a [`PublicKey`] of the
+/// [`CurveType::SECP256K1`](crate::CurveType::SECP256K1) curve. Returns [`None`] if the
+/// recovery failed, e.g. because the signature is invalid or fails the malleability check.
+#[cfg(feature = "unstable")]
+pub fn ecrecover_public_key(
+    hash: &[u8; 32],
+    signature: &[u8; 64],
+    v: u8,
+    malleability_flag: bool,
+) -> Option<PublicKey> {
+    ecrecover(hash, signature, v, malleability_flag).map(|key| {
+        PublicKey::from_parts(crate::CurveType::SECP256K1, key.to_vec()).unwrap_or_else(|_| abort())
+    })
+}
+
 /// Verifies signature of message using provided ED25519 Public Key
 pub fn ed25519_verify(signature: &[u8; 64], message: &[u8], public_key: &[u8; 32]) -> bool {
     unsafe {
@@ -494,6 +860,30 @@ pub fn promise_batch_action_deploy_contract(promise_index: u64, code: &[u8]) {
     }
 }
 
+/// Attaches a `DeployContract` action deploying the code stored under `key` to the batch of
+/// actions of the promise. The code is passed to the host through a register, so it is never
+/// copied into the memory of the contract. Returns `false` without attaching the action if
+/// nothing is stored under `key`.
+pub fn promise_batch_action_deploy_contract_from_storage(
+    promise_index: PromiseIndex,
+    key: &[u8],
+) -> bool {
+    match unsafe { sys::storage_read(key.len() as _, key.as_ptr() as _, ATOMIC_OP_REGISTER) } {
+        0 => false,
+        1 => {
+            unsafe {
+                sys::promise_batch_action_deploy_contract(
+                    promise_index,
+                    u64::MAX,
+                    ATOMIC_OP_REGISTER,
+                )
+            };
+            true
+        }
+        _ => abort(),
+    }
+}
+
 pub fn promise_batch_action_function_call(
     promise_index: PromiseIndex,
     function_name: &str,
@@ -659,7 +1049,44 @@ pub fn promise_result(result_idx: u64) -> PromiseResult {
             let data = expect_register(read_register(ATOMIC_OP_REGISTER));
             PromiseResult::Successful(data)
         }
-        Err(PromiseError::Failed) => PromiseResult::Failed,
+        Err(_) => PromiseResult::Failed,
+    }
+}
+
+/// If the current function is invoked by a callback we can access the execution results of the
+/// promises that caused the callback. This function deserializes the successful result of the
+/// promise under `result_idx` from JSON.
+///
+/// # Examples
+///
+/// ```
+/// use near_sdk::{env, PromiseError};
+///
+/// # near_sdk::testing_env!(
+/// #     near_sdk::test_utils::VMContextBuilder::new().build(),
+/// #     near_sdk::VMConfig::test(),
+/// #     near_sdk::RuntimeFeesConfig::test(),
+/// #     Default::default(),
+/// #     vec![
+/// #         near_sdk::PromiseResult::Successful(b"5".to_vec()),
+/// #         near_sdk::PromiseResult::Failed,
+/// #     ],
+/// # );
+/// let values: Vec<Result<u64, PromiseError>> =
+///     (0..env::promise_results_count()).map(env::promise_result_as).collect();
+/// assert_eq!(values, vec![Ok(5), Err(PromiseError::Failed)]);
+/// ```
+pub fn promise_result_as<T: serde::de::DeserializeOwned>(
+    result_idx: u64,
+) -> Result<T, PromiseError> {
+    match unsafe { sys::promise_result(result_idx, ATOMIC_OP_REGISTER) } {
+        0 => Err(PromiseError::NotReady),
+        1 => {
+            let data = expect_register(read_register(ATOMIC_OP_REGISTER));
+            serde_json::from_slice(&data).map_err(|_| PromiseError::DeserializationError)
+        }
+        2 => Err(PromiseError::Failed),
+        _ => abort(),
     }
 }
 
@@ -676,25 +1103,43 @@ pub fn promise_return(promise_idx: PromiseIndex) {
     unsafe { sys::promise_return(promise_idx) }
 }
 
+/// Schedules `promise` and considers its execution result as the execution result of this
+/// function, like returning a [`Promise`](crate::Promise) from a `#[near_bindgen]` method does.
+///
+/// This is a safer alternative to [`promise_return`] for methods without a `#[near_bindgen]`
+/// return value, as the promise index can't be mixed up with another one.
+///
+/// # Examples
+/// ```
+/// use near_sdk::{env, Promise};
+///
+/// let promise = Promise::new("bob.near".parse().unwrap()).transfer(1);
+/// env::promise_return_handle(promise);
+/// ```
+pub fn promise_return_handle(promise: crate::Promise) {
+    // The promise is scheduled and returned when it is dropped.
+    drop(promise.as_return());
+}
+
 // ###############
 // # Validator API #
 // ###############
 
 /// For a given account return its current stake. If the account is not a validator, returns 0.
-pub fn validator_stake(account_id: &AccountId) -> Balance {
+pub fn validator_stake(account_id: &AccountId) -> NearToken {
     let account_id: &str = account_id.as_ref();
     let data = [0u8; size_of::<Balance>()];
     unsafe {
         sys::validator_stake(account_id.len() as _, account_id.as_ptr() as _, data.as_ptr() as u64)
     };
-    Balance::from_le_bytes(data)
+    NearToken::from_yoctonear(Balance::from_le_bytes(data))
 }
 
 /// Returns the total stake of validators in the current epoch.
-pub fn validator_total_stake() -> Balance {
+pub fn validator_total_stake() -> NearToken {
     let data = [0u8; size_of::<Balance>()];
     unsafe { sys::validator_total_stake(data.as_ptr() as u64) };
-    Balance::from_le_bytes(data)
+    NearToken::from_yoctonear(Balance::from_le_bytes(data))
 }
 
 // #####################
@@ -716,6 +1161,42 @@ pub fn panic_str(message: &str) -> ! {
     unsafe { sys::panic_utf8(message.len() as _, message.as_ptr() as _) }
 }
 
+/// Terminates the execution of the program with a message already encoded as UTF-8, e.g. built
+/// in a byte buffer, without converting it to a `&str` first.
+///
+/// The encoding is only checked in debug builds. The host rejects invalid UTF-8, so the
+/// execution fails either way but without the message.
+pub fn panic_bytes(message: &[u8]) -> ! {
+    debug_assert!(std::str::from_utf8(message).is_ok(), "The panic message is not valid UTF-8");
+    unsafe { sys::panic_utf8(message.len() as _, message.as_ptr() as _) }
+}
+
+/// Terminates the execution of the program with `error` serialized as JSON as the panic message,
+/// so that clients can parse failures consistently.
+///
+/// # Examples
+///
+/// ```no_run
+/// use near_sdk::env;
+/// use near_sdk::serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// #[serde(crate = "near_sdk::serde")]
+/// struct Error {
+///     code: u32,
+///     message: String,
+/// }
+///
+/// // Panics with `{"code":404,"message":"Not found"}`.
+/// env::abort_with(&Error { code: 404, message: "Not found".to_string() });
+/// ```
+pub fn abort_with<E: serde::Serialize + ?Sized>(error: &E) -> ! {
+    match serde_json::to_string(error) {
+        Ok(message) => panic_str(&message),
+        Err(_) => panic_str("Failed to serialize the error"),
+    }
+}
+
 /// Aborts the current contract execution without a custom message.
 /// To include a message, use [`panic_str`].
 pub fn abort() -> ! {
@@ -740,6 +1221,100 @@ pub fn log_str(message: &str) {
     unsafe { sys::log_utf8(message.len() as _, message.as_ptr() as _) }
 }
 
+/// Marker appended by [`log_str_truncated`] to a message that was truncated.
+const LOG_TRUNCATED_MARKER: &str = "...";
+
+/// Logs at most `max_bytes` bytes of the message, e.g. to avoid paying for or exceeding the log
+/// limits with an unexpectedly large message. A truncated message ends with `...`, which counts
+/// towards `max_bytes`, and is cut on a character boundary so that it stays valid UTF-8.
+///
+/// If `max_bytes` is too small for the marker, the message is truncated without it.
+///
+/// # Examples
+///
+/// ```
+/// use near_sdk::env;
+///
+/// env::log_str_truncated("a short message", 100);
+/// // Logs "a lo...".
+/// env::log_str_truncated("a long message", 7);
+/// ```
+pub fn log_str_truncated(message: &str, max_bytes: usize) {
+    if message.len() <= max_bytes {
+        return log_str(message);
+    }
+    let marker = if max_bytes >= LOG_TRUNCATED_MARKER.len() { LOG_TRUNCATED_MARKER } else { "" };
+    let mut end = max_bytes - marker.len();
+    while !message.is_char_boundary(end) {
+        end -= 1;
+    }
+    log_str(&[&message[..end], marker].concat());
+}
+
+/// Logs a message already encoded as UTF-8, e.g. built in a byte buffer, without converting it
+/// to a `&str` first. This message is stored on chain.
+///
+/// The encoding is only checked in debug builds. The host rejects invalid UTF-8 and fails the
+/// execution.
+pub fn log_bytes(message: &[u8]) {
+    debug_assert!(std::str::from_utf8(message).is_ok(), "The log message is not valid UTF-8");
+    #[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+    eprintln!("{}", String::from_utf8_lossy(message));
+
+    unsafe { sys::log_utf8(message.len() as _, message.as_ptr() as _) }
+}
+
+/// Logs `value` serialized as JSON. Panics if `value` fails to serialize, instead of logging an
+/// invalid line.
+///
+/// # Examples
+///
+/// ```
+/// use near_sdk::env;
+/// use near_sdk::serde_json::json;
+///
+/// env::log_json(&json!({ "action": "deposit", "amount": "100" }));
+/// ```
+pub fn log_json<T: serde::Serialize + ?Sized>(value: &T) {
+    match serde_json::to_string(value) {
+        Ok(message) => log_str(&message),
+        Err(_) => panic_str("Failed to serialize the log message to JSON"),
+    }
+}
+
+/// Logs a [NEP-297](https://nomicon.io/Standards/EventsFormat) event, i.e.
+/// `EVENT_JSON:{"standard":..,"version":..,"event":..,"data":..}`, without defining an event
+/// enum with `#[near_bindgen(event_json(...))]`. Panics if `data` fails to serialize.
+///
+/// # Examples
+///
+/// ```
+/// use near_sdk::env;
+/// use near_sdk::serde_json::json;
+///
+/// // Logs `EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_mint","data":[..]}`.
+/// env::log_event("nep171", "1.0.0", "nft_mint", &[json!({ "owner_id": "alice.near", "token_ids": ["1"] })]);
+/// ```
+pub fn log_event<T: serde::Serialize + ?Sized>(
+    standard: &str,
+    version: &str,
+    event: &str,
+    data: &T,
+) {
+    #[derive(serde::Serialize)]
+    struct Event<'a, T: ?Sized> {
+        standard: &'a str,
+        version: &'a str,
+        event: &'a str,
+        data: &'a T,
+    }
+
+    match serde_json::to_string(&Event { standard, version, event, data }) {
+        Ok(json) => log_str(&format!("EVENT_JSON:{}", json)),
+        Err(_) => panic_str("Failed to serialize the event to JSON"),
+    }
+}
+
 /// Log the UTF-8 encodable message.
 #[deprecated(since = "4.0.0", note = "Use env::log_str for logging messages.")]
 pub fn log(message: &[u8]) {
@@ -819,6 +1394,20 @@ pub fn storage_has_key(key: &[u8]) -> bool {
     }
 }
 
+/// Checks which of the given keys have a key-value in the storage. The result has one entry per
+/// key, in the same order, as if [`storage_has_key`] was called for each of them.
+///
+/// # Examples
+/// ```
+/// use near_sdk::env;
+///
+/// env::storage_write(b"a", b"1");
+/// assert_eq!(env::storage_keys_exist(&[b"a", b"b"]), [true, false]);
+/// ```
+pub fn storage_keys_exist(keys: &[&[u8]]) -> Vec<bool> {
+    keys.iter().map(|key| storage_has_key(key)).collect()
+}
+
 // ############################################
 // # Saving and loading of the contract state #
 // ############################################
@@ -850,6 +1439,80 @@ pub fn storage_byte_cost() -> Balance {
     STORAGE_PRICE_PER_BYTE
 }
 
+/// Returns the cost of storing `bytes` bytes, i.e. `bytes * storage_byte_cost()`.
+///
+/// # Examples
+///
+/// ```
+/// use near_sdk::{env, NearToken};
+///
+/// assert_eq!(env::storage_cost_for_bytes(100), NearToken::from_millinear(1));
+/// ```
+pub fn storage_cost_for_bytes(bytes: StorageUsage) -> NearToken {
+    NearToken::from_yoctonear(Balance::from(bytes) * storage_byte_cost())
+}
+
+/// Tracks the storage used by the contract since the tracker was created, e.g. to charge the
+/// caller for exactly what a method stored.
+///
+/// The tracker only snapshots [`storage_usage`] when created. The delta is computed against the
+/// current storage usage whenever it is queried, so data written by collections is only counted
+/// once they are flushed.
+///
+/// # Examples
+///
+/// ```
+/// use near_sdk::env;
+///
+/// let tracker = env::StorageUsageTracker::new();
+/// env::storage_write(b"key", b"value");
+/// let cost = tracker.cost();
+/// assert_eq!(cost, env::storage_cost_for_bytes(tracker.bytes_added()));
+/// // Then charge the caller, e.g. `require!(env::attached_deposit() >= cost.as_yoctonear())`.
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StorageUsageTracker {
+    initial_usage: StorageUsage,
+}
+
+impl StorageUsageTracker {
+    /// Creates a tracker starting from the current [`storage_usage`].
+    pub fn new() -> Self {
+        Self { initial_usage: storage_usage() }
+    }
+
+    /// The storage usage when the tracker was created.
+    pub fn initial_usage(&self) -> StorageUsage {
+        self.initial_usage
+    }
+
+    /// Number of bytes added since the tracker was created, or `0` if storage was released.
+    pub fn bytes_added(&self) -> StorageUsage {
+        storage_usage().saturating_sub(self.initial_usage)
+    }
+
+    /// Number of bytes released since the tracker was created, or `0` if storage was added.
+    pub fn bytes_released(&self) -> StorageUsage {
+        self.initial_usage.saturating_sub(storage_usage())
+    }
+
+    /// Cost of the bytes added since the tracker was created.
+    pub fn cost(&self) -> NearToken {
+        storage_cost_for_bytes(self.bytes_added())
+    }
+
+    /// Cost of the bytes released since the tracker was created, which can be refunded.
+    pub fn refund(&self) -> NearToken {
+        storage_cost_for_bytes(self.bytes_released())
+    }
+}
+
+impl Default for StorageUsageTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // ##################
 // # Helper methods #
 // ##################
@@ -884,6 +1547,44 @@ pub fn is_valid_account_id(account_id: &[u8]) -> bool {
     !last_char_is_separator
 }
 
+/// Number of bytes of the salt hash used in the name of a subaccount from [`derive_subaccount`].
+const DERIVED_SUBACCOUNT_HASH_LEN: usize = 16;
+
+/// Derives the ID of a subaccount of `parent` from a salt, e.g. for a factory contract creating
+/// accounts for its users. The name of the subaccount is the hex encoding of the first 16 bytes
+/// of the SHA-256 hash of `salt`, so the same salt always gives the same account ID.
+///
+/// Returns an error if the result isn't a valid account ID, which happens if `parent` is longer
+/// than 31 characters, as the subaccount ID would be longer than 64 characters.
+///
+/// # Examples
+///
+/// ```
+/// use near_sdk::{env, AccountId};
+///
+/// let parent: AccountId = "factory.near".parse().unwrap();
+/// let account_id = env::derive_subaccount(&parent, b"alice").unwrap();
+/// assert!(account_id.is_sub_account_of(&parent));
+/// assert_eq!(account_id, env::derive_subaccount(&parent, b"alice").unwrap());
+/// ```
+pub fn derive_subaccount(
+    parent: &AccountId,
+    salt: &[u8],
+) -> Result<AccountId, crate::ParseAccountIdError> {
+    const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    let hash = sha256_array(salt);
+    let mut account_id =
+        String::with_capacity(DERIVED_SUBACCOUNT_HASH_LEN * 2 + 1 + parent.as_str().len());
+    for byte in &hash[..DERIVED_SUBACCOUNT_HASH_LEN] {
+        account_id.push(HEX_DIGITS[(byte >> 4) as usize] as char);
+        account_id.push(HEX_DIGITS[(byte & 0xf) as usize] as char);
+    }
+    account_id.push('.');
+    account_id.push_str(parent.as_str());
+    AccountId::try_from(account_id)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1001,6 +1702,25 @@ mod tests {
         );
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn incremental_hasher() {
+        let chunks: [&[u8]; 4] = [b"some", b"", b" val", b"ue"];
+
+        let mut sha256 = Hasher::sha256();
+        let mut keccak256 = Hasher::keccak256();
+        for chunk in chunks {
+            sha256.update(chunk);
+            keccak256.update(chunk);
+        }
+        assert_eq!(sha256.finalize(), super::sha256_array(b"some value"));
+        assert_eq!(keccak256.finalize(), super::keccak256_array(b"some value"));
+
+        // No data fed into the hasher is the same as hashing an empty slice.
+        assert_eq!(Hasher::sha256().finalize(), super::sha256_array(&[]));
+        assert_eq!(Hasher::keccak256().finalize(), super::keccak256_array(&[]));
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     #[test]
     fn random_seed_smoke_test() {
@@ -1050,7 +1770,143 @@ mod tests {
             from_slice::<'_, Vec<_>>(include_bytes!("../../tests/ecrecover-tests.json")).unwrap()
         {
             assert_eq!(super::ecrecover(&m, &sig, v, mc), res);
+
+            let public_key = super::ecrecover_public_key(&m, &sig, v, mc);
+            assert_eq!(
+                public_key.as_ref().map(|key| &key.as_bytes()[1..]),
+                res.as_ref().map(|r| &r[..])
+            );
+            if let Some(public_key) = public_key {
+                assert_eq!(public_key.curve_type(), crate::CurveType::SECP256K1);
+            }
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn log_bytes_matches_log_str() {
+        use crate::test_utils::get_logs;
+
+        let mut message = b"transfer ".to_vec();
+        message.extend_from_slice("100 Ⓝ".as_bytes());
+        super::log_bytes(&message);
+        super::log_str("transfer 100 Ⓝ");
+        let logs = get_logs();
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[0], logs[1]);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn panic_bytes_matches_panic_str() {
+        let panic_message = |f: fn()| {
+            let payload = std::panic::catch_unwind(f).unwrap_err();
+            payload.downcast_ref::<String>().cloned().unwrap()
+        };
+        let from_bytes = panic_message(|| super::panic_bytes("Not enough Ⓝ".as_bytes()));
+        let from_str = panic_message(|| super::panic_str("Not enough Ⓝ"));
+        assert!(from_bytes.contains("Not enough Ⓝ"));
+        assert_eq!(from_bytes, from_str);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    #[should_panic(expected = "The log message is not valid UTF-8")]
+    fn log_bytes_invalid_utf8() {
+        super::log_bytes(&[0xff, 0xfe]);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn current_account_id_is_read_once() {
+        use crate::test_utils::{accounts, gas_profile, VMContextBuilder};
+        let read_register = || gas_profile().get("read_register_base").copied();
+
+        crate::testing_env!(VMContextBuilder::new().current_account_id(accounts(0)).build());
+        assert_eq!(super::current_account_id(), accounts(0));
+        let first_read = read_register();
+        assert!(first_read.is_some());
+        assert_eq!(super::current_account_id(), accounts(0));
+        assert_eq!(super::current_account_id(), accounts(0));
+        assert_eq!(read_register(), first_read);
+
+        // A new context resets the cached id.
+        crate::testing_env!(VMContextBuilder::new().current_account_id(accounts(1)).build());
+        assert_eq!(super::current_account_id(), accounts(1));
+        assert_eq!(read_register(), first_read);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn is_predecessor() {
+        use crate::test_utils::{accounts, VMContextBuilder};
+
+        crate::testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(1)).build());
+        assert!(super::is_predecessor(&accounts(1)));
+        assert!(!super::is_predecessor(&accounts(0)));
+        assert!(!super::is_predecessor(&"bob.near.extra".parse().unwrap()));
+        assert!(!super::is_predecessor(&"bab".parse().unwrap()));
+        assert!(!super::is_predecessor(&"a".repeat(64).parse().unwrap()));
+        super::assert_predecessor(&accounts(1));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    #[should_panic(expected = "Method can only be called by alice")]
+    fn assert_predecessor_mismatch() {
+        use crate::test_utils::{accounts, VMContextBuilder};
+
+        crate::testing_env!(VMContextBuilder::new().predecessor_account_id(accounts(1)).build());
+        super::assert_predecessor(&accounts(0));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn remaining_gas() {
+        use crate::test_utils::VMContextBuilder;
+
+        crate::testing_env!(VMContextBuilder::new().prepaid_gas(Gas::from_tgas(10)).build());
+        let remaining = super::remaining_gas();
+        let used = super::used_gas();
+        assert!(remaining < Gas::from_tgas(10));
+        // `used_gas` is read after `remaining_gas`, so it also includes the cost of the latter.
+        assert!(remaining + used >= Gas::from_tgas(10));
+
+        assert!(super::gas_guard(Gas(0)));
+        assert!(super::gas_guard(Gas::from_tgas(9)));
+        assert!(!super::gas_guard(Gas::from_tgas(10)));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn gas_guard_stops_batch() {
+        use crate::test_utils::VMContextBuilder;
+
+        crate::testing_env!(VMContextBuilder::new().prepaid_gas(Gas::from_tgas(1)).build());
+        let mut processed = 0u32;
+        // Enough to process one more item and to save the progress.
+        while super::gas_guard(Gas::from_ggas(200)) {
+            super::storage_write(&processed.to_le_bytes(), &[0; 100]);
+            processed += 1;
         }
+        assert!(processed > 0);
+        assert!(super::remaining_gas() < Gas::from_ggas(200));
+        // The execution didn't run out of gas, so it can still save its progress.
+        super::storage_write(b"processed", &processed.to_le_bytes());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn block_timestamp_units() {
+        crate::testing_env!(crate::test_utils::VMContextBuilder::new()
+            .block_timestamp(1_700_000_123_456_789_999)
+            .build());
+        assert_eq!(super::block_timestamp(), 1_700_000_123_456_789_999);
+        assert_eq!(super::block_timestamp_ms(), 1_700_000_123_456);
+        assert_eq!(super::block_timestamp_s(), 1_700_000_123);
+        let duration = super::block_timestamp_duration();
+        assert_eq!(duration.as_secs(), 1_700_000_123);
+        assert_eq!(duration.subsec_nanos(), 456_789_999);
     }
 
     #[cfg(not(target_arch = "wasm32"))]
@@ -1065,6 +1921,29 @@ mod tests {
         assert_eq!(super::signer_account_pk(), key);
     }
 
+    #[test]
+    fn promise_result_as() {
+        crate::testing_env!(
+            crate::test_utils::VMContextBuilder::new().build(),
+            crate::VMConfig::test(),
+            crate::RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![
+                PromiseResult::Successful(b"\"first\"".to_vec()),
+                PromiseResult::Failed,
+                PromiseResult::Successful(b"[1,2,3]".to_vec()),
+                PromiseResult::Successful(b"not json".to_vec()),
+            ],
+        );
+
+        assert_eq!(super::promise_results_count(), 4);
+        assert_eq!(super::promise_result_as::<String>(0), Ok("first".to_string()));
+        assert_eq!(super::promise_result_as::<String>(1), Err(PromiseError::Failed));
+        assert_eq!(super::promise_result_as::<Vec<u8>>(2), Ok(vec![1, 2, 3]));
+        assert_eq!(super::promise_result_as::<String>(2), Err(PromiseError::DeserializationError));
+        assert_eq!(super::promise_result_as::<u64>(3), Err(PromiseError::DeserializationError));
+    }
+
     #[test]
     fn ed25519_verify() {
         const SIGNATURE: [u8; 64] = [
@@ -1108,4 +1987,315 @@ mod tests {
         assert!(!super::ed25519_verify(&SIGNATURE, &MESSAGE, &FORGED_PUBLIC_KEY));
         assert!(!super::ed25519_verify(&FORGED_SIGNATURE, &MESSAGE, &PUBLIC_KEY));
     }
+
+    #[test]
+    fn account_balances() {
+        use crate::test_utils::VMContextBuilder;
+        use crate::testing_env;
+
+        testing_env!(VMContextBuilder::new()
+            .account_balance(NearToken::from_near(10).as_yoctonear())
+            .account_locked_balance(NearToken::from_near(3).as_yoctonear())
+            .storage_usage(100_000)
+            .build());
+        assert_eq!(super::account_balance(), NearToken::from_near(10));
+        assert_eq!(super::account_locked_balance(), NearToken::from_near(3));
+        // The storage cost of 1 NEAR is covered by the locked balance.
+        assert_eq!(super::account_available_balance(), NearToken::from_near(10));
+
+        testing_env!(VMContextBuilder::new()
+            .account_balance(NearToken::from_near(10).as_yoctonear())
+            .account_locked_balance(NearToken::from_millinear(400).as_yoctonear())
+            .storage_usage(100_000)
+            .build());
+        assert_eq!(super::account_available_balance(), NearToken::from_millinear(9400));
+
+        testing_env!(VMContextBuilder::new()
+            .account_balance(NearToken::from_millinear(500).as_yoctonear())
+            .account_locked_balance(0)
+            .storage_usage(100_000)
+            .build());
+        assert_eq!(super::account_available_balance(), NearToken::from_yoctonear(0));
+    }
+
+    #[test]
+    fn validator_stakes() {
+        let validators = [
+            ("alice.near".to_string(), 10u128.pow(24)),
+            ("bob.near".to_string(), 5 * 10u128.pow(24)),
+        ];
+        crate::testing_env!(
+            crate::test_utils::VMContextBuilder::new().build(),
+            crate::VMConfig::test(),
+            crate::RuntimeFeesConfig::test(),
+            validators.into_iter().collect(),
+        );
+
+        let alice: AccountId = "alice.near".parse().unwrap();
+        let carol: AccountId = "carol.near".parse().unwrap();
+        assert_eq!(super::validator_stake(&alice), NearToken::from_near(1));
+        assert_eq!(super::validator_stake(&carol), NearToken::from_yoctonear(0));
+        assert_eq!(super::validator_total_stake(), NearToken::from_near(6));
+    }
+
+    #[test]
+    fn rng_is_deterministic() {
+        use crate::test_utils::VMContextBuilder;
+        use crate::testing_env;
+
+        testing_env!(VMContextBuilder::new().random_seed([7; 32]).build());
+        let mut rng = super::rng();
+        let mut other = super::rng();
+        let values: Vec<u64> = (0..10).map(|_| rng.next_u64()).collect();
+        assert_eq!(values, (0..10).map(|_| other.next_u64()).collect::<Vec<_>>());
+        assert_eq!(super::Rng::from_seed([7; 32]).next_u64(), values[0]);
+        assert_ne!(super::Rng::from_seed([8; 32]).next_u64(), values[0]);
+
+        // The default mocked seed is all zeros, which still produces varying values.
+        let mut zero = super::Rng::from_seed([0; 32]);
+        let (first, second) = (zero.next_u64(), zero.next_u64());
+        assert_ne!(first, 0);
+        assert_ne!(first, second);
+
+        let mut bytes = [0u8; 13];
+        super::rng().fill_bytes(&mut bytes);
+        let mut expected = [0u8; 16];
+        let mut rng = super::rng();
+        expected[..8].copy_from_slice(&rng.next_u64().to_le_bytes());
+        expected[8..].copy_from_slice(&rng.next_u64().to_le_bytes());
+        assert_eq!(bytes, expected[..13]);
+    }
+
+    #[test]
+    fn rng_gen_range() {
+        let mut rng = super::Rng::from_seed([42; 32]);
+        let mut counts = [0u32; 6];
+        for _ in 0..6000 {
+            let value = rng.gen_range(1..=6);
+            assert!((1..=6).contains(&value));
+            counts[value as usize - 1] += 1;
+        }
+        for count in counts {
+            assert!((850..=1150).contains(&count), "{:?}", counts);
+        }
+
+        let mut counts = [0u32; 10];
+        for _ in 0..10000 {
+            counts[rng.gen_range(10..20) as usize - 10] += 1;
+        }
+        for count in counts {
+            assert!((850..=1150).contains(&count), "{:?}", counts);
+        }
+
+        assert_eq!(rng.gen_range(5..6), 5);
+        assert_eq!(rng.gen_range(u64::MAX..), u64::MAX);
+        rng.gen_range(..);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot generate a value in an empty range")]
+    fn rng_gen_range_empty() {
+        super::Rng::from_seed([0; 32]).gen_range(5..5);
+    }
+    #[test]
+    fn storage_usage_tracker() {
+        use crate::test_utils::VMContextBuilder;
+        use crate::testing_env;
+
+        testing_env!(VMContextBuilder::new().build());
+        let before = super::storage_usage();
+        let tracker = super::StorageUsageTracker::new();
+        assert_eq!(tracker.initial_usage(), before);
+        assert_eq!(tracker.bytes_added(), 0);
+        assert_eq!(tracker.cost(), NearToken::from_yoctonear(0));
+
+        super::storage_write(b"key", &[0; 100]);
+        let added = super::storage_usage() - before;
+        assert!(added > 100);
+        assert_eq!(tracker.bytes_added(), added);
+        assert_eq!(tracker.bytes_released(), 0);
+        assert_eq!(tracker.cost(), super::storage_cost_for_bytes(added));
+
+        let tracker = super::StorageUsageTracker::new();
+        super::storage_remove(b"key");
+        assert_eq!(tracker.bytes_added(), 0);
+        assert_eq!(tracker.bytes_released(), added);
+        assert_eq!(tracker.refund(), super::storage_cost_for_bytes(added));
+        assert_eq!(super::storage_usage(), before);
+    }
+
+    #[test]
+    fn abort_with_json_payload() {
+        #[derive(serde::Serialize)]
+        struct Error {
+            code: u32,
+            message: String,
+        }
+
+        let err = std::panic::catch_unwind(|| {
+            super::abort_with(&Error { code: 404, message: "Not \"found\"".to_string() })
+        })
+        .unwrap_err();
+        let payload = err.downcast_ref::<String>().unwrap();
+        // The mocked blockchain panics with the `Debug` representation of the guest panic message.
+        let message = payload.split("panic_msg: ").nth(1).unwrap().trim_end_matches(" })");
+        let message: String = serde_json::from_str(message).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&message).unwrap();
+        assert_eq!(json, serde_json::json!({ "code": 404, "message": "Not \"found\"" }));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"{\"code\":400}"#)]
+    fn json_error_panics_with_json() {
+        #[derive(serde::Serialize)]
+        struct Error {
+            code: u32,
+        }
+
+        crate::FunctionError::panic(&crate::JsonError(Error { code: 400 }))
+    }
+
+    #[test]
+    fn log_json_and_event() {
+        use crate::test_utils::{get_logs, VMContextBuilder};
+
+        crate::testing_env!(VMContextBuilder::new().build());
+        super::log_json(&serde_json::json!({ "a": 1, "b": ["c"] }));
+        super::log_json("text");
+        super::log_event(
+            "nep171",
+            "1.0.0",
+            "nft_mint",
+            &[serde_json::json!({ "owner_id": "bob" })],
+        );
+        assert_eq!(
+            get_logs(),
+            vec![
+                r#"{"a":1,"b":["c"]}"#,
+                r#""text""#,
+                r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_mint","data":[{"owner_id":"bob"}]}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn log_str_truncated() {
+        use crate::test_utils::{get_logs, VMContextBuilder};
+
+        crate::testing_env!(VMContextBuilder::new().build());
+        super::log_str_truncated("héllo", 10);
+        super::log_str_truncated("héllo", 6);
+        super::log_str_truncated("héllo", 5);
+        // "é" takes two bytes, so it is dropped rather than split.
+        super::log_str_truncated("héllo", 4);
+        super::log_str_truncated("日本語", 8);
+        super::log_str_truncated("héllo", 2);
+        super::log_str_truncated("héllo", 0);
+        assert_eq!(get_logs(), ["héllo", "héllo", "h...", "h...", "日...", "h", ""]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to serialize the log message to JSON")]
+    fn log_json_serialization_failure() {
+        use std::collections::HashMap;
+
+        crate::testing_env!(crate::test_utils::VMContextBuilder::new().build());
+        // JSON object keys must be strings.
+        let value: HashMap<Vec<u8>, u8> = [(vec![1], 1)].into_iter().collect();
+        super::log_json(&value);
+    }
+
+    #[test]
+    fn input_register_matches_input() {
+        use crate::test_utils::VMContextBuilder;
+        use crate::testing_env;
+
+        let data: Vec<u8> = (0..=255).cycle().take(4096).collect();
+        let mut context = VMContextBuilder::new().build();
+        context.input = data.clone();
+        testing_env!(context);
+
+        let input = super::input_register().unwrap();
+        assert_eq!(input.len(), data.len());
+        assert_eq!(input.to_vec(), super::input().unwrap());
+
+        // Host calls using other registers do not clobber the input.
+        super::storage_write(b"key", b"value");
+        assert_eq!(super::storage_read(b"key").unwrap(), b"value");
+        super::sha256(b"data");
+        let _ = super::current_account_id();
+        let mut buf = Vec::with_capacity(data.len());
+        let capacity = buf.capacity();
+        assert_eq!(input.read_into(&mut buf), &data[..]);
+        assert_eq!(buf.capacity(), capacity);
+    }
+
+    #[test]
+    fn input_register_without_input() {
+        crate::testing_env!(crate::test_utils::VMContextBuilder::new().build());
+
+        let input = super::input_register().unwrap();
+        assert!(input.is_empty());
+        assert_eq!(input.len(), 0);
+        let mut buf = vec![1, 2, 3];
+        assert!(input.read_into(&mut buf).is_empty());
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn storage_keys_exist() {
+        crate::testing_env!(crate::test_utils::VMContextBuilder::new().build());
+        super::storage_write(b"a", b"1");
+        super::storage_write(b"c", b"");
+        assert_eq!(super::storage_keys_exist(&[b"a", b"b", b"c", b"a"]), [true, false, true, true]);
+        assert!(super::storage_keys_exist(&[]).is_empty());
+
+        super::storage_remove(b"a");
+        assert_eq!(super::storage_keys_exist(&[b"a", b"c"]), [false, true]);
+    }
+
+    #[test]
+    fn block_height() {
+        use crate::BlockHeight;
+
+        crate::testing_env!(crate::test_utils::VMContextBuilder::new().block_height(42).build());
+        assert_eq!(super::block_height(), BlockHeight(42));
+        assert_eq!(super::block_height().blocks_since(BlockHeight(40)), 2);
+    }
+
+    #[test]
+    fn derive_subaccount() {
+        use crate::AccountId;
+
+        let parent: AccountId = "factory.near".parse().unwrap();
+        let account_id = super::derive_subaccount(&parent, b"alice").unwrap();
+        // First 16 bytes of the SHA-256 hash of "alice".
+        assert_eq!(account_id.as_str(), "2bd806c97f0e00af1a1fc3328fa763a9.factory.near");
+        assert_eq!(account_id.parent_account_id(), Some(parent.clone()));
+        assert_eq!(super::derive_subaccount(&parent, b"alice"), Ok(account_id.clone()));
+        assert_ne!(super::derive_subaccount(&parent, b"bob"), Ok(account_id));
+        assert!(super::derive_subaccount(&parent, b"").is_ok());
+
+        // 32 characters for the hash and the separator leave 31 characters for the parent.
+        let parent: AccountId = "a".repeat(31).parse().unwrap();
+        assert_eq!(super::derive_subaccount(&parent, b"alice").unwrap().as_str().len(), 64);
+        let parent: AccountId = "a".repeat(32).parse().unwrap();
+        assert!(super::derive_subaccount(&parent, b"alice").is_err());
+    }
+
+    #[test]
+    fn promise_return_handle() {
+        use crate::test_utils::{accounts, gas_profile, get_created_receipts, VMContextBuilder};
+        use crate::Promise;
+
+        crate::testing_env!(VMContextBuilder::new().build());
+        super::promise_return_handle(Promise::new(accounts(1)).transfer(1));
+        assert_eq!(get_created_receipts().len(), 1);
+        // The runtime charges `promise_return` when the promise is marked as the result.
+        assert!(gas_profile().contains_key("promise_return"));
+
+        crate::testing_env!(VMContextBuilder::new().build());
+        drop(Promise::new(accounts(1)).transfer(1));
+        assert!(!gas_profile().contains_key("promise_return"));
+    }
 }
@@ -11,7 +11,7 @@ use near_primitives::transaction::Action as PrimitivesAction;
 use near_vm_logic::types::PromiseResult as VmPromiseResult;
 use near_vm_logic::{External, MemoryLike, VMConfig, VMLogic};
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Mocked blockchain that can be used in the tests for the smart contracts.
 /// It implements `BlockchainInterface` by redirecting calls to `VMLogic`. It unwraps errors of
@@ -81,6 +81,9 @@ impl MockedBlockchain {
             )
         };
 
+        // Host function costs are counted per thread, so start over for the new environment.
+        near_vm_logic::with_ext_cost_counter(|counter| counter.clear());
+
         let logic = RefCell::new(logic);
         Self { logic, logic_fixture }
     }
@@ -106,6 +109,29 @@ impl MockedBlockchain {
         self.logic.borrow_mut().gas(gas_amount.into()).unwrap()
     }
 
+    /// Returns the gas burnt so far by the host functions called.
+    pub fn burnt_gas(&self) -> Gas {
+        let mut logic = self.logic.borrow_mut();
+        // SAFETY: the pointer is valid for as long as `logic` is borrowed.
+        Gas(unsafe { (*logic.gas_counter_pointer()).burnt_gas })
+    }
+
+    /// Returns the gas burnt so far by each kind of host function cost, keyed by the name of the
+    /// cost, like `storage_write_base`. Costs which burnt no gas are omitted.
+    pub fn gas_profile(&self) -> BTreeMap<String, Gas> {
+        let ext_costs = &self.logic_fixture.config.ext_costs;
+        let mut profile = BTreeMap::new();
+        near_vm_logic::with_ext_cost_counter(|counter| {
+            for (cost, count) in counter.iter() {
+                let gas = count.saturating_mul(cost.gas(ext_costs));
+                if gas > 0 {
+                    profile.insert(format!("{:?}", cost), Gas(gas));
+                }
+            }
+        });
+        profile
+    }
+
     /// Returns logs created so far by the runtime.
     pub fn logs(&self) -> Vec<String> {
         self.logic.borrow().logs().to_vec()
@@ -5,7 +5,7 @@ mod receipt;
 
 pub(crate) use self::external::SdkExternal;
 pub use self::mocked_blockchain::MockedBlockchain;
-pub use self::receipt::{Receipt, VmAction};
+pub use self::receipt::{MockAction, Receipt, VmAction};
 use crate::AccountId;
 use core::cell::RefCell;
 use near_primitives_core::account::id::ParseAccountError;
@@ -45,3 +45,75 @@ pub enum VmAction {
         beneficiary_id: AccountId,
     },
 }
+
+impl VmAction {
+    /// Returns the name of the method called by a [`VmAction::FunctionCall`].
+    pub fn function_name(&self) -> Option<&str> {
+        match self {
+            VmAction::FunctionCall { function_name, .. } => Some(function_name),
+            _ => None,
+        }
+    }
+
+    /// Returns the deposit attached to a [`VmAction::FunctionCall`] or a [`VmAction::Transfer`].
+    pub fn deposit(&self) -> Option<Balance> {
+        match self {
+            VmAction::FunctionCall { deposit, .. } | VmAction::Transfer { deposit } => {
+                Some(*deposit)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the gas attached to a [`VmAction::FunctionCall`].
+    pub fn gas(&self) -> Option<Gas> {
+        match self {
+            VmAction::FunctionCall { gas, .. } => Some(*gas),
+            _ => None,
+        }
+    }
+}
+
+/// A single action scheduled by the contract, along with the account of the receipt it belongs
+/// to. See [`get_created_actions`](crate::test_utils::get_created_actions).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MockAction {
+    /// Index of the receipt the action belongs to, as returned by
+    /// [`get_created_receipts`](crate::test_utils::get_created_receipts).
+    pub receipt_index: usize,
+    pub receiver_id: AccountId,
+    pub action: VmAction,
+}
+
+impl MockAction {
+    /// Returns the name of the method called by the action, if it is a function call.
+    pub fn function_name(&self) -> Option<&str> {
+        self.action.function_name()
+    }
+
+    /// Returns the deposit attached to the action, if it is a function call or a transfer.
+    pub fn deposit(&self) -> Option<Balance> {
+        self.action.deposit()
+    }
+
+    /// Returns the gas attached to the action, if it is a function call.
+    pub fn gas(&self) -> Option<Gas> {
+        self.action.gas()
+    }
+}
+
+impl Receipt {
+    /// Returns the actions of the receipt as [`MockAction`]s, given the index of the receipt.
+    pub(crate) fn into_mock_actions(
+        self,
+        receipt_index: usize,
+    ) -> impl Iterator<Item = MockAction> {
+        let receiver_id = self.receiver_id;
+        self.actions.into_iter().map(move |action| MockAction {
+            receipt_index,
+            receiver_id: receiver_id.clone(),
+            action,
+        })
+    }
+}
@@ -1,7 +1,8 @@
 //! Helper classes to serialize and deserialize large integer types into base-10 string
 //! representations.
 //! NOTE: JSON standard can only work with integer up to 53 bits. So we need helper classes for
-//! 64-bit and 128-bit integers.
+//! 64-bit and 128-bit integers. [`I32`] is provided to serialize signed values consistently with
+//! the wider types.
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -80,6 +81,7 @@ impl_str_type!(U128, u128);
 impl_str_type!(U64, u64);
 impl_str_type!(I128, i128);
 impl_str_type!(I64, i64);
+impl_str_type!(I32, i32);
 
 #[cfg(test)]
 mod tests {
@@ -147,4 +149,32 @@ mod tests {
         test_serde!(I64, i64, i64::min_value());
         assert!(I64::from(i64::min_value()) < I64::from(i64::max_value()));
     }
+
+    #[test]
+    fn test_i32() {
+        test_serde!(I32, i32, 0);
+        test_serde!(I32, i32, 1);
+        test_serde!(I32, i32, -1);
+        test_serde!(I32, i32, -123);
+        test_serde!(I32, i32, 2i32.pow(30));
+        test_serde!(I32, i32, -(2i32.pow(30)));
+        test_serde!(I32, i32, i32::max_value());
+        test_serde!(I32, i32, i32::min_value());
+        assert!(I32::from(i32::min_value()) < I32::from(i32::max_value()));
+    }
+
+    #[test]
+    fn test_signed_serialized_as_strings() {
+        assert_eq!(serde_json::to_string(&I32(-5)).unwrap(), "\"-5\"");
+        assert_eq!(
+            serde_json::to_string(&I64(i64::min_value())).unwrap(),
+            "\"-9223372036854775808\""
+        );
+        assert_eq!(serde_json::from_str::<I32>("\"-2147483648\"").unwrap(), I32(i32::min_value()));
+        // Numbers are rejected like for the other string-wrapped integers.
+        assert!(serde_json::from_str::<I32>("-5").is_err());
+        // Out of range values are rejected.
+        assert!(serde_json::from_str::<I32>("\"2147483648\"").is_err());
+        assert!(serde_json::from_str::<I64>("\"-9223372036854775809\"").is_err());
+    }
 }
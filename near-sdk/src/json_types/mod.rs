@@ -7,8 +7,8 @@ mod vector;
 use crate::types::{AccountId, PublicKey};
 
 pub use hash::Base58CryptoHash;
-pub use integers::{I128, I64, U128, U64};
-pub use vector::Base64VecU8;
+pub use integers::{I128, I32, I64, U128, U64};
+pub use vector::{Base64DecodeError, Base64VecU8};
 
 #[deprecated(
     since = "4.0.0",
@@ -1,10 +1,51 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
 
 /// Helper class to serialize/deserialize `Vec<u8>` to base64 string.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
 pub struct Base64VecU8(#[serde(with = "base64_bytes")] pub Vec<u8>);
 
+impl Base64VecU8 {
+    /// Decodes the base64 string `encoded` and appends the bytes to `buf`, which can be reused
+    /// across calls to avoid allocating a new vector for each payload. On error, `buf` is left
+    /// unchanged.
+    ///
+    /// Deserializing a [`Base64VecU8`] decodes the string in the same way, without copying it
+    /// into an intermediate `String` first.
+    ///
+    /// # Examples
+    /// ```
+    /// use near_sdk::json_types::Base64VecU8;
+    ///
+    /// let mut buf = b"wasm:".to_vec();
+    /// Base64VecU8::decode_into("AGFzbQ==", &mut buf).unwrap();
+    /// assert_eq!(buf, b"wasm:\0asm");
+    ///
+    /// assert!(Base64VecU8::decode_into("not base64", &mut buf).is_err());
+    /// assert_eq!(buf, b"wasm:\0asm");
+    /// ```
+    pub fn decode_into(encoded: &str, buf: &mut Vec<u8>) -> Result<(), Base64DecodeError> {
+        let len = buf.len();
+        base64::decode_config_buf(encoded, base64::STANDARD, buf).map_err(|err| {
+            buf.truncate(len);
+            Base64DecodeError(err)
+        })
+    }
+}
+
+/// Error returned when decoding an invalid base64 string with [`Base64VecU8::decode_into`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Base64DecodeError(base64::DecodeError);
+
+impl fmt::Display for Base64DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl std::error::Error for Base64DecodeError {}
+
 impl From<Vec<u8>> for Base64VecU8 {
     fn from(v: Vec<u8>) -> Self {
         Self(v)
@@ -60,8 +101,23 @@ mod base64_bytes {
     where
         D: Deserializer<'de>,
     {
-        let s: String = Deserialize::deserialize(deserializer)?;
-        base64::decode(s.as_str()).map_err(de::Error::custom)
+        struct Base64Visitor;
+
+        impl<'de> de::Visitor<'de> for Base64Visitor {
+            type Value = Vec<u8>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a base64 string")
+            }
+
+            fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+                let mut bytes = Vec::new();
+                Base64VecU8::decode_into(s, &mut bytes).map_err(de::Error::custom)?;
+                Ok(bytes)
+            }
+        }
+
+        deserializer.deserialize_str(Base64Visitor)
     }
 }
 
@@ -101,6 +157,36 @@ mod tests {
         test_serde!(vec![123; 16000]);
     }
 
+    #[test]
+    fn decode_into() {
+        let bytes: Vec<u8> = (0..=255).cycle().take(3000).collect();
+        let encoded = base64::encode(&bytes);
+        let mut buf = Vec::new();
+        Base64VecU8::decode_into(&encoded, &mut buf).unwrap();
+        assert_eq!(buf, bytes);
+
+        // Appends to the existing content.
+        Base64VecU8::decode_into("AQID", &mut buf).unwrap();
+        assert_eq!(buf.len(), bytes.len() + 3);
+        assert_eq!(&buf[bytes.len()..], [1, 2, 3]);
+
+        let deserialized: Base64VecU8 =
+            serde_json::from_str(&serde_json::to_string(&encoded).unwrap()).unwrap();
+        assert_eq!(deserialized.0, bytes);
+    }
+
+    #[test]
+    fn decode_invalid() {
+        let mut buf = vec![7];
+        for invalid in ["A", "AQ!D", "AQID====", "AQ ID"] {
+            let err = Base64VecU8::decode_into(invalid, &mut buf).unwrap_err();
+            assert!(!err.to_string().is_empty());
+            assert_eq!(buf, [7]);
+            assert!(serde_json::from_str::<Base64VecU8>(&format!("\"{}\"", invalid)).is_err());
+        }
+        assert!(serde_json::from_str::<Base64VecU8>("[1, 2]").is_err());
+    }
+
     #[test]
     fn test_manual() {
         let a = vec![100, 121, 31, 20, 0, 23, 32];
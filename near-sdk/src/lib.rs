@@ -8,7 +8,8 @@ extern crate quickcheck;
 #[cfg(all(feature = "unstable", feature = "abi"))]
 pub use near_sdk_macros::NearSchema;
 pub use near_sdk_macros::{
-    ext_contract, near_bindgen, BorshStorageKey, EventMetadata, FunctionError, PanicOnDefault,
+    ext_contract, near_bindgen, BorshStorageKey, EventMetadata, FunctionError, IntoStorageKey,
+    PanicOnDefault,
 };
 
 pub mod store;
@@ -22,7 +23,7 @@ pub use environment::env;
 pub use near_sys as sys;
 
 mod promise;
-pub use promise::{Allowance, Promise, PromiseOrValue};
+pub use promise::{AccessKeyPermission, Action, Allowance, Promise, PromiseBatch, PromiseOrValue};
 
 // Private types just used within macro generation, not stable to be used.
 #[doc(hidden)]
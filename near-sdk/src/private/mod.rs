@@ -12,8 +12,12 @@ pub use schemars;
 mod metadata;
 pub use metadata::{Metadata, MethodMetadata};
 
+mod sorted_json;
+
 use crate::IntoStorageKey;
-use borsh::BorshSerialize;
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 
 /// Converts a Borsh serializable object into a `Vec<u8>` that is used for a storage key.
 ///
@@ -44,3 +48,203 @@ where
         self.try_to_vec().unwrap()
     }
 }
+
+/// Deserializes the input of a `#[serializer(auto)]` method from either JSON or Borsh.
+///
+/// Arguments are serialized as a JSON object, so JSON is tried first if the input starts with
+/// `{`, and Borsh otherwise. As Borsh data can also start with that byte, the other format is
+/// tried if the first one fails.
+pub fn deserialize_input_auto<T>(input: &[u8]) -> T
+where
+    T: DeserializeOwned + BorshDeserialize,
+{
+    let from_json = || serde_json::from_slice(input).ok();
+    let from_borsh = || T::try_from_slice(input).ok();
+    let value = if input.first() == Some(&b'{') {
+        from_json().or_else(from_borsh)
+    } else {
+        from_borsh().or_else(from_json)
+    };
+    value
+        .unwrap_or_else(|| crate::env::panic_str("Failed to deserialize input from Borsh or JSON."))
+}
+
+/// Whether the input of a `#[deny_empty_args]` method carries no arguments.
+///
+/// Clients calling a method without arguments send either an empty body or an empty JSON object,
+/// so both are accepted.
+pub fn is_empty_input(input: &[u8]) -> bool {
+    input.iter().all(u8::is_ascii_whitespace)
+        || matches!(
+            serde_json::from_slice::<serde_json::Map<String, serde_json::Value>>(input),
+            Ok(args) if args.is_empty()
+        )
+}
+
+/// Reads the old contract state passed to a `#[migrate(from = ...)]` method.
+///
+/// Panics if there is no state, or if it can't be deserialized as the old state, e.g. because the
+/// contract was already migrated. A migrated state is only detected this way: if the new state
+/// also deserializes as the old one, e.g. because only the meaning of a field changed, the
+/// migration runs again.
+pub fn read_state_to_migrate<T: BorshDeserialize>() -> T {
+    let state = crate::env::storage_read(crate::env::STATE_KEY)
+        .unwrap_or_else(|| crate::env::panic_str("The contract has no state to migrate"));
+    T::try_from_slice(&state).unwrap_or_else(|_| {
+        crate::env::panic_str("The contract state is not the state to migrate from, it may have been migrated already")
+    })
+}
+
+/// Prefix of the marker keys written by [`init_once`], followed by the name of the method.
+pub const INIT_ONCE_KEY_PREFIX: &[u8] = b"__INIT_ONCE__";
+
+/// Guards an `#[init(ignore_state)]` method so that it can only be called once.
+///
+/// The marker key [`INIT_ONCE_KEY_PREFIX`] followed by the method name is written the first time
+/// the method is called, and the method panics if the marker already exists. The marker is never
+/// removed, so it also blocks a method of the same name in a later version of the contract unless
+/// the contract removes the key first. `#[init(ignore_state, allow_repeat)]` skips this guard.
+pub fn init_once(method: &str) {
+    let key = [INIT_ONCE_KEY_PREFIX, method.as_bytes()].concat();
+    if crate::env::storage_write(&key, &[]) {
+        crate::env::panic_str(&format!("Method {} can only be called once", method));
+    }
+}
+
+/// Serializes a value compared or hashed by `#[near_bindgen(eq = "borsh", hash = "borsh")]`.
+pub fn borsh_bytes<T: BorshSerialize + ?Sized>(value: &T) -> Vec<u8> {
+    value.try_to_vec().unwrap_or_else(|_| crate::env::abort())
+}
+
+/// Serializes the result of a `#[result_serializer(json, sorted_keys)]` method as JSON, with the
+/// keys of every object sorted lexicographically, so that the output doesn't depend on the order
+/// of the struct fields.
+pub fn to_json_vec_sorted_keys<T: Serialize + ?Sized>(value: &T) -> serde_json::Result<Vec<u8>> {
+    sorted_json::to_vec(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{deserialize_input_auto, init_once, is_empty_input, to_json_vec_sorted_keys};
+    use borsh::{BorshDeserialize, BorshSerialize};
+    use serde::{Deserialize, Serialize};
+    use std::collections::HashMap;
+
+    #[derive(Debug, PartialEq, Deserialize, BorshDeserialize, BorshSerialize)]
+    struct Input {
+        a: u8,
+        b: String,
+    }
+
+    #[test]
+    fn deserialize_json_or_borsh() {
+        let expected = Input { a: 123, b: "text".to_string() };
+        assert_eq!(deserialize_input_auto::<Input>(br#"{"a":123,"b":"text"}"#), expected);
+        // The Borsh encoding starts with `{` (123) too, so JSON is tried first and fails.
+        let borsh = expected.try_to_vec().unwrap();
+        assert_eq!(borsh[0], b'{');
+        assert_eq!(deserialize_input_auto::<Input>(&borsh), expected);
+
+        let expected = Input { a: 1, b: "text".to_string() };
+        assert_eq!(deserialize_input_auto::<Input>(&expected.try_to_vec().unwrap()), expected);
+        assert_eq!(deserialize_input_auto::<Input>(br#" {"b":"text","a":1}"#), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to deserialize input from Borsh or JSON.")]
+    fn deserialize_invalid_input() {
+        crate::testing_env!(crate::test_utils::VMContextBuilder::new().build());
+        deserialize_input_auto::<Input>(b"{not valid");
+    }
+
+    #[test]
+    #[should_panic(expected = "Method migrate can only be called once")]
+    fn init_once_per_method() {
+        crate::testing_env!(crate::test_utils::VMContextBuilder::new().build());
+        init_once("migrate");
+        init_once("other");
+        init_once("migrate");
+    }
+
+    #[test]
+    fn empty_input() {
+        assert!(is_empty_input(b""));
+        assert!(is_empty_input(b" \n"));
+        assert!(is_empty_input(b"{}"));
+        assert!(is_empty_input(b" { } "));
+        assert!(!is_empty_input(br#"{"a":1}"#));
+        assert!(!is_empty_input(b"null"));
+        assert!(!is_empty_input(b"[]"));
+        assert!(!is_empty_input(&[0]));
+    }
+
+    #[derive(Serialize)]
+    struct Unsorted {
+        zeta: u8,
+        alpha: Vec<Nested>,
+        mid: Option<Nested>,
+    }
+
+    #[derive(Serialize)]
+    struct Nested {
+        y: u8,
+        b: u8,
+    }
+
+    #[test]
+    fn json_sorted_keys() {
+        let value = Unsorted {
+            zeta: 1,
+            alpha: vec![Nested { y: 2, b: 3 }],
+            mid: Some(Nested { y: 4, b: 5 }),
+        };
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            r#"{"zeta":1,"alpha":[{"y":2,"b":3}],"mid":{"y":4,"b":5}}"#
+        );
+        assert_eq!(
+            to_json_vec_sorted_keys(&value).unwrap(),
+            br#"{"alpha":[{"b":3,"y":2}],"mid":{"b":5,"y":4},"zeta":1}"#
+        );
+        assert_eq!(to_json_vec_sorted_keys(&[3, 1, 2]).unwrap(), b"[3,1,2]");
+    }
+
+    #[derive(Serialize)]
+    enum Variants {
+        Unit,
+        Newtype(Nested),
+        Tuple(u8, Nested),
+        Struct { y: u8, b: u8 },
+    }
+
+    #[test]
+    fn json_sorted_keys_matches_serde_json() {
+        let value = (
+            Variants::Unit,
+            Variants::Newtype(Nested { y: 1, b: 2 }),
+            Variants::Tuple(3, Nested { y: 4, b: 5 }),
+            Variants::Struct { y: 6, b: 7 },
+            [(2u8, "\"two\""), (1, "one")].into_iter().collect::<HashMap<_, _>>(),
+            (None::<u8>, 1.5f64, 'c', ()),
+        );
+        assert_eq!(
+            to_json_vec_sorted_keys(&value).unwrap(),
+            br#"["Unit",{"Newtype":{"b":2,"y":1}},{"Tuple":[3,{"b":5,"y":4}]},{"Struct":{"b":7,"y":6}},{"1":"one","2":"\"two\""},[null,1.5,"c",null]]"#
+        );
+    }
+
+    #[derive(Serialize)]
+    struct Large {
+        z: u128,
+        a: i128,
+    }
+
+    #[test]
+    fn json_sorted_keys_large_integers() {
+        let value = Large { z: u128::MAX, a: i128::MIN };
+        assert_eq!(
+            to_json_vec_sorted_keys(&value).unwrap(),
+            format!(r#"{{"a":{},"z":{}}}"#, i128::MIN, u128::MAX).into_bytes()
+        );
+    }
+}
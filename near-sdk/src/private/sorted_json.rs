@@ -0,0 +1,360 @@
+//! JSON serializer writing the keys of every object in sorted order, used by
+//! `#[result_serializer(json, sorted_keys)]`.
+//!
+//! Values are not converted to `serde_json::Value`, which can't hold integers larger than
+//! `u64::MAX` without the `arbitrary_precision` feature. Scalars are written by `serde_json` as is,
+//! and only objects are buffered to sort their entries.
+
+use std::collections::BTreeMap;
+use std::iter;
+
+use serde::ser::{self, Error as _, Serialize};
+use serde_json::{Error, Result};
+
+/// Serializes `value` as JSON with the keys of every object sorted lexicographically.
+pub(super) fn to_vec<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    value.serialize(Serializer)?.write(&mut buf)?;
+    Ok(buf)
+}
+
+/// A serialized value which keeps the entries of its objects in key order.
+enum Sorted {
+    /// A scalar, already serialized as JSON.
+    Raw(Vec<u8>),
+    Array(Vec<Sorted>),
+    Object(BTreeMap<String, Sorted>),
+}
+
+impl Sorted {
+    fn raw<T: Serialize + ?Sized>(value: &T) -> Result<Self> {
+        serde_json::to_vec(value).map(Self::Raw)
+    }
+
+    fn variant(variant: &'static str, value: Sorted) -> Self {
+        Self::Object(iter::once((variant.to_owned(), value)).collect())
+    }
+
+    fn write(self, buf: &mut Vec<u8>) -> Result<()> {
+        match self {
+            Self::Raw(bytes) => buf.extend_from_slice(&bytes),
+            Self::Array(values) => {
+                buf.push(b'[');
+                for (i, value) in values.into_iter().enumerate() {
+                    if i > 0 {
+                        buf.push(b',');
+                    }
+                    value.write(buf)?;
+                }
+                buf.push(b']');
+            }
+            Self::Object(entries) => {
+                buf.push(b'{');
+                for (i, (key, value)) in entries.into_iter().enumerate() {
+                    if i > 0 {
+                        buf.push(b',');
+                    }
+                    serde_json::to_writer(&mut *buf, &key)?;
+                    buf.push(b':');
+                    value.write(buf)?;
+                }
+                buf.push(b'}');
+            }
+        }
+        Ok(())
+    }
+
+    /// Converts a serialized map key into the string `serde_json` would use as the object key.
+    fn into_key(self) -> Result<String> {
+        match self {
+            Self::Raw(bytes) if bytes.starts_with(b"\"") => serde_json::from_slice(&bytes),
+            Self::Raw(bytes) if bytes != b"null" => String::from_utf8(bytes).map_err(Error::custom),
+            _ => Err(Error::custom("key must be a string")),
+        }
+    }
+}
+
+struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = Sorted;
+    type Error = Error;
+
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = VariantSerializer<SeqSerializer>;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = MapSerializer;
+    type SerializeStructVariant = VariantSerializer<MapSerializer>;
+
+    fn serialize_bool(self, v: bool) -> Result<Sorted> {
+        Sorted::raw(&v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Sorted> {
+        Sorted::raw(&v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Sorted> {
+        Sorted::raw(&v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Sorted> {
+        Sorted::raw(&v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Sorted> {
+        Sorted::raw(&v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Sorted> {
+        Sorted::raw(&v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Sorted> {
+        Sorted::raw(&v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Sorted> {
+        Sorted::raw(&v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Sorted> {
+        Sorted::raw(&v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Sorted> {
+        Sorted::raw(&v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Sorted> {
+        Sorted::raw(&v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Sorted> {
+        Sorted::raw(&v)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Sorted> {
+        Sorted::raw(&v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<Sorted> {
+        Sorted::raw(&v)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Sorted> {
+        Sorted::raw(v)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Sorted> {
+        Sorted::raw(v)
+    }
+
+    fn serialize_none(self) -> Result<Sorted> {
+        Sorted::raw(&())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Sorted> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Sorted> {
+        Sorted::raw(&())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Sorted> {
+        Sorted::raw(&())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Sorted> {
+        Sorted::raw(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Sorted> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Sorted> {
+        Ok(Sorted::variant(variant, value.serialize(self)?))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqSerializer> {
+        Ok(SeqSerializer(Vec::with_capacity(len.unwrap_or(0))))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqSerializer> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<SeqSerializer> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<VariantSerializer<SeqSerializer>> {
+        Ok(VariantSerializer { variant, inner: self.serialize_seq(Some(len))? })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<MapSerializer> {
+        Ok(MapSerializer::default())
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<MapSerializer> {
+        Ok(MapSerializer::default())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<VariantSerializer<MapSerializer>> {
+        Ok(VariantSerializer { variant, inner: MapSerializer::default() })
+    }
+}
+
+struct SeqSerializer(Vec<Sorted>);
+
+impl ser::SerializeSeq for SeqSerializer {
+    type Ok = Sorted;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.0.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Sorted> {
+        Ok(Sorted::Array(self.0))
+    }
+}
+
+impl ser::SerializeTuple for SeqSerializer {
+    type Ok = Sorted;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Sorted> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqSerializer {
+    type Ok = Sorted;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Sorted> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+#[derive(Default)]
+struct MapSerializer {
+    entries: BTreeMap<String, Sorted>,
+    /// The key of the entry whose value is serialized next.
+    key: Option<String>,
+}
+
+impl ser::SerializeMap for MapSerializer {
+    type Ok = Sorted;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        self.key = Some(key.serialize(Serializer)?.into_key()?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let key =
+            self.key.take().ok_or_else(|| Error::custom("value serialized before its key"))?;
+        self.entries.insert(key, value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Sorted> {
+        Ok(Sorted::Object(self.entries))
+    }
+}
+
+impl ser::SerializeStruct for MapSerializer {
+    type Ok = Sorted;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.entries.insert(key.to_owned(), value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Sorted> {
+        ser::SerializeMap::end(self)
+    }
+}
+
+struct VariantSerializer<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+impl ser::SerializeTupleVariant for VariantSerializer<SeqSerializer> {
+    type Ok = Sorted;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        ser::SerializeSeq::serialize_element(&mut self.inner, value)
+    }
+
+    fn end(self) -> Result<Sorted> {
+        Ok(Sorted::variant(self.variant, ser::SerializeSeq::end(self.inner)?))
+    }
+}
+
+impl ser::SerializeStructVariant for VariantSerializer<MapSerializer> {
+    type Ok = Sorted;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    fn end(self) -> Result<Sorted> {
+        Ok(Sorted::variant(self.variant, ser::SerializeMap::end(self.inner)?))
+    }
+}
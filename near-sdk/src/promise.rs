@@ -6,11 +6,11 @@ use std::num::NonZeroU128;
 use std::rc::Rc;
 
 use crate::env::migrate_to_allowance;
-use crate::{AccountId, Balance, Gas, GasWeight, PromiseIndex, PublicKey};
+use crate::{AccountId, Balance, Gas, GasWeight, NearToken, PromiseIndex, PublicKey};
 
 /// Allow an access key to spend either an unlimited or limited amount of gas
 // This wrapper prevents incorrect construction
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Allowance {
     Unlimited,
     Limited(NonZeroU128),
@@ -27,11 +27,159 @@ impl Allowance {
     }
 }
 
+/// An action of a [`PromiseBatch`], applied to the receiver account of the batch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Create the receiver account, see [`Promise::create_account`].
+    CreateAccount,
+    /// Deploy the given Wasm code to the receiver account, see [`Promise::deploy_contract`].
+    DeployContract(Vec<u8>),
+    /// Call a method of the contract deployed on the receiver account, see
+    /// [`Promise::function_call`].
+    FunctionCall { function_name: String, arguments: Vec<u8>, deposit: NearToken, gas: Gas },
+    /// Transfer tokens to the receiver account, see [`Promise::transfer`].
+    Transfer(NearToken),
+    /// Stake tokens of the receiver account, see [`Promise::stake`].
+    Stake { amount: NearToken, public_key: PublicKey },
+    /// Add an access key to the receiver account, see [`Promise::add_full_access_key_with_nonce`]
+    /// and [`Promise::add_access_key_allowance_with_nonce`].
+    AddKey { public_key: PublicKey, nonce: u64, permission: AccessKeyPermission },
+    /// Delete an access key of the receiver account, see [`Promise::delete_key`].
+    DeleteKey(PublicKey),
+    /// Delete the receiver account, sending its remaining balance to the given beneficiary, see
+    /// [`Promise::delete_account`].
+    DeleteAccount(AccountId),
+}
+
+/// Permission of an access key added with [`Action::AddKey`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AccessKeyPermission {
+    /// The key can sign any transaction on behalf of the account.
+    FullAccess,
+    /// The key can only call the given comma separated `function_names` of `receiver_id`, or
+    /// any of its methods if `function_names` is empty, spending at most `allowance` on gas.
+    FunctionCall { allowance: Allowance, receiver_id: AccountId, function_names: String },
+}
+
+impl From<Action> for PromiseAction {
+    fn from(action: Action) -> Self {
+        match action {
+            Action::CreateAccount => PromiseAction::CreateAccount,
+            Action::DeployContract(code) => PromiseAction::DeployContract { code },
+            Action::FunctionCall { function_name, arguments, deposit, gas } => {
+                PromiseAction::FunctionCall {
+                    function_name,
+                    arguments,
+                    amount: deposit.as_yoctonear(),
+                    gas,
+                }
+            }
+            Action::Transfer(amount) => PromiseAction::Transfer { amount: amount.as_yoctonear() },
+            Action::Stake { amount, public_key } => {
+                PromiseAction::Stake { amount: amount.as_yoctonear(), public_key }
+            }
+            Action::AddKey { public_key, nonce, permission: AccessKeyPermission::FullAccess } => {
+                PromiseAction::AddFullAccessKey { public_key, nonce }
+            }
+            Action::AddKey {
+                public_key,
+                nonce,
+                permission:
+                    AccessKeyPermission::FunctionCall { allowance, receiver_id, function_names },
+            } => PromiseAction::AddAccessKey {
+                public_key,
+                allowance,
+                receiver_id,
+                function_names,
+                nonce,
+            },
+            Action::DeleteKey(public_key) => PromiseAction::DeleteKey { public_key },
+            Action::DeleteAccount(beneficiary_id) => {
+                PromiseAction::DeleteAccount { beneficiary_id }
+            }
+        }
+    }
+}
+
+/// A declarative list of [`Action`]s on a single receiver account, turned into a [`Promise`]
+/// with [`PromiseBatch::into_promise`].
+///
+/// Unlike a [`Promise`], a batch is only scheduled once converted, so functions building it can
+/// be unit tested by comparing the actions.
+///
+/// ```
+/// use near_sdk::{AccessKeyPermission, Action, NearToken, PromiseBatch, PublicKey};
+///
+/// fn create_sub_account(code: Vec<u8>, public_key: PublicKey) -> PromiseBatch {
+///     PromiseBatch::new("sub.alice.near".parse().unwrap())
+///         .action(Action::CreateAccount)
+///         .action(Action::Transfer(NearToken::from_near(5)))
+///         .action(Action::DeployContract(code))
+///         .action(Action::AddKey {
+///             public_key,
+///             nonce: 0,
+///             permission: AccessKeyPermission::FullAccess,
+///         })
+/// }
+///
+/// let public_key: PublicKey = "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp".parse().unwrap();
+/// let batch = create_sub_account(vec![], public_key);
+/// assert_eq!(batch.actions()[1], Action::Transfer(NearToken::from_near(5)));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PromiseBatch {
+    receiver_id: AccountId,
+    actions: Vec<Action>,
+}
+
+impl PromiseBatch {
+    /// Create an empty batch acting on the given account.
+    pub fn new(receiver_id: AccountId) -> Self {
+        Self { receiver_id, actions: vec![] }
+    }
+
+    /// Append an action to the batch.
+    pub fn action(mut self, action: Action) -> Self {
+        self.actions.push(action);
+        self
+    }
+
+    /// The account the actions of this batch act on.
+    pub fn receiver_id(&self) -> &AccountId {
+        &self.receiver_id
+    }
+
+    /// The actions of this batch, in the order they will be executed.
+    pub fn actions(&self) -> &[Action] {
+        &self.actions
+    }
+
+    /// Convert the batch into a [`Promise`] with the same actions.
+    pub fn into_promise(self) -> Promise {
+        Promise::new(self.receiver_id).batch(self.actions)
+    }
+}
+
+impl Extend<Action> for PromiseBatch {
+    fn extend<I: IntoIterator<Item = Action>>(&mut self, iter: I) {
+        self.actions.extend(iter);
+    }
+}
+
+impl From<PromiseBatch> for Promise {
+    fn from(batch: PromiseBatch) -> Self {
+        batch.into_promise()
+    }
+}
+
 enum PromiseAction {
     CreateAccount,
     DeployContract {
         code: Vec<u8>,
     },
+    DeployContractFromStorage {
+        key: Vec<u8>,
+    },
     FunctionCall {
         function_name: String,
         arguments: Vec<u8>,
@@ -79,6 +227,14 @@ impl PromiseAction {
             DeployContract { code } => {
                 crate::env::promise_batch_action_deploy_contract(promise_index, code)
             }
+            DeployContractFromStorage { key } => {
+                if !crate::env::promise_batch_action_deploy_contract_from_storage(
+                    promise_index,
+                    key,
+                ) {
+                    crate::env::panic_str("No contract code is stored under the key to deploy.")
+                }
+            }
             FunctionCall { function_name, arguments, amount, gas } => {
                 crate::env::promise_batch_action_function_call(
                     promise_index,
@@ -157,6 +313,18 @@ impl PromiseSingle {
         *promise_lock = Some(promise_index);
         promise_index
     }
+
+    fn estimated_gas(&self) -> Gas {
+        let actions_gas = self.actions.borrow().iter().fold(Gas(0), |total, action| match action {
+            PromiseAction::FunctionCall { gas, .. }
+            | PromiseAction::FunctionCallWeight { gas, .. } => total.saturating_add(*gas),
+            _ => total,
+        });
+        match self.after.borrow().as_ref() {
+            Some(after) => actions_gas.saturating_add(after.estimated_gas()),
+            None => actions_gas,
+        }
+    }
 }
 
 pub struct PromiseJoint {
@@ -179,6 +347,10 @@ impl PromiseJoint {
         *promise_lock = Some(res);
         res
     }
+
+    fn estimated_gas(&self) -> Gas {
+        self.promise_a.estimated_gas().saturating_add(self.promise_b.estimated_gas())
+    }
 }
 
 /// A structure representing a result of the scheduled execution on another contract.
@@ -279,6 +451,37 @@ impl Promise {
         self.add_action(PromiseAction::DeployContract { code })
     }
 
+    /// Deploy the smart contract stored under `key` to the account on which this promise acts.
+    ///
+    /// Unlike [`deploy_contract`](Self::deploy_contract), the code is not loaded into the memory
+    /// of the contract, which suits factories keeping large contracts in their state. The value
+    /// under `key` has to be the raw Wasm code, e.g. written with
+    /// [`env::storage_write`](crate::env::storage_write), as collections like
+    /// [`LazyOption`](crate::store::LazyOption) add a Borsh length prefix to a `Vec<u8>`.
+    ///
+    /// The code is read when the promise is scheduled, and the execution panics if nothing is
+    /// stored under `key` at that point.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use near_sdk::{env, AccountId, Promise};
+    ///
+    /// const CODE_KEY: &[u8] = b"code";
+    ///
+    /// # let code = vec![];
+    /// env::storage_write(CODE_KEY, &code);
+    ///
+    /// let account_id: AccountId = "sub.factory.near".parse().unwrap();
+    /// Promise::new(account_id)
+    ///     .create_account()
+    ///     .transfer(10u128.pow(24))
+    ///     .deploy_contract_from_storage(CODE_KEY.to_vec());
+    /// ```
+    pub fn deploy_contract_from_storage(self, key: Vec<u8>) -> Self {
+        self.add_action(PromiseAction::DeployContractFromStorage { key })
+    }
+
     /// A low-level interface for making a function call to the account that this promise acts on.
     pub fn function_call(
         self,
@@ -293,6 +496,10 @@ impl Promise {
     /// A low-level interface for making a function call to the account that this promise acts on.
     /// unlike [`Promise::function_call`], this function accepts a weight to use relative unused gas
     /// on this function call at the end of the scheduling method execution.
+    ///
+    /// The function call is always attached at least `gas`. A weight of `0` means that only this
+    /// static gas is attached, and no unused gas will be distributed to this function call. See
+    /// [`GasWeight`] for how unused gas is split between weighted function calls.
     pub fn function_call_weight(
         self,
         function_name: String,
@@ -310,6 +517,57 @@ impl Promise {
         })
     }
 
+    /// Sets the [`GasWeight`] of all function calls of this promise, controlling the ratio of the
+    /// unused gas that flows to them relative to other weighted function calls, on top of their
+    /// static gas. This applies to function calls added with both [`Promise::function_call`] and
+    /// [`Promise::function_call_weight`].
+    ///
+    /// The runtime has no timeouts for cross-contract calls, so the weight is not observable by
+    /// the callback. The callback can read the gas it received with [`env::prepaid_gas`] to
+    /// decide whether enough is left to retry a failed call.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called on a joint promise.
+    ///
+    /// ```no_run
+    /// # use near_sdk::{Gas, GasWeight, Promise};
+    /// Promise::new("bob_near".parse().unwrap())
+    ///     .function_call("do_work".to_string(), vec![], 0, Gas(5_000_000_000_000))
+    ///     .with_unused_gas_ratio(GasWeight(3));
+    /// ```
+    ///
+    /// [`env::prepaid_gas`]: crate::env::prepaid_gas
+    pub fn with_unused_gas_ratio(self, weight: GasWeight) -> Self {
+        match &self.subtype {
+            PromiseSubtype::Single(x) => {
+                for action in x.actions.borrow_mut().iter_mut() {
+                    *action = match core::mem::replace(action, PromiseAction::CreateAccount) {
+                        PromiseAction::FunctionCall { function_name, arguments, amount, gas }
+                        | PromiseAction::FunctionCallWeight {
+                            function_name,
+                            arguments,
+                            amount,
+                            gas,
+                            ..
+                        } => PromiseAction::FunctionCallWeight {
+                            function_name,
+                            arguments,
+                            amount,
+                            gas,
+                            weight: GasWeight(weight.0),
+                        },
+                        other => other,
+                    };
+                }
+            }
+            PromiseSubtype::Joint(_) => {
+                crate::env::panic_str("Cannot set the unused gas ratio of a joint promise.")
+            }
+        }
+        self
+    }
+
     /// Transfer tokens to the account that this promise acts on.
     pub fn transfer(self, amount: Balance) -> Self {
         self.add_action(PromiseAction::Transfer { amount })
@@ -408,6 +666,13 @@ impl Promise {
         self.add_action(PromiseAction::DeleteAccount { beneficiary_id })
     }
 
+    /// Add the given actions to the account that this promise acts on, in order.
+    ///
+    /// Panics if this is a joint promise, like the other methods adding actions.
+    pub fn batch(self, actions: Vec<Action>) -> Self {
+        actions.into_iter().fold(self, |promise, action| promise.add_action(action.into()))
+    }
+
     /// Merge this promise with another promise, so that we can schedule execution of another
     /// smart contract right after all merged promises finish.
     ///
@@ -432,6 +697,32 @@ impl Promise {
         }
     }
 
+    /// Merges all of the given promises with [`Promise::and`], so that a single callback scheduled
+    /// with [`Promise::then`] runs after all of them finish. The callback receives the results in
+    /// the same order as the promises, e.g. through `#[callback_results]`.
+    ///
+    /// If `promises` is empty, this returns a promise on the current account with no actions,
+    /// which resolves successfully without a value. A callback scheduled after it receives a
+    /// single empty result, so callers expecting values should handle empty input separately.
+    ///
+    /// ```no_run
+    /// # use near_sdk::Promise;
+    /// let accounts = ["bob_near", "carol_near", "dave_near"];
+    /// let promises =
+    ///     accounts.iter().map(|a| Promise::new(a.parse().unwrap()).create_account());
+    /// let callback = Promise::new("eva_near".parse().unwrap()).create_account();
+    /// Promise::join(promises).then(callback);
+    /// ```
+    pub fn join<I>(promises: I) -> Promise
+    where
+        I: IntoIterator<Item = Promise>,
+    {
+        promises
+            .into_iter()
+            .reduce(Promise::and)
+            .unwrap_or_else(|| Promise::new(crate::env::current_account_id()))
+    }
+
     /// Schedules execution of another promise right after the current promise finish executing.
     ///
     /// In the following code `bob_near` and `dave_near` will be created concurrently. `carol_near`
@@ -461,6 +752,69 @@ impl Promise {
         other
     }
 
+    /// Schedules a function call on `account_id` right after the current promise finishes
+    /// executing, attaching `gas` and a `weight` of the unused gas. This is equivalent to calling
+    /// [`Promise::then`] with a promise created through [`Promise::function_call_weight`].
+    ///
+    /// In the following code the callback `on_done` will be attached at least 5 TGas, and will
+    /// receive a third of the gas left unused at the end of the current method execution, while
+    /// `do_work` receives the other two thirds.
+    /// ```no_run
+    /// # use near_sdk::{env, Gas, GasWeight, Promise};
+    /// Promise::new("bob_near".parse().unwrap())
+    ///     .function_call_weight("do_work".to_string(), vec![], 0, Gas(0), GasWeight(2))
+    ///     .then_weight(
+    ///         env::current_account_id(),
+    ///         "on_done".to_string(),
+    ///         vec![],
+    ///         0,
+    ///         Gas::ONE_TERA * 5,
+    ///         GasWeight(1),
+    ///     );
+    /// ```
+    pub fn then_weight(
+        self,
+        account_id: AccountId,
+        function_name: String,
+        arguments: Vec<u8>,
+        amount: Balance,
+        gas: Gas,
+        weight: GasWeight,
+    ) -> Promise {
+        self.then(Promise::new(account_id).function_call_weight(
+            function_name,
+            arguments,
+            amount,
+            gas,
+            weight,
+        ))
+    }
+
+    /// Returns the static gas attached to all function calls of this promise and of the promises
+    /// it is scheduled after, e.g. to check that enough gas is left before scheduling it.
+    ///
+    /// This is a best-effort estimate: the unused gas distributed by [`GasWeight`] and the gas
+    /// burnt to create the receipts are not included.
+    ///
+    /// ```
+    /// # use near_sdk::{Gas, Promise};
+    /// let promise = Promise::new("bob_near".parse().unwrap())
+    ///     .function_call("a".to_string(), vec![], 0, Gas::from_tgas(5))
+    ///     .then(Promise::new("carol_near".parse().unwrap()).function_call(
+    ///         "b".to_string(),
+    ///         vec![],
+    ///         0,
+    ///         Gas::from_tgas(3),
+    ///     ));
+    /// assert_eq!(promise.estimated_gas(), Gas::from_tgas(8));
+    /// ```
+    pub fn estimated_gas(&self) -> Gas {
+        match &self.subtype {
+            PromiseSubtype::Single(x) => x.estimated_gas(),
+            PromiseSubtype::Joint(x) => x.estimated_gas(),
+        }
+    }
+
     /// A specialized, relatively low-level API method. Allows to mark the given promise as the one
     /// that should be considered as a return value.
     ///
@@ -584,6 +938,59 @@ where
     }
 }
 
+impl<T> PromiseOrValue<T> {
+    /// Maps the value with `f`, leaving a promise unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::json_types::U128;
+    /// use near_sdk::PromiseOrValue;
+    ///
+    /// let unused: PromiseOrValue<u128> = PromiseOrValue::Value(5);
+    /// assert!(matches!(unused.map_value(U128), PromiseOrValue::Value(U128(5))));
+    /// ```
+    pub fn map_value<U, F>(self, f: F) -> PromiseOrValue<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        match self {
+            PromiseOrValue::Promise(promise) => PromiseOrValue::Promise(promise),
+            PromiseOrValue::Value(value) => PromiseOrValue::Value(f(value)),
+        }
+    }
+
+    /// Calls `f` with the promise, e.g. to attach a callback with [`Promise::then`], leaving a
+    /// value unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use near_sdk::{env, Gas, Promise, PromiseOrValue};
+    ///
+    /// # fn transfer() -> PromiseOrValue<bool> { PromiseOrValue::Value(true) }
+    /// let result = transfer().and_then_promise(|promise| {
+    ///     promise
+    ///         .then(Promise::new(env::current_account_id()).function_call(
+    ///             "on_transfer".to_string(),
+    ///             vec![],
+    ///             0,
+    ///             Gas::from_tgas(5),
+    ///         ))
+    ///         .into()
+    /// });
+    /// ```
+    pub fn and_then_promise<F>(self, f: F) -> PromiseOrValue<T>
+    where
+        F: FnOnce(Promise) -> PromiseOrValue<T>,
+    {
+        match self {
+            PromiseOrValue::Promise(promise) => f(promise),
+            PromiseOrValue::Value(value) => PromiseOrValue::Value(value),
+        }
+    }
+}
+
 impl<T> From<Promise> for PromiseOrValue<T> {
     fn from(promise: Promise) -> Self {
         PromiseOrValue::Promise(promise)
@@ -611,3 +1018,299 @@ impl<T: schemars::JsonSchema> schemars::JsonSchema for PromiseOrValue<T> {
         T::json_schema(gen)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::VmAction;
+    use crate::test_utils::{
+        accounts, get_created_actions, get_created_receipts, VMContextBuilder,
+    };
+    use crate::testing_env;
+
+    fn public_key() -> PublicKey {
+        "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp".parse().unwrap()
+    }
+
+    #[test]
+    fn batch_actions_are_recorded_in_order() {
+        testing_env!(VMContextBuilder::new().build());
+        let batch = PromiseBatch::new(accounts(1))
+            .action(Action::CreateAccount)
+            .action(Action::Transfer(NearToken::from_near(1)))
+            .action(Action::DeployContract(vec![1, 2, 3]))
+            .action(Action::FunctionCall {
+                function_name: "new".to_string(),
+                arguments: b"{}".to_vec(),
+                deposit: NearToken::from_yoctonear(1),
+                gas: Gas::ONE_TERA,
+            })
+            .action(Action::Stake { amount: NearToken::from_near(2), public_key: public_key() })
+            .action(Action::AddKey {
+                public_key: public_key(),
+                nonce: 0,
+                permission: AccessKeyPermission::FullAccess,
+            })
+            .action(Action::AddKey {
+                public_key: public_key(),
+                nonce: 1,
+                permission: AccessKeyPermission::FunctionCall {
+                    allowance: Allowance::limited(10).unwrap(),
+                    receiver_id: accounts(2),
+                    function_names: "a,b".to_string(),
+                },
+            })
+            .action(Action::DeleteKey(public_key()))
+            .action(Action::DeleteAccount(accounts(0)));
+        assert_eq!(batch.receiver_id(), &accounts(1));
+        assert_eq!(batch.actions().len(), 9);
+        drop(batch.into_promise());
+
+        let receipts = get_created_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].receiver_id, accounts(1));
+        assert_eq!(
+            receipts[0].actions,
+            vec![
+                VmAction::CreateAccount,
+                VmAction::Transfer { deposit: 10u128.pow(24) },
+                VmAction::DeployContract { code: vec![1, 2, 3] },
+                VmAction::FunctionCall {
+                    function_name: "new".to_string(),
+                    args: b"{}".to_vec(),
+                    gas: Gas::ONE_TERA,
+                    deposit: 1,
+                },
+                VmAction::Stake { stake: 2 * 10u128.pow(24), public_key: public_key() },
+                VmAction::AddKeyWithFullAccess { public_key: public_key(), nonce: 0 },
+                VmAction::AddKeyWithFunctionCall {
+                    public_key: public_key(),
+                    nonce: 1,
+                    allowance: Some(10),
+                    receiver_id: accounts(2),
+                    function_names: vec!["a".to_string(), "b".to_string()],
+                },
+                VmAction::DeleteKey { public_key: public_key() },
+                VmAction::DeleteAccount { beneficiary_id: accounts(0) },
+            ]
+        );
+    }
+
+    #[test]
+    fn batch_extends_promise() {
+        testing_env!(VMContextBuilder::new().build());
+        let mut batch = PromiseBatch::new(accounts(1));
+        batch.extend([Action::CreateAccount, Action::Transfer(NearToken::from_yoctonear(5))]);
+        drop(Promise::from(batch).batch(vec![Action::DeleteAccount(accounts(0))]));
+
+        let receipts = get_created_receipts();
+        assert_eq!(
+            receipts[0].actions,
+            vec![
+                VmAction::CreateAccount,
+                VmAction::Transfer { deposit: 5 },
+                VmAction::DeleteAccount { beneficiary_id: accounts(0) },
+            ]
+        );
+    }
+
+    #[test]
+    fn add_key_records_nonce() {
+        testing_env!(VMContextBuilder::new().build());
+        let old_key: PublicKey =
+            "ed25519:DWVN8LrXuQ3q3a2rmkZuxFgTHkvJm8zE9M4s6TbkrrKw".parse().unwrap();
+        Promise::new(accounts(1))
+            .add_full_access_key_with_nonce(public_key(), 7)
+            .add_access_key_allowance_with_nonce(
+                old_key.clone(),
+                Allowance::Unlimited,
+                accounts(2),
+                "ft_transfer".to_string(),
+                42,
+            )
+            .delete_key(old_key.clone());
+
+        let receipts = get_created_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(
+            receipts[0].actions,
+            vec![
+                VmAction::AddKeyWithFullAccess { public_key: public_key(), nonce: 7 },
+                VmAction::AddKeyWithFunctionCall {
+                    public_key: old_key.clone(),
+                    nonce: 42,
+                    allowance: None,
+                    receiver_id: accounts(2),
+                    function_names: vec!["ft_transfer".to_string()],
+                },
+                VmAction::DeleteKey { public_key: old_key },
+            ]
+        );
+    }
+
+    #[test]
+    fn deploy_contract_from_storage() {
+        testing_env!(VMContextBuilder::new().build());
+        let code = vec![0, 97, 115, 109, 1, 0, 0, 0];
+        crate::env::storage_write(b"code", &code);
+
+        Promise::new(accounts(1))
+            .create_account()
+            .deploy_contract_from_storage(b"code".to_vec())
+            .function_call("new".to_string(), b"{}".to_vec(), 0, Gas::ONE_TERA);
+
+        let receipts = get_created_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].actions[0], VmAction::CreateAccount);
+        assert_eq!(receipts[0].actions[1], VmAction::DeployContract { code });
+        assert_eq!(receipts[0].actions[2].function_name(), Some("new"));
+    }
+
+    #[test]
+    #[should_panic(expected = "No contract code is stored under the key to deploy.")]
+    fn deploy_contract_from_missing_key() {
+        testing_env!(VMContextBuilder::new().build());
+        Promise::new(accounts(1)).deploy_contract_from_storage(b"code".to_vec());
+    }
+
+    #[test]
+    fn created_actions_follow_receipts() {
+        testing_env!(VMContextBuilder::new().build());
+        Promise::new(accounts(0)).transfer(10u128.pow(24)).then(
+            Promise::new(accounts(1)).function_call(
+                "ft_resolve_transfer".to_string(),
+                b"{}".to_vec(),
+                1,
+                Gas::from_tgas(10),
+            ),
+        );
+
+        let actions = get_created_actions();
+        assert_eq!(actions.len(), 2);
+        assert_eq!(actions[0].receipt_index, 0);
+        assert_eq!(actions[0].receiver_id, accounts(0));
+        assert_eq!(actions[0].action, VmAction::Transfer { deposit: 10u128.pow(24) });
+        assert_eq!(actions[0].function_name(), None);
+        assert_eq!(actions[0].deposit(), Some(10u128.pow(24)));
+        assert_eq!(actions[0].gas(), None);
+
+        assert_eq!(actions[1].receipt_index, 1);
+        assert_eq!(actions[1].receiver_id, accounts(1));
+        assert_eq!(actions[1].function_name(), Some("ft_resolve_transfer"));
+        assert_eq!(actions[1].deposit(), Some(1));
+        assert_eq!(actions[1].gas(), Some(Gas::from_tgas(10)));
+    }
+
+    #[test]
+    fn promise_or_value_map_value() {
+        testing_env!(VMContextBuilder::new().build());
+        let value: PromiseOrValue<u8> = PromiseOrValue::Value(2);
+        assert!(matches!(value.map_value(|x| x.to_string()), PromiseOrValue::Value(x) if x == "2"));
+
+        let promise: PromiseOrValue<u8> = Promise::new(accounts(1)).transfer(1).into();
+        let mapped = promise.map_value(|_| -> String { panic!("the value must not be mapped") });
+        assert!(matches!(mapped, PromiseOrValue::Promise(_)));
+        drop(mapped);
+        let receipts = get_created_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].receiver_id, accounts(1));
+    }
+
+    #[test]
+    fn promise_or_value_and_then_promise() {
+        testing_env!(VMContextBuilder::new().build());
+        let value: PromiseOrValue<u8> = PromiseOrValue::Value(2);
+        let value = value.and_then_promise(|_| panic!("there is no promise"));
+        assert!(matches!(value, PromiseOrValue::Value(2)));
+
+        let promise: PromiseOrValue<u8> = Promise::new(accounts(1)).transfer(1).into();
+        let chained = promise.and_then_promise(|promise| {
+            promise
+                .then(Promise::new(accounts(0)).function_call(
+                    "on_transfer".to_string(),
+                    vec![],
+                    0,
+                    Gas::from_tgas(5),
+                ))
+                .into()
+        });
+        drop(chained);
+        let actions = get_created_actions();
+        assert_eq!(actions.len(), 2);
+        assert_eq!(actions[1].receiver_id, accounts(0));
+        assert_eq!(actions[1].function_name(), Some("on_transfer"));
+
+        let fallback: PromiseOrValue<u8> = Promise::new(accounts(1)).into();
+        assert!(matches!(
+            fallback.and_then_promise(|_| PromiseOrValue::Value(0)),
+            PromiseOrValue::Value(0)
+        ));
+    }
+
+    #[test]
+    fn with_unused_gas_ratio() {
+        testing_env!(VMContextBuilder::new().build());
+        let promise = Promise::new(accounts(1))
+            .transfer(1)
+            .function_call("f".to_string(), vec![], 0, Gas::from_tgas(5))
+            .function_call_weight("g".to_string(), vec![], 0, Gas::from_tgas(2), GasWeight(1))
+            .with_unused_gas_ratio(GasWeight(3));
+
+        match &promise.subtype {
+            PromiseSubtype::Single(x) => {
+                let actions = x.actions.borrow();
+                assert!(matches!(actions[0], PromiseAction::Transfer { amount: 1 }));
+                assert!(matches!(
+                    &actions[1],
+                    PromiseAction::FunctionCallWeight { function_name, gas, weight: GasWeight(3), .. }
+                        if function_name == "f" && *gas == Gas::from_tgas(5)
+                ));
+                assert!(matches!(
+                    &actions[2],
+                    PromiseAction::FunctionCallWeight { function_name, gas, weight: GasWeight(3), .. }
+                        if function_name == "g" && *gas == Gas::from_tgas(2)
+                ));
+            }
+            PromiseSubtype::Joint(_) => unreachable!(),
+        }
+
+        drop(promise);
+        let receipts = get_created_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].actions[0], VmAction::Transfer { deposit: 1 });
+        assert_eq!(receipts[0].actions[1].function_name(), Some("f"));
+        assert_eq!(receipts[0].actions[1].gas(), Some(Gas::from_tgas(5)));
+        assert_eq!(receipts[0].actions[2].function_name(), Some("g"));
+        assert_eq!(receipts[0].actions[2].gas(), Some(Gas::from_tgas(2)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot set the unused gas ratio of a joint promise.")]
+    fn with_unused_gas_ratio_joint() {
+        testing_env!(VMContextBuilder::new().build());
+        Promise::new(accounts(1))
+            .transfer(1)
+            .and(Promise::new(accounts(2)).transfer(1))
+            .with_unused_gas_ratio(GasWeight(3));
+    }
+
+    #[test]
+    fn estimated_gas() {
+        testing_env!(VMContextBuilder::new().build());
+        let call = |account, gas| {
+            Promise::new(accounts(account)).function_call("f".to_string(), vec![], 0, gas)
+        };
+        assert_eq!(Promise::new(accounts(1)).transfer(1).estimated_gas(), Gas(0));
+
+        let promise = call(1, Gas::from_tgas(5))
+            .function_call_weight("g".to_string(), vec![], 0, Gas::from_tgas(2), GasWeight(3))
+            .transfer(1);
+        assert_eq!(promise.estimated_gas(), Gas::from_tgas(7));
+
+        let joint = promise.and(call(2, Gas::from_tgas(10)));
+        assert_eq!(joint.estimated_gas(), Gas::from_tgas(17));
+
+        let chain = joint.then(call(0, Gas::from_tgas(4))).then(call(3, Gas::from_tgas(1)));
+        assert_eq!(chain.estimated_gas(), Gas::from_tgas(22));
+    }
+}
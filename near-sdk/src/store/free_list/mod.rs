@@ -224,6 +224,21 @@ where
         Drain::new(self)
     }
 
+    /// Retains only the elements for which the predicate returns `true`. Removed elements are
+    /// replaced with empty cells, so the indices of retained elements are unchanged.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        for i in 0..self.elements.len() {
+            if let Some(Slot::Occupied(value)) = self.elements.get_mut(i) {
+                if !f(value) {
+                    self.remove(FreeListIndex(i));
+                }
+            }
+        }
+    }
+
     /// Empty slots in the front of the list is swapped with occupied slots in back of the list.
     /// Defrag helps reduce gas cost in certain scenarios where lot of elements in front of the list are
     /// removed without getting replaced. Please see https://github.com/near/near-sdk-rs/issues/990
@@ -383,6 +398,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn retain() {
+        let mut bucket = FreeList::new(b"b");
+        let indices: Vec<_> = (0..6u8).map(|i| bucket.insert(i)).collect();
+        bucket.remove(indices[2]);
+
+        bucket.retain(|v| {
+            *v *= 10;
+            *v != 30
+        });
+
+        assert_eq!(bucket.len(), 4);
+        assert_eq!(bucket.iter().collect::<Vec<_>>(), [&0, &10, &40, &50]);
+        // Indices of retained elements are unchanged.
+        assert_eq!(bucket.get(indices[4]), Some(&40));
+        assert_eq!(bucket.get(indices[3]), None);
+
+        // Removed cells are reused on insert.
+        let i = bucket.insert(7);
+        assert!(i == indices[2] || i == indices[3]);
+        assert_eq!(bucket.len(), 5);
+    }
+
     #[test]
     fn bucket_iterator() {
         let mut bucket = FreeList::new(b"b");
@@ -1,18 +1,17 @@
 use std::fmt;
+use std::marker::PhantomData;
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use once_cell::unsync::OnceCell;
 
+use super::serialization::{Borsh, Deserialization, Serialization};
 use crate::utils::StableMap;
 use crate::{env, CacheEntry, EntryState, IntoStorageKey};
 
-const ERR_ELEMENT_DESERIALIZATION: &str = "Cannot deserialize element";
-const ERR_ELEMENT_SERIALIZATION: &str = "Cannot serialize element";
-
 #[derive(BorshSerialize, BorshDeserialize)]
-pub(crate) struct IndexMap<T>
+pub(crate) struct IndexMap<T, E = Borsh>
 where
-    T: BorshSerialize,
+    E: Serialization<T>,
 {
     pub(crate) prefix: Box<[u8]>,
     /// Cache for loads and intermediate changes to the underlying index map.
@@ -23,11 +22,13 @@ where
     /// Some functionality would be different from tests to Wasm if exceeding 32-bit length.
     #[borsh_skip]
     pub(crate) cache: StableMap<u32, OnceCell<CacheEntry<T>>>,
+    #[borsh_skip]
+    format: PhantomData<E>,
 }
 
-impl<T> IndexMap<T>
+impl<T, E> IndexMap<T, E>
 where
-    T: BorshSerialize,
+    E: Serialization<T>,
 {
     /// Create new index map. This creates a mapping of `u32` -> `T` in storage.
     ///
@@ -37,7 +38,11 @@ where
     where
         S: IntoStorageKey,
     {
-        Self { prefix: prefix.into_storage_key().into_boxed_slice(), cache: Default::default() }
+        Self {
+            prefix: prefix.into_storage_key().into_boxed_slice(),
+            cache: Default::default(),
+            format: PhantomData,
+        }
     }
 
     fn index_to_lookup_key(prefix: &[u8], index: u32, buf: &mut Vec<u8>) {
@@ -58,8 +63,7 @@ where
                     match v.value().as_ref() {
                         Some(modified) => {
                             buf.clear();
-                            BorshSerialize::serialize(modified, &mut buf)
-                                .unwrap_or_else(|_| env::panic_str(ERR_ELEMENT_SERIALIZATION));
+                            E::serialize(modified, &mut buf);
                             env::storage_write(&key_buf, &buf);
                         }
                         None => {
@@ -89,13 +93,12 @@ where
     }
 }
 
-impl<T> IndexMap<T>
+impl<T, E> IndexMap<T, E>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     fn deserialize_element(raw_element: &[u8]) -> T {
-        T::try_from_slice(raw_element)
-            .unwrap_or_else(|_| env::panic_str(ERR_ELEMENT_DESERIALIZATION))
+        E::deserialize(raw_element)
     }
 
     /// Returns the element by index or `None` if it is not present.
@@ -110,6 +113,24 @@ where
         entry.value().as_ref()
     }
 
+    /// Returns an owned copy of the element by index without caching it. Elements already in the
+    /// cache are cloned so pending changes are reflected. The `key_buf` is reused between calls
+    /// to avoid allocating a lookup key per element.
+    pub(crate) fn get_uncached(&self, index: u32, key_buf: &mut Vec<u8>) -> Option<T>
+    where
+        T: Clone,
+    {
+        let cached = self.cache.map_value_ref(&index, |entry| {
+            entry.get().map(|entry| entry.value().as_ref().cloned())
+        });
+        if let Some(Some(value)) = cached {
+            return value;
+        }
+        key_buf.clear();
+        Self::index_to_lookup_key(&self.prefix, index, key_buf);
+        env::storage_read(key_buf).as_deref().map(Self::deserialize_element)
+    }
+
     /// Returns a mutable reference to the element at the `index` provided.
     pub(crate) fn get_mut_inner(&mut self, index: u32) -> &mut CacheEntry<T> {
         let prefix = &self.prefix;
@@ -153,9 +174,10 @@ where
     }
 }
 
-impl<T> fmt::Debug for IndexMap<T>
+impl<T, E> fmt::Debug for IndexMap<T, E>
 where
-    T: BorshSerialize + BorshDeserialize + fmt::Debug,
+    T: fmt::Debug,
+    E: Deserialization<T>,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("IndexMap").field("prefix", &self.prefix).finish()
@@ -169,7 +191,7 @@ mod tests {
 
     #[test]
     fn basic_usage() {
-        let mut map = IndexMap::new(b"v".to_vec());
+        let mut map: IndexMap<u8> = IndexMap::new(b"v".to_vec());
 
         map.insert(3, 3u8);
         map.insert(43, 43);
@@ -0,0 +1,20 @@
+use super::IterableSet;
+use crate::store::key::ToKey;
+use crate::store::serialization::Deserialization;
+use borsh::BorshSerialize;
+
+impl<T, H, E> Extend<T> for IterableSet<T, H, E>
+where
+    T: BorshSerialize + Ord + Clone,
+    H: ToKey,
+    E: Deserialization<Option<T>>,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
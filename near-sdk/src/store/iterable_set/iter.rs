@@ -0,0 +1,104 @@
+use super::IterableSet;
+use crate::store::key::ToKey;
+use crate::store::serialization::{Borsh, Deserialization};
+use crate::store::vec;
+use borsh::BorshSerialize;
+use std::iter::FusedIterator;
+
+impl<'a, T, H, E> IntoIterator for &'a IterableSet<T, H, E>
+where
+    T: BorshSerialize + Ord,
+    H: ToKey,
+    E: Deserialization<Option<T>>,
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, E>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over elements of a [`IterableSet`], in insertion order.
+///
+/// This `struct` is created by the [`iter`] method on [`IterableSet`].
+/// See its documentation for more.
+///
+/// [`iter`]: IterableSet::iter
+pub struct Iter<'a, T, E = Borsh>
+where
+    T: BorshSerialize + Ord,
+    E: Deserialization<Option<T>>,
+{
+    elements: vec::Iter<'a, Option<T>, E>,
+    /// Number of elements left to yield, excluding tombstones.
+    remaining: u32,
+}
+
+impl<'a, T, E> Iter<'a, T, E>
+where
+    T: BorshSerialize + Ord,
+    E: Deserialization<Option<T>>,
+{
+    pub(super) fn new<H>(set: &'a IterableSet<T, H, E>) -> Self
+    where
+        H: ToKey,
+    {
+        Self { elements: set.elements.iter(), remaining: set.len }
+    }
+}
+
+impl<'a, T, E> Iterator for Iter<'a, T, E>
+where
+    T: BorshSerialize + Ord,
+    E: Deserialization<Option<T>>,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let value = self.elements.find_map(Option::as_ref)?;
+        self.remaining -= 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining as usize;
+        (remaining, Some(remaining))
+    }
+
+    fn count(self) -> usize {
+        self.remaining as usize
+    }
+}
+
+impl<'a, T, E> ExactSizeIterator for Iter<'a, T, E>
+where
+    T: BorshSerialize + Ord,
+    E: Deserialization<Option<T>>,
+{
+}
+
+impl<'a, T, E> FusedIterator for Iter<'a, T, E>
+where
+    T: BorshSerialize + Ord,
+    E: Deserialization<Option<T>>,
+{
+}
+
+impl<'a, T, E> DoubleEndedIterator for Iter<'a, T, E>
+where
+    T: BorshSerialize + Ord,
+    E: Deserialization<Option<T>>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let value = self.elements.by_ref().rev().find_map(Option::as_ref)?;
+        self.remaining -= 1;
+        Some(value)
+    }
+}
@@ -0,0 +1,430 @@
+mod impls;
+mod iter;
+
+pub use self::iter::Iter;
+use super::{LookupMap, Vector, ERR_INCONSISTENT_STATE};
+use crate::store::key::{Sha256, ToKey};
+use crate::store::serialization::{Borsh, Deserialization, Serialization};
+use crate::{env, IntoStorageKey};
+use borsh::{BorshDeserialize, BorshSerialize};
+use std::borrow::Borrow;
+use std::fmt;
+
+/// A lazily loaded storage set that iterates over its elements in insertion order.
+///
+/// Unlike [`UnorderedSet`], which reuses the slots of removed elements, removing an element
+/// leaves a tombstone in its place and new elements are always appended, so the iteration order
+/// is stable across removals. Re-inserting a removed element moves it to the end. Tombstones are
+/// skipped when iterating and can be cleared with [`compact`], which keeps the order.
+///
+/// As with the [`UnorderedSet`] type, an `IterableSet` requires that the elements
+/// implement the [`BorshSerialize`] and [`Ord`] traits. Some functions also require elements to
+/// be deserializable with the set's format, which is [`BorshDeserialize`] by default.
+///
+/// This set stores the values under a hash of the set's `prefix` and [`BorshSerialize`] of the
+/// element using the set's [`ToKey`] implementation. The default hash function is [`Sha256`], to
+/// use a custom function, use [`with_hasher`].
+///
+/// Elements are stored with [`Borsh`] by default. Another format, such as
+/// [`Json`](crate::store::serialization::Json), can be selected with the `E` type parameter and
+/// [`IterableSet::with_serialization`]. The storage keys are always derived from the Borsh
+/// serialization of the elements.
+///
+/// # Examples
+///
+/// ```
+/// use near_sdk::store::IterableSet;
+///
+/// let mut set = IterableSet::new(b"a");
+/// set.insert("a".to_string());
+/// set.insert("b".to_string());
+/// set.insert("c".to_string());
+/// assert!(set.remove("a"));
+/// set.insert("a".to_string());
+///
+/// assert_eq!(set.iter().collect::<Vec<_>>(), ["b", "c", "a"]);
+/// ```
+///
+/// [`UnorderedSet`]: crate::store::UnorderedSet
+/// [`compact`]: Self::compact
+/// [`with_hasher`]: Self::with_hasher
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct IterableSet<T, H = Sha256, E = Borsh>
+where
+    T: BorshSerialize + Ord,
+    H: ToKey,
+    E: Serialization<Option<T>>,
+{
+    elements: Vector<Option<T>, E>,
+    index: LookupMap<T, u32, H>,
+    len: u32,
+}
+
+impl<T, H, E> Drop for IterableSet<T, H, E>
+where
+    T: BorshSerialize + Ord,
+    H: ToKey,
+    E: Serialization<Option<T>>,
+{
+    fn drop(&mut self) {
+        self.flush()
+    }
+}
+
+impl<T, H, E> fmt::Debug for IterableSet<T, H, E>
+where
+    T: BorshSerialize + Ord + fmt::Debug,
+    H: ToKey,
+    E: Deserialization<Option<T>>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IterableSet")
+            .field("elements", &self.elements)
+            .field("index", &self.index)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl<T> IterableSet<T, Sha256>
+where
+    T: BorshSerialize + Ord,
+{
+    /// Create a new iterable set. Use `prefix` as a unique prefix for keys.
+    ///
+    /// This prefix can be anything that implements [`IntoStorageKey`]. The prefix is used when
+    /// storing and looking up values in storage to ensure no collisions with other collections.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::IterableSet;
+    ///
+    /// let mut set: IterableSet<String> = IterableSet::new(b"b");
+    /// ```
+    #[inline]
+    pub fn new<S>(prefix: S) -> Self
+    where
+        S: IntoStorageKey,
+    {
+        Self::with_hasher(prefix)
+    }
+}
+
+impl<T, H> IterableSet<T, H>
+where
+    T: BorshSerialize + Ord,
+    H: ToKey,
+{
+    /// Initialize an [`IterableSet`] with a custom hash function.
+    ///
+    /// # Example
+    /// ```
+    /// use near_sdk::store::{IterableSet, key::Keccak256};
+    ///
+    /// let set = IterableSet::<String, Keccak256>::with_hasher(b"m");
+    /// ```
+    pub fn with_hasher<S>(prefix: S) -> Self
+    where
+        S: IntoStorageKey,
+    {
+        Self::with_serialization(prefix)
+    }
+}
+
+impl<T, H, E> IterableSet<T, H, E>
+where
+    T: BorshSerialize + Ord,
+    H: ToKey,
+    E: Serialization<Option<T>>,
+{
+    /// Initialize an [`IterableSet`] with a custom hash function, storing its elements with
+    /// the format `E`.
+    ///
+    /// See [`IterableSet::new`] and [`IterableSet::with_hasher`] for creating a set with the
+    /// default [`Borsh`] format.
+    ///
+    /// # Example
+    /// ```
+    /// use near_sdk::store::key::Sha256;
+    /// use near_sdk::store::serialization::Json;
+    /// use near_sdk::store::IterableSet;
+    ///
+    /// let mut set: IterableSet<String, Sha256, Json> = IterableSet::with_serialization(b"m");
+    /// set.insert("a".to_string());
+    /// assert!(set.contains("a"));
+    /// ```
+    pub fn with_serialization<S>(prefix: S) -> Self
+    where
+        S: IntoStorageKey,
+    {
+        let mut vec_key = prefix.into_storage_key();
+        let map_key = [vec_key.as_slice(), b"m"].concat();
+        vec_key.push(b'v');
+        Self {
+            elements: Vector::with_serialization(vec_key),
+            index: LookupMap::with_hasher(map_key),
+            len: 0,
+        }
+    }
+
+    /// Returns the number of elements in the set.
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    /// Returns true if the set contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Clears the set, removing all values and tombstones.
+    pub fn clear(&mut self)
+    where
+        E: Deserialization<Option<T>>,
+    {
+        for value in self.elements.drain(..).flatten() {
+            self.index.set(value, None);
+        }
+        self.len = 0;
+    }
+
+    /// An iterator visiting all elements in insertion order.
+    /// The iterator element type is `&'a T`.
+    ///
+    /// Each tombstone left by a removed element costs a storage read when iterating over it,
+    /// until the set is [compacted](Self::compact).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::IterableSet;
+    ///
+    /// let mut set = IterableSet::new(b"b");
+    /// set.insert("a".to_string());
+    /// set.insert("b".to_string());
+    ///
+    /// for x in set.iter() {
+    ///     println!("{}", x);
+    /// }
+    /// ```
+    pub fn iter(&self) -> Iter<T, E>
+    where
+        E: Deserialization<Option<T>>,
+    {
+        Iter::new(self)
+    }
+
+    /// Returns `true` if the set contains the specified value.
+    ///
+    /// The value may be any borrowed form of the set's value type, but
+    /// [`BorshSerialize`], [`ToOwned<Owned = T>`](ToOwned) and [`Ord`] on the borrowed form *must*
+    /// match those for the value type.
+    pub fn contains<Q: ?Sized>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: BorshSerialize + ToOwned<Owned = T> + Ord,
+    {
+        self.index.contains_key(value)
+    }
+
+    /// Adds a value to the end of the set.
+    ///
+    /// If the set did not have this value present, `true` is returned.
+    ///
+    /// If the set did have this value present, `false` is returned and its position is kept.
+    pub fn insert(&mut self, value: T) -> bool
+    where
+        T: Clone,
+        E: Deserialization<Option<T>>,
+    {
+        let entry = self.index.get_mut_inner(&value);
+        if entry.value_mut().is_some() {
+            false
+        } else {
+            let element_index = self.elements.len();
+            self.elements.push(Some(value));
+            entry.replace(Some(element_index));
+            self.len += 1;
+            true
+        }
+    }
+
+    /// Removes a value from the set, leaving a tombstone in its place. Returns whether the value
+    /// was present in the set.
+    ///
+    /// The value may be any borrowed form of the set's value type, but
+    /// [`BorshSerialize`], [`ToOwned<Owned = T>`](ToOwned) and [`Ord`] on the borrowed form *must*
+    /// match those for the value type.
+    pub fn remove<Q: ?Sized>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: BorshSerialize + ToOwned<Owned = T> + Ord,
+        E: Deserialization<Option<T>>,
+    {
+        match self.index.remove(value) {
+            Some(element_index) => {
+                self.elements
+                    .replace(element_index, None)
+                    .unwrap_or_else(|| env::panic_str(ERR_INCONSISTENT_STATE));
+                self.len -= 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the tombstones left by removed elements, moving the remaining elements to the
+    /// front while keeping their order.
+    ///
+    /// This reads every slot of the set and writes each element that moves, so it should only
+    /// be called once enough elements have been removed to make iteration noticeably costlier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::IterableSet;
+    ///
+    /// let mut set = IterableSet::new(b"c");
+    /// set.extend([1u8, 2, 3, 4]);
+    /// set.remove(&1);
+    /// set.remove(&3);
+    ///
+    /// set.compact();
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), [&2, &4]);
+    /// ```
+    pub fn compact(&mut self)
+    where
+        T: Clone,
+        E: Deserialization<Option<T>>,
+    {
+        let mut write_index = 0;
+        for read_index in 0..self.elements.len() {
+            if read_index == write_index {
+                if self.elements[read_index].is_some() {
+                    write_index += 1;
+                }
+                continue;
+            }
+            if let Some(value) = self.elements.replace(read_index, None) {
+                self.index.insert(value.clone(), write_index);
+                self.elements.set(write_index, Some(value));
+                write_index += 1;
+            }
+        }
+        self.elements.resize_with(write_index, || None);
+    }
+
+    /// Flushes the intermediate values of the set before this is called when the structure is
+    /// [`Drop`]ed. This will write all modified values to storage but keep all cached values
+    /// in memory.
+    pub fn flush(&mut self) {
+        self.elements.flush();
+        self.index.flush();
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use crate::store::IterableSet;
+    use borsh::{BorshDeserialize, BorshSerialize};
+
+    #[test]
+    fn basic_functionality() {
+        let mut set = IterableSet::new(b"b");
+        assert!(set.is_empty());
+        assert!(set.insert("test".to_string()));
+        assert!(!set.insert("test".to_string()));
+        assert!(set.contains("test"));
+        assert_eq!(set.len(), 1);
+
+        assert!(set.remove("test"));
+        assert!(!set.remove("test"));
+        assert!(!set.contains("test"));
+        assert_eq!(set.len(), 0);
+    }
+
+    #[test]
+    fn insertion_order_is_kept() {
+        let mut set = IterableSet::new(b"s");
+        set.extend([3u8, 1, 4, 5, 9, 2]);
+        assert!(!set.insert(1));
+        assert!(set.remove(&1));
+        assert!(set.remove(&9));
+        assert_eq!(set.iter().collect::<Vec<_>>(), [&3, &4, &5, &2]);
+
+        // Re-inserted elements are moved to the end.
+        assert!(set.insert(1));
+        assert_eq!(set.iter().collect::<Vec<_>>(), [&3, &4, &5, &2, &1]);
+        assert_eq!(set.iter().rev().collect::<Vec<_>>(), [&1, &2, &5, &4, &3]);
+        assert_eq!(set.iter().len(), 5);
+        set.flush();
+
+        // The order is kept when loaded from storage.
+        let set = IterableSet::<u8>::try_from_slice(&set.try_to_vec().unwrap()).unwrap();
+        assert_eq!(set.len(), 5);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [&3, &4, &5, &2, &1]);
+    }
+
+    #[test]
+    fn compact() {
+        let mut set = IterableSet::new(b"s");
+        set.extend(0u32..10);
+        for i in (0..10).step_by(3) {
+            set.remove(&i);
+        }
+        assert_eq!(set.elements.len(), 10);
+
+        set.compact();
+        assert_eq!(set.elements.len(), 6);
+        assert_eq!(set.iter().copied().collect::<Vec<_>>(), [1, 2, 4, 5, 7, 8]);
+
+        // The index points to the moved elements.
+        assert!(set.remove(&4));
+        assert!(set.insert(0));
+        assert_eq!(set.iter().copied().collect::<Vec<_>>(), [1, 2, 5, 7, 8, 0]);
+        set.flush();
+
+        let mut set = IterableSet::<u32>::try_from_slice(&set.try_to_vec().unwrap()).unwrap();
+        set.compact();
+        assert_eq!(set.iter().copied().collect::<Vec<_>>(), [1, 2, 5, 7, 8, 0]);
+        assert!(set.contains(&8));
+    }
+
+    #[test]
+    fn json_serialization() {
+        use crate::env;
+        use crate::store::key::Sha256;
+        use crate::store::serialization::Json;
+
+        let mut set: IterableSet<String, Sha256, Json> = IterableSet::with_serialization(b"j");
+        set.insert("a".to_string());
+        set.insert("b".to_string());
+        set.remove("a");
+        set.flush();
+        // The removed element leaves a `null` tombstone.
+        let slot = |index: u32| [b"jv".as_slice(), &index.to_le_bytes()].concat();
+        assert_eq!(env::storage_read(&slot(0)).unwrap(), b"null");
+        assert_eq!(env::storage_read(&slot(1)).unwrap(), br#""b""#);
+
+        let set = IterableSet::<String, Sha256, Json>::try_from_slice(&set.try_to_vec().unwrap())
+            .unwrap();
+        assert!(set.contains("b"));
+        assert_eq!(set.iter().collect::<Vec<_>>(), ["b"]);
+    }
+
+    #[test]
+    fn clear() {
+        let mut set = IterableSet::new(b"s");
+        set.extend(0u8..5);
+        set.remove(&2);
+        set.clear();
+        assert!(set.is_empty());
+        assert_eq!(set.iter().next(), None);
+        assert!(!set.contains(&1));
+
+        set.insert(7);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [&7]);
+    }
+}
@@ -60,6 +60,10 @@ impl ToKey for Keccak256 {
 }
 
 /// Identity hash which just prefixes all of the serializes bytes and uses it as the key.
+///
+/// This avoids the cost of hashing, but the length of the storage key grows with the length of
+/// the serialized key, and two collections whose prefixes overlap (e.g. `b"m"` and `b"ma"`) can
+/// store values under the same storage key.
 pub enum Identity {}
 
 impl ToKey for Identity {
@@ -1,19 +1,18 @@
-use borsh::{BorshDeserialize, BorshSerialize};
-
 use super::Lazy;
+use crate::store::serialization::{Deserialization, Serialization};
 
-impl<T> Drop for Lazy<T>
+impl<T, E> Drop for Lazy<T, E>
 where
-    T: BorshSerialize,
+    E: Serialization<T>,
 {
     fn drop(&mut self) {
         self.flush()
     }
 }
 
-impl<T> core::ops::Deref for Lazy<T>
+impl<T, E> core::ops::Deref for Lazy<T, E>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     type Target = T;
 
@@ -22,29 +21,36 @@ where
     }
 }
 
-impl<T> core::ops::DerefMut for Lazy<T>
+impl<T, E> core::ops::DerefMut for Lazy<T, E>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     fn deref_mut(&mut self) -> &mut Self::Target {
         Self::get_mut(self)
     }
 }
 
-impl<T> core::cmp::PartialEq for Lazy<T>
+impl<T, E> core::cmp::PartialEq for Lazy<T, E>
 where
-    T: PartialEq + BorshSerialize + BorshDeserialize,
+    T: PartialEq,
+    E: Deserialization<T>,
 {
     fn eq(&self, other: &Self) -> bool {
         PartialEq::eq(self.get(), other.get())
     }
 }
 
-impl<T> core::cmp::Eq for Lazy<T> where T: Eq + BorshSerialize + BorshDeserialize {}
+impl<T, E> core::cmp::Eq for Lazy<T, E>
+where
+    T: Eq,
+    E: Deserialization<T>,
+{
+}
 
-impl<T> core::cmp::PartialOrd for Lazy<T>
+impl<T, E> core::cmp::PartialOrd for Lazy<T, E>
 where
-    T: PartialOrd + BorshSerialize + BorshDeserialize,
+    T: PartialOrd,
+    E: Deserialization<T>,
 {
     fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         PartialOrd::partial_cmp(self.get(), other.get())
@@ -63,36 +69,38 @@ where
     }
 }
 
-impl<T> core::cmp::Ord for Lazy<T>
+impl<T, E> core::cmp::Ord for Lazy<T, E>
 where
-    T: core::cmp::Ord + BorshSerialize + BorshDeserialize,
+    T: core::cmp::Ord,
+    E: Deserialization<T>,
 {
     fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         Ord::cmp(self.get(), other.get())
     }
 }
 
-impl<T> core::convert::AsRef<T> for Lazy<T>
+impl<T, E> core::convert::AsRef<T> for Lazy<T, E>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     fn as_ref(&self) -> &T {
         Self::get(self)
     }
 }
 
-impl<T> core::convert::AsMut<T> for Lazy<T>
+impl<T, E> core::convert::AsMut<T> for Lazy<T, E>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     fn as_mut(&mut self) -> &mut T {
         Self::get_mut(self)
     }
 }
 
-impl<T> std::fmt::Debug for Lazy<T>
+impl<T, E> std::fmt::Debug for Lazy<T, E>
 where
-    T: std::fmt::Debug + BorshSerialize + BorshDeserialize,
+    T: std::fmt::Debug,
+    E: Deserialization<T>,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if cfg!(feature = "expensive-debug") {
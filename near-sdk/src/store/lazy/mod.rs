@@ -6,16 +6,17 @@
 
 mod impls;
 
+use std::marker::PhantomData;
+
 use borsh::{BorshDeserialize, BorshSerialize};
 use once_cell::unsync::OnceCell;
 
 use crate::env;
+use crate::store::serialization::{Borsh, Deserialization, Serialization};
 use crate::store::ERR_INCONSISTENT_STATE;
 use crate::utils::{CacheEntry, EntryState};
 use crate::IntoStorageKey;
 
-const ERR_VALUE_SERIALIZATION: &str = "Cannot serialize value with Borsh";
-const ERR_VALUE_DESERIALIZATION: &str = "Cannot deserialize value with Borsh";
 const ERR_NOT_FOUND: &str = "No value found for the given key";
 
 fn expect_key_exists<T>(val: Option<T>) -> T {
@@ -26,22 +27,21 @@ fn expect_consistent_state<T>(val: Option<T>) -> T {
     val.unwrap_or_else(|| env::panic_str(ERR_INCONSISTENT_STATE))
 }
 
-pub(crate) fn load_and_deserialize<T>(key: &[u8]) -> CacheEntry<T>
+pub(crate) fn load_and_deserialize<T, E>(key: &[u8]) -> CacheEntry<T>
 where
-    T: BorshDeserialize,
+    E: Deserialization<T>,
 {
     let bytes = expect_key_exists(env::storage_read(key));
-    let val =
-        T::try_from_slice(&bytes).unwrap_or_else(|_| env::panic_str(ERR_VALUE_DESERIALIZATION));
-    CacheEntry::new_cached(Some(val))
+    CacheEntry::new_cached(Some(E::deserialize(&bytes)))
 }
 
-pub(crate) fn serialize_and_store<T>(key: &[u8], value: &T)
+pub(crate) fn serialize_and_store<T, E>(key: &[u8], value: &T)
 where
-    T: BorshSerialize,
+    E: Serialization<T>,
 {
-    let serialized = value.try_to_vec().unwrap_or_else(|_| env::panic_str(ERR_VALUE_SERIALIZATION));
-    env::storage_write(key, &serialized);
+    let mut buf = Vec::new();
+    E::serialize(value, &mut buf);
+    env::storage_write(key, &buf);
 }
 
 /// An persistent lazily loaded value, that stores a value in the storage.
@@ -49,6 +49,10 @@ where
 /// This will only write to the underlying store if the value has changed, and will only read the
 /// existing value from storage once.
 ///
+/// The value is serialized with [`Borsh`] by default. Another format, such as
+/// [`Json`](crate::store::serialization::Json), can be selected with the `E` type parameter and
+/// [`Lazy::with_serialization`].
+///
 /// # Examples
 /// ```
 /// use near_sdk::store::Lazy;
@@ -60,15 +64,18 @@ where
 /// assert_eq!(a.get(), "new string");
 /// ```
 #[derive(BorshSerialize, BorshDeserialize)]
-pub struct Lazy<T>
+pub struct Lazy<T, E = Borsh>
 where
-    T: BorshSerialize,
+    E: Serialization<T>,
 {
     /// Key bytes to index the contract's storage.
     storage_key: Box<[u8]>,
     #[borsh_skip]
     /// Cached value which is lazily loaded and deserialized from storage.
     cache: OnceCell<CacheEntry<T>>,
+
+    #[borsh_skip]
+    format: PhantomData<E>,
 }
 
 impl<T> Lazy<T>
@@ -81,12 +88,37 @@ where
     /// This prefix can be anything that implements [`IntoStorageKey`]. The prefix is used when
     /// storing and looking up values in storage to ensure no collisions with other collections.
     pub fn new<S>(key: S, value: T) -> Self
+    where
+        S: IntoStorageKey,
+    {
+        Self::with_serialization(key, value)
+    }
+}
+
+impl<T, E> Lazy<T, E>
+where
+    E: Serialization<T>,
+{
+    /// Initializes new lazily loaded value with a given storage prefix and the value to initialize
+    /// it with, which is serialized with the format `E`.
+    ///
+    /// # Examples
+    /// ```
+    /// use near_sdk::store::serialization::Json;
+    /// use near_sdk::store::Lazy;
+    ///
+    /// let mut a: Lazy<String, Json> = Lazy::with_serialization(b"a", "value".to_owned());
+    /// a.set("new value".to_owned());
+    /// assert_eq!(a.get(), "new value");
+    /// ```
+    pub fn with_serialization<S>(key: S, value: T) -> Self
     where
         S: IntoStorageKey,
     {
         Self {
             storage_key: key.into_storage_key().into_boxed_slice(),
             cache: OnceCell::from(CacheEntry::new_modified(Some(value))),
+            format: PhantomData,
         }
     }
 
@@ -101,6 +133,30 @@ where
         }
     }
 
+    /// Returns `true` if the value has been modified since it was loaded or last flushed, meaning
+    /// it will be written to storage on [`flush`](Self::flush) or when dropped.
+    ///
+    /// # Examples
+    /// ```
+    /// use near_sdk::store::Lazy;
+    ///
+    /// let mut a = Lazy::new(b"a", 8u32);
+    /// assert!(a.is_dirty());
+    ///
+    /// a.flush();
+    /// assert!(!a.is_dirty());
+    ///
+    /// // Immutable access does not modify the value.
+    /// assert_eq!(a.get(), &8);
+    /// assert!(!a.is_dirty());
+    ///
+    /// *a.get_mut() += 1;
+    /// assert!(a.is_dirty());
+    /// ```
+    pub fn is_dirty(&self) -> bool {
+        matches!(self.cache.get(), Some(v) if v.is_modified())
+    }
+
     /// Writes any changes to the value to storage. This will automatically be done when the
     /// value is dropped through [`Drop`] so this should only be used when the changes need to be
     /// reflected in the underlying storage before then.
@@ -109,7 +165,7 @@ where
             if v.is_modified() {
                 // Value was modified, serialize and put the serialized bytes in storage.
                 let value = expect_consistent_state(v.value().as_ref());
-                serialize_and_store(&self.storage_key, value);
+                serialize_and_store::<T, E>(&self.storage_key, value);
 
                 // Replaces cache entry state to cached because the value in memory matches the
                 // stored value. This avoids writing the same value twice.
@@ -119,9 +175,9 @@ where
     }
 }
 
-impl<T> Lazy<T>
+impl<T, E> Lazy<T, E>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     /// Returns a reference to the lazily loaded storage value.
     /// The load from storage only happens once, and if the value is already cached, it will not
@@ -129,18 +185,21 @@ where
     ///
     /// This function will panic if the cache is not loaded and the value at the key does not exist.
     pub fn get(&self) -> &T {
-        let entry = self.cache.get_or_init(|| load_and_deserialize(&self.storage_key));
+        let entry = self.cache.get_or_init(|| load_and_deserialize::<T, E>(&self.storage_key));
 
         expect_consistent_state(entry.value().as_ref())
     }
 
-    /// Returns a reference to the lazily loaded storage value.
+    /// Returns a mutable reference to the lazily loaded storage value.
     /// The load from storage only happens once, and if the value is already cached, it will not
     /// be reloaded.
     ///
+    /// Borrowing the value mutably marks it as modified, so it will be written to storage on
+    /// [`flush`](Self::flush) or when dropped.
+    ///
     /// This function will panic if the cache is not loaded and the value at the key does not exist.
     pub fn get_mut(&mut self) -> &mut T {
-        self.cache.get_or_init(|| load_and_deserialize(&self.storage_key));
+        self.cache.get_or_init(|| load_and_deserialize::<T, E>(&self.storage_key));
         let entry = self.cache.get_mut().unwrap_or_else(|| env::abort());
 
         expect_consistent_state(entry.value_mut().as_mut())
@@ -183,6 +242,53 @@ mod tests {
         assert_eq!(lazy_loaded, b);
     }
 
+    #[test]
+    pub fn test_json_serialization() {
+        use crate::store::serialization::Json;
+
+        let mut a: Lazy<(u8, String), Json> = Lazy::with_serialization(b"j", (1, "a".to_string()));
+        a.flush();
+        assert_eq!(env::storage_read(b"j").unwrap(), br#"[1,"a"]"#);
+
+        let a = Lazy::<(u8, String), Json>::try_from_slice(&a.try_to_vec().unwrap()).unwrap();
+        assert_eq!(a.get(), &(1, "a".to_string()));
+    }
+
+    #[test]
+    pub fn test_dirty_tracking() {
+        let mut a = Lazy::new(b"d", 8u32);
+        assert!(a.is_dirty());
+        a.flush();
+        assert!(!a.is_dirty());
+        a.set(9);
+        assert!(a.is_dirty());
+        a.flush();
+
+        // Loading the value lazily and reading it does not mark it as modified.
+        let serialized = borsh::to_vec(&a).unwrap();
+        drop(a);
+        let mut a = Lazy::<u32>::try_from_slice(&serialized).unwrap();
+        assert!(!a.is_dirty());
+        assert_eq!(a.get(), &9);
+        assert_eq!(*a, 9);
+        assert!(!a.is_dirty());
+
+        // Nothing is written on flush or drop, so changes to storage made in the meantime are
+        // preserved.
+        env::storage_write(b"d", &10u32.try_to_vec().unwrap());
+        a.flush();
+        assert_eq!(u32::try_from_slice(&env::storage_read(b"d").unwrap()).unwrap(), 10);
+        drop(a);
+        assert_eq!(u32::try_from_slice(&env::storage_read(b"d").unwrap()).unwrap(), 10);
+
+        // A mutable borrow marks the value as modified, and it is written on drop.
+        let mut a = Lazy::<u32>::try_from_slice(&serialized).unwrap();
+        *a.get_mut() += 1;
+        assert!(a.is_dirty());
+        drop(a);
+        assert_eq!(u32::try_from_slice(&env::storage_read(b"d").unwrap()).unwrap(), 11);
+    }
+
     #[test]
     pub fn test_debug() {
         let mut lazy = Lazy::new(b"m", 8u8);
@@ -1,19 +1,18 @@
-use borsh::{BorshDeserialize, BorshSerialize};
-
 use super::LazyOption;
+use crate::store::serialization::{Deserialization, Serialization};
 
-impl<T> Drop for LazyOption<T>
+impl<T, E> Drop for LazyOption<T, E>
 where
-    T: BorshSerialize,
+    E: Serialization<T>,
 {
     fn drop(&mut self) {
         self.flush()
     }
 }
 
-impl<T> core::ops::Deref for LazyOption<T>
+impl<T, E> core::ops::Deref for LazyOption<T, E>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     type Target = Option<T>;
 
@@ -22,18 +21,19 @@ where
     }
 }
 
-impl<T> core::ops::DerefMut for LazyOption<T>
+impl<T, E> core::ops::DerefMut for LazyOption<T, E>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     fn deref_mut(&mut self) -> &mut Self::Target {
         Self::get_mut(self)
     }
 }
 
-impl<T> std::fmt::Debug for LazyOption<T>
+impl<T, E> std::fmt::Debug for LazyOption<T, E>
 where
-    T: std::fmt::Debug + BorshSerialize + BorshDeserialize,
+    T: std::fmt::Debug,
+    E: Deserialization<T>,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if cfg!(feature = "expensive-debug") {
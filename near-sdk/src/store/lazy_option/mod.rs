@@ -1,13 +1,25 @@
 mod impls;
 
+use std::marker::PhantomData;
+
 use borsh::{BorshDeserialize, BorshSerialize};
 use once_cell::unsync::OnceCell;
 
 use crate::env;
-use crate::store::lazy::{load_and_deserialize, serialize_and_store};
+use crate::store::lazy::serialize_and_store;
+use crate::store::serialization::{Borsh, Deserialization, Serialization};
 use crate::utils::{CacheEntry, EntryState};
 use crate::IntoStorageKey;
 
+/// Loads the value stored under `key`, caching `None` if there is no value in storage.
+fn load_and_deserialize<T, E>(key: &[u8]) -> CacheEntry<T>
+where
+    E: Deserialization<T>,
+{
+    let value = env::storage_read(key).map(|bytes| E::deserialize(&bytes));
+    CacheEntry::new_cached(value)
+}
+
 /// An persistent lazily loaded option, that stores a `value` in the storage when `Some(value)`
 /// is set, and not when `None` is set. `LazyOption` also [`Deref`]s into [`Option`] so we get
 /// all its APIs for free.
@@ -15,6 +27,10 @@ use crate::IntoStorageKey;
 /// This will only write to the underlying store if the value has changed, and will only read the
 /// existing value from storage once.
 ///
+/// The value is serialized with [`Borsh`] by default. Another format, such as
+/// [`Json`](crate::store::serialization::Json), can be selected with the `E` type parameter and
+/// [`LazyOption::with_serialization`].
+///
 /// # Examples
 /// ```
 /// use near_sdk::store::LazyOption;
@@ -32,9 +48,9 @@ use crate::IntoStorageKey;
 /// ```
 /// [`Deref`]: std::ops::Deref
 #[derive(BorshSerialize, BorshDeserialize)]
-pub struct LazyOption<T>
+pub struct LazyOption<T, E = Borsh>
 where
-    T: BorshSerialize,
+    E: Serialization<T>,
 {
     /// Key bytes to index the contract's storage.
     prefix: Box<[u8]>,
@@ -42,6 +58,9 @@ where
     /// Cached value which is lazily loaded and deserialized from storage.
     #[borsh_skip]
     cache: OnceCell<CacheEntry<T>>,
+
+    #[borsh_skip]
+    format: PhantomData<E>,
 }
 
 impl<T> LazyOption<T>
@@ -53,6 +72,30 @@ where
     /// This prefix can be anything that implements [`IntoStorageKey`]. The prefix is used when
     /// storing and looking up values in storage to ensure no collisions with other collections.
     pub fn new<S>(prefix: S, value: Option<T>) -> Self
+    where
+        S: IntoStorageKey,
+    {
+        Self::with_serialization(prefix, value)
+    }
+}
+
+impl<T, E> LazyOption<T, E>
+where
+    E: Serialization<T>,
+{
+    /// Create a new lazy option with the given `prefix` and the initial value, which is
+    /// serialized with the format `E`.
+    ///
+    /// # Examples
+    /// ```
+    /// use near_sdk::store::serialization::Json;
+    /// use near_sdk::store::LazyOption;
+    ///
+    /// let mut a: LazyOption<String, Json> = LazyOption::with_serialization(b"a", None);
+    /// a.set(Some("value".to_owned()));
+    /// assert_eq!(a.get(), &Some("value".to_owned()));
+    /// ```
+    pub fn with_serialization<S>(prefix: S, value: Option<T>) -> Self
     where
         S: IntoStorageKey,
     {
@@ -61,7 +104,11 @@ where
             None => CacheEntry::new_cached(None),
         };
 
-        Self { prefix: prefix.into_storage_key().into_boxed_slice(), cache: OnceCell::from(cache) }
+        Self {
+            prefix: prefix.into_storage_key().into_boxed_slice(),
+            cache: OnceCell::from(cache),
+            format: PhantomData,
+        }
     }
 
     /// Updates the value with a new value. This does not load the current value from storage.
@@ -86,7 +133,7 @@ where
             }
 
             match v.value().as_ref() {
-                Some(value) => serialize_and_store(&self.prefix, value),
+                Some(value) => serialize_and_store::<T, E>(&self.prefix, value),
                 None => {
                     env::storage_remove(&self.prefix);
                 }
@@ -99,15 +146,15 @@ where
     }
 }
 
-impl<T> LazyOption<T>
+impl<T, E> LazyOption<T, E>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     /// Returns a reference to the lazily loaded optional.
     /// The load from storage only happens once, and if the value is already cached, it will not
     /// be reloaded.
     pub fn get(&self) -> &Option<T> {
-        let entry = self.cache.get_or_init(|| load_and_deserialize(&self.prefix));
+        let entry = self.cache.get_or_init(|| load_and_deserialize::<T, E>(&self.prefix));
         entry.value()
     }
 
@@ -115,10 +162,47 @@ where
     /// The load from storage only happens once, and if the value is already cached, it will not
     /// be reloaded.
     pub fn get_mut(&mut self) -> &mut Option<T> {
-        self.cache.get_or_init(|| load_and_deserialize(&self.prefix));
+        self.cache.get_or_init(|| load_and_deserialize::<T, E>(&self.prefix));
         let entry = self.cache.get_mut().unwrap_or_else(|| env::abort());
         entry.value_mut()
     }
+
+    /// Sets the value to `Some(value)`, returning the previous value, which is loaded from
+    /// storage if it isn't cached yet. The new value is written to storage when flushed.
+    ///
+    /// # Examples
+    /// ```
+    /// use near_sdk::store::LazyOption;
+    ///
+    /// let mut a = LazyOption::new(b"a", None);
+    /// assert_eq!(a.replace(1u8), None);
+    /// assert_eq!(a.replace(2), Some(1));
+    /// assert_eq!(a.get(), &Some(2));
+    /// ```
+    pub fn replace(&mut self, value: T) -> Option<T> {
+        self.get_mut().replace(value)
+    }
+
+    /// Takes the value out, leaving `None` in its place, and returns it. The previous value is
+    /// loaded from storage if it isn't cached yet, and removed from storage when flushed.
+    ///
+    /// If there is no value, nothing is modified, so flushing doesn't remove it from storage again.
+    ///
+    /// # Examples
+    /// ```
+    /// use near_sdk::store::LazyOption;
+    ///
+    /// let mut a = LazyOption::new(b"a", Some(1u8));
+    /// assert_eq!(a.take(), Some(1));
+    /// assert_eq!(a.take(), None);
+    /// assert!(a.is_none());
+    /// ```
+    pub fn take(&mut self) -> Option<T> {
+        if self.get().is_none() {
+            return None;
+        }
+        self.get_mut().take()
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -162,6 +246,48 @@ mod tests {
         assert!(!env::storage_has_key(b"a"));
     }
 
+    #[test]
+    pub fn test_json_serialization() {
+        use crate::store::serialization::Json;
+
+        let mut a: LazyOption<(u8, String), Json> =
+            LazyOption::with_serialization(b"j", Some((1, "a".to_string())));
+        a.flush();
+        assert_eq!(env::storage_read(b"j").unwrap(), br#"[1,"a"]"#);
+
+        let a = LazyOption::<(u8, String), Json>::try_from_slice(&a.try_to_vec().unwrap()).unwrap();
+        assert_eq!(a.get(), &Some((1, "a".to_string())));
+    }
+
+    #[test]
+    pub fn test_replace_take() {
+        let load = || LazyOption::<u32>::try_from_slice(&borsh::to_vec(&b"a".to_vec()).unwrap());
+        let mut a = LazyOption::new(b"a", Some(1u32));
+        a.flush();
+
+        // The previous value is loaded from storage.
+        let mut a = load().unwrap();
+        assert_eq!(a.replace(2), Some(1));
+        assert_eq!(a.get(), &Some(2));
+        drop(a);
+        assert_eq!(u32::try_from_slice(&env::storage_read(b"a").unwrap()).unwrap(), 2);
+
+        let mut a = load().unwrap();
+        assert_eq!(a.take(), Some(2));
+        assert!(a.is_none());
+        drop(a);
+        assert!(!env::storage_has_key(b"a"));
+
+        // Taking nothing doesn't modify the value.
+        let mut a = load().unwrap();
+        assert_eq!(a.take(), None);
+        assert!(!a.cache.get().unwrap().is_modified());
+        assert_eq!(a.replace(3), None);
+        assert!(a.cache.get().unwrap().is_modified());
+        drop(a);
+        assert_eq!(u32::try_from_slice(&env::storage_read(b"a").unwrap()).unwrap(), 3);
+    }
+
     #[test]
     pub fn test_debug() {
         let mut lazy_option = LazyOption::new(b"m", None);
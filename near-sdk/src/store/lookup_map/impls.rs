@@ -1,14 +1,15 @@
 use std::borrow::Borrow;
 
-use borsh::{BorshDeserialize, BorshSerialize};
+use borsh::BorshSerialize;
 
 use super::{LookupMap, ToKey, ERR_NOT_EXIST};
 use crate::env;
+use crate::store::serialization::{Deserialization, Serialization};
 
-impl<K, V, H> Extend<(K, V)> for LookupMap<K, V, H>
+impl<K, V, H, E> Extend<(K, V)> for LookupMap<K, V, H, E>
 where
     K: BorshSerialize + Ord,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
     fn extend<I>(&mut self, iter: I)
@@ -21,10 +22,10 @@ where
     }
 }
 
-impl<K, V, H, Q: ?Sized> core::ops::Index<&Q> for LookupMap<K, V, H>
+impl<K, V, H, E, Q: ?Sized> core::ops::Index<&Q> for LookupMap<K, V, H, E>
 where
     K: BorshSerialize + Ord + Borrow<Q>,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 
     Q: BorshSerialize + ToOwned<Owned = K>,
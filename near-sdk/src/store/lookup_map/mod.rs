@@ -3,10 +3,12 @@ mod impls;
 
 use std::borrow::Borrow;
 use std::fmt;
+use std::marker::PhantomData;
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use once_cell::unsync::OnceCell;
 
+use super::serialization::{Borsh, Deserialization, Serialization};
 use super::ERR_NOT_EXIST;
 use crate::store::key::{Identity, ToKey};
 use crate::utils::{EntryState, StableMap};
@@ -14,9 +16,6 @@ use crate::{env, CacheEntry, IntoStorageKey};
 
 pub use entry::{Entry, OccupiedEntry, VacantEntry};
 
-const ERR_ELEMENT_DESERIALIZATION: &str = "Cannot deserialize element";
-const ERR_ELEMENT_SERIALIZATION: &str = "Cannot serialize element";
-
 /// A non-iterable, lazily loaded storage map that stores its content directly on the storage trie.
 ///
 /// This map stores the values under a hash of the map's `prefix` and [`BorshSerialize`] of the key
@@ -28,6 +27,10 @@ const ERR_ELEMENT_SERIALIZATION: &str = "Cannot serialize element";
 /// To use a custom function, use [`with_hasher`]. Alternative builtin hash functions can be found
 /// at [`near_sdk::store::key`](crate::store::key).
 ///
+/// Values are serialized with [`Borsh`] by default. Another format, such as
+/// [`Json`](crate::store::serialization::Json), can be selected with the `E` type parameter and
+/// [`LookupMap::with_serialization`].
+///
 /// # Examples
 /// ```
 /// use near_sdk::store::LookupMap;
@@ -76,10 +79,10 @@ const ERR_ELEMENT_SERIALIZATION: &str = "Cannot serialize element";
 ///
 /// [`with_hasher`]: Self::with_hasher
 #[derive(BorshSerialize, BorshDeserialize)]
-pub struct LookupMap<K, V, H = Identity>
+pub struct LookupMap<K, V, H = Identity, E = Borsh>
 where
     K: BorshSerialize + Ord,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
     prefix: Box<[u8]>,
@@ -88,6 +91,8 @@ where
     /// invalidated.
     #[borsh_skip]
     cache: StableMap<K, EntryAndHash<V, H::KeyType>>,
+    #[borsh_skip]
+    format: PhantomData<E>,
 }
 
 struct EntryAndHash<V, T> {
@@ -101,10 +106,10 @@ impl<V, T> Default for EntryAndHash<V, T> {
     }
 }
 
-impl<K, V, H> Drop for LookupMap<K, V, H>
+impl<K, V, H, E> Drop for LookupMap<K, V, H, E>
 where
     K: BorshSerialize + Ord,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
     fn drop(&mut self) {
@@ -112,10 +117,10 @@ where
     }
 }
 
-impl<K, V, H> fmt::Debug for LookupMap<K, V, H>
+impl<K, V, H, E> fmt::Debug for LookupMap<K, V, H, E>
 where
     K: BorshSerialize + Ord,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -157,6 +162,15 @@ where
 {
     /// Initialize a [`LookupMap`] with a custom hash function.
     ///
+    /// With [`Identity`], the storage key is the prefix followed by the serialized key, which
+    /// avoids hashing and is cheapest for small or fixed-length keys, such as 32-byte token IDs.
+    /// Long variable-length keys produce equally long storage keys, and a prefix that is itself a
+    /// prefix of another collection's (e.g. `b"m"` and `b"ma"`) can make the serialized keys of the
+    /// two collections collide. Hashing with [`Sha256`](crate::store::key::Sha256) or
+    /// [`Keccak256`](crate::store::key::Keccak256) keeps every storage key 32 bytes long, but
+    /// still requires prefixes that do not overlap. Changing the hasher of an existing map
+    /// changes all of its storage keys, so previously stored values won't be found.
+    ///
     /// # Example
     /// ```
     /// use near_sdk::store::{LookupMap, key::Keccak256};
@@ -167,7 +181,41 @@ where
     where
         S: IntoStorageKey,
     {
-        Self { prefix: prefix.into_storage_key().into_boxed_slice(), cache: Default::default() }
+        Self::with_serialization(prefix)
+    }
+}
+
+impl<K, V, H, E> LookupMap<K, V, H, E>
+where
+    K: BorshSerialize + Ord,
+    E: Serialization<V>,
+    H: ToKey,
+{
+    /// Initialize a [`LookupMap`] with a custom hash function which serializes its values with the
+    /// format `E`.
+    ///
+    /// See [`LookupMap::new`] and [`LookupMap::with_hasher`] for creating a map with the default
+    /// [`Borsh`] format.
+    ///
+    /// # Example
+    /// ```
+    /// use near_sdk::store::key::Identity;
+    /// use near_sdk::store::serialization::Json;
+    /// use near_sdk::store::LookupMap;
+    ///
+    /// let mut map: LookupMap<u32, String, Identity, Json> = LookupMap::with_serialization(b"m");
+    /// map.insert(1, "a".to_string());
+    /// assert_eq!(map[&1], "a");
+    /// ```
+    pub fn with_serialization<S>(prefix: S) -> Self
+    where
+        S: IntoStorageKey,
+    {
+        Self {
+            prefix: prefix.into_storage_key().into_boxed_slice(),
+            cache: Default::default(),
+            format: PhantomData,
+        }
     }
 
     /// Overwrites the current value for the given key.
@@ -201,14 +249,14 @@ where
     }
 }
 
-impl<K, V, H> LookupMap<K, V, H>
+impl<K, V, H, E> LookupMap<K, V, H, E>
 where
     K: BorshSerialize + Ord,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     fn deserialize_element(bytes: &[u8]) -> V {
-        V::try_from_slice(bytes).unwrap_or_else(|_| env::panic_str(ERR_ELEMENT_DESERIALIZATION))
+        E::deserialize(bytes)
     }
 
     fn load_element<Q: ?Sized>(prefix: &[u8], key: &Q) -> (H::KeyType, Option<V>)
@@ -415,12 +463,36 @@ where
             Entry::Vacant(VacantEntry { key, entry })
         }
     }
+
+    /// Returns a mutable reference to the value corresponding to the key, inserting the result
+    /// of `f` first if the map has no value for it. The value is only read from storage once,
+    /// and `f` is only called if it is missing.
+    ///
+    /// This is a shorthand for [`entry(key).or_insert_with(f)`](Entry::or_insert_with).
+    ///
+    /// # Example
+    /// ```
+    /// use near_sdk::store::LookupMap;
+    ///
+    /// let mut balances: LookupMap<String, u128> = LookupMap::new(b"m");
+    ///
+    /// *balances.get_or_insert_with("alice".to_string(), || 100) += 10;
+    /// *balances.get_or_insert_with("alice".to_string(), || 100) += 10;
+    ///
+    /// assert_eq!(balances["alice"], 120);
+    /// ```
+    pub fn get_or_insert_with<F: FnOnce() -> V>(&mut self, key: K, f: F) -> &mut V
+    where
+        K: Clone,
+    {
+        self.entry(key).or_insert_with(f)
+    }
 }
 
-impl<K, V, H> LookupMap<K, V, H>
+impl<K, V, H, E> LookupMap<K, V, H, E>
 where
     K: BorshSerialize + Ord,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
     /// Flushes the intermediate values of the map before this is called when the structure is
@@ -439,8 +511,7 @@ where
                     match val.value().as_ref() {
                         Some(modified) => {
                             buf.clear();
-                            BorshSerialize::serialize(modified, &mut buf)
-                                .unwrap_or_else(|_| env::panic_str(ERR_ELEMENT_SERIALIZATION));
+                            E::serialize(modified, &mut buf);
                             env::storage_write(key.as_ref(), &buf);
                         }
                         None => {
@@ -464,6 +535,7 @@ mod tests {
     use super::LookupMap;
     use crate::env;
     use crate::store::key::{Keccak256, ToKey};
+    use crate::test_utils::storage_reads;
     use crate::test_utils::test_env::setup_free;
     use arbitrary::{Arbitrary, Unstructured};
     use rand::seq::SliceRandom;
@@ -586,6 +658,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn json_serialization() {
+        use crate::store::key::Identity;
+        use crate::store::serialization::Json;
+        use borsh::{BorshDeserialize, BorshSerialize};
+
+        let mut map: LookupMap<u8, (u8, String), Identity, Json> =
+            LookupMap::with_serialization(b"j");
+        map.insert(1, (1, "a".to_string()));
+        map.flush();
+        assert_eq!(env::storage_read(b"j\x01").unwrap(), br#"[1,"a"]"#);
+
+        let map = LookupMap::<u8, (u8, String), Identity, Json>::try_from_slice(
+            &map.try_to_vec().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(map[&1], (1, "a".to_string()));
+    }
+
     #[test]
     fn size_of_map() {
         assert_eq!(core::mem::size_of::<LookupMap<u8, u8>>(), 48);
@@ -610,6 +701,67 @@ mod tests {
         assert_eq!(lm1.get(&8), Some("New".to_string()));
     }
 
+    #[test]
+    fn hashers_use_different_storage_keys() {
+        use crate::store::key::{Identity, Sha256};
+
+        let token_id = [7u8; 32];
+        let mut identity = LookupMap::<[u8; 32], u64, Identity>::with_hasher(b"m");
+        let mut hashed = LookupMap::<[u8; 32], u64, Sha256>::with_hasher(b"m");
+        identity.insert(token_id, 1);
+        hashed.insert(token_id, 2);
+        identity.flush();
+        hashed.flush();
+
+        let identity_key = Identity::to_key(b"m", &token_id, &mut Vec::new());
+        let hashed_key = Sha256::to_key(b"m", &token_id, &mut Vec::new());
+        assert_eq!(identity_key, [&b"m"[..], &token_id].concat());
+        assert_eq!(hashed_key, env::sha256_array(&identity_key));
+        assert!(env::storage_has_key(&identity_key));
+        assert!(env::storage_has_key(&hashed_key));
+
+        let identity = LookupMap::<[u8; 32], u64, Identity>::with_hasher(b"m");
+        let hashed = LookupMap::<[u8; 32], u64, Sha256>::with_hasher(b"m");
+        assert_eq!(identity.get(&token_id), Some(&1));
+        assert_eq!(hashed.get(&token_id), Some(&2));
+    }
+
+    #[test]
+    fn get_or_insert_with() {
+        let mut map = LookupMap::new(b"m");
+        map.insert(1u32, 10u64);
+        drop(map);
+
+        let mut map: LookupMap<u32, u64> = LookupMap::new(b"m");
+        let mut calls = 0;
+        let initial_reads = storage_reads();
+
+        // Hit: the closure is not called.
+        *map.get_or_insert_with(1, || {
+            calls += 1;
+            0
+        }) += 1;
+        assert_eq!(calls, 0);
+        assert_eq!(storage_reads() - initial_reads, 1);
+
+        // Miss: a single read, then the closure is called once.
+        *map.get_or_insert_with(2, || {
+            calls += 1;
+            20
+        }) += 1;
+        assert_eq!(calls, 1);
+        assert_eq!(storage_reads() - initial_reads, 2);
+
+        // The inserted value is cached.
+        assert_eq!(*map.get_or_insert_with(2, || unreachable!()), 21);
+        assert_eq!(storage_reads() - initial_reads, 2);
+        drop(map);
+
+        let map = LookupMap::<u32, u64>::new(b"m");
+        assert_eq!(map.get(&1), Some(&11));
+        assert_eq!(map.get(&2), Some(&21));
+    }
+
     #[test]
     fn test_extend() {
         let mut map = LookupMap::new(b"m");
@@ -658,6 +810,51 @@ mod tests {
         assert_eq!(dup_map[&5], 8);
     }
 
+    #[test]
+    fn entry_single_storage_read() {
+        let mut map = LookupMap::new(b"m");
+        map.insert(1u8, 1u8);
+        map.flush();
+
+        // Gas used for loading a single existing and non-existing value from storage.
+        let fresh = LookupMap::<u8, u8>::new(b"m");
+        let start = env::used_gas();
+        assert_eq!(fresh.get(&1), Some(&1));
+        assert_eq!(fresh.get(&2), None);
+        let two_reads = env::used_gas() - start;
+
+        let mut fresh = LookupMap::<u8, u8>::new(b"m");
+        let start = env::used_gas();
+        *fresh.entry(1).and_modify(|v| *v += 1).or_insert(0) += 1;
+        *fresh.entry(2).and_modify(|v| *v += 1).or_insert(5) += 1;
+        // Only one read per key, follow up operations work on the cached value.
+        assert_eq!(env::used_gas() - start, two_reads);
+        assert_eq!(fresh[&1], 3);
+        assert_eq!(fresh[&2], 6);
+
+        drop(fresh);
+        let reloaded = LookupMap::<u8, u8>::new(b"m");
+        assert_eq!(reloaded[&1], 3);
+        assert_eq!(reloaded[&2], 6);
+    }
+
+    #[test]
+    fn entry_matches_std() {
+        let mut map = LookupMap::new(b"m");
+        let mut baseline = HashMap::new();
+        for key in [1u8, 2, 1, 3, 1, 2] {
+            let value = *map.entry(key).and_modify(|v| *v *= 2).or_insert(1u32);
+            let expected = *baseline.entry(key).and_modify(|v| *v *= 2).or_insert(1u32);
+            assert_eq!(value, expected);
+        }
+        assert_eq!(*map.entry(4).or_default(), *baseline.entry(4).or_default());
+        assert_eq!(*map.entry(5).or_insert_with(|| 7), *baseline.entry(5).or_insert_with(|| 7));
+
+        for (key, value) in baseline {
+            assert_eq!(map[&key], value);
+        }
+    }
+
     #[derive(Arbitrary, Debug)]
     enum Op {
         Insert(u8, u8),
@@ -116,6 +116,73 @@ where
         env::storage_has_key(lookup_key.as_ref())
     }
 
+    /// Returns `true` if the set contains all of the given values, stopping at the first value
+    /// that is missing. Returns `true` if `values` is empty.
+    ///
+    /// The values may be any borrowed form of the set's value type, but
+    /// [`BorshSerialize`] on the borrowed form *must* match those for the value type.
+    ///
+    /// # Example
+    /// ```
+    /// use near_sdk::store::LookupSet;
+    ///
+    /// const ADMIN: u8 = 0;
+    /// const MINTER: u8 = 1;
+    /// const BURNER: u8 = 2;
+    ///
+    /// let mut roles: LookupSet<u8> = LookupSet::new(b"r");
+    /// roles.insert(ADMIN);
+    /// roles.insert(MINTER);
+    ///
+    /// assert!(roles.contains_all(&[ADMIN, MINTER]));
+    /// assert!(!roles.contains_all(&[ADMIN, BURNER]));
+    /// ```
+    pub fn contains_all<Q>(&self, values: &[Q]) -> bool
+    where
+        T: Borrow<Q>,
+        Q: BorshSerialize,
+    {
+        let mut buffer = Vec::new();
+        values.iter().all(|value| {
+            buffer.clear();
+            let lookup_key = H::to_key(&self.prefix, value, &mut buffer);
+            env::storage_has_key(lookup_key.as_ref())
+        })
+    }
+
+    /// Returns the number of the given values contained in the set. Duplicate values are
+    /// counted each time they appear in `values`.
+    ///
+    /// The values may be any borrowed form of the set's value type, but
+    /// [`BorshSerialize`] on the borrowed form *must* match those for the value type.
+    ///
+    /// # Example
+    /// ```
+    /// use near_sdk::store::LookupSet;
+    ///
+    /// let mut set: LookupSet<String> = LookupSet::new(b"s");
+    /// set.insert("a".to_string());
+    /// set.insert("b".to_string());
+    ///
+    /// let values = ["a".to_string(), "c".to_string(), "b".to_string()];
+    /// assert_eq!(set.intersection_len(&values), 2);
+    /// ```
+    pub fn intersection_len<Q>(&self, values: &[Q]) -> usize
+    where
+        T: Borrow<Q>,
+        Q: BorshSerialize,
+    {
+        let mut buffer = Vec::new();
+        values
+            .iter()
+            .filter(|value| {
+                buffer.clear();
+                let lookup_key = H::to_key(&self.prefix, *value, &mut buffer);
+                env::storage_has_key(lookup_key.as_ref())
+            })
+            .count()
+    }
+
     /// Adds a value to the set.
     ///
     /// If the set did not have this value present, true is returned.
@@ -189,6 +256,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_contains_all_and_intersection_len() {
+        let mut set = LookupSet::new(b"m");
+        for value in [1u32, 2, 3] {
+            set.insert(value);
+        }
+
+        assert!(set.contains_all::<u32>(&[]));
+        assert_eq!(set.intersection_len::<u32>(&[]), 0);
+
+        // All present
+        assert!(set.contains_all(&[1u32, 2, 3]));
+        assert_eq!(set.intersection_len(&[3u32, 1, 2]), 3);
+
+        // None present
+        assert!(!set.contains_all(&[4u32, 5]));
+        assert_eq!(set.intersection_len(&[4u32, 5]), 0);
+
+        // Partial overlap
+        assert!(!set.contains_all(&[1u32, 4, 2]));
+        assert_eq!(set.intersection_len(&[1u32, 4, 2, 2]), 3);
+    }
+
     #[test]
     fn test_remove_last_reinsert() {
         let mut set = LookupSet::new(b"m");
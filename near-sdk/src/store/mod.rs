@@ -49,6 +49,9 @@
 //! - [`UnorderedSet`]: Analogous to [`std::collections::HashSet`], and is an iterable
 //! version of [`LookupSet`] and persisted to storage.
 //!
+//! - [`IterableSet`]: Like [`UnorderedSet`], but iterates over its elements in insertion
+//! order, which is kept when elements are removed.
+//!
 //! Basic Types:
 //!
 //! - [`Lazy<T>`](Lazy): Lazily loaded type that can be used in place of a type `T`.
@@ -83,6 +86,9 @@ pub use self::unordered_map::UnorderedMap;
 pub mod unordered_set;
 pub use self::unordered_set::UnorderedSet;
 
+pub mod iterable_set;
+pub use self::iterable_set::IterableSet;
+
 #[cfg(feature = "unstable")]
 pub mod tree_map;
 #[cfg(feature = "unstable")]
@@ -97,6 +103,9 @@ pub(crate) use self::free_list::FreeList;
 /// Storage key hash function types and trait to override map hash functions.
 pub mod key;
 
+/// Formats and traits to select how collection values are serialized.
+pub mod serialization;
+
 pub(crate) const ERR_INCONSISTENT_STATE: &str =
     "The collection is an inconsistent state. Did previous smart \
         contract execution terminate unexpectedly?";
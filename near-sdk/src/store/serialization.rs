@@ -0,0 +1,79 @@
+//! Formats used to serialize the values of the collections.
+//!
+//! All collections storing values, [`Vector`](crate::store::Vector),
+//! [`LookupMap`](crate::store::LookupMap), [`UnorderedMap`](crate::store::UnorderedMap),
+//! [`TreeMap`](crate::store::TreeMap), [`IterableSet`](crate::store::IterableSet),
+//! [`Lazy`](crate::store::Lazy) and [`LazyOption`](crate::store::LazyOption), store them with
+//! [`Borsh`](crate::store::serialization::Borsh) by default.
+//! [`Json`](crate::store::serialization::Json) can be selected instead through their format type
+//! parameter, for example `Vector<T, Json>`, which makes the raw storage readable by off-chain
+//! tooling at the cost of more gas and storage. Keys, including the elements of the sets, are
+//! always serialized with Borsh, as they are hashed into storage keys.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::env;
+
+pub(crate) const ERR_ELEMENT_DESERIALIZATION: &str = "Cannot deserialize element";
+const ERR_ELEMENT_SERIALIZATION: &str = "Cannot serialize element";
+
+/// Format used to serialize values of type `T` into storage.
+pub trait Serialization<T> {
+    /// Serializes `value` into `buf`, panicking if the value cannot be serialized.
+    fn serialize(value: &T, buf: &mut Vec<u8>);
+}
+
+/// Format used to deserialize values of type `T` from storage.
+pub trait Deserialization<T>: Serialization<T> {
+    /// Deserializes a value from `bytes`, panicking if the bytes are not a valid value.
+    fn deserialize(bytes: &[u8]) -> T;
+}
+
+/// Serializes values with [`borsh`]. This is the default format of all collections.
+#[derive(Debug)]
+pub enum Borsh {}
+
+/// Serializes values as JSON using [`serde_json`].
+#[derive(Debug)]
+pub enum Json {}
+
+impl<T> Serialization<T> for Borsh
+where
+    T: BorshSerialize,
+{
+    fn serialize(value: &T, buf: &mut Vec<u8>) {
+        BorshSerialize::serialize(value, buf)
+            .unwrap_or_else(|_| env::panic_str(ERR_ELEMENT_SERIALIZATION));
+    }
+}
+
+impl<T> Deserialization<T> for Borsh
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    fn deserialize(bytes: &[u8]) -> T {
+        T::try_from_slice(bytes).unwrap_or_else(|_| env::panic_str(ERR_ELEMENT_DESERIALIZATION))
+    }
+}
+
+impl<T> Serialization<T> for Json
+where
+    T: Serialize,
+{
+    fn serialize(value: &T, buf: &mut Vec<u8>) {
+        serde_json::to_writer(buf, value)
+            .unwrap_or_else(|_| env::panic_str(ERR_ELEMENT_SERIALIZATION));
+    }
+}
+
+impl<T> Deserialization<T> for Json
+where
+    T: Serialize + DeserializeOwned,
+{
+    fn deserialize(bytes: &[u8]) -> T {
+        serde_json::from_slice(bytes)
+            .unwrap_or_else(|_| env::panic_str(ERR_ELEMENT_DESERIALIZATION))
+    }
+}
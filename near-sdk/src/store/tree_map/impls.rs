@@ -3,12 +3,13 @@ use std::borrow::Borrow;
 use borsh::{BorshDeserialize, BorshSerialize};
 
 use crate::env;
+use crate::store::serialization::Deserialization;
 use crate::store::{key::ToKey, TreeMap, ERR_NOT_EXIST};
 
-impl<K, V, H> Extend<(K, V)> for TreeMap<K, V, H>
+impl<K, V, H, E> Extend<(K, V)> for TreeMap<K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     fn extend<I>(&mut self, iter: I)
@@ -21,10 +22,10 @@ where
     }
 }
 
-impl<K, V, H, Q: ?Sized> core::ops::Index<&Q> for TreeMap<K, V, H>
+impl<K, V, H, E, Q: ?Sized> core::ops::Index<&Q> for TreeMap<K, V, H, E>
 where
     K: BorshSerialize + Ord + Clone + Borrow<Q>,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 
     Q: BorshSerialize + ToOwned<Owned = K>,
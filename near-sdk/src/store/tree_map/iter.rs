@@ -7,29 +7,30 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use super::{expect, LookupMap, Tree, TreeMap};
 use crate::store::free_list::FreeListIndex;
 use crate::store::key::ToKey;
+use crate::store::serialization::{Borsh, Deserialization, Serialization};
 
-impl<'a, K, V, H> IntoIterator for &'a TreeMap<K, V, H>
+impl<'a, K, V, H, E> IntoIterator for &'a TreeMap<K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     type Item = (&'a K, &'a V);
-    type IntoIter = Iter<'a, K, V, H>;
+    type IntoIter = Iter<'a, K, V, H, E>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.iter()
     }
 }
 
-impl<'a, K, V, H> IntoIterator for &'a mut TreeMap<K, V, H>
+impl<'a, K, V, H, E> IntoIterator for &'a mut TreeMap<K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     type Item = (&'a K, &'a mut V);
-    type IntoIter = IterMut<'a, K, V, H>;
+    type IntoIter = IterMut<'a, K, V, H, E>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.iter_mut()
@@ -39,31 +40,31 @@ where
 /// An iterator over elements of a [`TreeMap`], in sorted order.
 ///
 /// This `struct` is created by the `iter` method on [`TreeMap`].
-pub struct Iter<'a, K, V, H>
+pub struct Iter<'a, K, V, H, E = Borsh>
 where
     K: BorshSerialize + Ord + BorshDeserialize,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
     keys: Keys<'a, K>,
-    values: &'a LookupMap<K, V, H>,
+    values: &'a LookupMap<K, V, H, E>,
 }
 
-impl<'a, K, V, H> Iter<'a, K, V, H>
+impl<'a, K, V, H, E> Iter<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
-    pub(super) fn new(map: &'a TreeMap<K, V, H>) -> Self {
+    pub(super) fn new(map: &'a TreeMap<K, V, H, E>) -> Self {
         Self { keys: Keys::new(&map.tree), values: &map.values }
     }
 }
 
-impl<'a, K, V, H> Iterator for Iter<'a, K, V, H>
+impl<'a, K, V, H, E> Iterator for Iter<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     type Item = (&'a K, &'a V);
@@ -88,25 +89,25 @@ where
     }
 }
 
-impl<'a, K, V, H> ExactSizeIterator for Iter<'a, K, V, H>
+impl<'a, K, V, H, E> ExactSizeIterator for Iter<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
 }
-impl<'a, K, V, H> FusedIterator for Iter<'a, K, V, H>
+impl<'a, K, V, H, E> FusedIterator for Iter<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
 }
 
-impl<'a, K, V, H> DoubleEndedIterator for Iter<'a, K, V, H>
+impl<'a, K, V, H, E> DoubleEndedIterator for Iter<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
@@ -121,10 +122,10 @@ where
     }
 }
 
-fn get_entry_mut<'a, K, V, H>(map: &mut LookupMap<K, V, H>, key: &'a K) -> (&'a K, &'a mut V)
+fn get_entry_mut<'a, K, V, H, E>(map: &mut LookupMap<K, V, H, E>, key: &'a K) -> (&'a K, &'a mut V)
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     let entry = expect(map.get_mut(key));
@@ -141,33 +142,33 @@ where
 /// A mutable iterator over elements of a [`TreeMap`], in sorted order.
 ///
 /// This `struct` is created by the `iter_mut` method on [`TreeMap`].
-pub struct IterMut<'a, K, V, H>
+pub struct IterMut<'a, K, V, H, E = Borsh>
 where
     K: BorshSerialize + Ord + BorshDeserialize,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
     /// Values iterator which contains empty and filled cells.
     keys: Keys<'a, K>,
     /// Exclusive reference to underlying map to lookup values with `keys`.
-    values: &'a mut LookupMap<K, V, H>,
+    values: &'a mut LookupMap<K, V, H, E>,
 }
 
-impl<'a, K, V, H> IterMut<'a, K, V, H>
+impl<'a, K, V, H, E> IterMut<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
-    pub(super) fn new(map: &'a mut TreeMap<K, V, H>) -> Self {
+    pub(super) fn new(map: &'a mut TreeMap<K, V, H, E>) -> Self {
         Self { keys: Keys::new(&map.tree), values: &mut map.values }
     }
 }
 
-impl<'a, K, V, H> Iterator for IterMut<'a, K, V, H>
+impl<'a, K, V, H, E> Iterator for IterMut<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     type Item = (&'a K, &'a mut V);
@@ -190,25 +191,25 @@ where
     }
 }
 
-impl<'a, K, V, H> ExactSizeIterator for IterMut<'a, K, V, H>
+impl<'a, K, V, H, E> ExactSizeIterator for IterMut<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
 }
-impl<'a, K, V, H> FusedIterator for IterMut<'a, K, V, H>
+impl<'a, K, V, H, E> FusedIterator for IterMut<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
 }
 
-impl<'a, K, V, H> DoubleEndedIterator for IterMut<'a, K, V, H>
+impl<'a, K, V, H, E> DoubleEndedIterator for IterMut<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
@@ -675,30 +676,30 @@ where
 /// An iterator over the values of a [`TreeMap`], in order by key.
 ///
 /// This `struct` is created by the `values` method on [`TreeMap`].
-pub struct Values<'a, K, V, H>
+pub struct Values<'a, K, V, H, E = Borsh>
 where
     K: BorshSerialize + Ord + BorshDeserialize,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
-    inner: Iter<'a, K, V, H>,
+    inner: Iter<'a, K, V, H, E>,
 }
 
-impl<'a, K, V, H> Values<'a, K, V, H>
+impl<'a, K, V, H, E> Values<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
-    pub(super) fn new(map: &'a TreeMap<K, V, H>) -> Self {
+    pub(super) fn new(map: &'a TreeMap<K, V, H, E>) -> Self {
         Self { inner: map.iter() }
     }
 }
 
-impl<'a, K, V, H> Iterator for Values<'a, K, V, H>
+impl<'a, K, V, H, E> Iterator for Values<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     type Item = &'a V;
@@ -720,25 +721,25 @@ where
     }
 }
 
-impl<'a, K, V, H> ExactSizeIterator for Values<'a, K, V, H>
+impl<'a, K, V, H, E> ExactSizeIterator for Values<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
 }
-impl<'a, K, V, H> FusedIterator for Values<'a, K, V, H>
+impl<'a, K, V, H, E> FusedIterator for Values<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
 }
 
-impl<'a, K, V, H> DoubleEndedIterator for Values<'a, K, V, H>
+impl<'a, K, V, H, E> DoubleEndedIterator for Values<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
@@ -753,30 +754,30 @@ where
 /// A mutable iterator over values of a [`TreeMap`], in order by key.
 ///
 /// This `struct` is created by the `values_mut` method on [`TreeMap`].
-pub struct ValuesMut<'a, K, V, H>
+pub struct ValuesMut<'a, K, V, H, E = Borsh>
 where
     K: BorshSerialize + Ord + BorshDeserialize,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
-    inner: IterMut<'a, K, V, H>,
+    inner: IterMut<'a, K, V, H, E>,
 }
 
-impl<'a, K, V, H> ValuesMut<'a, K, V, H>
+impl<'a, K, V, H, E> ValuesMut<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
-    pub(super) fn new(map: &'a mut TreeMap<K, V, H>) -> Self {
+    pub(super) fn new(map: &'a mut TreeMap<K, V, H, E>) -> Self {
         Self { inner: map.iter_mut() }
     }
 }
 
-impl<'a, K, V, H> Iterator for ValuesMut<'a, K, V, H>
+impl<'a, K, V, H, E> Iterator for ValuesMut<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     type Item = &'a mut V;
@@ -798,25 +799,25 @@ where
     }
 }
 
-impl<'a, K, V, H> ExactSizeIterator for ValuesMut<'a, K, V, H>
+impl<'a, K, V, H, E> ExactSizeIterator for ValuesMut<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
 }
-impl<'a, K, V, H> FusedIterator for ValuesMut<'a, K, V, H>
+impl<'a, K, V, H, E> FusedIterator for ValuesMut<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
 }
 
-impl<'a, K, V, H> DoubleEndedIterator for ValuesMut<'a, K, V, H>
+impl<'a, K, V, H, E> DoubleEndedIterator for ValuesMut<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
@@ -830,24 +831,24 @@ where
 
 /// An iterator over a range of elements of a [`TreeMap`], in sorted order.
 ///
-/// This `struct` is created by the `iter` method on [`TreeMap`].
-pub struct Range<'a, K, V, H>
+/// This `struct` is created by the `range` method on [`TreeMap`].
+pub struct Range<'a, K, V, H, E = Borsh>
 where
     K: BorshSerialize + Ord + BorshDeserialize,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
     keys: KeysRange<'a, K>,
-    values: &'a LookupMap<K, V, H>,
+    values: &'a LookupMap<K, V, H, E>,
 }
 
-impl<'a, K, V, H> Range<'a, K, V, H>
+impl<'a, K, V, H, E> Range<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
-    pub(super) fn new<Q>(map: &'a TreeMap<K, V, H>, bounds: (Bound<&Q>, Bound<&Q>)) -> Self
+    pub(super) fn new<Q>(map: &'a TreeMap<K, V, H, E>, bounds: (Bound<&Q>, Bound<&Q>)) -> Self
     where
         K: Borrow<Q>,
         Q: ?Sized + Ord,
@@ -856,10 +857,10 @@ where
     }
 }
 
-impl<'a, K, V, H> Iterator for Range<'a, K, V, H>
+impl<'a, K, V, H, E> Iterator for Range<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     type Item = (&'a K, &'a V);
@@ -880,18 +881,18 @@ where
     }
 }
 
-impl<'a, K, V, H> FusedIterator for Range<'a, K, V, H>
+impl<'a, K, V, H, E> FusedIterator for Range<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
 }
 
-impl<'a, K, V, H> DoubleEndedIterator for Range<'a, K, V, H>
+impl<'a, K, V, H, E> DoubleEndedIterator for Range<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
@@ -908,25 +909,25 @@ where
 
 /// A mutable iterator over a range of elements of a [`TreeMap`], in sorted order.
 ///
-/// This `struct` is created by the `iter_mut` method on [`TreeMap`].
-pub struct RangeMut<'a, K, V, H>
+/// This `struct` is created by the `range_mut` method on [`TreeMap`].
+pub struct RangeMut<'a, K, V, H, E = Borsh>
 where
     K: BorshSerialize + Ord + BorshDeserialize,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
     keys: KeysRange<'a, K>,
     /// Exclusive reference to underlying map to lookup values with `keys`.
-    values: &'a mut LookupMap<K, V, H>,
+    values: &'a mut LookupMap<K, V, H, E>,
 }
 
-impl<'a, K, V, H> RangeMut<'a, K, V, H>
+impl<'a, K, V, H, E> RangeMut<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
-    pub(super) fn new<Q>(map: &'a mut TreeMap<K, V, H>, bounds: (Bound<&Q>, Bound<&Q>)) -> Self
+    pub(super) fn new<Q>(map: &'a mut TreeMap<K, V, H, E>, bounds: (Bound<&Q>, Bound<&Q>)) -> Self
     where
         K: Borrow<Q>,
         Q: ?Sized + Ord,
@@ -935,10 +936,10 @@ where
     }
 }
 
-impl<'a, K, V, H> Iterator for RangeMut<'a, K, V, H>
+impl<'a, K, V, H, E> Iterator for RangeMut<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     type Item = (&'a K, &'a mut V);
@@ -957,18 +958,18 @@ where
     }
 }
 
-impl<'a, K, V, H> FusedIterator for RangeMut<'a, K, V, H>
+impl<'a, K, V, H, E> FusedIterator for RangeMut<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
 }
 
-impl<'a, K, V, H> DoubleEndedIterator for RangeMut<'a, K, V, H>
+impl<'a, K, V, H, E> DoubleEndedIterator for RangeMut<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
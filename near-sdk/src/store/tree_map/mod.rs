@@ -5,6 +5,7 @@ mod iter;
 use super::lookup_map as lm;
 use crate::store::free_list::{FreeList, FreeListIndex};
 use crate::store::key::{Sha256, ToKey};
+use crate::store::serialization::{Borsh, Deserialization, Serialization};
 use crate::store::LookupMap;
 use crate::{env, IntoStorageKey};
 use borsh::{BorshDeserialize, BorshSerialize};
@@ -28,20 +29,24 @@ fn expect<T>(val: Option<T>) -> T {
 /// - `min`/`max`:              O(log(N))
 /// - `above`/`below`:          O(log(N))
 /// - `range` of K elements:    O(Klog(N))
-pub struct TreeMap<K, V, H = Sha256>
+///
+/// Values are serialized with [`Borsh`] by default. Another format, such as
+/// [`Json`](crate::store::serialization::Json), can be selected with the `E` type parameter and
+/// [`TreeMap::with_serialization`].
+pub struct TreeMap<K, V, H = Sha256, E = Borsh>
 where
     K: BorshSerialize + Ord,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
-    values: LookupMap<K, V, H>,
+    values: LookupMap<K, V, H, E>,
     tree: Tree<K>,
 }
 
-impl<K, V, H> Drop for TreeMap<K, V, H>
+impl<K, V, H, E> Drop for TreeMap<K, V, H, E>
 where
     K: BorshSerialize + Ord,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
     fn drop(&mut self) {
@@ -49,10 +54,11 @@ where
     }
 }
 
-impl<K, V, H> fmt::Debug for TreeMap<K, V, H>
+impl<K, V, H, E> fmt::Debug for TreeMap<K, V, H, E>
 where
     K: Ord + Clone + fmt::Debug + BorshSerialize + BorshDeserialize,
-    V: fmt::Debug + BorshSerialize + BorshDeserialize,
+    V: fmt::Debug,
+    E: Deserialization<V>,
     H: ToKey,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -65,10 +71,10 @@ where
 
 //? Manual implementations needed only because borsh derive is leaking field types
 // https://github.com/near/borsh-rs/issues/41
-impl<K, V, H> BorshSerialize for TreeMap<K, V, H>
+impl<K, V, H, E> BorshSerialize for TreeMap<K, V, H, E>
 where
     K: BorshSerialize + Ord,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
     fn serialize<W: borsh::maybestd::io::Write>(
@@ -81,10 +87,10 @@ where
     }
 }
 
-impl<K, V, H> BorshDeserialize for TreeMap<K, V, H>
+impl<K, V, H, E> BorshDeserialize for TreeMap<K, V, H, E>
 where
     K: BorshSerialize + Ord,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
     fn deserialize(buf: &mut &[u8]) -> Result<Self, borsh::maybestd::io::Error> {
@@ -156,6 +162,46 @@ where
     {
         Self::with_hasher(prefix)
     }
+
+    /// Creates a new map with the given prefix from entries sorted by key in ascending order.
+    ///
+    /// The tree is built balanced bottom-up, so unlike inserting the entries one by one, no
+    /// rotations are needed and the existing values don't have to be read from storage. Like
+    /// [`Extend`], nothing is written to storage until the map is flushed.
+    ///
+    /// The keys **must** be strictly increasing, without duplicates. This is only checked in
+    /// debug builds, other inputs produce an inconsistent tree.
+    ///
+    /// # Example
+    /// ```
+    /// use near_sdk::store::TreeMap;
+    ///
+    /// let map: TreeMap<u32, u32> = TreeMap::from_sorted_iter(b"t", (0..10).map(|i| (i, i * i)));
+    /// assert_eq!(map.len(), 10);
+    /// assert_eq!(map.get(&3), Some(&9));
+    /// assert_eq!(map.floor_key(&20), Some(&9));
+    /// ```
+    pub fn from_sorted_iter<S, I>(prefix: S, iter: I) -> Self
+    where
+        S: IntoStorageKey,
+        I: IntoIterator<Item = (K, V)>,
+        K: Clone + BorshDeserialize,
+        V: BorshDeserialize,
+    {
+        let mut map = Self::new(prefix);
+        let mut keys = Vec::new();
+        for (key, value) in iter {
+            debug_assert!(
+                keys.last().into_iter().all(|last| last < &key),
+                "keys passed to `TreeMap::from_sorted_iter` must be strictly increasing"
+            );
+            map.values.set(key.clone(), Some(value));
+            keys.push(key);
+        }
+        let len = keys.len() as u32;
+        map.tree.root = map.tree.build_sorted(&mut keys.into_iter(), len).map(|(id, _)| id);
+        map
+    }
 }
 
 impl<K, V, H> TreeMap<K, V, H>
@@ -165,13 +211,73 @@ where
     H: ToKey,
 {
     pub fn with_hasher<S>(prefix: S) -> Self
+    where
+        S: IntoStorageKey,
+    {
+        Self::with_serialization(prefix)
+    }
+}
+
+impl<K, V, H, E> TreeMap<K, V, H, E>
+where
+    K: BorshSerialize + Ord,
+    E: Serialization<V>,
+    H: ToKey,
+{
+    /// Initialize a [`TreeMap`] with a custom hash function which serializes its values with the
+    /// format `E`.
+    ///
+    /// See [`TreeMap::new`] and [`TreeMap::with_hasher`] for creating a map with the default
+    /// [`Borsh`] format.
+    ///
+    /// # Example
+    /// ```
+    /// use near_sdk::store::key::Sha256;
+    /// use near_sdk::store::serialization::Json;
+    /// use near_sdk::store::TreeMap;
+    ///
+    /// let mut map: TreeMap<u32, String, Sha256, Json> = TreeMap::with_serialization(b"t");
+    /// map.insert(1, "a".to_string());
+    /// assert_eq!(map[&1], "a");
+    /// ```
+    pub fn with_serialization<S>(prefix: S) -> Self
     where
         S: IntoStorageKey,
     {
         let mut vec_key = prefix.into_storage_key();
         let map_key = [vec_key.as_slice(), b"v"].concat();
         vec_key.push(b'n');
-        Self { values: LookupMap::with_hasher(map_key), tree: Tree::new(vec_key) }
+        Self { values: LookupMap::with_serialization(map_key), tree: Tree::new(vec_key) }
+    }
+
+    /// Copies all entries of the map to a new map with the given prefix and the same hasher.
+    ///
+    /// This reads every entry and inserts it into the new map, which rebuilds the tree, so it
+    /// costs `O(n)` storage reads and writes when the copy is flushed. Both maps are independent
+    /// afterwards.
+    ///
+    /// # Example
+    /// ```
+    /// use near_sdk::store::TreeMap;
+    ///
+    /// let mut map: TreeMap<String, u8> = TreeMap::new(b"a");
+    /// map.insert("a".to_string(), 1);
+    ///
+    /// let mut copy = map.clone_to(b"b");
+    /// copy.insert("b".to_string(), 2);
+    /// assert_eq!(map.len(), 1);
+    /// assert_eq!(copy.get("a"), Some(&1));
+    /// ```
+    pub fn clone_to<S>(&self, new_prefix: S) -> Self
+    where
+        S: IntoStorageKey,
+        K: BorshDeserialize + Clone,
+        V: Clone,
+        E: Deserialization<V>,
+    {
+        let mut map = Self::with_serialization(new_prefix);
+        map.extend(self.iter().map(|(k, v)| (k.clone(), v.clone())));
+        map
     }
 
     /// Return the amount of elements inside of the map.
@@ -185,10 +291,10 @@ where
     }
 }
 
-impl<K, V, H> TreeMap<K, V, H>
+impl<K, V, H, E> TreeMap<K, V, H, E>
 where
     K: Ord + Clone + BorshSerialize,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     /// Clears the map, removing all key-value pairs. Keeps the allocated memory
@@ -336,6 +442,18 @@ where
         self.below_at(root, key)
     }
 
+    /// Returns the smallest key of the tree.
+    fn min_key(&self) -> Option<&K> {
+        let root = self.root?;
+        self.min_at(root).map(|((_, node), _)| &node.key)
+    }
+
+    /// Returns the largest key of the tree.
+    fn max_key(&self) -> Option<&K> {
+        let root = self.root?;
+        self.max_at(root).map(|((_, node), _)| &node.key)
+    }
+
     fn equal_key<Q>(&self, key: &Q) -> Option<&K>
     where
         K: Borrow<Q>,
@@ -521,6 +639,24 @@ impl<K> Tree<K>
 where
     K: Ord + BorshSerialize + BorshDeserialize + Clone,
 {
+    // Builds a balanced subtree from the next `len` keys, which must be sorted. Returns the index
+    // and the height of its root.
+    fn build_sorted<I>(&mut self, keys: &mut I, len: u32) -> Option<(FreeListIndex, u32)>
+    where
+        I: Iterator<Item = K>,
+    {
+        if len == 0 {
+            return None;
+        }
+        let lft_len = len / 2;
+        let lft = self.build_sorted(keys, lft_len);
+        let key = expect(keys.next());
+        let rgt = self.build_sorted(keys, len - lft_len - 1);
+        let ht = 1 + std::cmp::max(lft.map_or(0, |(_, ht)| ht), rgt.map_or(0, |(_, ht)| ht));
+        let node = Node { key, lft: lft.map(|(id, _)| id), rgt: rgt.map(|(id, _)| id), ht };
+        Some((self.nodes.insert(node), ht))
+    }
+
     fn internal_insert(&mut self, key: K) {
         if let Some(root) = self.root {
             let node = expect(self.node(root)).clone();
@@ -796,15 +932,201 @@ where
     }
 }
 
-impl<K, V, H> TreeMap<K, V, H>
+impl<K, V, H, E> TreeMap<K, V, H, E>
 where
     K: BorshSerialize + Ord,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
+    /// Returns the entry with the smallest key in the map, or `None` if the map is empty.
+    ///
+    /// This walks down the left side of the tree, so it only loads `O(log n)` nodes from storage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::TreeMap;
+    ///
+    /// let mut map = TreeMap::new(b"t");
+    /// assert_eq!(map.first_key_value(), None);
+    /// map.insert(20, "b".to_string());
+    /// map.insert(10, "a".to_string());
+    /// assert_eq!(map.first_key_value(), Some((&10, &"a".to_string())));
+    /// ```
+    pub fn first_key_value(&self) -> Option<(&K, &V)>
+    where
+        K: BorshDeserialize + Clone,
+        E: Deserialization<V>,
+    {
+        self.tree.min_key().map(|k| (k, expect(self.values.get::<K>(k))))
+    }
+
+    /// Returns the entry with the largest key in the map, or `None` if the map is empty.
+    ///
+    /// This walks down the right side of the tree, so it only loads `O(log n)` nodes from storage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::TreeMap;
+    ///
+    /// let mut map = TreeMap::new(b"t");
+    /// assert_eq!(map.last_key_value(), None);
+    /// map.insert(20, "b".to_string());
+    /// map.insert(10, "a".to_string());
+    /// assert_eq!(map.last_key_value(), Some((&20, &"b".to_string())));
+    /// ```
+    pub fn last_key_value(&self) -> Option<(&K, &V)>
+    where
+        K: BorshDeserialize + Clone,
+        E: Deserialization<V>,
+    {
+        self.tree.max_key().map(|k| (k, expect(self.values.get::<K>(k))))
+    }
+
+    /// Returns the largest key that is less than or equal to `key`, or `None` if there is no such key.
+    ///
+    /// This walks the tree from the root, so it only loads `O(log n)` nodes from storage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::TreeMap;
+    ///
+    /// let mut map = TreeMap::new(b"t");
+    /// for key in [10, 20, 30] {
+    ///     map.insert(key, ());
+    /// }
+    /// assert_eq!(map.floor_key(&20), Some(&20));
+    /// assert_eq!(map.floor_key(&25), Some(&20));
+    /// assert_eq!(map.floor_key(&5), None);
+    /// ```
+    pub fn floor_key<Q>(&self, key: &Q) -> Option<&K>
+    where
+        K: Borrow<Q> + BorshDeserialize,
+        Q: ?Sized + Ord,
+    {
+        self.tree.floor_key(key)
+    }
+
+    /// Returns the entry with the largest key that is less than or equal to `key`, like [`floor_key`](Self::floor_key).
+    pub fn floor_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q> + BorshDeserialize + Clone,
+        E: Deserialization<V>,
+        Q: ?Sized + Ord,
+    {
+        self.tree.floor_key(key).map(|k| (k, expect(self.values.get::<K>(k))))
+    }
+
+    /// Returns the smallest key that is greater than or equal to `key`, or `None` if there is no such key.
+    ///
+    /// This walks the tree from the root, so it only loads `O(log n)` nodes from storage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::TreeMap;
+    ///
+    /// let mut map = TreeMap::new(b"t");
+    /// for key in [10, 20, 30] {
+    ///     map.insert(key, ());
+    /// }
+    /// assert_eq!(map.ceil_key(&20), Some(&20));
+    /// assert_eq!(map.ceil_key(&25), Some(&30));
+    /// assert_eq!(map.ceil_key(&35), None);
+    /// ```
+    pub fn ceil_key<Q>(&self, key: &Q) -> Option<&K>
+    where
+        K: Borrow<Q> + BorshDeserialize,
+        Q: ?Sized + Ord,
+    {
+        self.tree.ceil_key(key)
+    }
+
+    /// Returns the entry with the smallest key that is greater than or equal to `key`, like [`ceil_key`](Self::ceil_key).
+    pub fn ceil_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q> + BorshDeserialize + Clone,
+        E: Deserialization<V>,
+        Q: ?Sized + Ord,
+    {
+        self.tree.ceil_key(key).map(|k| (k, expect(self.values.get::<K>(k))))
+    }
+
+    /// Returns the largest key that is strictly less than `key`, or `None` if there is no such key.
+    ///
+    /// This walks the tree from the root, so it only loads `O(log n)` nodes from storage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::TreeMap;
+    ///
+    /// let mut map = TreeMap::new(b"t");
+    /// for key in [10, 20, 30] {
+    ///     map.insert(key, ());
+    /// }
+    /// assert_eq!(map.lower_key(&20), Some(&10));
+    /// assert_eq!(map.lower_key(&25), Some(&20));
+    /// assert_eq!(map.lower_key(&10), None);
+    /// ```
+    pub fn lower_key<Q>(&self, key: &Q) -> Option<&K>
+    where
+        K: Borrow<Q> + BorshDeserialize,
+        Q: ?Sized + Ord,
+    {
+        self.tree.lower(key)
+    }
+
+    /// Returns the entry with the largest key that is strictly less than `key`, like [`lower_key`](Self::lower_key).
+    pub fn lower_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q> + BorshDeserialize + Clone,
+        E: Deserialization<V>,
+        Q: ?Sized + Ord,
+    {
+        self.tree.lower(key).map(|k| (k, expect(self.values.get::<K>(k))))
+    }
+
+    /// Returns the smallest key that is strictly greater than `key`, or `None` if there is no such key.
+    ///
+    /// This walks the tree from the root, so it only loads `O(log n)` nodes from storage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::TreeMap;
+    ///
+    /// let mut map = TreeMap::new(b"t");
+    /// for key in [10, 20, 30] {
+    ///     map.insert(key, ());
+    /// }
+    /// assert_eq!(map.higher_key(&20), Some(&30));
+    /// assert_eq!(map.higher_key(&25), Some(&30));
+    /// assert_eq!(map.higher_key(&30), None);
+    /// ```
+    pub fn higher_key<Q>(&self, key: &Q) -> Option<&K>
+    where
+        K: Borrow<Q> + BorshDeserialize,
+        Q: ?Sized + Ord,
+    {
+        self.tree.higher(key)
+    }
+
+    /// Returns the entry with the smallest key that is strictly greater than `key`, like [`higher_key`](Self::higher_key).
+    pub fn higher_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q> + BorshDeserialize + Clone,
+        E: Deserialization<V>,
+        Q: ?Sized + Ord,
+    {
+        self.tree.higher(key).map(|k| (k, expect(self.values.get::<K>(k))))
+    }
+
     /// An iterator visiting all key-value pairs in arbitrary order.
     /// The iterator element type is `(&'a K, &'a V)`.
-    pub fn iter(&self) -> Iter<K, V, H>
+    pub fn iter(&self) -> Iter<K, V, H, E>
     where
         K: BorshDeserialize,
     {
@@ -814,7 +1136,7 @@ where
     /// An iterator visiting all key-value pairs in arbitrary order,
     /// with exclusive references to the values.
     /// The iterator element type is `(&'a K, &'a mut V)`.
-    pub fn iter_mut(&mut self) -> IterMut<K, V, H>
+    pub fn iter_mut(&mut self) -> IterMut<K, V, H, E>
     where
         K: BorshDeserialize,
     {
@@ -832,7 +1154,7 @@ where
 
     /// An iterator visiting all values in arbitrary order.
     /// The iterator element type is `&'a V`.
-    pub fn values(&self) -> Values<K, V, H>
+    pub fn values(&self) -> Values<K, V, H, E>
     where
         K: BorshDeserialize,
     {
@@ -841,7 +1163,7 @@ where
 
     /// A mutable iterator visiting all values in arbitrary order.
     /// The iterator element type is `&'a mut V`.
-    pub fn values_mut(&mut self) -> ValuesMut<K, V, H>
+    pub fn values_mut(&mut self) -> ValuesMut<K, V, H, E>
     where
         K: BorshDeserialize,
     {
@@ -855,10 +1177,9 @@ where
     /// `range((Excluded(4), Included(10)))` will yield a left-exclusive, right-inclusive
     /// range from 4 to 10.
     ///
-    /// # Panics
-    ///
-    /// Panics if range `start > end`.
-    /// Panics if range `start == end` and both bounds are `Excluded`.
+    /// Unlike [`BTreeMap::range`](std::collections::BTreeMap::range), this does not panic on
+    /// degenerate bounds. If `start > end`, or `start == end` and both bounds are `Excluded`,
+    /// the returned iterator is empty.
     ///
     /// # Examples
     ///
@@ -877,7 +1198,7 @@ where
     /// }
     /// assert_eq!(Some((&5, &"b".to_string())), map.range(4..).next());
     /// ```
-    pub fn range<'a, R: 'a, Q: 'a>(&'a self, range: R) -> Range<'a, K, V, H>
+    pub fn range<'a, R: 'a, Q: 'a>(&'a self, range: R) -> Range<'a, K, V, H, E>
     where
         K: BorshDeserialize + Borrow<Q>,
         Q: ?Sized + Ord,
@@ -893,10 +1214,9 @@ where
     /// `range((Excluded(4), Included(10)))` will yield a left-exclusive, right-inclusive
     /// range from 4 to 10.
     ///
-    /// # Panics
-    ///
-    /// Panics if range `start > end`.
-    /// Panics if range `start == end` and both bounds are `Excluded`.
+    /// Unlike [`BTreeMap::range`](std::collections::BTreeMap::range), this does not panic on
+    /// degenerate bounds. If `start > end`, or `start == end` and both bounds are `Excluded`,
+    /// the returned iterator is empty.
     ///
     /// # Examples
     ///
@@ -916,7 +1236,7 @@ where
     ///     println!("{} => {}", id, balance);
     /// }
     /// ```
-    pub fn range_mut<R, Q>(&mut self, range: R) -> RangeMut<'_, K, V, H>
+    pub fn range_mut<R, Q>(&mut self, range: R) -> RangeMut<'_, K, V, H, E>
     where
         K: BorshDeserialize + Borrow<Q>,
         Q: ?Sized + Ord,
@@ -926,10 +1246,10 @@ where
     }
 }
 
-impl<K, V, H> TreeMap<K, V, H>
+impl<K, V, H, E> TreeMap<K, V, H, E>
 where
     K: BorshSerialize + Ord,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     /// Removes a key from the map, returning the stored key and value if the
@@ -960,6 +1280,114 @@ where
         })
     }
 
+    /// Removes and returns the entry with the smallest key in the map, or `None` if the map is
+    /// empty. Together with [`insert`](Self::insert), this allows using the map as a priority
+    /// queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::TreeMap;
+    ///
+    /// let mut map = TreeMap::new(b"t");
+    /// map.insert(2, "b".to_string());
+    /// map.insert(1, "a".to_string());
+    /// assert_eq!(map.pop_first(), Some((1, "a".to_string())));
+    /// assert_eq!(map.pop_first(), Some((2, "b".to_string())));
+    /// assert_eq!(map.pop_first(), None);
+    /// ```
+    pub fn pop_first(&mut self) -> Option<(K, V)>
+    where
+        K: BorshDeserialize + Clone,
+    {
+        let key = self.tree.min_key()?.clone();
+        self.remove_entry(&key)
+    }
+
+    /// Removes and returns the entry with the largest key in the map, or `None` if the map is
+    /// empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::TreeMap;
+    ///
+    /// let mut map = TreeMap::new(b"t");
+    /// map.insert(2, "b".to_string());
+    /// map.insert(1, "a".to_string());
+    /// assert_eq!(map.pop_last(), Some((2, "b".to_string())));
+    /// assert_eq!(map.pop_last(), Some((1, "a".to_string())));
+    /// assert_eq!(map.pop_last(), None);
+    /// ```
+    pub fn pop_last(&mut self) -> Option<(K, V)>
+    where
+        K: BorshDeserialize + Clone,
+    {
+        let key = self.tree.max_key()?.clone();
+        self.remove_entry(&key)
+    }
+
+    /// Removes all entries with keys in the given range, returning the number of removed
+    /// entries. The range accepts the same bounds as [`range`](Self::range).
+    ///
+    /// Values are deleted without being read from storage. When the range covers a large part
+    /// of the map, the remaining keys are rebuilt into a balanced tree once instead of
+    /// rebalancing after every removal, which is cheaper than calling [`remove`](Self::remove)
+    /// for each key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::TreeMap;
+    ///
+    /// let mut map: TreeMap<u32, u32> = TreeMap::new(b"t");
+    /// map.extend((0..10).map(|i| (i, i)));
+    /// assert_eq!(map.remove_range(..4), 4);
+    /// assert_eq!(map.remove_range(8..), 2);
+    /// assert_eq!(map.keys().copied().collect::<Vec<_>>(), [4, 5, 6, 7]);
+    /// ```
+    pub fn remove_range<R, Q>(&mut self, range: R) -> usize
+    where
+        K: Borrow<Q> + BorshDeserialize + Clone,
+        Q: ?Sized + Ord,
+        R: RangeBounds<Q>,
+    {
+        let removed: Vec<K> =
+            iter::KeysRange::new(&self.tree, (range.start_bound(), range.end_bound()))
+                .cloned()
+                .collect();
+        if removed.is_empty() {
+            return 0;
+        }
+
+        let remaining = self.len() - removed.len() as u32;
+        let height = self.tree.root.and_then(|id| self.tree.node(id)).map_or(0, |node| node.ht);
+        if (removed.len() as u64) * u64::from(height) < u64::from(remaining) {
+            // Few keys are removed, so updating the paths to them is cheaper than a rebuild.
+            for key in &removed {
+                expect(self.tree.do_remove::<K>(key));
+            }
+        } else {
+            let mut keys: Vec<K> = self
+                .tree
+                .nodes
+                .drain()
+                .map(|node| node.key)
+                .filter(|key| !range.contains(key.borrow()))
+                .collect();
+            keys.sort_unstable();
+            self.tree.root =
+                self.tree.build_sorted(&mut keys.into_iter(), remaining).map(|(id, _)| id);
+        }
+
+        let count = removed.len();
+        for key in removed {
+            // Set instead of remove to avoid loading the value from storage.
+            self.values.set(key, None);
+        }
+        count
+    }
+
     /// Gets the given key's corresponding entry in the map for in-place manipulation.
     /// ```
     /// use near_sdk::store::TreeMap;
@@ -984,10 +1412,10 @@ where
     }
 }
 
-impl<K, V, H> TreeMap<K, V, H>
+impl<K, V, H, E> TreeMap<K, V, H, E>
 where
     K: BorshSerialize + Ord,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
     /// Flushes the intermediate values of the map before this is called when the structure is
@@ -1050,6 +1478,95 @@ mod tests {
         h.ceil() as u32
     }
 
+    #[test]
+    fn test_clone_to() {
+        let mut map = TreeMap::new(next_trie_id());
+        map.extend((0u32..10).map(|i| (i, i * 2)));
+        map.flush();
+
+        let mut copy = map.clone_to(next_trie_id());
+        assert_eq!(copy.iter().collect::<Vec<_>>(), map.iter().collect::<Vec<_>>());
+        copy.insert(0, 100);
+        copy.remove(&1);
+        copy.flush();
+
+        let original: TreeMap<u32, u32> =
+            TreeMap::try_from_slice(&map.try_to_vec().unwrap()).unwrap();
+        assert_eq!(original.len(), 10);
+        assert_eq!(original.get(&0), Some(&0));
+        assert_eq!(original.get(&1), Some(&2));
+        let copy: TreeMap<u32, u32> = TreeMap::try_from_slice(&copy.try_to_vec().unwrap()).unwrap();
+        assert_eq!(copy.len(), 9);
+        assert_eq!(copy.get(&0), Some(&100));
+    }
+
+    #[test]
+    fn test_from_sorted_iter() {
+        setup_free();
+        for n in [0u32, 1, 2, 3, 7, 8, 100, 1000] {
+            let mut map = TreeMap::from_sorted_iter(next_trie_id(), (0..n).map(|i| (i, i * 2)));
+            assert_eq!(map.len(), n);
+            // A complete binary tree has the minimal height.
+            assert_eq!(height(&map), 32 - n.leading_zeros());
+            if let Some(root) = map.tree.root {
+                assert!(is_balanced(&map, root));
+            }
+            assert_eq!(
+                map.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(),
+                (0..n).map(|i| (i, i * 2)).collect::<Vec<_>>()
+            );
+            assert_eq!(map.lower_key(&n), n.checked_sub(1).as_ref());
+
+            // The map behaves as one built by insertion afterwards.
+            map.insert(n, 0);
+            map.remove(&0);
+            if let Some(root) = map.tree.root {
+                assert!(is_balanced(&map, root));
+            }
+            map.flush();
+            let map: TreeMap<u32, u32> =
+                TreeMap::try_from_slice(&map.try_to_vec().unwrap()).unwrap();
+            assert_eq!(map.len(), n);
+            assert_eq!(
+                map.iter().map(|(k, _)| *k).collect::<Vec<_>>(),
+                (1..=n).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_sorted_iter_storage_ops() {
+        use crate::test_utils::{gas_profile, VMContextBuilder};
+        use crate::testing_env;
+
+        let count = |cost: &str| gas_profile().get(cost).copied().unwrap_or_default();
+        let n = 500u32;
+
+        testing_env!(VMContextBuilder::new().build());
+        let mut naive = TreeMap::new(b"n");
+        naive.extend((0..n).map(|i| (i, i)));
+        naive.flush();
+        let (naive_reads, naive_writes) = (count("storage_read_base"), count("storage_write_base"));
+
+        testing_env!(VMContextBuilder::new().build());
+        let mut bulk = TreeMap::from_sorted_iter(b"b", (0..n).map(|i| (i, i)));
+        bulk.flush();
+        let (bulk_reads, bulk_writes) = (count("storage_read_base"), count("storage_write_base"));
+
+        assert!(height(&bulk) <= height(&naive));
+        assert_eq!(bulk_reads.0, 0);
+        assert!(naive_reads.0 > 0);
+        assert!(bulk_writes <= naive_writes);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "keys passed to `TreeMap::from_sorted_iter` must be strictly increasing"
+    )]
+    fn test_from_sorted_iter_unsorted() {
+        let _ = TreeMap::from_sorted_iter(next_trie_id(), [(2u32, 0u32), (1, 0)]);
+    }
+
     #[test]
     fn test_empty() {
         let map: TreeMap<u8, u8> = TreeMap::new(b't');
@@ -1282,6 +1799,57 @@ mod tests {
         map.clear();
     }
 
+    #[test]
+    fn test_first_last_key_value() {
+        let mut map: TreeMap<u32, u32> = TreeMap::new(next_trie_id());
+        assert_eq!(map.first_key_value(), None);
+        assert_eq!(map.last_key_value(), None);
+
+        map.insert(30, 3);
+        assert_eq!(map.first_key_value(), Some((&30, &3)));
+        assert_eq!(map.last_key_value(), Some((&30, &3)));
+
+        for x in [50, 10, 40, 20] {
+            map.insert(x, x / 10);
+        }
+        assert_eq!(map.first_key_value(), Some((&10, &1)));
+        assert_eq!(map.last_key_value(), Some((&50, &5)));
+
+        map.clear();
+    }
+
+    #[test]
+    fn test_pop_first_last() {
+        let mut map: TreeMap<u32, u32> = TreeMap::new(next_trie_id());
+        assert_eq!(map.pop_first(), None);
+        assert_eq!(map.pop_last(), None);
+
+        map.insert(1, 10);
+        assert_eq!(map.pop_last(), Some((1, 10)));
+        assert!(map.is_empty());
+        assert_eq!(map.pop_first(), None);
+
+        for x in [30, 10, 50, 20, 40] {
+            map.insert(x, x * 10);
+        }
+        assert_eq!(map.pop_first(), Some((10, 100)));
+        assert_eq!(map.pop_last(), Some((50, 500)));
+        assert_eq!(map.len(), 3);
+        assert!(!map.contains_key(&10));
+        assert!(!map.contains_key(&50));
+        assert_eq!(map.first_key_value(), Some((&20, &200)));
+        assert_eq!(map.last_key_value(), Some((&40, &400)));
+
+        // Popping in a loop visits the entries in order.
+        let mut popped = vec![];
+        while let Some((key, _)) = map.pop_first() {
+            popped.push(key);
+        }
+        assert_eq!(popped, [20, 30, 40]);
+        assert!(map.is_empty());
+        assert!(map.tree.nodes.is_empty());
+    }
+
     #[test]
     fn test_ceil_key() {
         let mut map: TreeMap<u32, u32> = TreeMap::new(next_trie_id());
@@ -1302,6 +1870,42 @@ mod tests {
         map.clear();
     }
 
+    #[test]
+    fn test_key_navigation() {
+        let mut map: TreeMap<u32, u32> = TreeMap::new(next_trie_id());
+        assert_eq!(map.floor_key(&10), None);
+        assert_eq!(map.ceil_key(&10), None);
+        assert_eq!(map.lower_key(&10), None);
+        assert_eq!(map.higher_key(&10), None);
+
+        for x in [10, 20, 30, 40, 50] {
+            map.insert(x, x * 2);
+        }
+
+        assert_eq!(map.floor_key(&5), None);
+        assert_eq!(map.floor_key(&35), Some(&30));
+        assert_eq!(map.floor_key(&60), Some(&50));
+        assert_eq!(map.ceil_key(&5), Some(&10));
+        assert_eq!(map.ceil_key(&35), Some(&40));
+        assert_eq!(map.ceil_key(&60), None);
+        assert_eq!(map.lower_key(&10), None);
+        assert_eq!(map.lower_key(&30), Some(&20));
+        assert_eq!(map.higher_key(&30), Some(&40));
+        assert_eq!(map.higher_key(&50), None);
+
+        assert_eq!(map.floor_key_value(&30), Some((&30, &60)));
+        assert_eq!(map.ceil_key_value(&31), Some((&40, &80)));
+        assert_eq!(map.lower_key_value(&11), Some((&10, &20)));
+        assert_eq!(map.higher_key_value(&49), Some((&50, &100)));
+        assert_eq!(map.higher_key_value(&50), None);
+
+        map.remove(&30);
+        assert_eq!(map.floor_key_value(&30), Some((&20, &40)));
+        assert_eq!(map.ceil_key_value(&30), Some((&40, &80)));
+
+        map.clear();
+    }
+
     #[test]
     fn test_remove_1() {
         let mut map: TreeMap<u32, u32> = TreeMap::new(next_trie_id());
@@ -1518,6 +2122,62 @@ mod tests {
         map.clear();
     }
 
+    fn check_remove_range<R: RangeBounds<u32> + Clone>(n: u32, range: R) {
+        setup_free();
+        let mut map: TreeMap<u32, u32> = TreeMap::new(next_trie_id());
+        let mut expected = BTreeMap::new();
+        for x in random(n) {
+            map.insert(x, x + 1);
+            expected.insert(x, x + 1);
+        }
+        let in_range = expected.keys().filter(|k| range.contains(*k)).count();
+        expected.retain(|k, _| !range.contains(k));
+
+        assert_eq!(map.remove_range(range.clone()), in_range);
+        assert_eq!(map.len(), expected.len() as u32);
+        assert!(height(&map) <= max_tree_height(map.len()));
+        assert_eq!(map.iter().map(|(k, v)| (*k, *v)).collect::<BTreeMap<_, _>>(), expected);
+        assert!(map.range(range.clone()).next().is_none());
+        assert_eq!(map.remove_range(range), 0);
+
+        // Removed values must be gone from storage as well.
+        map.flush();
+        let map = TreeMap::<u32, u32>::try_from_slice(&map.try_to_vec().unwrap()).unwrap();
+        for x in 0..1000 {
+            assert_eq!(map.get(&x), expected.get(&x));
+        }
+    }
+
+    #[test]
+    fn test_remove_range_prefix() {
+        check_remove_range(100, ..300);
+        check_remove_range(100, ..=20);
+    }
+
+    #[test]
+    fn test_remove_range_suffix() {
+        check_remove_range(100, 700..);
+        check_remove_range(100, 980..);
+    }
+
+    #[test]
+    fn test_remove_range_interior() {
+        check_remove_range(100, 200..800);
+        check_remove_range(100, (Bound::Excluded(400), Bound::Included(420)));
+        check_remove_range(100, 500..500);
+    }
+
+    #[test]
+    fn test_remove_range_all() {
+        check_remove_range(50, ..);
+        let mut map: TreeMap<u32, u32> = TreeMap::new(next_trie_id());
+        assert_eq!(map.remove_range(..), 0);
+        map.insert(1, 1);
+        assert_eq!(map.remove_range(1..=1), 1);
+        assert!(map.is_empty());
+        assert_eq!(map.tree.root, None);
+    }
+
     #[test]
     fn test_remove_root_3() {
         let mut map: TreeMap<u32, u32> = TreeMap::new(next_trie_id());
@@ -1657,6 +2317,26 @@ mod tests {
         assert_eq!(map.iter().rev().count(), 0);
     }
 
+    #[test]
+    fn test_for_loop_over_references() {
+        let mut map = TreeMap::new(b"t");
+        map.extend((1u32..=10).rev().map(|i| (i, u64::from(i))));
+
+        let mut keys = Vec::new();
+        let mut sum = 0;
+        for (key, value) in &map {
+            keys.push(*key);
+            sum += value;
+        }
+        assert_eq!(keys, (1..=10).collect::<Vec<_>>());
+        assert_eq!(sum, 55);
+
+        for (_, value) in &mut map {
+            *value *= 3;
+        }
+        assert_eq!((&map).into_iter().map(|(_, v)| v).sum::<u64>(), 165);
+    }
+
     #[test]
     fn test_iter_from() {
         let mut map: TreeMap<u32, u32> = TreeMap::new(next_trie_id());
@@ -1806,6 +2486,97 @@ mod tests {
         map.clear();
     }
 
+    #[test]
+    fn test_range_empty() {
+        let mut map: TreeMap<u32, u32> = TreeMap::new(next_trie_id());
+
+        assert_eq!(map.range(..).count(), 0);
+        assert_eq!(map.range(10..20).next(), None);
+        assert_eq!(map.range((Bound::Excluded(10), Bound::Unbounded)).next_back(), None);
+        assert_eq!(map.range_mut(..=20).next(), None);
+
+        map.insert(10, 1);
+        map.insert(20, 2);
+        map.insert(30, 3);
+
+        // Ranges that fall between or outside of the existing keys.
+        assert_eq!(map.range(11..20).count(), 0);
+        assert_eq!(map.range(31..).count(), 0);
+        assert_eq!(map.range(..10).count(), 0);
+        assert_eq!(map.range((Bound::Excluded(10), Bound::Excluded(20))).count(), 0);
+        assert_eq!(map.range_mut(21..30).next(), None);
+    }
+
+    #[test]
+    fn test_range_single_element() {
+        let mut map: TreeMap<u32, u32> = TreeMap::new(next_trie_id());
+        for x in [10, 20, 30, 40] {
+            map.insert(x, x + 1);
+        }
+
+        assert_eq!(map.range(20..=20).collect::<Vec<_>>(), [(&20, &21)]);
+        assert_eq!(map.range(20..=20).rev().collect::<Vec<_>>(), [(&20, &21)]);
+        assert_eq!(map.range(15..25).collect::<Vec<_>>(), [(&20, &21)]);
+        assert_eq!(
+            map.range((Bound::Excluded(10), Bound::Excluded(30))).collect::<Vec<_>>(),
+            [(&20, &21)]
+        );
+        assert_eq!(map.range(40..).collect::<Vec<_>>(), [(&40, &41)]);
+        assert_eq!(map.range(..=10).collect::<Vec<_>>(), [(&10, &11)]);
+
+        // Key is not in the map.
+        assert_eq!(map.range(25..=25).count(), 0);
+        // Included start and excluded end at the same key.
+        assert_eq!(map.range(20..20).count(), 0);
+
+        let mut iter = map.range_mut(30..=30);
+        let (_, value) = iter.next().unwrap();
+        *value = 0;
+        assert_eq!(iter.next(), None);
+        assert_eq!(map[&30], 0);
+    }
+
+    #[test]
+    fn test_range_reversed_bounds() {
+        let mut map: TreeMap<u32, u32> = TreeMap::new(next_trie_id());
+        for x in 0..10 {
+            map.insert(x * 10, x);
+        }
+
+        let reversed = [
+            (Bound::Included(40), Bound::Included(20)),
+            (Bound::Included(40), Bound::Excluded(20)),
+            (Bound::Excluded(40), Bound::Included(20)),
+            (Bound::Excluded(40), Bound::Excluded(20)),
+            (Bound::Excluded(40), Bound::Included(40)),
+            (Bound::Excluded(40), Bound::Excluded(40)),
+            (Bound::Included(45), Bound::Included(41)),
+        ];
+        for bounds in reversed {
+            assert_eq!(map.range(bounds).next(), None, "{:?}", bounds);
+            assert_eq!(map.range(bounds).next_back(), None, "{:?}", bounds);
+            assert_eq!(map.range_mut(bounds).next(), None, "{:?}", bounds);
+            assert_eq!(map.range_mut(bounds).next_back(), None, "{:?}", bounds);
+        }
+    }
+
+    #[test]
+    fn test_range_mut() {
+        let mut map: TreeMap<u32, u32> = TreeMap::new(next_trie_id());
+        for x in 0..10 {
+            map.insert(x, 0);
+        }
+
+        for (_, v) in map.range_mut((Bound::Excluded(2), Bound::Included(5))) {
+            *v += 1;
+        }
+        for (_, v) in map.range_mut(7..).rev() {
+            *v += 2;
+        }
+
+        assert_eq!(map.values().copied().collect::<Vec<_>>(), [0, 0, 0, 1, 1, 1, 0, 2, 2, 2]);
+    }
+
     #[test]
     fn test_iter_rev_from_empty() {
         let map: TreeMap<u32, u32> = TreeMap::new(next_trie_id());
@@ -2122,6 +2893,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn json_serialization() {
+        use crate::store::key::Sha256;
+        use crate::store::serialization::Json;
+
+        let mut map: TreeMap<u8, String, Sha256, Json> = TreeMap::with_serialization(b"j");
+        map.insert(2, "b".to_string());
+        map.insert(1, "a".to_string());
+        map.flush();
+        let key = Sha256::to_key(b"jv", &2u8, &mut Vec::new());
+        assert_eq!(env::storage_read(&key).unwrap(), br#""b""#);
+
+        let map = TreeMap::<u8, String, Sha256, Json>::try_from_slice(&map.try_to_vec().unwrap())
+            .unwrap();
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            [(&1, &"a".to_string()), (&2, &"b".to_string())]
+        );
+    }
+
     #[test]
     fn issue993() {
         fn swap_set<H>(map: &mut TreeMap<(), (), H>)
@@ -4,11 +4,12 @@ use borsh::{BorshDeserialize, BorshSerialize};
 
 use super::{ToKey, UnorderedMap, ERR_NOT_EXIST};
 use crate::env;
+use crate::store::serialization::Deserialization;
 
-impl<K, V, H> Extend<(K, V)> for UnorderedMap<K, V, H>
+impl<K, V, H, E> Extend<(K, V)> for UnorderedMap<K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     fn extend<I>(&mut self, iter: I)
@@ -21,10 +22,10 @@ where
     }
 }
 
-impl<K, V, H, Q: ?Sized> core::ops::Index<&Q> for UnorderedMap<K, V, H>
+impl<K, V, H, E, Q: ?Sized> core::ops::Index<&Q> for UnorderedMap<K, V, H, E>
 where
     K: BorshSerialize + Ord + Clone + Borrow<Q>,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 
     Q: BorshSerialize + ToOwned<Owned = K>,
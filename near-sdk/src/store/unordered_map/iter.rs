@@ -2,31 +2,32 @@ use std::iter::FusedIterator;
 
 use borsh::{BorshDeserialize, BorshSerialize};
 
-use super::{LookupMap, ToKey, UnorderedMap, ValueAndIndex, ERR_INCONSISTENT_STATE};
+use super::{Indexed, LookupMap, ToKey, UnorderedMap, ValueAndIndex, ERR_INCONSISTENT_STATE};
+use crate::store::serialization::{Borsh, Deserialization, Serialization};
 use crate::{env, store::free_list};
 
-impl<'a, K, V, H> IntoIterator for &'a UnorderedMap<K, V, H>
+impl<'a, K, V, H, E> IntoIterator for &'a UnorderedMap<K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     type Item = (&'a K, &'a V);
-    type IntoIter = Iter<'a, K, V, H>;
+    type IntoIter = Iter<'a, K, V, H, E>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.iter()
     }
 }
 
-impl<'a, K, V, H> IntoIterator for &'a mut UnorderedMap<K, V, H>
+impl<'a, K, V, H, E> IntoIterator for &'a mut UnorderedMap<K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     type Item = (&'a K, &'a mut V);
-    type IntoIter = IterMut<'a, K, V, H>;
+    type IntoIter = IterMut<'a, K, V, H, E>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.iter_mut()
@@ -36,33 +37,33 @@ where
 /// An iterator over elements of a [`UnorderedMap`].
 ///
 /// This `struct` is created by the `iter` method on [`UnorderedMap`].
-pub struct Iter<'a, K, V, H>
+pub struct Iter<'a, K, V, H, E = Borsh>
 where
     K: BorshSerialize + Ord + BorshDeserialize,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
     /// Values iterator which contains empty and filled cells.
     keys: free_list::Iter<'a, K>,
     /// Reference to underlying map to lookup values with `keys`.
-    values: &'a LookupMap<K, ValueAndIndex<V>, H>,
+    values: &'a LookupMap<K, ValueAndIndex<V>, H, Indexed<E>>,
 }
 
-impl<'a, K, V, H> Iter<'a, K, V, H>
+impl<'a, K, V, H, E> Iter<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
-    pub(super) fn new(map: &'a UnorderedMap<K, V, H>) -> Self {
+    pub(super) fn new(map: &'a UnorderedMap<K, V, H, E>) -> Self {
         Self { keys: map.keys.iter(), values: &map.values }
     }
 }
 
-impl<'a, K, V, H> Iterator for Iter<'a, K, V, H>
+impl<'a, K, V, H, E> Iterator for Iter<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     type Item = (&'a K, &'a V);
@@ -87,25 +88,25 @@ where
     }
 }
 
-impl<'a, K, V, H> ExactSizeIterator for Iter<'a, K, V, H>
+impl<'a, K, V, H, E> ExactSizeIterator for Iter<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
 }
-impl<'a, K, V, H> FusedIterator for Iter<'a, K, V, H>
+impl<'a, K, V, H, E> FusedIterator for Iter<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
 }
 
-impl<'a, K, V, H> DoubleEndedIterator for Iter<'a, K, V, H>
+impl<'a, K, V, H, E> DoubleEndedIterator for Iter<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
@@ -123,31 +124,31 @@ where
 /// A mutable iterator over elements of a [`UnorderedMap`].
 ///
 /// This `struct` is created by the `iter_mut` method on [`UnorderedMap`].
-pub struct IterMut<'a, K, V, H>
+pub struct IterMut<'a, K, V, H, E = Borsh>
 where
     K: BorshSerialize + Ord + BorshDeserialize,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
     /// Values iterator which contains empty and filled cells.
     keys: free_list::Iter<'a, K>,
     /// Exclusive reference to underlying map to lookup values with `keys`.
-    values: &'a mut LookupMap<K, ValueAndIndex<V>, H>,
+    values: &'a mut LookupMap<K, ValueAndIndex<V>, H, Indexed<E>>,
 }
 
-impl<'a, K, V, H> IterMut<'a, K, V, H>
+impl<'a, K, V, H, E> IterMut<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
-    pub(super) fn new(map: &'a mut UnorderedMap<K, V, H>) -> Self {
+    pub(super) fn new(map: &'a mut UnorderedMap<K, V, H, E>) -> Self {
         Self { keys: map.keys.iter(), values: &mut map.values }
     }
     fn get_entry_mut<'b>(&'b mut self, key: &'a K) -> (&'a K, &'a mut V)
     where
         K: Clone,
-        V: BorshDeserialize,
+        E: Deserialization<V>,
     {
         let entry =
             self.values.get_mut(key).unwrap_or_else(|| env::panic_str(ERR_INCONSISTENT_STATE));
@@ -162,10 +163,10 @@ where
     }
 }
 
-impl<'a, K, V, H> Iterator for IterMut<'a, K, V, H>
+impl<'a, K, V, H, E> Iterator for IterMut<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     type Item = (&'a K, &'a mut V);
@@ -188,25 +189,25 @@ where
     }
 }
 
-impl<'a, K, V, H> ExactSizeIterator for IterMut<'a, K, V, H>
+impl<'a, K, V, H, E> ExactSizeIterator for IterMut<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
 }
-impl<'a, K, V, H> FusedIterator for IterMut<'a, K, V, H>
+impl<'a, K, V, H, E> FusedIterator for IterMut<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
 }
 
-impl<'a, K, V, H> DoubleEndedIterator for IterMut<'a, K, V, H>
+impl<'a, K, V, H, E> DoubleEndedIterator for IterMut<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
@@ -233,10 +234,10 @@ impl<'a, K> Keys<'a, K>
 where
     K: BorshSerialize + BorshDeserialize,
 {
-    pub(super) fn new<V, H>(map: &'a UnorderedMap<K, V, H>) -> Self
+    pub(super) fn new<V, H, E>(map: &'a UnorderedMap<K, V, H, E>) -> Self
     where
         K: Ord,
-        V: BorshSerialize,
+        E: Serialization<V>,
         H: ToKey,
     {
         Self { inner: map.keys.iter() }
@@ -277,30 +278,30 @@ where
 /// An iterator over the values of a [`UnorderedMap`].
 ///
 /// This `struct` is created by the `values` method on [`UnorderedMap`].
-pub struct Values<'a, K, V, H>
+pub struct Values<'a, K, V, H, E = Borsh>
 where
     K: BorshSerialize + Ord + BorshDeserialize,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
-    inner: Iter<'a, K, V, H>,
+    inner: Iter<'a, K, V, H, E>,
 }
 
-impl<'a, K, V, H> Values<'a, K, V, H>
+impl<'a, K, V, H, E> Values<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
-    pub(super) fn new(map: &'a UnorderedMap<K, V, H>) -> Self {
+    pub(super) fn new(map: &'a UnorderedMap<K, V, H, E>) -> Self {
         Self { inner: map.iter() }
     }
 }
 
-impl<'a, K, V, H> Iterator for Values<'a, K, V, H>
+impl<'a, K, V, H, E> Iterator for Values<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     type Item = &'a V;
@@ -322,25 +323,25 @@ where
     }
 }
 
-impl<'a, K, V, H> ExactSizeIterator for Values<'a, K, V, H>
+impl<'a, K, V, H, E> ExactSizeIterator for Values<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
 }
-impl<'a, K, V, H> FusedIterator for Values<'a, K, V, H>
+impl<'a, K, V, H, E> FusedIterator for Values<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
 }
 
-impl<'a, K, V, H> DoubleEndedIterator for Values<'a, K, V, H>
+impl<'a, K, V, H, E> DoubleEndedIterator for Values<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
@@ -355,30 +356,30 @@ where
 /// A mutable iterator over values of a [`UnorderedMap`].
 ///
 /// This `struct` is created by the `values_mut` method on [`UnorderedMap`].
-pub struct ValuesMut<'a, K, V, H>
+pub struct ValuesMut<'a, K, V, H, E = Borsh>
 where
     K: BorshSerialize + Ord + BorshDeserialize,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
-    inner: IterMut<'a, K, V, H>,
+    inner: IterMut<'a, K, V, H, E>,
 }
 
-impl<'a, K, V, H> ValuesMut<'a, K, V, H>
+impl<'a, K, V, H, E> ValuesMut<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
-    pub(super) fn new(map: &'a mut UnorderedMap<K, V, H>) -> Self {
+    pub(super) fn new(map: &'a mut UnorderedMap<K, V, H, E>) -> Self {
         Self { inner: map.iter_mut() }
     }
 }
 
-impl<'a, K, V, H> Iterator for ValuesMut<'a, K, V, H>
+impl<'a, K, V, H, E> Iterator for ValuesMut<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     type Item = &'a mut V;
@@ -400,25 +401,25 @@ where
     }
 }
 
-impl<'a, K, V, H> ExactSizeIterator for ValuesMut<'a, K, V, H>
+impl<'a, K, V, H, E> ExactSizeIterator for ValuesMut<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
 }
-impl<'a, K, V, H> FusedIterator for ValuesMut<'a, K, V, H>
+impl<'a, K, V, H, E> FusedIterator for ValuesMut<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
 }
 
-impl<'a, K, V, H> DoubleEndedIterator for ValuesMut<'a, K, V, H>
+impl<'a, K, V, H, E> DoubleEndedIterator for ValuesMut<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
@@ -430,25 +431,25 @@ where
     }
 }
 
-/// A draining iterator for [`UnorderedMap<K, V, H>`].
+/// A draining iterator for [`UnorderedMap<K, V, H, E>`].
 #[derive(Debug)]
-pub struct Drain<'a, K, V, H>
+pub struct Drain<'a, K, V, H, E = Borsh>
 where
     K: BorshSerialize + BorshDeserialize + Ord,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
     keys: free_list::Drain<'a, K>,
-    values: &'a mut LookupMap<K, ValueAndIndex<V>, H>,
+    values: &'a mut LookupMap<K, ValueAndIndex<V>, H, Indexed<E>>,
 }
 
-impl<'a, K, V, H> Drain<'a, K, V, H>
+impl<'a, K, V, H, E> Drain<'a, K, V, H, E>
 where
     K: BorshSerialize + BorshDeserialize + Ord,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
-    pub(crate) fn new(list: &'a mut UnorderedMap<K, V, H>) -> Self {
+    pub(crate) fn new(list: &'a mut UnorderedMap<K, V, H, E>) -> Self {
         Self { keys: list.keys.drain(), values: &mut list.values }
     }
 
@@ -459,7 +460,7 @@ where
     fn remove_value(&mut self, key: K) -> (K, V)
     where
         K: Clone,
-        V: BorshDeserialize,
+        E: Deserialization<V>,
     {
         let value = self
             .values
@@ -471,10 +472,10 @@ where
     }
 }
 
-impl<'a, K, V, H> Iterator for Drain<'a, K, V, H>
+impl<'a, K, V, H, E> Iterator for Drain<'a, K, V, H, E>
 where
     K: BorshSerialize + BorshDeserialize + Ord + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     type Item = (K, V);
@@ -494,26 +495,26 @@ where
     }
 }
 
-impl<'a, K, V, H> ExactSizeIterator for Drain<'a, K, V, H>
+impl<'a, K, V, H, E> ExactSizeIterator for Drain<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
 }
 
-impl<'a, K, V, H> FusedIterator for Drain<'a, K, V, H>
+impl<'a, K, V, H, E> FusedIterator for Drain<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
 }
 
-impl<'a, K, V, H> DoubleEndedIterator for Drain<'a, K, V, H>
+impl<'a, K, V, H, E> DoubleEndedIterator for Drain<'a, K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
@@ -521,3 +522,17 @@ where
         Some(self.remove_value(key))
     }
 }
+
+impl<'a, K, V, H, E> Drop for Drain<'a, K, V, H, E>
+where
+    K: BorshSerialize + BorshDeserialize + Ord,
+    E: Serialization<V>,
+    H: ToKey,
+{
+    fn drop(&mut self) {
+        // Remove the values of any keys not yielded, without loading them from storage.
+        for key in self.keys.by_ref() {
+            self.values.set(key, None);
+        }
+    }
+}
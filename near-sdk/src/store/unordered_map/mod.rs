@@ -3,11 +3,15 @@ mod impls;
 mod iter;
 
 use std::borrow::Borrow;
+use std::marker::PhantomData;
 use std::{fmt, mem};
 
 use borsh::{BorshDeserialize, BorshSerialize};
 
 use crate::store::key::{Sha256, ToKey};
+use crate::store::serialization::{
+    Borsh, Deserialization, Serialization, ERR_ELEMENT_DESERIALIZATION,
+};
 use crate::{env, IntoStorageKey};
 
 pub use entry::{Entry, OccupiedEntry, VacantEntry};
@@ -28,6 +32,10 @@ use super::{FreeList, LookupMap, ERR_INCONSISTENT_STATE, ERR_NOT_EXIST};
 /// use [`with_hasher`]. Alternative builtin hash functions can be found at
 /// [`near_sdk::store::key`](crate::store::key).
 ///
+/// Values are serialized with [`Borsh`] by default. Another format, such as
+/// [`Json`](crate::store::serialization::Json), can be selected with the `E` type parameter and
+/// [`UnorderedMap::with_serialization`].
+///
 /// # Examples
 /// ```
 /// use near_sdk::store::UnorderedMap;
@@ -75,28 +83,56 @@ use super::{FreeList, LookupMap, ERR_INCONSISTENT_STATE, ERR_NOT_EXIST};
 /// ```
 ///
 /// [`with_hasher`]: Self::with_hasher
-pub struct UnorderedMap<K, V, H = Sha256>
+pub struct UnorderedMap<K, V, H = Sha256, E = Borsh>
 where
     K: BorshSerialize + Ord,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
     keys: FreeList<K>,
-    values: LookupMap<K, ValueAndIndex<V>, H>,
+    values: LookupMap<K, ValueAndIndex<V>, H, Indexed<E>>,
 }
 
-#[derive(BorshSerialize, BorshDeserialize)]
 struct ValueAndIndex<V> {
     value: V,
     key_index: FreeListIndex,
 }
 
+/// Stores a [`ValueAndIndex`] as its value in the format `E` followed by the little-endian key
+/// index, which matches the Borsh layout of the pair.
+struct Indexed<E>(PhantomData<E>);
+
+impl<V, E> Serialization<ValueAndIndex<V>> for Indexed<E>
+where
+    E: Serialization<V>,
+{
+    fn serialize(value: &ValueAndIndex<V>, buf: &mut Vec<u8>) {
+        E::serialize(&value.value, buf);
+        buf.extend_from_slice(&value.key_index.0.to_le_bytes());
+    }
+}
+
+impl<V, E> Deserialization<ValueAndIndex<V>> for Indexed<E>
+where
+    E: Deserialization<V>,
+{
+    fn deserialize(bytes: &[u8]) -> ValueAndIndex<V> {
+        let value_len = bytes
+            .len()
+            .checked_sub(mem::size_of::<u32>())
+            .unwrap_or_else(|| env::panic_str(ERR_ELEMENT_DESERIALIZATION));
+        let (value, key_index) = bytes.split_at(value_len);
+        let key_index = u32::from_le_bytes(key_index.try_into().unwrap());
+        ValueAndIndex { value: E::deserialize(value), key_index: FreeListIndex(key_index) }
+    }
+}
+
 //? Manual implementations needed only because borsh derive is leaking field types
 // https://github.com/near/borsh-rs/issues/41
-impl<K, V, H> BorshSerialize for UnorderedMap<K, V, H>
+impl<K, V, H, E> BorshSerialize for UnorderedMap<K, V, H, E>
 where
     K: BorshSerialize + Ord,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
     fn serialize<W: borsh::maybestd::io::Write>(
@@ -109,10 +145,10 @@ where
     }
 }
 
-impl<K, V, H> BorshDeserialize for UnorderedMap<K, V, H>
+impl<K, V, H, E> BorshDeserialize for UnorderedMap<K, V, H, E>
 where
     K: BorshSerialize + Ord,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
     fn deserialize(buf: &mut &[u8]) -> Result<Self, borsh::maybestd::io::Error> {
@@ -123,10 +159,10 @@ where
     }
 }
 
-impl<K, V, H> Drop for UnorderedMap<K, V, H>
+impl<K, V, H, E> Drop for UnorderedMap<K, V, H, E>
 where
     K: BorshSerialize + Ord,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
     fn drop(&mut self) {
@@ -134,10 +170,11 @@ where
     }
 }
 
-impl<K, V, H> fmt::Debug for UnorderedMap<K, V, H>
+#[cfg(not(feature = "expensive-debug"))]
+impl<K, V, H, E> fmt::Debug for UnorderedMap<K, V, H, E>
 where
     K: BorshSerialize + Ord + BorshDeserialize + fmt::Debug,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -148,6 +185,19 @@ where
     }
 }
 
+#[cfg(feature = "expensive-debug")]
+impl<K, V, H, E> fmt::Debug for UnorderedMap<K, V, H, E>
+where
+    K: BorshSerialize + Ord + BorshDeserialize + Clone + fmt::Debug,
+    V: fmt::Debug,
+    E: Deserialization<V>,
+    H: ToKey,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
 impl<K, V> UnorderedMap<K, V, Sha256>
 where
     K: BorshSerialize + Ord,
@@ -172,6 +222,34 @@ where
     {
         Self::with_hasher(prefix)
     }
+
+    /// Creates a new iterable map with the given prefix and inserts all entries of `iter`. If a
+    /// key is repeated, the last value is kept.
+    ///
+    /// Like [`Extend`], the entries and the length are only kept in memory until the map is
+    /// flushed, so the length is written once regardless of how many entries are inserted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::UnorderedMap;
+    ///
+    /// let entries = [("a".to_string(), 1u8), ("b".to_string(), 2), ("a".to_string(), 3)];
+    /// let map = UnorderedMap::from_iter_with_prefix(b"m", entries);
+    /// assert_eq!(map.len(), 2);
+    /// assert_eq!(map["a"], 3);
+    /// ```
+    pub fn from_iter_with_prefix<S, I>(prefix: S, iter: I) -> Self
+    where
+        S: IntoStorageKey,
+        I: IntoIterator<Item = (K, V)>,
+        K: BorshDeserialize + Clone,
+        V: BorshDeserialize,
+    {
+        let mut map = Self::new(prefix);
+        map.extend(iter);
+        map
+    }
 }
 
 impl<K, V, H> UnorderedMap<K, V, H>
@@ -189,13 +267,73 @@ where
     /// let map = UnorderedMap::<String, String, Keccak256>::with_hasher(b"m");
     /// ```
     pub fn with_hasher<S>(prefix: S) -> Self
+    where
+        S: IntoStorageKey,
+    {
+        Self::with_serialization(prefix)
+    }
+}
+
+impl<K, V, H, E> UnorderedMap<K, V, H, E>
+where
+    K: BorshSerialize + Ord,
+    E: Serialization<V>,
+    H: ToKey,
+{
+    /// Initialize a [`UnorderedMap`] with a custom hash function which serializes its values with
+    /// the format `E`.
+    ///
+    /// See [`UnorderedMap::new`] and [`UnorderedMap::with_hasher`] for creating a map with the
+    /// default [`Borsh`] format.
+    ///
+    /// # Example
+    /// ```
+    /// use near_sdk::store::key::Sha256;
+    /// use near_sdk::store::serialization::Json;
+    /// use near_sdk::store::UnorderedMap;
+    ///
+    /// let mut map: UnorderedMap<String, u8, Sha256, Json> =
+    ///     UnorderedMap::with_serialization(b"m");
+    /// map.insert("a".to_string(), 1);
+    /// assert_eq!(map["a"], 1);
+    /// ```
+    pub fn with_serialization<S>(prefix: S) -> Self
     where
         S: IntoStorageKey,
     {
         let mut vec_key = prefix.into_storage_key();
         let map_key = [vec_key.as_slice(), b"m"].concat();
         vec_key.push(b'v');
-        Self { keys: FreeList::new(vec_key), values: LookupMap::with_hasher(map_key) }
+        Self { keys: FreeList::new(vec_key), values: LookupMap::with_serialization(map_key) }
+    }
+
+    /// Copies all entries of the map to a new map with the given prefix and the same hasher.
+    ///
+    /// This reads every entry and writes it under the new prefix when the copy is flushed,
+    /// so it costs `O(n)` storage reads and writes. Both maps are independent afterwards.
+    ///
+    /// # Example
+    /// ```
+    /// use near_sdk::store::UnorderedMap;
+    ///
+    /// let mut map: UnorderedMap<String, u8> = UnorderedMap::new(b"a");
+    /// map.insert("a".to_string(), 1);
+    ///
+    /// let mut copy = map.clone_to(b"b");
+    /// copy.insert("b".to_string(), 2);
+    /// assert_eq!(map.len(), 1);
+    /// assert_eq!(copy["a"], 1);
+    /// ```
+    pub fn clone_to<S>(&self, new_prefix: S) -> Self
+    where
+        S: IntoStorageKey,
+        K: BorshDeserialize + Clone,
+        V: Clone,
+        E: Deserialization<V>,
+    {
+        let mut map = Self::with_serialization(new_prefix);
+        map.extend(self.iter().map(|(k, v)| (k.clone(), v.clone())));
+        map
     }
 
     /// Return the amount of elements inside of the map.
@@ -247,7 +385,7 @@ where
     pub fn clear(&mut self)
     where
         K: BorshDeserialize + Clone,
-        V: BorshDeserialize,
+        E: Deserialization<V>,
     {
         for k in self.keys.drain() {
             // Set instead of remove to avoid loading the value from storage.
@@ -272,7 +410,7 @@ where
     ///     println!("key: {} val: {}", key, val);
     /// }
     /// ```
-    pub fn iter(&self) -> Iter<K, V, H>
+    pub fn iter(&self) -> Iter<K, V, H, E>
     where
         K: BorshDeserialize,
     {
@@ -302,7 +440,7 @@ where
     ///     println!("key: {} val: {}", key, val);
     /// }
     /// ```
-    pub fn iter_mut(&mut self) -> IterMut<K, V, H>
+    pub fn iter_mut(&mut self) -> IterMut<K, V, H, E>
     where
         K: BorshDeserialize,
     {
@@ -350,7 +488,7 @@ where
     ///     println!("{}", val);
     /// }
     /// ```
-    pub fn values(&self) -> Values<K, V, H>
+    pub fn values(&self) -> Values<K, V, H, E>
     where
         K: BorshDeserialize,
     {
@@ -378,7 +516,7 @@ where
     ///     println!("{}", val);
     /// }
     /// ```
-    pub fn values_mut(&mut self) -> ValuesMut<K, V, H>
+    pub fn values_mut(&mut self) -> ValuesMut<K, V, H, E>
     where
         K: BorshDeserialize,
     {
@@ -405,18 +543,52 @@ where
     ///
     /// assert!(a.is_empty());
     /// ```
-    pub fn drain(&mut self) -> Drain<K, V, H>
+    pub fn drain(&mut self) -> Drain<K, V, H, E>
     where
         K: BorshDeserialize,
     {
         Drain::new(self)
     }
+
+    /// An iterator visiting all key-value pairs in ascending order of the keys.
+    /// The iterator element type is `(&'a K, &'a V)`.
+    ///
+    /// Unlike [`iter`](Self::iter), the order does not depend on the history of insertions and
+    /// removals. This loads every entry of the map and sorts them before yielding the first one,
+    /// so it is `O(n log n)` and should only be used for occasional reads, such as dumping the
+    /// state. Consider using [`TreeMap`](crate::store::TreeMap) if sorted iteration is needed
+    /// regularly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::UnorderedMap;
+    ///
+    /// let mut map = UnorderedMap::new(b"m");
+    /// map.insert("c".to_string(), 3);
+    /// map.insert("a".to_string(), 1);
+    /// map.insert("b".to_string(), 2);
+    /// map.remove("a");
+    ///
+    /// let entries: Vec<_> = map.iter_sorted().collect();
+    /// assert_eq!(entries, [(&"b".to_string(), &2), (&"c".to_string(), &3)]);
+    /// ```
+    pub fn iter_sorted(&self) -> std::vec::IntoIter<(&K, &V)>
+    where
+        K: BorshDeserialize + Clone,
+        E: Deserialization<V>,
+    {
+        let mut entries: Vec<_> = self.iter().collect();
+        // Keys are unique, so an unstable sort still yields a deterministic order.
+        entries.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        entries.into_iter()
+    }
 }
 
-impl<K, V, H> UnorderedMap<K, V, H>
+impl<K, V, H, E> UnorderedMap<K, V, H, E>
 where
     K: BorshSerialize + Ord,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     /// Returns a reference to the value corresponding to the key.
@@ -635,10 +807,10 @@ where
     }
 }
 
-impl<K, V, H> UnorderedMap<K, V, H>
+impl<K, V, H, E> UnorderedMap<K, V, H, E>
 where
     K: BorshSerialize + Ord,
-    V: BorshSerialize,
+    E: Serialization<V>,
     H: ToKey,
 {
     /// Flushes the intermediate values of the map before this is called when the structure is
@@ -650,10 +822,10 @@ where
     }
 }
 
-impl<K, V, H> UnorderedMap<K, V, H>
+impl<K, V, H, E> UnorderedMap<K, V, H, E>
 where
     K: BorshSerialize + BorshDeserialize + Ord + Clone,
-    V: BorshSerialize + BorshDeserialize,
+    E: Deserialization<V>,
     H: ToKey,
 {
     /// Remove empty placeholders leftover from calling [`remove`](Self::remove).
@@ -689,6 +861,48 @@ where
             }
         });
     }
+
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// In other words, remove all pairs `(k, v)` for which `f(&k, &mut v)` returns `false`.
+    /// The elements are visited in unsorted (and unspecified) order.
+    ///
+    /// # Performance
+    ///
+    /// This loads every value in the map. Removed keys are replaced with placeholders in the
+    /// same way as [`remove`](Self::remove), so consider calling [`defrag`](Self::defrag)
+    /// after removing a large amount of entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::UnorderedMap;
+    ///
+    /// let mut map: UnorderedMap<u32, u32> = UnorderedMap::new(b"m");
+    /// map.extend((0..8).map(|x| (x, x * 10)));
+    ///
+    /// // Keep only the elements with even-numbered keys.
+    /// map.retain(|&k, _| k % 2 == 0);
+    /// assert_eq!(map.len(), 4);
+    /// assert_eq!(map.get(&3), None);
+    /// assert_eq!(map[&4], 40);
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        let values = &mut self.values;
+        self.keys.retain(|key| {
+            let entry =
+                values.get_mut(key).unwrap_or_else(|| env::panic_str(ERR_INCONSISTENT_STATE));
+            let retain = f(key, &mut entry.value);
+            if !retain {
+                // Value is already loaded in the cache, so this does not read from storage.
+                values.remove(key);
+            }
+            retain
+        });
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -700,7 +914,161 @@ mod tests {
     use borsh::{BorshDeserialize, BorshSerialize};
     use rand::RngCore;
     use rand::SeedableRng;
-    use std::collections::HashMap;
+    use std::collections::{BTreeMap, HashMap};
+
+    #[test]
+    fn for_loop_over_references() {
+        let mut map = UnorderedMap::new(b"m");
+        map.extend((1u32..=10).map(|i| (i, u64::from(i) * 2)));
+
+        let mut sum = 0;
+        for (_, value) in &map {
+            sum += value;
+        }
+        assert_eq!(sum, 110);
+
+        for (key, value) in &mut map {
+            *value += u64::from(*key);
+        }
+        assert_eq!((&map).into_iter().map(|(_, v)| v).sum::<u64>(), 165);
+    }
+
+    #[test]
+    fn json_serialization() {
+        use crate::env;
+        use crate::store::key::{Sha256, ToKey};
+        use crate::store::serialization::Json;
+
+        let key = Sha256::to_key(b"jm", &2u8, &mut Vec::new());
+        let mut map: UnorderedMap<u8, String, Sha256, Json> =
+            UnorderedMap::with_serialization(b"j");
+        map.insert(1, "a".to_string());
+        map.insert(2, "b".to_string());
+        map.flush();
+        // The value is followed by the little-endian index of its key.
+        assert_eq!(
+            env::storage_read(&key).unwrap(),
+            [br#""b""#.as_slice(), &[1, 0, 0, 0]].concat()
+        );
+
+        let map =
+            UnorderedMap::<u8, String, Sha256, Json>::try_from_slice(&map.try_to_vec().unwrap())
+                .unwrap();
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            [(&1, &"a".to_string()), (&2, &"b".to_string())]
+        );
+
+        // The default format keeps the Borsh layout of the value and key index.
+        let key = Sha256::to_key(b"bm", &2u8, &mut Vec::new());
+        let mut borsh_map: UnorderedMap<u8, String> = UnorderedMap::new(b"b");
+        borsh_map.insert(1, "a".to_string());
+        borsh_map.insert(2, "b".to_string());
+        borsh_map.flush();
+        assert_eq!(env::storage_read(&key).unwrap(), ("b".to_string(), 1u32).try_to_vec().unwrap());
+    }
+
+    #[test]
+    fn debug() {
+        let mut map = UnorderedMap::new(b"m");
+        map.insert(3u8, "c".to_string());
+        map.insert(1u8, "a".to_string());
+        if cfg!(feature = "expensive-debug") {
+            assert_eq!(format!("{:?}", map), r#"{3: "c", 1: "a"}"#);
+        } else {
+            assert!(format!("{:?}", map).starts_with("UnorderedMap { keys: "));
+        }
+    }
+
+    #[cfg(not(feature = "expensive-debug"))]
+    #[test]
+    fn debug_without_clone_keys() {
+        #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, PartialOrd, Ord, Debug)]
+        struct Key(u8);
+        #[derive(BorshSerialize, BorshDeserialize)]
+        struct Value(u8);
+
+        let map: UnorderedMap<Key, Value> = UnorderedMap::new(b"m");
+        assert!(format!("{:?}", map).starts_with("UnorderedMap { keys: "));
+    }
+
+    #[test]
+    fn clone_to() {
+        let mut map = UnorderedMap::new(b"a");
+        map.extend((0u32..10).map(|i| (i, i * 2)));
+        map.flush();
+
+        let mut copy = map.clone_to(b"b");
+        assert_eq!(copy.iter().collect::<Vec<_>>(), map.iter().collect::<Vec<_>>());
+        copy.insert(0, 100);
+        copy.remove(&1);
+        copy.flush();
+
+        let original: UnorderedMap<u32, u32> =
+            UnorderedMap::try_from_slice(&map.try_to_vec().unwrap()).unwrap();
+        assert_eq!(original.len(), 10);
+        assert_eq!(original[&0], 0);
+        assert_eq!(original[&1], 2);
+        let copy: UnorderedMap<u32, u32> =
+            UnorderedMap::try_from_slice(&copy.try_to_vec().unwrap()).unwrap();
+        assert_eq!(copy.len(), 9);
+        assert_eq!(copy[&0], 100);
+        assert_eq!(copy.get(&1), None);
+    }
+
+    #[test]
+    fn drain_clears_storage() {
+        let mut map = UnorderedMap::new(b"m");
+        map.extend((0u32..10).map(|i| (i, i * 2)));
+        map.remove(&3);
+        map.flush();
+
+        let mut drained: Vec<_> = map.drain().collect();
+        drained.sort_unstable();
+        let expected: Vec<_> = (0u32..10).filter(|&i| i != 3).map(|i| (i, i * 2)).collect();
+        assert_eq!(drained, expected);
+        assert!(map.is_empty());
+        assert_eq!(map.len(), 0);
+        assert_eq!(map.get(&0), None);
+
+        map.flush();
+        crate::mock::with_mocked_blockchain(|m| assert!(m.take_storage().is_empty()));
+    }
+
+    #[test]
+    fn drain_early_drop_clears_storage() {
+        let mut map = UnorderedMap::new(b"m");
+        map.extend((0u32..10).map(|i| (i, i * 2)));
+        map.flush();
+
+        {
+            let mut drain = map.drain();
+            assert!(drain.next().is_some());
+            assert!(drain.next_back().is_some());
+        }
+        assert!(map.is_empty());
+        assert_eq!(map.iter().count(), 0);
+        assert!((0u32..10).all(|i| !map.contains_key(&i)));
+
+        map.flush();
+        crate::mock::with_mocked_blockchain(|m| assert!(m.take_storage().is_empty()));
+    }
+
+    #[test]
+    fn from_iter_with_prefix() {
+        setup_free();
+        let entries = (0..2000u32).map(|i| (i % 1000, i));
+        let mut map = UnorderedMap::from_iter_with_prefix(b"m", entries);
+        assert_eq!(map.len(), 1000);
+        map.flush();
+
+        map.extend((1000..1500).map(|i| (i, i)));
+        assert_eq!(map.len(), 1500);
+        let actual: BTreeMap<u32, u32> = map.iter().map(|(k, v)| (*k, *v)).collect();
+        let expected: BTreeMap<u32, u32> =
+            (0..1500).map(|i| (i, if i < 1000 { i + 1000 } else { i })).collect();
+        assert_eq!(actual, expected);
+    }
 
     #[test]
     fn basic_functionality() {
@@ -717,6 +1085,35 @@ mod tests {
         assert_eq!(map.len(), 0);
     }
 
+    #[test]
+    fn iter_sorted_matches_btree_map() {
+        let mut map = UnorderedMap::new(b"s");
+        let mut baseline = BTreeMap::new();
+        let mut rng = rand_xorshift::XorShiftRng::seed_from_u64(3);
+        for _ in 0..500 {
+            let key = rng.next_u32() % 100;
+            if rng.next_u32() % 3 == 0 {
+                assert_eq!(map.remove(&key), baseline.remove(&key));
+            } else {
+                let value = rng.next_u64();
+                assert_eq!(map.insert(key, value), baseline.insert(key, value));
+            }
+        }
+
+        let sorted: Vec<_> = map.iter_sorted().collect();
+        let expected: Vec<_> = baseline.iter().collect();
+        assert_eq!(sorted, expected);
+
+        // The order is independent of the swap-remove history of the map.
+        map.flush();
+        let mut reloaded: UnorderedMap<u32, u64> = UnorderedMap::new(b"r");
+        for (k, v) in baseline.iter().rev() {
+            reloaded.insert(*k, *v);
+        }
+        assert!(reloaded.iter().map(|(k, _)| *k).ne(map.iter().map(|(k, _)| *k)));
+        assert!(reloaded.iter_sorted().eq(map.iter_sorted()));
+    }
+
     #[test]
     fn entry_api() {
         let mut map = UnorderedMap::new(b"b");
@@ -733,6 +1130,76 @@ mod tests {
         assert_eq!(*value, 12);
     }
 
+    #[test]
+    fn entry_updates_len() {
+        let mut map = UnorderedMap::new(b"b");
+        let mut baseline = HashMap::new();
+        for key in [1u8, 2, 1, 3, 1, 2] {
+            let value = *map.entry(key).and_modify(|v| *v *= 2).or_insert(1u32);
+            let expected = *baseline.entry(key).and_modify(|v| *v *= 2).or_insert(1u32);
+            assert_eq!(value, expected);
+            assert_eq!(map.len() as usize, baseline.len());
+        }
+
+        // Occupied entries should not insert or change the length.
+        map.entry(1).or_insert_with(|| panic!("entry is occupied"));
+        assert_eq!(map.len(), 3);
+
+        let mut entries: Vec<_> = map.iter().map(|(&k, &v)| (k, v)).collect();
+        entries.sort();
+        assert_eq!(entries, [(1, 4), (2, 2), (3, 1)]);
+    }
+
+    #[test]
+    fn entry_single_storage_read() {
+        let mut map = UnorderedMap::new(b"b");
+        map.insert(1u8, 1u8);
+        map.flush();
+        let serialized = map.try_to_vec().unwrap();
+        drop(map);
+
+        let fresh = UnorderedMap::<u8, u8>::try_from_slice(&serialized).unwrap();
+        let start = crate::env::used_gas();
+        assert_eq!(fresh.get(&1), Some(&1));
+        let single_read = crate::env::used_gas() - start;
+
+        let mut fresh = UnorderedMap::<u8, u8>::try_from_slice(&serialized).unwrap();
+        let start = crate::env::used_gas();
+        *fresh.entry(1).and_modify(|v| *v += 1).or_insert(0) += 1;
+        assert_eq!(crate::env::used_gas() - start, single_read);
+        assert_eq!(fresh[&1], 3);
+    }
+
+    #[test]
+    fn retain() {
+        let mut map = UnorderedMap::new(b"b");
+        map.extend((0u32..10).map(|x| (x, x)));
+        map.remove(&3);
+
+        map.retain(|k, v| {
+            *v *= 2;
+            k % 2 == 0
+        });
+
+        assert_eq!(map.len(), 5);
+        let mut entries: Vec<_> = map.iter().map(|(&k, &v)| (k, v)).collect();
+        entries.sort();
+        assert_eq!(entries, [(0, 0), (2, 4), (4, 8), (6, 12), (8, 16)]);
+        assert_eq!(map.get(&1), None);
+
+        // Check that the map is consistent after being written to and loaded from storage.
+        map.flush();
+        let serialized = map.try_to_vec().unwrap();
+        let mut map = UnorderedMap::<u32, u32>::try_from_slice(&serialized).unwrap();
+        assert_eq!(map.len(), 5);
+        assert_eq!(map.keys().count(), 5);
+        map.insert(1, 1);
+        map.defrag();
+        let mut keys: Vec<_> = map.keys().copied().collect();
+        keys.sort();
+        assert_eq!(keys, [0, 1, 2, 4, 6, 8]);
+    }
+
     #[test]
     fn map_iterator() {
         let mut map = UnorderedMap::new(b"b");
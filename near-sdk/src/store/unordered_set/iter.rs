@@ -158,6 +158,8 @@ where
     H: ToKey,
 {
     pub(super) fn new(set: &'a UnorderedSet<T, H>, other: &'a UnorderedSet<T, H>) -> Self {
+        // Values are only looked up in the larger set, so that fewer of them are read.
+        let (set, other) = if set.len() <= other.len() { (set, other) } else { (other, set) };
         Self { elements: set.elements.iter(), other }
     }
 }
@@ -104,10 +104,14 @@ where
     H: ToKey,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("UnorderedSet")
-            .field("elements", &self.elements)
-            .field("index", &self.index)
-            .finish()
+        if cfg!(feature = "expensive-debug") {
+            f.debug_set().entries(self.iter()).finish()
+        } else {
+            f.debug_struct("UnorderedSet")
+                .field("elements", &self.elements)
+                .field("index", &self.index)
+                .finish()
+        }
     }
 }
 
@@ -134,6 +138,32 @@ where
     {
         Self::with_hasher(prefix)
     }
+
+    /// Creates a new iterable set with the given prefix and inserts all values of `iter`,
+    /// skipping duplicates.
+    ///
+    /// Like [`Extend`], the values and the length are only kept in memory until the set is
+    /// flushed, so the length is written once regardless of how many values are inserted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::UnorderedSet;
+    ///
+    /// let set = UnorderedSet::from_iter_with_prefix(b"s", [1u8, 2, 1, 3]);
+    /// assert_eq!(set.len(), 3);
+    /// assert!(set.contains(&2));
+    /// ```
+    pub fn from_iter_with_prefix<S, I>(prefix: S, iter: I) -> Self
+    where
+        S: IntoStorageKey,
+        I: IntoIterator<Item = T>,
+        T: BorshDeserialize + Clone,
+    {
+        let mut set = Self::new(prefix);
+        set.extend(iter);
+        set
+    }
 }
 
 impl<T, H> UnorderedSet<T, H>
@@ -160,6 +190,34 @@ where
         Self { elements: FreeList::new(vec_key), index: LookupMap::with_hasher(map_key) }
     }
 
+    /// Copies all elements of the set to a new set with the given prefix and the same hasher.
+    ///
+    /// This reads every element and writes it under the new prefix when the copy is flushed,
+    /// so it costs `O(n)` storage reads and writes. Both sets are independent afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::UnorderedSet;
+    ///
+    /// let mut set: UnorderedSet<String> = UnorderedSet::new(b"a");
+    /// set.insert("a".to_string());
+    ///
+    /// let mut copy = set.clone_to(b"b");
+    /// copy.insert("b".to_string());
+    /// assert_eq!(set.len(), 1);
+    /// assert!(copy.contains("a"));
+    /// ```
+    pub fn clone_to<S>(&self, new_prefix: S) -> Self
+    where
+        S: IntoStorageKey,
+        T: BorshDeserialize + Clone,
+    {
+        let mut set = Self::with_hasher(new_prefix);
+        set.extend(self.iter().cloned());
+        set
+    }
+
     /// Returns the number of elements in the set.
     pub fn len(&self) -> u32 {
         self.elements.len()
@@ -183,6 +241,9 @@ where
     /// Visits the values representing the difference, i.e., the values that are in `self` but not
     /// in `other`.
     ///
+    /// The iterator is lazy: every value of `self` is read from storage, and looked up in `other`,
+    /// as it is visited, so a full iteration costs O(n) reads over the length of `self`.
+    ///
     /// # Examples
     ///
     /// ```
@@ -213,6 +274,9 @@ where
     /// Visits the values representing the symmetric difference, i.e., the values that are in
     /// `self` or in `other` but not in both.
     ///
+    /// The iterator is lazy: every value of both sets is read from storage and looked up in the other
+    /// set as it is visited, so a full iteration costs O(n + m) reads.
+    ///
     /// # Examples
     ///
     /// ```
@@ -246,6 +310,9 @@ where
     /// Visits the values representing the intersection, i.e., the values that are both in `self`
     /// and `other`.
     ///
+    /// The iterator is lazy and visits the values of the smaller set, looking each of them up in the
+    /// larger one, so a full iteration costs O(min(n, m)) reads.
+    ///
     /// # Examples
     ///
     /// ```
@@ -276,6 +343,9 @@ where
     /// Visits the values representing the union, i.e., all the values in `self` or `other`, without
     /// duplicates.
     ///
+    /// The iterator is lazy: the values of `self` are visited first, then the values of `other`
+    /// which are looked up in `self`, so a full iteration costs O(n + m) reads.
+    ///
     /// # Examples
     ///
     /// ```
@@ -516,6 +586,56 @@ mod tests {
     use rand::SeedableRng;
     use std::collections::HashSet;
 
+    #[test]
+    fn test_debug() {
+        let mut set = UnorderedSet::new(b"s");
+        set.insert("b".to_string());
+        set.insert("a".to_string());
+        if cfg!(feature = "expensive-debug") {
+            assert_eq!(format!("{:?}", set), r#"{"b", "a"}"#);
+        } else {
+            assert!(format!("{:?}", set).starts_with("UnorderedSet { elements: "));
+        }
+    }
+
+    #[test]
+    fn test_clone_to() {
+        let mut set = UnorderedSet::new(b"a");
+        set.extend(0u32..10);
+        set.flush();
+
+        let mut copy = set.clone_to(b"b");
+        assert_eq!(copy.iter().collect::<Vec<_>>(), set.iter().collect::<Vec<_>>());
+        copy.remove(&0);
+        copy.insert(10);
+        copy.flush();
+
+        let original: UnorderedSet<u32> =
+            UnorderedSet::try_from_slice(&set.try_to_vec().unwrap()).unwrap();
+        assert_eq!(original.len(), 10);
+        assert!(original.contains(&0));
+        assert!(!original.contains(&10));
+        let copy: UnorderedSet<u32> =
+            UnorderedSet::try_from_slice(&copy.try_to_vec().unwrap()).unwrap();
+        assert_eq!(copy.len(), 10);
+        assert!(!copy.contains(&0));
+        assert!(copy.contains(&10));
+    }
+
+    #[test]
+    fn from_iter_with_prefix() {
+        setup_free();
+        let mut set = UnorderedSet::from_iter_with_prefix(b"s", (0..2000u32).map(|i| i % 1000));
+        assert_eq!(set.len(), 1000);
+        set.flush();
+
+        set.extend(500..1500);
+        assert_eq!(set.len(), 1500);
+        let mut values: Vec<u32> = set.iter().copied().collect();
+        values.sort_unstable();
+        assert_eq!(values, (0..1500).collect::<Vec<_>>());
+    }
+
     #[test]
     fn basic_functionality() {
         let mut set = UnorderedSet::new(b"b");
@@ -704,6 +824,26 @@ mod tests {
         assert_eq!(set1.intersection(&set2).collect::<HashSet<_>>(), HashSet::new());
     }
 
+    #[test]
+    fn test_intersection_visits_smaller_set() {
+        let mut large = UnorderedSet::new(b"m");
+        large.extend(0..50u32);
+
+        let mut small = UnorderedSet::new(b"n");
+        small.insert(3);
+        small.insert(100);
+
+        for intersection in [large.intersection(&small), small.intersection(&large)] {
+            assert_eq!(intersection.size_hint(), (0, Some(2)));
+            assert_eq!(intersection.collect::<Vec<_>>(), [&3]);
+        }
+
+        // Disjoint sets.
+        small.remove(&3);
+        assert_eq!(large.intersection(&small).next(), None);
+        assert_eq!(small.intersection(&large).next(), None);
+    }
+
     #[test]
     fn test_union() {
         let mut set1 = UnorderedSet::new(b"m");
@@ -1,45 +1,44 @@
-use borsh::{BorshDeserialize, BorshSerialize};
-
 use super::iter::{Iter, IterMut};
 use super::{Vector, ERR_INDEX_OUT_OF_BOUNDS};
 use crate::env;
+use crate::store::serialization::{Deserialization, Serialization};
 
-impl<T> Drop for Vector<T>
+impl<T, E> Drop for Vector<T, E>
 where
-    T: BorshSerialize,
+    E: Serialization<T>,
 {
     fn drop(&mut self) {
         self.flush()
     }
 }
 
-impl<'a, T> IntoIterator for &'a Vector<T>
+impl<'a, T, E> IntoIterator for &'a Vector<T, E>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     type Item = &'a T;
-    type IntoIter = Iter<'a, T>;
+    type IntoIter = Iter<'a, T, E>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.iter()
     }
 }
 
-impl<'a, T> IntoIterator for &'a mut Vector<T>
+impl<'a, T, E> IntoIterator for &'a mut Vector<T, E>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     type Item = &'a mut T;
-    type IntoIter = IterMut<'a, T>;
+    type IntoIter = IterMut<'a, T, E>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.iter_mut()
     }
 }
 
-impl<T> Extend<T> for Vector<T>
+impl<T, E> Extend<T> for Vector<T, E>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     fn extend<I>(&mut self, iter: I)
     where
@@ -51,9 +50,9 @@ where
     }
 }
 
-impl<T> core::ops::Index<u32> for Vector<T>
+impl<T, E> core::ops::Index<u32> for Vector<T, E>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     type Output = T;
 
@@ -62,9 +61,9 @@ where
     }
 }
 
-impl<T> core::ops::IndexMut<u32> for Vector<T>
+impl<T, E> core::ops::IndexMut<u32> for Vector<T, E>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     fn index_mut(&mut self, index: u32) -> &mut Self::Output {
         self.get_mut(index).unwrap_or_else(|| env::panic_str(ERR_INDEX_OUT_OF_BOUNDS))
@@ -1,26 +1,26 @@
-use borsh::{BorshDeserialize, BorshSerialize};
 use core::{iter::FusedIterator, ops::Range};
 
 use super::{Vector, ERR_INDEX_OUT_OF_BOUNDS};
 use crate::env;
+use crate::store::serialization::{Borsh, Deserialization};
 
 /// An iterator over references to each element in the stored vector.
 #[derive(Debug)]
-pub struct Iter<'a, T>
+pub struct Iter<'a, T, E = Borsh>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     /// Underlying vector to iterate through
-    vec: &'a Vector<T>,
+    vec: &'a Vector<T, E>,
     /// Range of indices to iterate.
     range: Range<u32>,
 }
 
-impl<'a, T> Iter<'a, T>
+impl<'a, T, E> Iter<'a, T, E>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
-    pub(super) fn new(vec: &'a Vector<T>) -> Self {
+    pub(super) fn new(vec: &'a Vector<T, E>) -> Self {
         Self { vec, range: Range { start: 0, end: vec.len() } }
     }
 
@@ -30,9 +30,9 @@ where
     }
 }
 
-impl<'a, T> Iterator for Iter<'a, T>
+impl<'a, T, E> Iterator for Iter<'a, T, E>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     type Item = &'a T;
 
@@ -55,12 +55,12 @@ where
     }
 }
 
-impl<'a, T> ExactSizeIterator for Iter<'a, T> where T: BorshSerialize + BorshDeserialize {}
-impl<'a, T> FusedIterator for Iter<'a, T> where T: BorshSerialize + BorshDeserialize {}
+impl<'a, T, E> ExactSizeIterator for Iter<'a, T, E> where E: Deserialization<T> {}
+impl<'a, T, E> FusedIterator for Iter<'a, T, E> where E: Deserialization<T> {}
 
-impl<'a, T> DoubleEndedIterator for Iter<'a, T>
+impl<'a, T, E> DoubleEndedIterator for Iter<'a, T, E>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
         <Self as DoubleEndedIterator>::nth_back(self, 0)
@@ -72,24 +72,115 @@ where
     }
 }
 
+/// An iterator over the elements of a stored vector in chunks of owned copies.
+///
+/// This `struct` is created by the [`chunks`] method on [`Vector`].
+/// See its documentation for more.
+///
+/// [`chunks`]: Vector::chunks
+#[derive(Debug)]
+pub struct Chunks<'a, T, E = Borsh>
+where
+    E: Deserialization<T>,
+{
+    /// Underlying vector to iterate through
+    vec: &'a Vector<T, E>,
+    /// Range of indices left to read from the vector.
+    range: Range<u32>,
+    /// Maximum amount of elements in a chunk.
+    size: usize,
+    /// Lookup key buffer reused for every read.
+    key_buf: Vec<u8>,
+}
+
+impl<'a, T, E> Chunks<'a, T, E>
+where
+    T: Clone,
+    E: Deserialization<T>,
+{
+    pub(super) fn new(vec: &'a Vector<T, E>, size: usize) -> Self {
+        if size == 0 {
+            env::panic_str("chunk size must be non-zero");
+        }
+        Self {
+            vec,
+            range: Range { start: 0, end: vec.len() },
+            size,
+            key_buf: Vec::with_capacity(vec.values.prefix.len() + 4),
+        }
+    }
+
+    /// Returns number of chunks left to iterate.
+    fn remaining(&self) -> usize {
+        let len = self.range.len();
+        len / self.size + (len % self.size != 0) as usize
+    }
+}
+
+impl<'a, T, E> Iterator for Chunks<'a, T, E>
+where
+    T: Clone,
+    E: Deserialization<T>,
+{
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.range.is_empty() {
+            return None;
+        }
+        let mut chunk = Vec::with_capacity(self.size.min(self.range.len()));
+        for idx in self.range.by_ref().take(self.size) {
+            let value = self
+                .vec
+                .values
+                .get_uncached(idx, &mut self.key_buf)
+                .unwrap_or_else(|| env::panic_str(ERR_INDEX_OUT_OF_BOUNDS));
+            chunk.push(value);
+        }
+        Some(chunk)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining();
+        (remaining, Some(remaining))
+    }
+
+    fn count(self) -> usize {
+        self.remaining()
+    }
+}
+
+impl<'a, T, E> ExactSizeIterator for Chunks<'a, T, E>
+where
+    T: Clone,
+    E: Deserialization<T>,
+{
+}
+impl<'a, T, E> FusedIterator for Chunks<'a, T, E>
+where
+    T: Clone,
+    E: Deserialization<T>,
+{
+}
+
 /// An iterator over exclusive references to each element of a stored vector.
 #[derive(Debug)]
-pub struct IterMut<'a, T>
+pub struct IterMut<'a, T, E = Borsh>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     /// Mutable reference to vector used to iterate through.
-    vec: &'a mut Vector<T>,
+    vec: &'a mut Vector<T, E>,
     /// Range of indices to iterate.
     range: Range<u32>,
 }
 
-impl<'a, T> IterMut<'a, T>
+impl<'a, T, E> IterMut<'a, T, E>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     /// Creates a new iterator for the given storage vector.
-    pub(crate) fn new(vec: &'a mut Vector<T>) -> Self {
+    pub(crate) fn new(vec: &'a mut Vector<T, E>) -> Self {
         let end = vec.len();
         Self { vec, range: Range { start: 0, end } }
     }
@@ -100,9 +191,9 @@ where
     }
 }
 
-impl<'a, T> IterMut<'a, T>
+impl<'a, T, E> IterMut<'a, T, E>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     fn get_mut<'b>(&'b mut self, at: u32) -> Option<&'a mut T> {
         self.vec.get_mut(at).map(|value| {
@@ -115,9 +206,9 @@ where
     }
 }
 
-impl<'a, T> Iterator for IterMut<'a, T>
+impl<'a, T, E> Iterator for IterMut<'a, T, E>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     type Item = &'a mut T;
 
@@ -140,12 +231,12 @@ where
     }
 }
 
-impl<'a, T> ExactSizeIterator for IterMut<'a, T> where T: BorshSerialize + BorshDeserialize {}
-impl<'a, T> FusedIterator for IterMut<'a, T> where T: BorshSerialize + BorshDeserialize {}
+impl<'a, T, E> ExactSizeIterator for IterMut<'a, T, E> where E: Deserialization<T> {}
+impl<'a, T, E> FusedIterator for IterMut<'a, T, E> where E: Deserialization<T> {}
 
-impl<'a, T> DoubleEndedIterator for IterMut<'a, T>
+impl<'a, T, E> DoubleEndedIterator for IterMut<'a, T, E>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
         <Self as DoubleEndedIterator>::nth_back(self, 0)
@@ -157,26 +248,26 @@ where
     }
 }
 
-/// A draining iterator for [`Vector<T>`].
+/// A draining iterator for [`Vector<T, E>`].
 #[derive(Debug)]
-pub struct Drain<'a, T>
+pub struct Drain<'a, T, E = Borsh>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     /// Mutable reference to vector used to iterate through.
-    vec: &'a mut Vector<T>,
+    vec: &'a mut Vector<T, E>,
     /// Range of indices to iterate.
     range: Range<u32>,
     /// Range of elements to delete.
     delete_range: Range<u32>,
 }
 
-impl<'a, T> Drain<'a, T>
+impl<'a, T, E> Drain<'a, T, E>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     /// Creates a new iterator for the given storage vector.
-    pub(crate) fn new(vec: &'a mut Vector<T>, range: Range<u32>) -> Self {
+    pub(crate) fn new(vec: &'a mut Vector<T, E>, range: Range<u32>) -> Self {
         Self { vec, delete_range: range.clone(), range }
     }
 
@@ -194,9 +285,9 @@ where
     }
 }
 
-impl<'a, T> Drop for Drain<'a, T>
+impl<'a, T, E> Drop for Drain<'a, T, E>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     fn drop(&mut self) {
         let delete_indices = (self.delete_range.start..self.range.start)
@@ -209,6 +300,10 @@ where
 
         // Shift values after delete into slots deleted.
         let shift_len = self.delete_range.len() as u32;
+        if shift_len == 0 {
+            // Nothing was removed, so avoid touching the tail of the vector.
+            return;
+        }
         for i in self.delete_range.end..self.vec.len() {
             self.vec.swap(i, i - shift_len);
         }
@@ -218,9 +313,9 @@ where
     }
 }
 
-impl<'a, T> Iterator for Drain<'a, T>
+impl<'a, T, E> Iterator for Drain<'a, T, E>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     type Item = T;
 
@@ -252,12 +347,12 @@ where
     }
 }
 
-impl<'a, T> ExactSizeIterator for Drain<'a, T> where T: BorshSerialize + BorshDeserialize {}
-impl<'a, T> FusedIterator for Drain<'a, T> where T: BorshSerialize + BorshDeserialize {}
+impl<'a, T, E> ExactSizeIterator for Drain<'a, T, E> where E: Deserialization<T> {}
+impl<'a, T, E> FusedIterator for Drain<'a, T, E> where E: Deserialization<T> {}
 
-impl<'a, T> DoubleEndedIterator for Drain<'a, T>
+impl<'a, T, E> DoubleEndedIterator for Drain<'a, T, E>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
         let delete_idx = self.range.next_back()?;
@@ -56,13 +56,15 @@ mod impls;
 mod iter;
 
 use std::{
+    cmp::Ordering,
     fmt,
     ops::{Bound, Range, RangeBounds},
 };
 
 use borsh::{BorshDeserialize, BorshSerialize};
 
-pub use self::iter::{Drain, Iter, IterMut};
+pub use self::iter::{Chunks, Drain, Iter, IterMut};
+use super::serialization::{Borsh, Deserialization, Serialization};
 use super::ERR_INCONSISTENT_STATE;
 use crate::{env, IntoStorageKey};
 
@@ -89,6 +91,10 @@ fn expect_consistent_state<T>(val: Option<T>) -> T {
 /// This type should be a drop in replacement for [`Vec`] in most cases and will provide contracts
 /// a vector structure which scales much better as the contract data grows.
 ///
+/// Elements are serialized with [`Borsh`] by default. Another format, such as
+/// [`Json`](crate::store::serialization::Json), can be selected with the `E` type parameter and
+/// [`Vector::with_serialization`].
+///
 /// # Examples
 /// ```
 /// use near_sdk::store::Vector;
@@ -111,19 +117,19 @@ fn expect_consistent_state<T>(val: Option<T>) -> T {
 /// vec.extend([1, 2, 3].iter().copied());
 /// assert!(Iterator::eq(vec.into_iter(), [7, 1, 2, 3].iter()));
 /// ```
-pub struct Vector<T>
+pub struct Vector<T, E = Borsh>
 where
-    T: BorshSerialize,
+    E: Serialization<T>,
 {
     pub(crate) len: u32,
-    pub(crate) values: IndexMap<T>,
+    pub(crate) values: IndexMap<T, E>,
 }
 
 //? Manual implementations needed only because borsh derive is leaking field types
 // https://github.com/near/borsh-rs/issues/41
-impl<T> BorshSerialize for Vector<T>
+impl<T, E> BorshSerialize for Vector<T, E>
 where
-    T: BorshSerialize,
+    E: Serialization<T>,
 {
     fn serialize<W: borsh::maybestd::io::Write>(
         &self,
@@ -135,9 +141,9 @@ where
     }
 }
 
-impl<T> BorshDeserialize for Vector<T>
+impl<T, E> BorshDeserialize for Vector<T, E>
 where
-    T: BorshSerialize,
+    E: Serialization<T>,
 {
     fn deserialize(buf: &mut &[u8]) -> Result<Self, borsh::maybestd::io::Error> {
         Ok(Self {
@@ -157,9 +163,58 @@ fn collections_vec_not_backwards_compatible() {
     assert!(Vector::<u8>::try_from_slice(&v1.try_to_vec().unwrap()).is_err());
 }
 
-impl<T> Vector<T>
+impl<T> Vector<T, Borsh>
 where
     T: BorshSerialize,
+{
+    /// Create new vector with zero elements. Prefixes storage accesss with the prefix provided.
+    ///
+    /// This prefix can be anything that implements [`IntoStorageKey`]. The prefix is used when
+    /// storing and looking up values in storage to ensure no collisions with other collections.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::Vector;
+    ///
+    /// let mut vec: Vector<u8> = Vector::new(b"a");
+    /// ```
+    pub fn new<S>(prefix: S) -> Self
+    where
+        S: IntoStorageKey,
+    {
+        Self::with_serialization(prefix)
+    }
+
+    /// Creates a new vector with the given prefix and appends all elements of `iter` to it.
+    ///
+    /// Like [`Extend`], elements and the length are only kept in memory until the vector is
+    /// flushed, so the length is written once regardless of how many elements are appended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::Vector;
+    ///
+    /// let vec = Vector::from_iter_with_prefix(b"v", 0..10u32);
+    /// assert_eq!(vec.len(), 10);
+    /// assert_eq!(vec[9], 9);
+    /// ```
+    pub fn from_iter_with_prefix<S, I>(prefix: S, iter: I) -> Self
+    where
+        S: IntoStorageKey,
+        I: IntoIterator<Item = T>,
+        T: BorshDeserialize,
+    {
+        let mut vec = Self::new(prefix);
+        vec.extend(iter);
+        vec
+    }
+}
+
+impl<T, E> Vector<T, E>
+where
+    E: Serialization<T>,
 {
     /// Returns the number of elements in the vector, also referred to as its size.
     /// This function returns a `u32` rather than the [`Vec`] equivalent of `usize` to have
@@ -196,25 +251,57 @@ where
         self.len == 0
     }
 
-    /// Create new vector with zero elements. Prefixes storage accesss with the prefix provided.
+    /// Create new vector with zero elements which serializes its elements with the format `E`.
+    /// Prefixes storage access with the prefix provided.
     ///
-    /// This prefix can be anything that implements [`IntoStorageKey`]. The prefix is used when
-    /// storing and looking up values in storage to ensure no collisions with other collections.
+    /// See [`Vector::new`] for creating a vector with the default [`Borsh`] format.
     ///
     /// # Examples
     ///
     /// ```
+    /// use near_sdk::store::serialization::Json;
     /// use near_sdk::store::Vector;
     ///
-    /// let mut vec: Vector<u8> = Vector::new(b"a");
+    /// let mut vec: Vector<String, Json> = Vector::with_serialization(b"a");
+    /// vec.push("test".to_string());
+    /// assert_eq!(vec[0], "test");
     /// ```
-    pub fn new<S>(prefix: S) -> Self
+    pub fn with_serialization<S>(prefix: S) -> Self
     where
         S: IntoStorageKey,
     {
         Self { len: 0, values: IndexMap::new(prefix) }
     }
 
+    /// Copies all elements of the vector to a new vector with the given prefix.
+    ///
+    /// This reads every element and writes it under the new prefix when the copy is flushed,
+    /// so it costs `O(n)` storage reads and writes. Both vectors are independent afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::Vector;
+    ///
+    /// let mut vec = Vector::new(b"a");
+    /// vec.extend([1u8, 2, 3]);
+    ///
+    /// let mut copy = vec.clone_to(b"b");
+    /// copy.push(4);
+    /// assert_eq!(vec.len(), 3);
+    /// assert_eq!(copy.iter().collect::<Vec<_>>(), [&1, &2, &3, &4]);
+    /// ```
+    pub fn clone_to<S>(&self, new_prefix: S) -> Self
+    where
+        S: IntoStorageKey,
+        T: Clone,
+        E: Deserialization<T>,
+    {
+        let mut vec = Self::with_serialization(new_prefix);
+        vec.extend(self.iter().cloned());
+        vec
+    }
+
     /// Removes all elements from the collection. This will remove all storage values for the
     /// length of the [`Vector`].
     ///
@@ -298,11 +385,72 @@ where
             self.len.checked_add(1).unwrap_or_else(|| env::panic_str(ERR_INDEX_OUT_OF_BOUNDS));
         self.set(last_idx, element)
     }
+
+    /// Resizes the vector in place so that its length is `new_len`.
+    ///
+    /// If `new_len` is greater than the length, the vector is extended by clones of `value`.
+    /// Otherwise, the vector is truncated and the removed elements are deleted from storage
+    /// without being loaded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::Vector;
+    ///
+    /// let mut vec = Vector::new(b"v");
+    /// vec.push("hello".to_string());
+    /// vec.resize(3, "world".to_string());
+    /// assert_eq!(vec.iter().collect::<Vec<_>>(), ["hello", "world", "world"]);
+    ///
+    /// vec.resize(1, String::new());
+    /// assert_eq!(vec.iter().collect::<Vec<_>>(), ["hello"]);
+    /// ```
+    pub fn resize(&mut self, new_len: u32, value: T)
+    where
+        T: Clone,
+    {
+        self.resize_with(new_len, || value.clone())
+    }
+
+    /// Resizes the vector in place so that its length is `new_len`.
+    ///
+    /// If `new_len` is greater than the length, the vector is extended by the values returned
+    /// by calling `f`. Otherwise, the vector is truncated and the removed elements are deleted
+    /// from storage without being loaded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::Vector;
+    ///
+    /// let mut vec = Vector::new(b"v");
+    /// let mut next = 0;
+    /// vec.resize_with(4, || {
+    ///     next += 1;
+    ///     next
+    /// });
+    /// assert_eq!(vec.iter().collect::<Vec<_>>(), [&1, &2, &3, &4]);
+    /// ```
+    pub fn resize_with<F>(&mut self, new_len: u32, mut f: F)
+    where
+        F: FnMut() -> T,
+    {
+        if new_len <= self.len {
+            for i in new_len..self.len {
+                self.values.set(i, None);
+            }
+            self.len = new_len;
+        } else {
+            while self.len < new_len {
+                self.push(f());
+            }
+        }
+    }
 }
 
-impl<T> Vector<T>
+impl<T, E> Vector<T, E>
 where
-    T: BorshSerialize + BorshDeserialize,
+    E: Deserialization<T>,
 {
     /// Returns the element by index or `None` if it is not present.
     ///
@@ -349,6 +497,92 @@ where
         self.values.get_mut(index)
     }
 
+    /// Binary searches this sorted vector for the given element.
+    ///
+    /// See [`binary_search_by`](Self::binary_search_by) for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::Vector;
+    ///
+    /// let mut vec: Vector<u32> = Vector::new(b"v");
+    /// vec.extend([1, 3, 5, 7]);
+    ///
+    /// assert_eq!(vec.binary_search(&5), Ok(2));
+    /// assert_eq!(vec.binary_search(&4), Err(2));
+    /// ```
+    pub fn binary_search(&self, x: &T) -> Result<u32, u32>
+    where
+        T: Ord,
+    {
+        self.binary_search_by(|p| p.cmp(x))
+    }
+
+    /// Binary searches this sorted vector with a comparator function.
+    ///
+    /// The comparator function should return whether its argument is [`Less`](Ordering::Less),
+    /// [`Equal`](Ordering::Equal) or [`Greater`](Ordering::Greater) than the desired target,
+    /// consistently with the order of the vector. Only the probed elements are loaded from
+    /// storage, so this reads `O(log n)` elements.
+    ///
+    /// If a matching element is found, returns [`Ok`] with its index. If there are multiple
+    /// matches, any one of them may be returned. If no match is found, returns [`Err`] with the
+    /// index where a matching element could be inserted while keeping the vector sorted, e.g.
+    /// with [`insert`](Self::insert).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::Vector;
+    ///
+    /// let mut vec: Vector<u32> = Vector::new(b"v");
+    /// vec.extend([1, 3, 5, 7]);
+    ///
+    /// let index = vec.binary_search_by(|probe| probe.cmp(&6)).unwrap_or_else(|index| index);
+    /// vec.insert(index, 6);
+    /// assert_eq!(vec.iter().copied().collect::<Vec<_>>(), &[1, 3, 5, 6, 7]);
+    /// ```
+    pub fn binary_search_by<F>(&self, mut f: F) -> Result<u32, u32>
+    where
+        F: FnMut(&T) -> Ordering,
+    {
+        let mut left = 0;
+        let mut right = self.len();
+        while left < right {
+            let mid = left + (right - left) / 2;
+            match f(expect_consistent_state(self.get(mid))) {
+                Ordering::Less => left = mid + 1,
+                Ordering::Greater => right = mid,
+                Ordering::Equal => return Ok(mid),
+            }
+        }
+        Err(left)
+    }
+
+    /// Binary searches this sorted vector with a key extraction function.
+    ///
+    /// See [`binary_search_by`](Self::binary_search_by) for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::Vector;
+    ///
+    /// let mut vec: Vector<(u32, String)> = Vector::new(b"v");
+    /// vec.extend([(1, "a".to_string()), (4, "b".to_string()), (9, "c".to_string())]);
+    ///
+    /// assert_eq!(vec.binary_search_by_key(&4, |&(id, _)| id), Ok(1));
+    /// assert_eq!(vec.binary_search_by_key(&10, |&(id, _)| id), Err(3));
+    /// ```
+    pub fn binary_search_by_key<B, F>(&self, b: &B, mut f: F) -> Result<u32, u32>
+    where
+        F: FnMut(&T) -> B,
+        B: Ord,
+    {
+        self.binary_search_by(|k| f(k).cmp(b))
+    }
+
     pub(crate) fn swap(&mut self, a: u32, b: u32) {
         if a >= self.len() || b >= self.len() {
             env::panic_str(ERR_INDEX_OUT_OF_BOUNDS);
@@ -408,6 +642,40 @@ where
         prev
     }
 
+    /// Inserts an element at `index`, shifting all elements after it to the right.
+    ///
+    /// This is `O(n)`, as every element after `index` is loaded and written back one position
+    /// further. Use [`push`](Self::push) if the order doesn't matter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len` or if the new length exceeds `u32::MAX`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::Vector;
+    ///
+    /// let mut vec: Vector<u8> = Vector::new(b"v");
+    /// vec.extend([1, 2, 3]);
+    ///
+    /// vec.insert(1, 4);
+    /// assert_eq!(vec.iter().copied().collect::<Vec<_>>(), &[1, 4, 2, 3]);
+    ///
+    /// vec.insert(4, 5);
+    /// assert_eq!(vec.iter().copied().collect::<Vec<_>>(), &[1, 4, 2, 3, 5]);
+    /// ```
+    pub fn insert(&mut self, index: u32, element: T) {
+        if index > self.len() {
+            env::panic_str(ERR_INDEX_OUT_OF_BOUNDS);
+        }
+
+        self.push(element);
+        for i in (index + 1..self.len()).rev() {
+            self.values.swap(i - 1, i);
+        }
+    }
+
     /// Inserts a element at `index`, returns an evicted element.
     ///
     /// # Panics
@@ -450,10 +718,42 @@ where
     /// assert_eq!(iterator.next(), Some(&4));
     /// assert_eq!(iterator.next(), None);
     /// ```
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<T, E> {
         Iter::new(self)
     }
 
+    /// Returns an iterator over the [`Vector`] elements in chunks of `size` owned copies, for
+    /// example to process a large vector over several transactions. The last chunk is shorter if
+    /// the length of the vector isn't a multiple of `size`.
+    ///
+    /// Only the elements of a chunk are read from storage when it is yielded, and they are not
+    /// kept in the vector's cache.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::store::Vector;
+    ///
+    /// let mut vec = Vector::new(b"v");
+    /// vec.extend([1, 2, 3, 4, 5]);
+    ///
+    /// let mut chunks = vec.chunks(2);
+    /// assert_eq!(chunks.next(), Some(vec![1, 2]));
+    /// assert_eq!(chunks.next(), Some(vec![3, 4]));
+    /// assert_eq!(chunks.next(), Some(vec![5]));
+    /// assert_eq!(chunks.next(), None);
+    /// ```
+    pub fn chunks(&self, size: usize) -> Chunks<T, E>
+    where
+        T: Clone,
+    {
+        Chunks::new(self, size)
+    }
+
     /// Returns an iterator over the [`Vector`] that allows modifying each value. This iterator
     /// will lazily load any values iterated over from storage.
     ///
@@ -470,7 +770,7 @@ where
     /// }
     /// assert_eq!(vec.iter().copied().collect::<Vec<_>>(), &[3u32, 4, 6]);
     /// ```
-    pub fn iter_mut(&mut self) -> IterMut<T> {
+    pub fn iter_mut(&mut self) -> IterMut<T, E> {
         IterMut::new(self)
     }
 
@@ -501,7 +801,7 @@ where
     /// vec.drain(..);
     /// assert!(vec.is_empty());
     /// ```
-    pub fn drain<R>(&mut self, range: R) -> Drain<T>
+    pub fn drain<R>(&mut self, range: R) -> Drain<T, E>
     where
         R: RangeBounds<u32>,
     {
@@ -526,9 +826,10 @@ where
     }
 }
 
-impl<T> fmt::Debug for Vector<T>
+impl<T, E> fmt::Debug for Vector<T, E>
 where
-    T: BorshSerialize + BorshDeserialize + fmt::Debug,
+    T: fmt::Debug,
+    E: Deserialization<T>,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if cfg!(feature = "expensive-debug") {
@@ -552,6 +853,42 @@ mod tests {
     use super::Vector;
     use crate::{store::IndexMap, test_utils::test_env::setup_free};
 
+    #[test]
+    fn test_for_loop_over_references() {
+        let mut vec = Vector::new(b"v");
+        vec.extend(1u64..=10);
+
+        let mut sum = 0;
+        for value in &vec {
+            sum += value;
+        }
+        assert_eq!(sum, 55);
+
+        for value in &mut vec {
+            *value *= 2;
+        }
+        assert_eq!((&vec).into_iter().sum::<u64>(), 110);
+    }
+
+    #[test]
+    fn test_clone_to() {
+        let mut vec = Vector::new(b"a");
+        vec.extend(0u32..10);
+        vec.flush();
+
+        let mut copy = vec.clone_to(b"b");
+        copy.set(0, 100);
+        copy.push(10);
+        copy.flush();
+
+        let original = Vector::<u32>::try_from_slice(&vec.try_to_vec().unwrap()).unwrap();
+        assert_eq!(original.iter().copied().collect::<Vec<_>>(), (0..10).collect::<Vec<_>>());
+        let copy = Vector::<u32>::try_from_slice(&copy.try_to_vec().unwrap()).unwrap();
+        assert_eq!(copy.len(), 11);
+        assert_eq!(copy[0], 100);
+        assert_eq!(copy.iter().skip(1).copied().collect::<Vec<_>>(), (1..11).collect::<Vec<_>>());
+    }
+
     #[test]
     fn test_push_pop() {
         let mut rng = rand_xorshift::XorShiftRng::seed_from_u64(0);
@@ -618,6 +955,156 @@ mod tests {
         assert_eq!(actual, baseline);
     }
 
+    #[test]
+    pub fn test_swap_remove_positions() {
+        let mut vec = Vector::from_iter_with_prefix(b"v".to_vec(), 0..5u32);
+        assert_eq!(vec.swap_remove(4), 4);
+        assert_eq!(vec.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3]);
+        assert_eq!(vec.swap_remove(0), 0);
+        assert_eq!(vec.iter().copied().collect::<Vec<_>>(), [3, 1, 2]);
+        assert_eq!(vec.swap_remove(1), 1);
+        assert_eq!(vec.iter().copied().collect::<Vec<_>>(), [3, 2]);
+        vec.flush();
+
+        let mut vec = Vector::<u32> { len: vec.len(), values: IndexMap::new(b"v".to_vec()) };
+        assert_eq!(vec.iter().copied().collect::<Vec<_>>(), [3, 2]);
+        assert_eq!(vec.swap_remove(0), 3);
+        assert_eq!(vec.swap_remove(0), 2);
+        assert!(vec.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds")]
+    pub fn test_swap_remove_out_of_bounds() {
+        let mut vec = Vector::from_iter_with_prefix(b"v".to_vec(), 0..3u32);
+        vec.swap_remove(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds")]
+    pub fn test_swap_remove_empty() {
+        let mut vec: Vector<u32> = Vector::new(b"v".to_vec());
+        vec.swap_remove(0);
+    }
+
+    #[test]
+    pub fn test_insert() {
+        let mut vec: Vector<u32> = Vector::new(b"v".to_vec());
+        vec.insert(0, 1);
+        vec.insert(0, 0);
+        vec.insert(2, 3);
+        vec.insert(2, 2);
+        assert_eq!(vec.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3]);
+        vec.flush();
+
+        let mut vec = Vector::<u32> { len: vec.len(), values: IndexMap::new(b"v".to_vec()) };
+        vec.insert(4, 4);
+        vec.insert(1, 10);
+        vec.flush();
+        let vec = Vector::<u32> { len: vec.len(), values: IndexMap::new(b"v".to_vec()) };
+        assert_eq!(vec.iter().copied().collect::<Vec<_>>(), [0, 10, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    pub fn test_insert_matches_vec() {
+        let mut rng = rand_xorshift::XorShiftRng::seed_from_u64(3);
+        let mut vec = Vector::new(b"v".to_vec());
+        let mut baseline = vec![];
+        for _ in 0..200 {
+            let index = rng.gen::<u32>() % (vec.len() + 1);
+            let value = rng.gen::<u64>();
+            vec.insert(index, value);
+            baseline.insert(index as usize, value);
+        }
+        let actual: Vec<_> = vec.iter().cloned().collect();
+        assert_eq!(actual, baseline);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds")]
+    pub fn test_insert_out_of_bounds() {
+        let mut vec = Vector::from_iter_with_prefix(b"v".to_vec(), 0..3u32);
+        vec.insert(4, 0);
+    }
+
+    #[test]
+    pub fn test_binary_search() {
+        let mut vec = Vector::new(b"v");
+        let sorted: Vec<u32> = (0..100).map(|i| i * 2).collect();
+        vec.extend(sorted.iter().copied());
+
+        for x in 0..=200 {
+            let expected = sorted.binary_search(&x).map(|i| i as u32).map_err(|i| i as u32);
+            assert_eq!(vec.binary_search(&x), expected);
+            assert_eq!(vec.binary_search_by(|probe| probe.cmp(&x)), expected);
+            assert_eq!(vec.binary_search_by_key(&x, |&probe| probe), expected);
+        }
+
+        let empty: Vector<u32> = Vector::new(b"e");
+        assert_eq!(empty.binary_search(&1), Err(0));
+    }
+
+    #[test]
+    pub fn test_binary_search_storage_reads() {
+        use crate::test_utils::{storage_reads, VMContextBuilder};
+        use crate::testing_env;
+
+        let n = 1_000u32;
+        let mut vec = Vector::new(b"v");
+        vec.extend(0..n);
+        vec.flush();
+        let serialized = vec.try_to_vec().unwrap();
+        let max_reads = u64::from(u32::BITS - n.leading_zeros());
+
+        for x in [0, 1, n / 3, n - 1, n] {
+            // Load the vector without any cached elements.
+            let vec = Vector::<u32>::try_from_slice(&serialized).unwrap();
+            testing_env!(VMContextBuilder::new().build());
+            assert_eq!(vec.binary_search(&x).is_ok(), x < n);
+            assert!(storage_reads() <= max_reads);
+        }
+    }
+
+    #[test]
+    pub fn test_iter_both_ends() {
+        let mut vec = Vector::new(b"v");
+        vec.extend(0u32..7);
+
+        let mut iter = vec.iter();
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.next_back(), Some(&6));
+        assert_eq!(iter.next_back(), Some(&5));
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next_back(), Some(&4));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.next(), None);
+
+        let mut iter = vec.iter_mut();
+        *iter.next_back().unwrap() = 60;
+        *iter.next().unwrap() = 10;
+        assert_eq!(iter.rev().map(|x| *x).collect::<Vec<_>>(), [5, 4, 3, 2, 1]);
+        assert_eq!(vec.iter().rev().take(2).copied().collect::<Vec<_>>(), [60, 5]);
+    }
+
+    #[test]
+    pub fn test_iter_rev_storage_reads() {
+        use crate::test_utils::{storage_reads, VMContextBuilder};
+        use crate::testing_env;
+
+        let mut vec = Vector::new(b"v");
+        vec.extend(0u32..1_000);
+        vec.flush();
+        let vec = Vector::<u32>::try_from_slice(&vec.try_to_vec().unwrap()).unwrap();
+
+        // Only the last elements are loaded from storage.
+        testing_env!(VMContextBuilder::new().build());
+        assert_eq!(vec.iter().rev().take(3).copied().collect::<Vec<_>>(), [999, 998, 997]);
+        assert_eq!(storage_reads(), 3);
+    }
+
     #[test]
     pub fn test_clear() {
         let mut rng = rand_xorshift::XorShiftRng::seed_from_u64(3);
@@ -633,6 +1120,41 @@ mod tests {
         }
     }
 
+    #[test]
+    pub fn test_resize() {
+        let prefix = b"r".to_vec();
+        let stored =
+            |index: u32| crate::env::storage_has_key(&[&prefix[..], &index.to_le_bytes()].concat());
+
+        let mut vec = Vector::new(prefix.clone());
+        vec.resize(5, 7u64);
+        vec.flush();
+        assert_eq!(vec.iter().copied().collect::<Vec<_>>(), [7; 5]);
+        assert!((0..5).all(stored));
+
+        // Resizing to the same length is a no-op.
+        vec.resize_with(5, || unreachable!());
+        assert_eq!(vec.len(), 5);
+
+        vec.resize(2, 0);
+        vec.flush();
+        assert_eq!(vec.iter().copied().collect::<Vec<_>>(), [7, 7]);
+        assert!((0..2).all(stored));
+        assert!(!(2..5).any(stored));
+
+        let mut next = 0;
+        vec.resize_with(4, || {
+            next += 1;
+            next
+        });
+        assert_eq!(vec.iter().copied().collect::<Vec<_>>(), [7, 7, 1, 2]);
+
+        vec.resize(0, 0);
+        vec.flush();
+        assert!(vec.is_empty());
+        assert!(!(0..5).any(stored));
+    }
+
     #[test]
     pub fn test_extend() {
         let mut rng = rand_xorshift::XorShiftRng::seed_from_u64(0);
@@ -657,6 +1179,92 @@ mod tests {
         assert_eq!(actual, baseline);
     }
 
+    #[test]
+    fn test_chunks() {
+        let mut vec = Vector::new(b"v".to_vec());
+        vec.extend(0..6u64);
+        vec.flush();
+        let mut vec = Vector::<u64> { len: vec.len(), values: IndexMap::new(b"v".to_vec()) };
+        *vec.get_mut(1).unwrap() = 42;
+
+        let chunks: Vec<_> = vec.chunks(3).collect();
+        assert_eq!(chunks, [vec![0, 42, 2], vec![3, 4, 5]]);
+        let chunks: Vec<_> = vec.chunks(4).collect();
+        assert_eq!(chunks, [vec![0, 42, 2, 3], vec![4, 5]]);
+        let chunks: Vec<_> = vec.chunks(10).collect();
+        assert_eq!(chunks, [vec![0, 42, 2, 3, 4, 5]]);
+
+        let mut chunks = vec.chunks(4);
+        assert_eq!(chunks.len(), 2);
+        chunks.next();
+        assert_eq!(chunks.len(), 1);
+        chunks.next();
+        assert_eq!(chunks.len(), 0);
+        assert_eq!(chunks.next(), None);
+
+        // Only the modified element was loaded into the cache.
+        assert_eq!(vec.values.cache.inner().len(), 1);
+
+        let empty = Vector::<u64>::new(b"e".to_vec());
+        assert_eq!(empty.chunks(2).next(), None);
+    }
+
+    #[test]
+    fn test_chunks_max_size() {
+        let mut vec = Vector::new(b"v".to_vec());
+        vec.extend([1u64, 2, 3]);
+        let chunks = vec.chunks(usize::MAX);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks.collect::<Vec<_>>(), [vec![1, 2, 3]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn test_chunks_zero_size() {
+        let vec = Vector::<u64>::new(b"v".to_vec());
+        let _ = vec.chunks(0);
+    }
+
+    #[test]
+    fn test_json_serialization() {
+        use crate::store::serialization::Json;
+
+        let key = [b"j".as_slice(), &0u32.to_le_bytes()].concat();
+        let mut vec: Vector<(u8, String), Json> = Vector::with_serialization(b"j".to_vec());
+        vec.push((1, "a".to_string()));
+        vec.flush();
+        assert_eq!(crate::env::storage_read(&key).unwrap(), br#"[1,"a"]"#);
+
+        let vec = Vector::<(u8, String), Json>::try_from_slice(&vec.try_to_vec().unwrap()).unwrap();
+        assert_eq!(vec[0], (1, "a".to_string()));
+
+        let key = [b"b".as_slice(), &0u32.to_le_bytes()].concat();
+        let mut borsh_vec: Vector<(u8, String)> = Vector::new(b"b".to_vec());
+        borsh_vec.push((1, "a".to_string()));
+        borsh_vec.flush();
+        let borsh_bytes = crate::env::storage_read(&key).unwrap();
+        assert_eq!(borsh_bytes, (1u8, "a".to_string()).try_to_vec().unwrap());
+        assert_ne!(borsh_bytes, br#"[1,"a"]"#);
+
+        let borsh_vec =
+            Vector::<(u8, String)>::try_from_slice(&borsh_vec.try_to_vec().unwrap()).unwrap();
+        assert_eq!(borsh_vec[0], (1, "a".to_string()));
+    }
+
+    #[test]
+    fn test_from_iter_with_prefix() {
+        setup_free();
+        let mut vec = Vector::from_iter_with_prefix(b"v".to_vec(), 0..1000u32);
+        assert_eq!(vec.len(), 1000);
+        // Nothing is written to storage until the vector is flushed.
+        assert!(!crate::env::storage_has_key(&[b"v".as_slice(), &0u32.to_le_bytes()].concat()));
+        vec.flush();
+
+        vec.extend(1000..2000);
+        assert_eq!(vec.len(), 2000);
+        assert!(vec.iter().copied().eq(0..2000));
+    }
+
     #[test]
     fn test_debug() {
         let mut rng = rand_xorshift::XorShiftRng::seed_from_u64(4);
@@ -779,6 +1387,50 @@ mod tests {
         crate::mock::with_mocked_blockchain(|m| assert!(m.take_storage().is_empty()));
     }
 
+    #[test]
+    fn drain_early_drop() {
+        let mut vec = Vector::new(b"v");
+        vec.extend(0u8..10);
+
+        // Dropping without consuming removes the whole range.
+        drop(vec.drain(2..5));
+        assert_eq!(vec.iter().copied().collect::<Vec<_>>(), [0, 1, 5, 6, 7, 8, 9]);
+
+        // Partially consumed from both ends, the rest of the range is removed on drop.
+        {
+            let mut drain = vec.drain(1..6);
+            assert_eq!(drain.next(), Some(1));
+            assert_eq!(drain.next_back(), Some(8));
+        }
+        assert_eq!(vec.iter().copied().collect::<Vec<_>>(), [0, 9]);
+
+        // Empty and out of bounds ranges leave the vector untouched.
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = vec.drain(2..1).count();
+        assert_eq!(reversed, 0);
+        assert_eq!(vec.drain(5..).count(), 0);
+        assert_eq!(vec.drain(1..1).next(), None);
+        assert_eq!(vec.iter().copied().collect::<Vec<_>>(), [0, 9]);
+    }
+
+    #[test]
+    fn drain_persists_removal() {
+        let mut vec = Vector::new(b"v");
+        vec.extend(0u32..6);
+        vec.flush();
+
+        let drained: Vec<_> = vec.drain(..2).collect();
+        assert_eq!(drained, [0, 1]);
+        vec.flush();
+
+        // Reload from storage to make sure the tail was shifted and the length persisted.
+        let serialized = vec.try_to_vec().unwrap();
+        let vec = Vector::<u32>::deserialize(&mut serialized.as_slice()).unwrap();
+        assert_eq!(vec.len(), 4);
+        assert_eq!(vec.iter().copied().collect::<Vec<_>>(), [2, 3, 4, 5]);
+        crate::mock::with_mocked_blockchain(|m| assert_eq!(m.take_storage().len(), 4));
+    }
+
     #[derive(Arbitrary, Debug)]
     enum Op {
         Push(u8),
@@ -1,7 +1,7 @@
 use crate::mock::MockedBlockchain;
 use crate::test_utils::test_env::*;
 use crate::AccountId;
-use crate::{Balance, BlockHeight, EpochHeight, Gas, PromiseResult, PublicKey, StorageUsage};
+use crate::{Balance, EpochHeight, Gas, PromiseResult, PublicKey, StorageUsage};
 use near_primitives_core::runtime::fees::RuntimeFeesConfig;
 use near_vm_logic::{VMConfig, ViewConfig};
 use std::convert::TryInto;
@@ -17,6 +17,7 @@ pub fn accounts(id: usize) -> AccountId {
 #[derive(Clone)]
 pub struct VMContextBuilder {
     pub context: VMContext,
+    promise_results: Vec<PromiseResult>,
 }
 
 impl Default for VMContextBuilder {
@@ -45,7 +46,7 @@ pub struct VMContext {
     /// Encoded as base64 string to be able to pass input in borsh binary format.
     pub input: Vec<u8>,
     /// The current block height.
-    pub block_index: BlockHeight,
+    pub block_index: u64,
     /// The current block timestamp (number of non-leap-nanoseconds since January 1, 1970 0:00:00 UTC).
     pub block_timestamp: u64,
     /// The current epoch height.
@@ -102,6 +103,7 @@ impl VMContextBuilder {
                 view_config: None,
                 output_data_receivers: vec![],
             },
+            promise_results: vec![],
         }
     }
 
@@ -126,12 +128,12 @@ impl VMContextBuilder {
     }
 
     #[deprecated(since = "4.1.2", note = "Use `block_height` method instead")]
-    pub fn block_index(&mut self, block_index: BlockHeight) -> &mut Self {
+    pub fn block_index(&mut self, block_index: u64) -> &mut Self {
         self.context.block_index = block_index;
         self
     }
 
-    pub fn block_height(&mut self, block_height: BlockHeight) -> &mut Self {
+    pub fn block_height(&mut self, block_height: u64) -> &mut Self {
         self.context.block_index = block_height;
         self
     }
@@ -182,9 +184,39 @@ impl VMContextBuilder {
         self
     }
 
+    /// Sets the promise results returned by [`build_with_results`](Self::build_with_results)
+    /// along with the context.
+    pub fn promise_results(&mut self, promise_results: Vec<PromiseResult>) -> &mut Self {
+        self.promise_results = promise_results;
+        self
+    }
+
     pub fn build(&self) -> VMContext {
         self.context.clone()
     }
+
+    /// Builds the context along with the promise results set, which are not part of
+    /// [`VMContext`] and are passed to [`testing_env!`](crate::testing_env) separately.
+    ///
+    /// # Example
+    /// ```
+    /// use near_sdk::test_utils::VMContextBuilder;
+    /// use near_sdk::{testing_env, PromiseResult, RuntimeFeesConfig, VMConfig};
+    ///
+    /// let (context, promise_results) =
+    ///     VMContextBuilder::new().promise_results(vec![PromiseResult::Failed]).build_with_results();
+    /// testing_env!(
+    ///     context,
+    ///     VMConfig::test(),
+    ///     RuntimeFeesConfig::test(),
+    ///     Default::default(),
+    ///     promise_results,
+    /// );
+    /// assert_eq!(near_sdk::env::promise_result(0), PromiseResult::Failed);
+    /// ```
+    pub fn build_with_results(&self) -> (VMContext, Vec<PromiseResult>) {
+        (self.context.clone(), self.promise_results.clone())
+    }
 }
 
 /// Initializes the [`MockedBlockchain`] with a single promise result during execution.
@@ -204,3 +236,56 @@ pub fn testing_env_with_promise_results(context: VMContext, promise_result: Prom
         None,
     ));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::env;
+
+    #[test]
+    fn setters_round_trip() {
+        let mut builder = VMContextBuilder::new();
+        let (context, promise_results) = builder
+            .prepaid_gas(Gas(42))
+            .random_seed([7; 32])
+            .is_view(true)
+            .promise_results(vec![PromiseResult::Failed])
+            .build_with_results();
+        assert_eq!(context.prepaid_gas, Gas(42));
+        assert_eq!(context.random_seed, [7; 32]);
+        assert!(context.is_view());
+        assert_eq!(promise_results, vec![PromiseResult::Failed]);
+
+        let (context, promise_results) =
+            VMContextBuilder::new().is_view(false).build_with_results();
+        assert!(!context.is_view());
+        assert!(promise_results.is_empty());
+    }
+
+    #[test]
+    fn promise_results_used_by_testing_env() {
+        let (context, promise_results) = VMContextBuilder::new()
+            .promise_results(vec![PromiseResult::Successful(b"1".to_vec()), PromiseResult::Failed])
+            .build_with_results();
+        crate::testing_env!(
+            context,
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            promise_results,
+        );
+        assert_eq!(env::promise_results_count(), 2);
+        assert_eq!(env::promise_result(0), PromiseResult::Successful(b"1".to_vec()));
+        assert_eq!(env::promise_result(1), PromiseResult::Failed);
+    }
+
+    #[test]
+    fn context_setters_used_by_testing_env() {
+        crate::testing_env!(VMContextBuilder::new()
+            .prepaid_gas(Gas::ONE_TERA)
+            .random_seed([3; 32])
+            .build());
+        assert_eq!(env::prepaid_gas(), Gas::ONE_TERA);
+        assert_eq!(env::random_seed_array(), [3; 32]);
+    }
+}
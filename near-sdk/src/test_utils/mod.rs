@@ -3,9 +3,11 @@
 pub mod test_env;
 
 pub(crate) mod context;
-use crate::mock::Receipt;
+use crate::mock::{MockAction, Receipt};
+use crate::Gas;
 #[allow(deprecated)]
 pub use context::{accounts, testing_env_with_promise_results, VMContextBuilder};
+use std::collections::BTreeMap;
 
 /// Initializes a testing environment to mock interactions which would otherwise go through a
 /// validator node. This macro will initialize or overwrite the [`MockedBlockchain`]
@@ -93,11 +95,139 @@ pub fn get_logs() -> Vec<String> {
     crate::mock::with_mocked_blockchain(|b| b.logs())
 }
 
+/// A [NEP-297](https://nomicon.io/Standards/EventsFormat) event logged by the contract, as
+/// returned by [`get_events`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+#[serde(crate = "serde")]
+pub struct EventLog {
+    pub standard: String,
+    pub version: String,
+    pub event: String,
+    /// The `data` of the event, if any.
+    #[serde(default)]
+    pub data: Option<serde_json::Value>,
+}
+
+impl EventLog {
+    /// Deserializes the `data` of the event, e.g. into the types used to emit it.
+    pub fn parse_data<T: serde::de::DeserializeOwned>(&self) -> serde_json::Result<T> {
+        T::deserialize(self.data.as_ref().unwrap_or(&serde_json::Value::Null))
+    }
+}
+
+/// Returns the events logged so far, i.e. the logs starting with `EVENT_JSON:`, decoded into
+/// [`EventLog`]s. Other logs are skipped. Only available in unit tests.
+///
+/// Panics if an event log isn't valid NEP-297 JSON.
+///
+/// # Examples
+///
+/// ```
+/// use near_sdk::test_utils::{get_events, VMContextBuilder};
+/// use near_sdk::serde_json::json;
+/// use near_sdk::{env, testing_env};
+///
+/// testing_env!(VMContextBuilder::new().build());
+/// env::log_str("not an event");
+/// env::log_event("nep171", "1.0.0", "nft_mint", &[json!({ "owner_id": "alice.near" })]);
+///
+/// let events = get_events();
+/// assert_eq!(events.len(), 1);
+/// assert_eq!(events[0].event, "nft_mint");
+/// assert_eq!(events[0].data, Some(json!([{ "owner_id": "alice.near" }])));
+/// ```
+pub fn get_events() -> Vec<EventLog> {
+    get_logs()
+        .iter()
+        .filter_map(|log| log.strip_prefix("EVENT_JSON:"))
+        .map(|json| {
+            serde_json::from_str(json)
+                .unwrap_or_else(|err| panic!("Invalid event log `{}`: {}", json, err))
+        })
+        .collect()
+}
+
 /// Accessing receipts created by the contract. Only available in unit tests.
 pub fn get_created_receipts() -> Vec<Receipt> {
     crate::mock::with_mocked_blockchain(|b| b.created_receipts())
 }
 
+/// Returns the actions scheduled by the contract, flattened across all created receipts in the
+/// order they were scheduled. Only available in unit tests.
+///
+/// # Examples
+///
+/// ```
+/// use near_sdk::test_utils::{accounts, get_created_actions, VMContextBuilder};
+/// use near_sdk::{testing_env, Gas, Promise};
+///
+/// testing_env!(VMContextBuilder::new().build());
+/// Promise::new(accounts(1)).transfer(10u128.pow(24)).function_call(
+///     "ft_resolve_transfer".to_string(),
+///     vec![],
+///     0,
+///     Gas::from_tgas(5),
+/// );
+///
+/// let actions = get_created_actions();
+/// assert_eq!(actions.len(), 2);
+/// assert_eq!(actions[0].receiver_id, accounts(1));
+/// assert_eq!(actions[0].deposit(), Some(10u128.pow(24)));
+/// assert_eq!(actions[1].function_name(), Some("ft_resolve_transfer"));
+/// assert_eq!(actions[1].gas(), Some(Gas::from_tgas(5)));
+/// ```
+pub fn get_created_actions() -> Vec<MockAction> {
+    get_created_receipts()
+        .into_iter()
+        .enumerate()
+        .flat_map(|(index, receipt)| receipt.into_mock_actions(index))
+        .collect()
+}
+
+/// Returns the gas burnt so far by the host functions called since the last
+/// [`testing_env!`](crate::testing_env). Only available in unit tests.
+///
+/// The amount is computed from the costs of the mocked [`VMConfig`](crate::VMConfig) and
+/// [`RuntimeFeesConfig`](crate::RuntimeFeesConfig), so it is only an approximation of the gas
+/// used on chain. It excludes the gas attached to promises and the cost of executing Wasm
+/// instructions, as no Wasm is executed in unit tests.
+///
+/// # Examples
+///
+/// ```
+/// use near_sdk::test_utils::{used_gas, VMContextBuilder};
+/// use near_sdk::{env, testing_env, Gas};
+///
+/// testing_env!(VMContextBuilder::new().build());
+/// env::storage_write(b"key", b"value");
+/// assert!(used_gas() > Gas(0));
+/// assert!(used_gas() < Gas::ONE_TERA * 5);
+/// ```
+pub fn used_gas() -> Gas {
+    crate::mock::with_mocked_blockchain(|b| b.burnt_gas())
+}
+
+/// Returns the gas burnt so far by each kind of host function cost since the last
+/// [`testing_env!`](crate::testing_env), keyed by the name of the cost like `storage_write_base`
+/// or `storage_write_value_byte`. Only available in unit tests.
+///
+/// Like [`used_gas`], the numbers are approximate to the costs of the mocked
+/// [`VMConfig`](crate::VMConfig). The fees burnt for creating receipts are not host function
+/// costs, so they are only included in [`used_gas`].
+pub fn gas_profile() -> BTreeMap<String, Gas> {
+    crate::mock::with_mocked_blockchain(|b| b.gas_profile())
+}
+
+/// Returns the number of storage reads since the last [`testing_env!`](crate::testing_env), e.g.
+/// to check how many entries a collection loads from storage. Only available in unit tests.
+pub fn storage_reads() -> u64 {
+    let mut reads = 0;
+    near_vm_logic::with_ext_cost_counter(|counter| {
+        reads = counter.get(&near_vm_logic::ExtCosts::storage_read_base).copied().unwrap_or(0);
+    });
+    reads
+}
+
 /// Objects stored on the trie directly should have identifiers. If identifier is not provided
 /// explicitly than `Default` trait would use this index to generate an id.
 #[cfg(test)]
@@ -111,3 +241,71 @@ pub(crate) fn next_trie_id() -> Vec<u8> {
         id.to_le_bytes().to_vec()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::LookupMap;
+    use crate::{env, testing_env};
+    use near_vm_logic::ExtCosts;
+
+    fn write_entries(map: &mut LookupMap<u32, Vec<u8>>, count: u32) {
+        for i in 0..count {
+            map.insert(i, vec![0; 100]);
+        }
+        map.flush();
+    }
+
+    #[test]
+    fn used_gas_grows_with_storage_writes() {
+        testing_env!(VMContextBuilder::new().build());
+        assert_eq!(used_gas(), Gas(0));
+        assert!(gas_profile().is_empty());
+
+        let mut map = LookupMap::new(b"m");
+        write_entries(&mut map, 10);
+        let ten_writes = used_gas();
+        assert!(ten_writes > Gas(0));
+        assert!(ten_writes < Gas::ONE_TERA * 5);
+
+        // A new environment starts counting from zero.
+        testing_env!(VMContextBuilder::new().build());
+        let mut map = LookupMap::new(b"n");
+        write_entries(&mut map, 20);
+        assert!(used_gas() > ten_writes);
+    }
+
+    #[test]
+    fn gas_profile_breaks_down_host_costs() {
+        testing_env!(VMContextBuilder::new().build());
+        env::storage_write(b"a", &[1; 10]);
+        env::storage_write(b"b", &[2; 30]);
+
+        let profile = gas_profile();
+        let config = crate::VMConfig::test();
+        let costs = &config.ext_costs;
+        assert_eq!(
+            profile["storage_write_base"].0,
+            2 * costs.gas_cost(ExtCosts::storage_write_base)
+        );
+        assert_eq!(
+            profile["storage_write_value_byte"].0,
+            40 * costs.gas_cost(ExtCosts::storage_write_value_byte)
+        );
+        assert!(!profile.contains_key("storage_read_base"));
+        assert_eq!(profile.values().map(|gas| gas.0).sum::<u64>(), used_gas().0);
+    }
+
+    #[test]
+    fn storage_reads_counts_reads() {
+        testing_env!(VMContextBuilder::new().build());
+        env::storage_write(b"a", &[1]);
+        assert_eq!(storage_reads(), 0);
+        env::storage_read(b"a");
+        env::storage_read(b"b");
+        assert_eq!(storage_reads(), 2);
+
+        testing_env!(VMContextBuilder::new().build());
+        assert_eq!(storage_reads(), 0);
+    }
+}
@@ -55,6 +55,84 @@ impl AccountId {
         debug_assert!(is_valid_account_id(id.as_bytes()));
         Self(id)
     }
+
+    /// Returns `true` if the account ID is a top-level account, i.e. it has no parent account.
+    /// The `system` account is not considered a top-level account.
+    ///
+    /// # Examples
+    /// ```
+    /// use near_sdk::AccountId;
+    ///
+    /// assert!("near".parse::<AccountId>().unwrap().is_top_level());
+    /// assert!(!"alice.near".parse::<AccountId>().unwrap().is_top_level());
+    /// assert!(!"system".parse::<AccountId>().unwrap().is_top_level());
+    /// ```
+    pub fn is_top_level(&self) -> bool {
+        !self.is_system() && !self.0.contains('.')
+    }
+
+    /// Returns `true` if the account ID is a direct subaccount of `parent`, e.g. `alice.near` is a
+    /// subaccount of `near`, but `app.alice.near` is not.
+    ///
+    /// # Examples
+    /// ```
+    /// use near_sdk::AccountId;
+    ///
+    /// let near: AccountId = "near".parse().unwrap();
+    /// let alice: AccountId = "alice.near".parse().unwrap();
+    /// let app: AccountId = "app.alice.near".parse().unwrap();
+    ///
+    /// assert!(alice.is_sub_account_of(&near));
+    /// assert!(app.is_sub_account_of(&alice));
+    /// assert!(!app.is_sub_account_of(&near));
+    /// ```
+    pub fn is_sub_account_of(&self, parent: &AccountId) -> bool {
+        matches!(
+            self.0.strip_suffix(parent.as_str()).and_then(|prefix| prefix.strip_suffix('.')),
+            Some(label) if !label.is_empty() && !label.contains('.')
+        )
+    }
+
+    /// Returns the account ID this account is a direct subaccount of, or `None` if it is not a
+    /// subaccount of any account.
+    ///
+    /// # Examples
+    /// ```
+    /// use near_sdk::AccountId;
+    ///
+    /// let app: AccountId = "app.alice.near".parse().unwrap();
+    /// assert_eq!(app.parent_account_id(), Some("alice.near".parse().unwrap()));
+    /// assert_eq!("near".parse::<AccountId>().unwrap().parent_account_id(), None);
+    /// ```
+    pub fn parent_account_id(&self) -> Option<AccountId> {
+        self.0.split_once('.').map(|(_, parent)| Self(parent.to_string()))
+    }
+
+    /// Validates all the given account IDs, stopping at the first invalid one. On failure,
+    /// returns the index of the invalid ID along with the error.
+    ///
+    /// # Examples
+    /// ```
+    /// use near_sdk::AccountId;
+    ///
+    /// let ids = AccountId::validate_batch(&["alice.near", "bob.near"]).unwrap();
+    /// assert_eq!(ids[1].as_str(), "bob.near");
+    ///
+    /// let (index, _) = AccountId::validate_batch(&["alice.near", "Bob.near", "a"]).unwrap_err();
+    /// assert_eq!(index, 1);
+    /// ```
+    pub fn validate_batch(ids: &[&str]) -> Result<Vec<AccountId>, (usize, ParseAccountIdError)> {
+        let mut account_ids = Vec::with_capacity(ids.len());
+        for (index, id) in ids.iter().enumerate() {
+            validate_account_id(id).map_err(|err| (index, err))?;
+            account_ids.push(Self(id.to_string()));
+        }
+        Ok(account_ids)
+    }
+
+    fn is_system(&self) -> bool {
+        self.0 == "system"
+    }
 }
 
 impl fmt::Display for AccountId {
@@ -157,6 +235,21 @@ mod tests {
         assert_eq!(key.as_ref(), &"alice.near".to_string());
     }
 
+    #[test]
+    fn validate_batch() {
+        assert_eq!(AccountId::validate_batch(&[]), Ok(vec![]));
+
+        let ids = ["alice.near", "bob.near", "0123456789abcdef0123456789abcdef"];
+        let account_ids = AccountId::validate_batch(&ids).unwrap();
+        assert_eq!(account_ids.iter().map(AccountId::as_str).collect::<Vec<_>>(), ids);
+
+        let index = |ids: &[&str]| AccountId::validate_batch(ids).unwrap_err().0;
+        assert_eq!(index(&["Alice.near", "bob.near"]), 0);
+        assert_eq!(index(&["alice.near", "bob.near", "invalid."]), 2);
+        // Only the first invalid ID is reported.
+        assert_eq!(index(&["alice.near", "a", "bob..near", "carol.near"]), 1);
+    }
+
     #[test]
     fn borsh_serialize_impl() {
         let id = "test.near";
@@ -165,4 +258,39 @@ mod tests {
         // Test to make sure the account ID is serialized as a string through borsh
         assert_eq!(str::try_to_vec(id).unwrap(), account_id.try_to_vec().unwrap());
     }
+
+    #[test]
+    fn sub_account_helpers() {
+        let id = |s: &str| s.parse::<AccountId>().unwrap();
+
+        // Implicit accounts are top-level accounts.
+        let implicit = id("0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef");
+        assert!(implicit.is_top_level());
+        assert_eq!(implicit.parent_account_id(), None);
+
+        // The system account is neither a top-level account nor a subaccount.
+        let system = id("system");
+        assert!(!system.is_top_level());
+        assert_eq!(system.parent_account_id(), None);
+        assert!(!id("near").is_sub_account_of(&system));
+
+        let nested = id("a.b-c.d_e.alice.near");
+        assert!(!nested.is_top_level());
+        assert!(nested.is_sub_account_of(&id("b-c.d_e.alice.near")));
+        assert!(!nested.is_sub_account_of(&id("alice.near")));
+        assert!(!nested.is_sub_account_of(&nested));
+        let mut parents = vec![];
+        let mut current = nested;
+        while let Some(parent) = current.parent_account_id() {
+            assert!(current.is_sub_account_of(&parent));
+            parents.push(parent.to_string());
+            current = parent;
+        }
+        assert_eq!(parents, ["b-c.d_e.alice.near", "d_e.alice.near", "alice.near", "near"]);
+        assert!(current.is_top_level());
+
+        // Labels must match exactly, not just as a string suffix.
+        assert!(!id("alice.near").is_sub_account_of(&id("ear")));
+        assert!(!id("xnear").is_sub_account_of(&id("near")));
+    }
 }
@@ -0,0 +1,217 @@
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use core::ops;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Height of a block, as returned by [`env::block_height`](crate::env::block_height).
+///
+/// Using this type over a bare `u64` for deadlines avoids mixing up block heights with
+/// timestamps. Adding or subtracting a number of blocks gives another height, while
+/// [`BlockHeight::blocks_since`] gives the number of blocks between two heights.
+///
+/// # Examples
+///
+/// ```
+/// use near_sdk::BlockHeight;
+///
+/// let created = BlockHeight(100);
+/// let expires = created + 50;
+/// assert!(BlockHeight(120) < expires);
+/// assert_eq!(BlockHeight(120).blocks_since(created), 20);
+/// ```
+#[derive(
+    Default,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    PartialOrd,
+    Ord,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    Hash,
+    BorshSchema,
+)]
+#[repr(transparent)]
+pub struct BlockHeight(pub u64);
+
+impl BlockHeight {
+    /// Returns the number of blocks from `earlier` to this height, or `0` if `earlier` is not
+    /// before this height.
+    pub const fn blocks_since(&self, earlier: BlockHeight) -> u64 {
+        self.0.saturating_sub(earlier.0)
+    }
+
+    /// Checked addition of a number of blocks. Returns [`None`] if overflow occurred.
+    pub const fn checked_add(self, blocks: u64) -> Option<Self> {
+        match self.0.checked_add(blocks) {
+            Some(height) => Some(Self(height)),
+            None => None,
+        }
+    }
+
+    /// Checked subtraction of a number of blocks. Returns [`None`] if the result would be negative.
+    pub const fn checked_sub(self, blocks: u64) -> Option<Self> {
+        match self.0.checked_sub(blocks) {
+            Some(height) => Some(Self(height)),
+            None => None,
+        }
+    }
+}
+
+impl fmt::Display for BlockHeight {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl Serialize for BlockHeight {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut buf = [0u8; 20];
+        let remainder = {
+            use std::io::Write;
+
+            let mut w: &mut [u8] = &mut buf;
+            write!(w, "{}", self.0).unwrap_or_else(|_| crate::env::abort());
+            w.len()
+        };
+        let len = buf.len() - remainder;
+
+        let s = std::str::from_utf8(&buf[..len]).unwrap_or_else(|_| crate::env::abort());
+        serializer.serialize_str(s)
+    }
+}
+
+impl<'de> Deserialize<'de> for BlockHeight {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct BlockHeightVisitor;
+
+        impl<'de> de::Visitor<'de> for BlockHeightVisitor {
+            type Value = BlockHeight;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a block height as a string or an integer")
+            }
+
+            fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+                s.parse::<u64>().map(BlockHeight).map_err(|err| de::Error::custom(err.to_string()))
+            }
+
+            fn visit_u64<E: de::Error>(self, height: u64) -> Result<Self::Value, E> {
+                Ok(BlockHeight(height))
+            }
+
+            fn visit_i64<E: de::Error>(self, height: i64) -> Result<Self::Value, E> {
+                u64::try_from(height)
+                    .map(BlockHeight)
+                    .map_err(|_| de::Error::invalid_value(de::Unexpected::Signed(height), &self))
+            }
+        }
+
+        deserializer.deserialize_any(BlockHeightVisitor)
+    }
+}
+
+#[cfg(feature = "abi")]
+impl schemars::JsonSchema for BlockHeight {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        String::schema_name()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        String::json_schema(gen)
+    }
+}
+
+impl From<u64> for BlockHeight {
+    fn from(height: u64) -> Self {
+        Self(height)
+    }
+}
+
+impl From<BlockHeight> for u64 {
+    fn from(height: BlockHeight) -> Self {
+        height.0
+    }
+}
+
+impl ops::Add<u64> for BlockHeight {
+    type Output = Self;
+
+    fn add(self, blocks: u64) -> Self {
+        Self(self.0 + blocks)
+    }
+}
+
+impl ops::AddAssign<u64> for BlockHeight {
+    fn add_assign(&mut self, blocks: u64) {
+        self.0 += blocks;
+    }
+}
+
+impl ops::Sub<u64> for BlockHeight {
+    type Output = Self;
+
+    fn sub(self, blocks: u64) -> Self {
+        Self(self.0 - blocks)
+    }
+}
+
+impl ops::SubAssign<u64> for BlockHeight {
+    fn sub_assign(&mut self, blocks: u64) {
+        self.0 -= blocks;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_ser() {
+        for val in [0, 8, u64::MAX] {
+            let ser = serde_json::to_string(&BlockHeight(val)).unwrap();
+            assert_eq!(ser, format!("\"{}\"", val));
+            assert_eq!(serde_json::from_str::<BlockHeight>(&ser).unwrap(), BlockHeight(val));
+        }
+        assert_eq!(serde_json::from_str::<BlockHeight>("7").unwrap(), BlockHeight(7));
+        assert!(serde_json::from_str::<BlockHeight>("-1").is_err());
+    }
+
+    #[test]
+    fn arithmetic() {
+        let mut height = BlockHeight(10) + 5;
+        assert_eq!(height, BlockHeight(15));
+        height -= 3;
+        assert_eq!(height, BlockHeight(12));
+        height += 1;
+        assert_eq!(height - 13, BlockHeight(0));
+
+        assert_eq!(BlockHeight(u64::MAX).checked_add(1), None);
+        assert_eq!(BlockHeight(1).checked_add(1), Some(BlockHeight(2)));
+        assert_eq!(BlockHeight(0).checked_sub(1), None);
+
+        assert_eq!(BlockHeight(15).blocks_since(BlockHeight(10)), 5);
+        assert_eq!(BlockHeight(10).blocks_since(BlockHeight(15)), 0);
+    }
+
+    #[test]
+    fn comparison() {
+        assert!(BlockHeight(1) < BlockHeight(2));
+        assert!(BlockHeight(2) >= BlockHeight(2));
+        assert_eq!(BlockHeight(3).max(BlockHeight(5)), BlockHeight(5));
+        assert_eq!(BlockHeight::from(4u64), BlockHeight(4));
+        assert_eq!(u64::from(BlockHeight(4)), 4);
+    }
+}
@@ -1,6 +1,10 @@
 /// Enables contract runtime to panic with the given type. Any error type used in conjunction
 /// with `#[handle_result]` has to implement this trait.
 ///
+/// It is implemented for string types and by `#[derive(FunctionError)]` using the `Display`
+/// implementation of the type. Implement it manually to control how errors are reported, e.g.
+/// with a code serialized as JSON through [`env::abort_with`](crate::env::abort_with).
+///
 /// ```
 /// use near_sdk::FunctionError;
 ///
@@ -64,3 +68,43 @@ impl FunctionError for Abort {
         crate::env::abort()
     }
 }
+
+/// A wrapper used in conjunction with [FunctionError] to abort with the wrapped error serialized
+/// as JSON, see [`env::abort_with`](crate::env::abort_with).
+///
+/// ```
+/// use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+/// use near_sdk::serde::Serialize;
+/// use near_sdk::{near_bindgen, JsonError};
+///
+/// #[derive(Serialize)]
+/// #[serde(crate = "near_sdk::serde")]
+/// pub struct Error {
+///     code: u32,
+///     message: String,
+/// }
+///
+/// #[near_bindgen]
+/// #[derive(Default, BorshDeserialize, BorshSerialize)]
+/// pub struct Contract;
+///
+/// #[near_bindgen]
+/// impl Contract {
+///     #[handle_result]
+///     pub fn foo(&self, text: &str) -> Result<String, JsonError<Error>> {
+///         if text == "success" {
+///             Ok("success".to_string())
+///         } else {
+///             Err(JsonError(Error { code: 400, message: "Expected success".to_string() }))
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonError<E>(pub E);
+
+impl<E: serde::Serialize> FunctionError for JsonError<E> {
+    fn panic(&self) -> ! {
+        crate::env::abort_with(&self.0)
+    }
+}
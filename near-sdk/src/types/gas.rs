@@ -23,6 +23,104 @@ pub struct Gas(pub u64);
 impl Gas {
     /// One Tera gas, which is 10^12 gas units.
     pub const ONE_TERA: Gas = Gas(1_000_000_000_000);
+    /// One Giga gas, which is 10^9 gas units.
+    pub const ONE_GIGA: Gas = Gas(1_000_000_000);
+
+    /// Creates a new `Gas` from the given amount of Tera gas.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the amount of gas overflows `u64`, which fails the build when evaluated in a
+    /// `const` context.
+    pub const fn from_tgas(tgas: u64) -> Self {
+        match tgas.checked_mul(Self::ONE_TERA.0) {
+            Some(gas) => Self(gas),
+            None => panic!("Gas overflow"),
+        }
+    }
+
+    /// Creates a new `Gas` from the given amount of Giga gas.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the amount of gas overflows `u64`, which fails the build when evaluated in a
+    /// `const` context.
+    pub const fn from_ggas(ggas: u64) -> Self {
+        match ggas.checked_mul(Self::ONE_GIGA.0) {
+            Some(gas) => Self(gas),
+            None => panic!("Gas overflow"),
+        }
+    }
+
+    /// Checked addition. Returns [`None`] if overflow occurred.
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_add(rhs.0) {
+            Some(gas) => Some(Self(gas)),
+            None => None,
+        }
+    }
+
+    /// Checked subtraction. Returns [`None`] if the result would be negative.
+    pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_sub(rhs.0) {
+            Some(gas) => Some(Self(gas)),
+            None => None,
+        }
+    }
+
+    /// Checked division by a scalar. Returns [`None`] if `rhs == 0`.
+    pub const fn checked_div(self, rhs: u64) -> Option<Self> {
+        match self.0.checked_div(rhs) {
+            Some(gas) => Some(Self(gas)),
+            None => None,
+        }
+    }
+
+    /// Saturating addition. Returns `Gas(u64::MAX)` if overflow occurred.
+    pub const fn saturating_add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+
+    /// Saturating subtraction. Returns `Gas(0)` if the result would be negative.
+    pub const fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+
+    /// Saturating division by a scalar. Unsigned division never overflows, so this only differs
+    /// from `/` by being a `const fn`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs == 0`.
+    pub const fn saturating_div(self, rhs: u64) -> Self {
+        Self(self.0.saturating_div(rhs))
+    }
+
+    /// Splits the gas evenly into `parts`, e.g. to attach gas to each call of a fan-out, and
+    /// returns the gas of each part. The remainder of an uneven split is left unallocated.
+    /// Returns `Gas(0)` if `parts == 0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::Gas;
+    ///
+    /// let per_call = Gas::from_tgas(100).split_evenly(3);
+    /// assert_eq!(per_call, Gas(33_333_333_333_333));
+    /// assert!(per_call * 3 <= Gas::from_tgas(100));
+    /// ```
+    pub const fn split_evenly(self, parts: u64) -> Self {
+        match self.0.checked_div(parts) {
+            Some(gas) => Self(gas),
+            None => Self(0),
+        }
+    }
+
+    /// Returns the amount of gas in Tera gas as a floating point number, e.g. for logging.
+    /// Use the integer value for any computation, as the conversion may lose precision.
+    pub fn as_tgas_f64(self) -> f64 {
+        self.0 as f64 / Self::ONE_TERA.0 as f64
+    }
 }
 
 impl Serialize for Gas {
@@ -50,8 +148,32 @@ impl<'de> Deserialize<'de> for Gas {
     where
         D: Deserializer<'de>,
     {
-        let s: String = Deserialize::deserialize(deserializer)?;
-        s.parse::<u64>().map(Self).map_err(|err| de::Error::custom(err.to_string()))
+        struct GasVisitor;
+
+        impl<'de> de::Visitor<'de> for GasVisitor {
+            type Value = Gas;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("an amount of gas as a string or an integer")
+            }
+
+            fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+                s.parse::<u64>().map(Gas).map_err(|err| de::Error::custom(err.to_string()))
+            }
+
+            // Accepted for backward compatibility with clients sending gas as a JSON number.
+            fn visit_u64<E: de::Error>(self, gas: u64) -> Result<Self::Value, E> {
+                Ok(Gas(gas))
+            }
+
+            fn visit_i64<E: de::Error>(self, gas: i64) -> Result<Self::Value, E> {
+                u64::try_from(gas)
+                    .map(Gas)
+                    .map_err(|_| de::Error::invalid_value(de::Unexpected::Signed(gas), &self))
+            }
+        }
+
+        deserializer.deserialize_any(GasVisitor)
     }
 }
 
@@ -152,4 +274,60 @@ mod tests {
         test_json_ser(8);
         test_json_ser(0);
     }
+
+    #[test]
+    fn json_de_integer() {
+        assert_eq!(serde_json::from_str::<Gas>("0").unwrap(), Gas(0));
+        assert_eq!(serde_json::from_str::<Gas>(&u64::MAX.to_string()).unwrap(), Gas(u64::MAX));
+        assert!(serde_json::from_str::<Gas>("-1").is_err());
+        assert!(serde_json::from_str::<Gas>("1.5").is_err());
+        assert!(serde_json::from_str::<Gas>("\"-1\"").is_err());
+    }
+
+    #[test]
+    fn constructors() {
+        assert_eq!(Gas::from_tgas(5), Gas(5_000_000_000_000));
+        assert_eq!(Gas::from_ggas(5), Gas(5_000_000_000));
+        assert_eq!(Gas::from_tgas(1), Gas::ONE_TERA);
+    }
+
+    #[test]
+    #[should_panic(expected = "Gas overflow")]
+    fn from_tgas_overflow() {
+        Gas::from_tgas(u64::MAX / Gas::ONE_TERA.0 + 1);
+    }
+
+    #[test]
+    fn checked_and_saturating_arithmetic() {
+        let max = Gas(u64::MAX);
+        assert_eq!(max.checked_add(Gas(0)), Some(max));
+        assert_eq!(max.checked_add(Gas(1)), None);
+        assert_eq!(Gas(1).checked_sub(Gas(1)), Some(Gas(0)));
+        assert_eq!(Gas(0).checked_sub(Gas(1)), None);
+        assert_eq!(Gas(10).checked_div(3), Some(Gas(3)));
+        assert_eq!(Gas(10).checked_div(0), None);
+
+        assert_eq!(max.saturating_add(Gas(1)), max);
+        assert_eq!(Gas(1).saturating_add(Gas(2)), Gas(3));
+        assert_eq!(Gas(0).saturating_sub(Gas(1)), Gas(0));
+        assert_eq!(Gas(3).saturating_sub(Gas(1)), Gas(2));
+        assert_eq!(max.saturating_div(1), max);
+        assert_eq!(Gas(10).saturating_div(4), Gas(2));
+    }
+
+    #[test]
+    fn split_evenly() {
+        assert_eq!(Gas::from_tgas(90).split_evenly(3), Gas::from_tgas(30));
+        assert_eq!(Gas(10).split_evenly(3), Gas(3));
+        assert_eq!(Gas(2).split_evenly(3), Gas(0));
+        assert_eq!(Gas(u64::MAX).split_evenly(1), Gas(u64::MAX));
+        assert_eq!(Gas::from_tgas(90).split_evenly(0), Gas(0));
+    }
+
+    #[test]
+    fn as_tgas_f64() {
+        assert_eq!(Gas::from_tgas(5).as_tgas_f64(), 5.0);
+        assert_eq!(Gas::from_ggas(2_500).as_tgas_f64(), 2.5);
+        assert_eq!(Gas(0).as_tgas_f64(), 0.0);
+    }
 }
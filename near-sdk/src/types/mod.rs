@@ -13,9 +13,15 @@ pub use self::account_id::{AccountId, ParseAccountIdError};
 mod gas;
 pub use self::gas::Gas;
 
+mod block_height;
+pub use self::block_height::BlockHeight;
+
+mod near_token;
+pub use self::near_token::{NearToken, ParseNearTokenError, RoundingMode};
+
 mod error;
-pub use self::error::Abort;
 pub use self::error::FunctionError;
+pub use self::error::{Abort, JsonError};
 
 /// Raw type for duration in nanoseconds
 pub type Duration = u64;
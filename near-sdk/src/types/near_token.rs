@@ -0,0 +1,646 @@
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+use super::{Balance, ONE_NEAR};
+
+/// Number of decimals of yoctoNEAR in one NEAR.
+const NEAR_DECIMALS: usize = 24;
+/// Number of decimals of yoctoNEAR in one milliNEAR.
+const MILLINEAR_DECIMALS: usize = 21;
+
+/// Amount of NEAR tokens, stored internally as yoctoNEAR ([`Balance`]).
+///
+/// The arithmetic methods operate on the inner yoctoNEAR amount and signal overflow or division
+/// by zero by returning [`None`] instead of silently wrapping or clamping.
+///
+/// Amounts can be parsed from strings with a `NEAR`, `mNEAR` or `yoctoNEAR` unit, and numbers
+/// without a unit are interpreted as yoctoNEAR. [`Display`](fmt::Display) formats the amount in
+/// NEAR without losing precision, so the output can be parsed back into the same amount.
+///
+/// With serde, the amount is a string of yoctoNEAR, as JSON numbers can't represent every
+/// [`Balance`] in JavaScript. Integers are also accepted when deserializing.
+///
+/// # Examples
+///
+/// ```
+/// use near_sdk::NearToken;
+///
+/// let amount: NearToken = "1.5 NEAR".parse().unwrap();
+/// assert_eq!(amount, NearToken::from_millinear(1_500));
+/// assert_eq!(amount.to_string(), "1.5 NEAR");
+/// assert_eq!("250 mNEAR".parse(), Ok(NearToken::from_millinear(250)));
+/// assert_eq!("1000".parse(), Ok(NearToken::from_yoctonear(1000)));
+///
+/// let amount = NearToken::from_near(2);
+/// let remaining = amount.checked_sub(NearToken::from_yoctonear(1)).unwrap();
+/// assert_eq!(remaining.as_yoctonear(), 2 * 10u128.pow(24) - 1);
+///
+/// assert_eq!(amount.checked_add(NearToken::from_yoctonear(u128::MAX)), None);
+/// assert_eq!(amount.checked_div(0), None);
+/// ```
+#[derive(
+    Default,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    PartialOrd,
+    Ord,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    Hash,
+    BorshSchema,
+)]
+#[repr(transparent)]
+pub struct NearToken(Balance);
+
+impl NearToken {
+    /// Zero NEAR tokens.
+    pub const ZERO: NearToken = NearToken(0);
+
+    /// Creates an amount of tokens from yoctoNEAR, which is 10^-24 of one NEAR.
+    pub const fn from_yoctonear(yoctonear: Balance) -> Self {
+        Self(yoctonear)
+    }
+
+    /// Creates an amount of tokens from milliNEAR, which is 10^-3 of one NEAR.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the amount overflows when converted to yoctoNEAR.
+    pub const fn from_millinear(millinear: u128) -> Self {
+        match millinear.checked_mul(ONE_NEAR / 1_000) {
+            Some(yoctonear) => Self(yoctonear),
+            None => panic!("NearToken overflow"),
+        }
+    }
+
+    /// Creates an amount of tokens from whole NEAR.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the amount overflows when converted to yoctoNEAR.
+    pub const fn from_near(near: u128) -> Self {
+        match near.checked_mul(ONE_NEAR) {
+            Some(yoctonear) => Self(yoctonear),
+            None => panic!("NearToken overflow"),
+        }
+    }
+
+    /// Creates an amount of tokens from a floating point amount of NEAR, rounding the result to
+    /// a whole number of yoctoNEAR with the given [`RoundingMode`].
+    ///
+    /// The conversion is exact and deterministic: the binary value of the `f64` is converted to
+    /// yoctoNEAR without any intermediate floating point arithmetic, and only the final fraction
+    /// of a yoctoNEAR is rounded. Note that `f64` only has 53 bits of precision, so most decimal
+    /// amounts like `0.1` are not exactly representable and the result can differ from the
+    /// decimal amount by more than one yoctoNEAR. Prefer [`NearToken::from_millinear`] or parsing
+    /// a string when the exact decimal amount is known.
+    ///
+    /// Returns an error if the value is NaN, infinite, negative or does not fit into 128 bits of
+    /// yoctoNEAR.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk::{NearToken, RoundingMode};
+    ///
+    /// assert_eq!(
+    ///     NearToken::from_near_with_rounding(1.5, RoundingMode::Floor),
+    ///     Ok(NearToken::from_millinear(1_500))
+    /// );
+    ///
+    /// // 0.1 is slightly more than 0.1 NEAR as an `f64`.
+    /// let floor = NearToken::from_near_with_rounding(0.1, RoundingMode::Floor).unwrap();
+    /// let ceil = NearToken::from_near_with_rounding(0.1, RoundingMode::Ceil).unwrap();
+    /// assert_eq!(floor.as_yoctonear(), 100_000_000_000_000_005_551_115);
+    /// assert_eq!(ceil.as_yoctonear(), 100_000_000_000_000_005_551_116);
+    ///
+    /// assert!(NearToken::from_near_with_rounding(f64::NAN, RoundingMode::Nearest).is_err());
+    /// assert!(NearToken::from_near_with_rounding(-1.0, RoundingMode::Nearest).is_err());
+    /// ```
+    pub fn from_near_with_rounding(
+        value: f64,
+        mode: RoundingMode,
+    ) -> Result<Self, ParseNearTokenError> {
+        if !value.is_finite() {
+            return Err(ParseNearTokenErrorKind::NotFinite.into());
+        }
+        if value < 0.0 {
+            return Err(ParseNearTokenErrorKind::Negative.into());
+        }
+
+        // The value is `mantissa * 2^exponent`, so the amount in yoctoNEAR is
+        // `mantissa * 5^24 * 2^(exponent + 24)`. The mantissa has at most 53 bits and 5^24 has
+        // 56 bits, so their product fits into 128 bits.
+        let bits = value.to_bits();
+        let biased_exponent = ((bits >> 52) & 0x7ff) as i32;
+        let fraction = bits & ((1 << 52) - 1);
+        let (mantissa, exponent) = if biased_exponent == 0 {
+            (fraction, -1074)
+        } else {
+            (fraction | (1 << 52), biased_exponent - 1075)
+        };
+        let scaled = u128::from(mantissa) * 5u128.pow(NEAR_DECIMALS as u32);
+        let shift = exponent + NEAR_DECIMALS as i32;
+
+        let yoctonear = if shift >= 0 {
+            let shift = shift as u32;
+            if scaled != 0 && shift > scaled.leading_zeros() {
+                return Err(ParseNearTokenErrorKind::Overflow.into());
+            }
+            scaled << shift
+        } else {
+            let shift = shift.unsigned_abs();
+            let (quotient, remainder) = if shift >= u128::BITS {
+                (0, scaled)
+            } else {
+                (scaled >> shift, scaled & ((1 << shift) - 1))
+            };
+            let round_up = match mode {
+                RoundingMode::Floor => false,
+                RoundingMode::Ceil => remainder != 0,
+                // Half of the divisor is `2^(shift - 1)`, ties are rounded up.
+                RoundingMode::Nearest => shift <= u128::BITS && remainder >> (shift - 1) != 0,
+            };
+            // Cannot overflow, as the quotient has at most 127 bits.
+            quotient + u128::from(round_up)
+        };
+        Ok(Self(yoctonear))
+    }
+
+    /// Returns the amount of tokens in yoctoNEAR.
+    pub const fn as_yoctonear(&self) -> Balance {
+        self.0
+    }
+
+    /// Returns `true` if the amount is zero.
+    pub const fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Checked addition. Returns [`None`] if overflow occurred.
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_add(rhs.0) {
+            Some(yoctonear) => Some(Self(yoctonear)),
+            None => None,
+        }
+    }
+
+    /// Checked subtraction. Returns [`None`] if the result would be negative.
+    pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_sub(rhs.0) {
+            Some(yoctonear) => Some(Self(yoctonear)),
+            None => None,
+        }
+    }
+
+    /// Checked multiplication by a scalar. Returns [`None`] if overflow occurred.
+    pub const fn checked_mul(self, rhs: u128) -> Option<Self> {
+        match self.0.checked_mul(rhs) {
+            Some(yoctonear) => Some(Self(yoctonear)),
+            None => None,
+        }
+    }
+
+    /// Checked division by a scalar. Returns [`None`] if `rhs == 0`.
+    pub const fn checked_div(self, rhs: u128) -> Option<Self> {
+        match self.0.checked_div(rhs) {
+            Some(yoctonear) => Some(Self(yoctonear)),
+            None => None,
+        }
+    }
+
+    /// Checked remainder of division by a scalar. Returns [`None`] if `rhs == 0`.
+    pub const fn checked_rem(self, rhs: u128) -> Option<Self> {
+        match self.0.checked_rem(rhs) {
+            Some(yoctonear) => Some(Self(yoctonear)),
+            None => None,
+        }
+    }
+}
+
+/// How to round a fraction of a yoctoNEAR in [`NearToken::from_near_with_rounding`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Rounds down to the previous yoctoNEAR.
+    Floor,
+    /// Rounds up to the next yoctoNEAR.
+    Ceil,
+    /// Rounds to the nearest yoctoNEAR, rounding up half of a yoctoNEAR.
+    Nearest,
+}
+
+impl From<Balance> for NearToken {
+    fn from(yoctonear: Balance) -> Self {
+        Self(yoctonear)
+    }
+}
+
+impl From<NearToken> for Balance {
+    fn from(amount: NearToken) -> Self {
+        amount.0
+    }
+}
+
+impl Serialize for NearToken {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut buf = [0u8; 39];
+        let remainder = {
+            use std::io::Write;
+
+            let mut w: &mut [u8] = &mut buf;
+            write!(w, "{}", self.0).unwrap_or_else(|_| crate::env::abort());
+            w.len()
+        };
+        let len = buf.len() - remainder;
+
+        let s = std::str::from_utf8(&buf[..len]).unwrap_or_else(|_| crate::env::abort());
+        serializer.serialize_str(s)
+    }
+}
+
+impl<'de> Deserialize<'de> for NearToken {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct NearTokenVisitor;
+
+        impl<'de> de::Visitor<'de> for NearTokenVisitor {
+            type Value = NearToken;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an amount of yoctoNEAR as a string or an integer")
+            }
+
+            fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+                s.parse::<Balance>()
+                    .map(NearToken)
+                    .map_err(|err| de::Error::custom(err.to_string()))
+            }
+
+            // Accepted for backward compatibility with clients sending amounts as JSON numbers.
+            fn visit_u64<E: de::Error>(self, yoctonear: u64) -> Result<Self::Value, E> {
+                Ok(NearToken(yoctonear.into()))
+            }
+
+            fn visit_u128<E: de::Error>(self, yoctonear: u128) -> Result<Self::Value, E> {
+                Ok(NearToken(yoctonear))
+            }
+
+            fn visit_i64<E: de::Error>(self, yoctonear: i64) -> Result<Self::Value, E> {
+                Balance::try_from(yoctonear)
+                    .map(NearToken)
+                    .map_err(|_| de::Error::invalid_value(de::Unexpected::Signed(yoctonear), &self))
+            }
+        }
+
+        deserializer.deserialize_any(NearTokenVisitor)
+    }
+}
+
+#[cfg(feature = "abi")]
+impl schemars::JsonSchema for NearToken {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        String::schema_name()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        String::json_schema(gen)
+    }
+}
+
+impl fmt::Display for NearToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let whole = self.0 / ONE_NEAR;
+        let fraction = self.0 % ONE_NEAR;
+        if fraction == 0 {
+            write!(f, "{} NEAR", whole)
+        } else {
+            let fraction = format!("{:0width$}", fraction, width = NEAR_DECIMALS);
+            write!(f, "{}.{} NEAR", whole, fraction.trim_end_matches('0'))
+        }
+    }
+}
+
+impl FromStr for NearToken {
+    type Err = ParseNearTokenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.starts_with('-') {
+            return Err(ParseNearTokenErrorKind::Negative.into());
+        }
+
+        let unit_start = s.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(s.len());
+        let (amount, unit) = s.split_at(unit_start);
+        let decimals = match unit.trim_start() {
+            "" | "yoctoNEAR" => 0,
+            "mNEAR" => MILLINEAR_DECIMALS,
+            "NEAR" => NEAR_DECIMALS,
+            _ => return Err(ParseNearTokenErrorKind::InvalidUnit.into()),
+        };
+
+        let (whole, fraction) = match amount.split_once('.') {
+            Some((whole, fraction)) if !whole.is_empty() && !fraction.is_empty() => {
+                (whole, fraction)
+            }
+            None if !amount.is_empty() => (amount, ""),
+            _ => return Err(ParseNearTokenErrorKind::InvalidNumber.into()),
+        };
+        if !fraction.bytes().all(|b| b.is_ascii_digit()) {
+            // Contains more than one decimal point.
+            return Err(ParseNearTokenErrorKind::InvalidNumber.into());
+        }
+        if fraction.len() > decimals {
+            return Err(ParseNearTokenErrorKind::TooManyFractionalDigits(decimals).into());
+        }
+
+        // Only digits are left at this point, so parsing can only fail on overflow. The fraction
+        // is padded with zeros to the number of decimals of the unit.
+        let overflow = |_| ParseNearTokenError::from(ParseNearTokenErrorKind::Overflow);
+        let whole: u128 = whole.parse().map_err(overflow)?;
+        let fraction: u128 =
+            format!("{:0<width$}", fraction, width = decimals).parse().unwrap_or_default();
+
+        whole
+            .checked_mul(10u128.pow(decimals as u32))
+            .and_then(|whole| whole.checked_add(fraction))
+            .map(Self)
+            .ok_or_else(|| ParseNearTokenErrorKind::Overflow.into())
+    }
+}
+
+/// Error returned when parsing a [`NearToken`] from a string or converting it from a floating
+/// point amount fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseNearTokenError {
+    kind: ParseNearTokenErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ParseNearTokenErrorKind {
+    InvalidNumber,
+    InvalidUnit,
+    Negative,
+    NotFinite,
+    TooManyFractionalDigits(usize),
+    Overflow,
+}
+
+impl From<ParseNearTokenErrorKind> for ParseNearTokenError {
+    fn from(kind: ParseNearTokenErrorKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl fmt::Display for ParseNearTokenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ParseNearTokenErrorKind::InvalidNumber => write!(f, "invalid number of tokens"),
+            ParseNearTokenErrorKind::InvalidUnit => {
+                write!(f, "invalid unit, expected one of `NEAR`, `mNEAR` or `yoctoNEAR`")
+            }
+            ParseNearTokenErrorKind::Negative => write!(f, "amount of tokens cannot be negative"),
+            ParseNearTokenErrorKind::NotFinite => write!(f, "amount of tokens must be finite"),
+            ParseNearTokenErrorKind::TooManyFractionalDigits(max) => {
+                write!(f, "too many fractional digits for the unit, at most {} allowed", max)
+            }
+            ParseNearTokenErrorKind::Overflow => {
+                write!(f, "amount of tokens does not fit into 128 bits of yoctoNEAR")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseNearTokenError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX: NearToken = NearToken::from_yoctonear(u128::MAX);
+    const ONE_YOCTO: NearToken = NearToken::from_yoctonear(1);
+
+    #[test]
+    fn json_round_trip() {
+        for amount in [0, 1, ONE_NEAR, u64::MAX as u128 + 1, u128::MAX] {
+            let amount = NearToken::from_yoctonear(amount);
+            let json = serde_json::to_string(&amount).unwrap();
+            assert_eq!(json, format!("\"{}\"", amount.as_yoctonear()));
+            assert_eq!(serde_json::from_str::<NearToken>(&json).unwrap(), amount);
+        }
+    }
+
+    #[test]
+    fn json_de_integer() {
+        assert_eq!(serde_json::from_str::<NearToken>("0").unwrap(), NearToken::ZERO);
+        assert_eq!(
+            serde_json::from_str::<NearToken>(&u64::MAX.to_string()).unwrap(),
+            NearToken::from_yoctonear(u64::MAX.into())
+        );
+        assert!(serde_json::from_str::<NearToken>("-1").is_err());
+        // Only yoctoNEAR amounts are accepted, not the units of `FromStr`.
+        assert!(serde_json::from_str::<NearToken>("\"1 NEAR\"").is_err());
+        assert!(serde_json::from_str::<NearToken>("\"340282366920938463463374607431768211456\"")
+            .is_err());
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(NearToken::from_near(1).as_yoctonear(), ONE_NEAR);
+        assert_eq!(NearToken::from_millinear(1_500), NearToken::from_yoctonear(ONE_NEAR * 3 / 2));
+        assert_eq!(Balance::from(NearToken::from(42)), 42);
+        assert!(NearToken::ZERO.is_zero());
+    }
+
+    #[test]
+    #[should_panic(expected = "NearToken overflow")]
+    fn from_near_overflow() {
+        NearToken::from_near(u128::MAX / ONE_NEAR + 1);
+    }
+
+    #[test]
+    fn checked_add() {
+        assert_eq!(MAX.checked_add(NearToken::ZERO), Some(MAX));
+        assert_eq!(MAX.checked_add(ONE_YOCTO), None);
+        assert_eq!(MAX.checked_sub(ONE_YOCTO).unwrap().checked_add(ONE_YOCTO), Some(MAX));
+    }
+
+    #[test]
+    fn checked_sub() {
+        assert_eq!(NearToken::ZERO.checked_sub(ONE_YOCTO), None);
+        assert_eq!(ONE_YOCTO.checked_sub(ONE_YOCTO), Some(NearToken::ZERO));
+        assert_eq!(
+            NearToken::from_near(1)
+                .checked_sub(NearToken::from_near(1).checked_add(ONE_YOCTO).unwrap()),
+            None
+        );
+        assert_eq!(MAX.checked_sub(MAX), Some(NearToken::ZERO));
+    }
+
+    #[test]
+    fn checked_mul() {
+        assert_eq!(MAX.checked_mul(1), Some(MAX));
+        assert_eq!(MAX.checked_mul(2), None);
+        assert_eq!(MAX.checked_mul(0), Some(NearToken::ZERO));
+        assert_eq!(NearToken::from_near(2).checked_mul(3), Some(NearToken::from_near(6)));
+    }
+
+    #[test]
+    fn checked_div_rem() {
+        assert_eq!(MAX.checked_div(0), None);
+        assert_eq!(MAX.checked_rem(0), None);
+        assert_eq!(MAX.checked_div(1), Some(MAX));
+        assert_eq!(MAX.checked_rem(2), Some(ONE_YOCTO));
+        assert_eq!(NearToken::from_near(7).checked_div(2), Some(NearToken::from_millinear(3_500)));
+        assert_eq!(NearToken::from_yoctonear(7).checked_rem(4), Some(NearToken::from_yoctonear(3)));
+    }
+
+    #[test]
+    fn parse_units() {
+        assert_eq!("1.5 NEAR".parse(), Ok(NearToken::from_millinear(1_500)));
+        assert_eq!("1.5NEAR".parse(), Ok(NearToken::from_millinear(1_500)));
+        assert_eq!(" 2 NEAR ".parse(), Ok(NearToken::from_near(2)));
+        assert_eq!("250 mNEAR".parse(), Ok(NearToken::from_millinear(250)));
+        assert_eq!("0.5 mNEAR".parse(), Ok(NearToken::from_yoctonear(ONE_NEAR / 2_000)));
+        assert_eq!("1000000 yoctoNEAR".parse(), Ok(NearToken::from_yoctonear(1_000_000)));
+        assert_eq!("1000000".parse(), Ok(NearToken::from_yoctonear(1_000_000)));
+        assert_eq!("0.000000000000000000000001 NEAR".parse(), Ok(ONE_YOCTO));
+        assert_eq!(u128::MAX.to_string().parse(), Ok(MAX));
+    }
+
+    #[test]
+    fn parse_errors() {
+        fn kind(s: &str) -> ParseNearTokenErrorKind {
+            s.parse::<NearToken>().unwrap_err().kind
+        }
+
+        assert_eq!(
+            kind("0.0000000000000000000000001 NEAR"),
+            ParseNearTokenErrorKind::TooManyFractionalDigits(24)
+        );
+        assert_eq!(
+            kind("0.0000000000000000000001 mNEAR"),
+            ParseNearTokenErrorKind::TooManyFractionalDigits(21)
+        );
+        assert_eq!(kind("1.5 yoctoNEAR"), ParseNearTokenErrorKind::TooManyFractionalDigits(0));
+        assert_eq!(kind("1.5"), ParseNearTokenErrorKind::TooManyFractionalDigits(0));
+        assert_eq!(kind("-1 NEAR"), ParseNearTokenErrorKind::Negative);
+        assert_eq!(kind("1 near"), ParseNearTokenErrorKind::InvalidUnit);
+        assert_eq!(kind("1 MNEAR"), ParseNearTokenErrorKind::InvalidUnit);
+        assert_eq!(kind("1 NEAR NEAR"), ParseNearTokenErrorKind::InvalidUnit);
+        assert_eq!(kind("1e3 NEAR"), ParseNearTokenErrorKind::InvalidUnit);
+        assert_eq!(kind(""), ParseNearTokenErrorKind::InvalidNumber);
+        assert_eq!(kind("NEAR"), ParseNearTokenErrorKind::InvalidNumber);
+        assert_eq!(kind(".5 NEAR"), ParseNearTokenErrorKind::InvalidNumber);
+        assert_eq!(kind("1. NEAR"), ParseNearTokenErrorKind::InvalidNumber);
+        assert_eq!(kind("1.2.3 NEAR"), ParseNearTokenErrorKind::InvalidNumber);
+        assert_eq!(kind("340282366920939 NEAR"), ParseNearTokenErrorKind::Overflow);
+        assert_eq!(
+            kind("340282366920938463463374607431768211456"),
+            ParseNearTokenErrorKind::Overflow
+        );
+    }
+
+    #[test]
+    fn display_round_trip() {
+        for amount in [
+            NearToken::ZERO,
+            ONE_YOCTO,
+            NearToken::from_millinear(1_500),
+            NearToken::from_near(10),
+            NearToken::from_yoctonear(ONE_NEAR + 10),
+            MAX,
+        ] {
+            assert_eq!(amount.to_string().parse(), Ok(amount));
+        }
+        assert_eq!(NearToken::ZERO.to_string(), "0 NEAR");
+        assert_eq!(ONE_YOCTO.to_string(), "0.000000000000000000000001 NEAR");
+        assert_eq!(NearToken::from_millinear(1_250).to_string(), "1.25 NEAR");
+    }
+
+    #[test]
+    fn from_near_representable_floats() {
+        // Multiples of 0.125 NEAR are exactly representable, so all rounding modes agree with
+        // the integer conversion.
+        for millinear in (0..=1_000_000).step_by(125) {
+            let value = millinear as f64 / 1_000.0;
+            for mode in [RoundingMode::Floor, RoundingMode::Ceil, RoundingMode::Nearest] {
+                assert_eq!(
+                    NearToken::from_near_with_rounding(value, mode),
+                    Ok(NearToken::from_millinear(millinear))
+                );
+            }
+        }
+        assert_eq!(
+            NearToken::from_near_with_rounding(-0.0, RoundingMode::Ceil),
+            Ok(NearToken::ZERO)
+        );
+    }
+
+    #[test]
+    fn from_near_rounding() {
+        for millinear in 1..10_000 {
+            let value = millinear as f64 / 1_000.0;
+            let floor = NearToken::from_near_with_rounding(value, RoundingMode::Floor).unwrap();
+            let ceil = NearToken::from_near_with_rounding(value, RoundingMode::Ceil).unwrap();
+            let nearest = NearToken::from_near_with_rounding(value, RoundingMode::Nearest).unwrap();
+            assert!(floor <= nearest && nearest <= ceil);
+            assert!(ceil.as_yoctonear() - floor.as_yoctonear() <= 1);
+
+            // The only error comes from the representation of the value as an `f64`.
+            let exact = NearToken::from_millinear(millinear).as_yoctonear();
+            let error = exact.abs_diff(nearest.as_yoctonear());
+            assert!(error as f64 <= exact as f64 * f64::EPSILON);
+        }
+
+        // The smallest positive `f64` is far less than a yoctoNEAR.
+        let tiny = f64::from_bits(1);
+        assert_eq!(
+            NearToken::from_near_with_rounding(tiny, RoundingMode::Floor),
+            Ok(NearToken::ZERO)
+        );
+        assert_eq!(
+            NearToken::from_near_with_rounding(tiny, RoundingMode::Nearest),
+            Ok(NearToken::ZERO)
+        );
+        assert_eq!(NearToken::from_near_with_rounding(tiny, RoundingMode::Ceil), Ok(ONE_YOCTO));
+
+        // 2^-25 NEAR is 5^24 / 2 yoctoNEAR, which ends in exactly half of a yoctoNEAR.
+        let half = 2f64.powi(-25);
+        let floor = NearToken::from_yoctonear(5u128.pow(24) / 2);
+        assert_eq!(NearToken::from_near_with_rounding(half, RoundingMode::Floor), Ok(floor));
+        assert_eq!(
+            NearToken::from_near_with_rounding(half, RoundingMode::Nearest),
+            Ok(NearToken::from_yoctonear(floor.as_yoctonear() + 1))
+        );
+    }
+
+    #[test]
+    fn from_near_float_errors() {
+        fn kind(value: f64) -> ParseNearTokenErrorKind {
+            NearToken::from_near_with_rounding(value, RoundingMode::Nearest).unwrap_err().kind
+        }
+
+        assert_eq!(kind(f64::NAN), ParseNearTokenErrorKind::NotFinite);
+        assert_eq!(kind(f64::INFINITY), ParseNearTokenErrorKind::NotFinite);
+        assert_eq!(kind(f64::NEG_INFINITY), ParseNearTokenErrorKind::NotFinite);
+        assert_eq!(kind(-1.0), ParseNearTokenErrorKind::Negative);
+        assert_eq!(kind(-f64::MIN_POSITIVE), ParseNearTokenErrorKind::Negative);
+        assert_eq!(kind(1e15), ParseNearTokenErrorKind::Overflow);
+        assert_eq!(kind(f64::MAX), ParseNearTokenErrorKind::Overflow);
+        assert!(NearToken::from_near_with_rounding(3e14, RoundingMode::Nearest).is_ok());
+    }
+}
@@ -24,8 +24,6 @@ pub type StorageUsageChange = i64;
 /// Nonce for transactions.
 #[deprecated(since = "4.0.0", note = "Type has no connection with the SDK")]
 pub type Nonce = u64;
-/// Height of the block.
-pub type BlockHeight = u64;
 /// Height of the epoch.
 pub type EpochHeight = u64;
 /// Shard index, from 0 to NUM_SHARDS - 1.
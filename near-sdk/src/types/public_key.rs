@@ -76,6 +76,19 @@ impl PublicKey {
         }
     }
 
+    /// Creates a public key from its curve and the key data without the curve prefix, which
+    /// must be 32 bytes for ed25519 and 64 bytes for secp256k1.
+    ///
+    /// # Example
+    /// ```
+    /// use near_sdk::{CurveType, PublicKey};
+    ///
+    /// let key = PublicKey::from_parts(CurveType::ED25519, vec![7; 32]).unwrap();
+    /// assert_eq!(key.curve_type(), CurveType::ED25519);
+    /// assert_eq!(key.key_data(), [7; 32]);
+    ///
+    /// assert!(PublicKey::from_parts(CurveType::SECP256K1, vec![7; 32]).is_err());
+    /// ```
     pub fn from_parts(curve: CurveType, data: Vec<u8>) -> Result<Self, ParsePublicKeyError> {
         let expected_length = curve.data_len();
         if data.len() != expected_length {
@@ -104,6 +117,12 @@ impl PublicKey {
     pub fn curve_type(&self) -> CurveType {
         CurveType::from_u8(self.data[0]).unwrap_or_else(|_| crate::env::abort())
     }
+
+    /// Returns the key data without the curve prefix, i.e. the `data` passed to
+    /// [`from_parts`](Self::from_parts).
+    pub fn key_data(&self) -> &[u8] {
+        &self.data[1..]
+    }
 }
 
 impl From<PublicKey> for Vec<u8> {
@@ -212,7 +231,7 @@ impl std::fmt::Display for ParsePublicKeyError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self.kind {
             ParsePublicKeyErrorKind::InvalidLength(l) => {
-                write!(f, "invalid length of the public key, expected 32 got {}", l)
+                write!(f, "invalid length of the public key, got {} bytes", l)
             }
             ParsePublicKeyErrorKind::Base58(e) => write!(f, "base58 decoding error: {}", e),
             ParsePublicKeyErrorKind::UnknownCurve => write!(f, "unknown curve kind"),
@@ -271,6 +290,38 @@ mod tests {
         assert_eq!(actual, "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp");
     }
 
+    #[test]
+    fn test_public_key_from_parts() {
+        let ed =
+            PublicKey::from_parts(CurveType::ED25519, expected_key().key_data().to_vec()).unwrap();
+        assert_eq!(ed, expected_key());
+        assert_eq!(ed.curve_type(), CurveType::ED25519);
+        assert_eq!(ed.key_data().len(), 32);
+
+        let secp = PublicKey::from_parts(CurveType::SECP256K1, (0..64).collect()).unwrap();
+        assert_eq!(secp.curve_type(), CurveType::SECP256K1);
+        assert_eq!(secp.key_data(), (0..64).collect::<Vec<u8>>());
+        assert_eq!(secp.as_bytes()[0], CurveType::SECP256K1 as u8);
+        assert_eq!(PublicKey::try_from(secp.clone().into_bytes()).unwrap(), secp);
+    }
+
+    #[test]
+    fn test_public_key_from_parts_invalid_length() {
+        for (curve, len) in [
+            (CurveType::ED25519, 0),
+            (CurveType::ED25519, 31),
+            (CurveType::ED25519, 64),
+            (CurveType::SECP256K1, 32),
+            (CurveType::SECP256K1, 65),
+        ] {
+            let err = PublicKey::from_parts(curve, vec![1; len]).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!("invalid length of the public key, got {} bytes", len)
+            );
+        }
+    }
+
     #[test]
     fn test_public_key_borsh_format_change() {
         // Original struct to reference Borsh serialization from
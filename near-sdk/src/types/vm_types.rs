@@ -12,7 +12,7 @@ pub type IteratorIndex = u64;
 
 /// When there is a callback attached to one or more contract calls the execution results of these
 /// calls are available to the contract invoked through the callback.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PromiseResult {
     Successful(Vec<u8>),
     Failed,
@@ -34,4 +34,8 @@ impl From<PromiseResult> for VmPromiseResult {
 pub enum PromiseError {
     /// Promise result failed.
     Failed,
+    /// Promise has not finished executing yet.
+    NotReady,
+    /// Promise result succeeded, but could not be deserialized into the expected type.
+    DeserializationError,
 }
@@ -88,6 +88,37 @@ pub fn assert_one_yocto() {
     require!(env::attached_deposit() == 1, "Requires attached deposit of exactly 1 yoctoNEAR")
 }
 
+/// Assert that at least 1 yoctoNEAR was attached.
+pub fn assert_at_least_one_yocto() {
+    require!(env::attached_deposit() >= 1, "Requires attached deposit of at least 1 yoctoNEAR")
+}
+
+/// Helper macro to assert that exactly 1 yoctoNEAR was attached, like [`assert_one_yocto`], with
+/// an optional custom panic message.
+///
+/// Requiring a deposit makes sure that the call was signed with a full access key, which is
+/// required for sensitive methods such as token transfers.
+///
+/// # Examples
+///
+/// ```no_run
+/// use near_sdk::require_one_yocto;
+///
+/// # fn main() {
+/// require_one_yocto!();
+/// require_one_yocto!("Transfers require 1 yoctoNEAR");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! require_one_yocto {
+    () => {
+        $crate::assert_one_yocto()
+    };
+    ($message:expr $(,)?) => {
+        $crate::require!($crate::env::attached_deposit() == 1, $message)
+    };
+}
+
 /// Returns true if promise was successful.
 /// Fails if called outside a callback that received 1 promise result.
 pub fn is_promise_success() -> bool {
@@ -122,7 +153,54 @@ macro_rules! setup_alloc {
 
 #[cfg(test)]
 mod tests {
-    use crate::test_utils::get_logs;
+    use crate::test_utils::{get_logs, VMContextBuilder};
+
+    fn attach(deposit: crate::Balance) {
+        crate::testing_env!(VMContextBuilder::new().attached_deposit(deposit).build());
+    }
+
+    #[test]
+    fn test_one_yocto() {
+        attach(1);
+        super::assert_one_yocto();
+        super::assert_at_least_one_yocto();
+        require_one_yocto!();
+        require_one_yocto!("custom message");
+    }
+
+    #[test]
+    #[should_panic(expected = "Requires attached deposit of exactly 1 yoctoNEAR")]
+    fn test_one_yocto_without_deposit() {
+        attach(0);
+        super::assert_one_yocto();
+    }
+
+    #[test]
+    #[should_panic(expected = "Requires attached deposit of exactly 1 yoctoNEAR")]
+    fn test_one_yocto_with_more() {
+        attach(2);
+        require_one_yocto!();
+    }
+
+    #[test]
+    #[should_panic(expected = "custom message")]
+    fn test_one_yocto_custom_message() {
+        attach(0);
+        require_one_yocto!("custom message");
+    }
+
+    #[test]
+    fn test_at_least_one_yocto_with_more() {
+        attach(10u128.pow(24));
+        super::assert_at_least_one_yocto();
+    }
+
+    #[test]
+    #[should_panic(expected = "Requires attached deposit of at least 1 yoctoNEAR")]
+    fn test_at_least_one_yocto_without_deposit() {
+        attach(0);
+        super::assert_at_least_one_yocto();
+    }
 
     #[test]
     fn test_log_simple() {
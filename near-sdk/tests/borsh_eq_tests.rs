@@ -0,0 +1,50 @@
+use near_sdk::borsh::BorshSerialize;
+use near_sdk::{near_bindgen, AccountId};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+#[near_bindgen(eq = "borsh", hash = "borsh")]
+#[derive(BorshSerialize, Debug)]
+struct Pair {
+    account_id: AccountId,
+    nonce: u64,
+}
+
+#[near_bindgen(eq = "borsh", hash = "borsh")]
+#[derive(BorshSerialize, Debug)]
+enum Key<T> {
+    Single(T),
+    Pair(T, T),
+}
+
+fn hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn pair(account_id: &str, nonce: u64) -> Pair {
+    Pair { account_id: account_id.parse().unwrap(), nonce }
+}
+
+#[test]
+fn equal_values() {
+    assert_eq!(pair("alice.near", 1), pair("alice.near", 1));
+    assert_eq!(hash(&pair("alice.near", 1)), hash(&pair("alice.near", 1)));
+    assert_ne!(pair("alice.near", 1), pair("alice.near", 2));
+    assert_ne!(pair("alice.near", 1), pair("bob.near", 1));
+
+    assert_eq!(Key::Single(1u8), Key::Single(1u8));
+    assert_eq!(hash(&Key::Pair(1u8, 2)), hash(&Key::Pair(1u8, 2)));
+    assert_ne!(Key::Single(1u8), Key::Pair(1u8, 1));
+}
+
+#[test]
+fn hash_set_of_values() {
+    let set: HashSet<_> =
+        [pair("alice.near", 1), pair("alice.near", 1), pair("bob.near", 1)].into_iter().collect();
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(&pair("bob.near", 1)));
+    assert!(!set.contains(&pair("bob.near", 2)));
+}
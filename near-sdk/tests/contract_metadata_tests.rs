@@ -0,0 +1,55 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::near_bindgen;
+use near_sdk::serde_json::{self, json};
+
+#[near_bindgen(contract_metadata(
+    version = "39f2d2646f2f60e18ab53337501370dc02a5661c",
+    link = "https://github.com/near/near-sdk-rs",
+    standards = ["nep141", "nep145", "nep171:1.1.0"]
+))]
+#[derive(Default, BorshDeserialize, BorshSerialize)]
+pub struct Contract {}
+
+mod minimal {
+    use super::*;
+
+    #[near_bindgen(contract_metadata())]
+    #[derive(Default, BorshDeserialize, BorshSerialize)]
+    pub enum Contract {
+        #[default]
+        V1,
+    }
+}
+
+#[test]
+fn contract_source_metadata() {
+    let metadata: serde_json::Value =
+        serde_json::from_str(Contract::CONTRACT_SOURCE_METADATA).unwrap();
+    assert_eq!(
+        metadata,
+        json!({
+            "version": "39f2d2646f2f60e18ab53337501370dc02a5661c",
+            "link": "https://github.com/near/near-sdk-rs",
+            "standards": [
+                { "standard": "nep330", "version": "1.1.0" },
+                { "standard": "nep141", "version": "1.0.0" },
+                { "standard": "nep145", "version": "1.0.0" },
+                { "standard": "nep171", "version": "1.1.0" },
+            ],
+        })
+    );
+}
+
+#[test]
+fn minimal_contract_source_metadata() {
+    let metadata: serde_json::Value =
+        serde_json::from_str(minimal::Contract::CONTRACT_SOURCE_METADATA).unwrap();
+    assert_eq!(
+        metadata,
+        json!({
+            "version": null,
+            "link": null,
+            "standards": [{ "standard": "nep330", "version": "1.1.0" }],
+        })
+    );
+}
@@ -0,0 +1,51 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::{env, near_bindgen, testing_env};
+
+#[near_bindgen]
+#[derive(Default, BorshDeserialize, BorshSerialize)]
+pub struct Counter {
+    count: u32,
+}
+
+#[near_bindgen]
+impl Counter {
+    #[deny_empty_args]
+    pub fn increment(&mut self) {
+        self.count += 1;
+    }
+}
+
+/// Sets up a context where the contract is called with the given input.
+fn call_context(input: &[u8]) {
+    let mut context = VMContextBuilder::new().build();
+    context.input = input.to_vec();
+    testing_env!(context);
+}
+
+fn count() -> u32 {
+    env::state_read::<Counter>().unwrap().count
+}
+
+#[test]
+fn empty_input_is_accepted() {
+    call_context(b"");
+    increment();
+    assert_eq!(count(), 1);
+}
+
+#[test]
+fn empty_json_object_is_accepted() {
+    call_context(b"{}");
+    increment();
+    call_context(b" { } ");
+    increment();
+    assert_eq!(count(), 2);
+}
+
+#[test]
+#[should_panic(expected = "Method increment doesn't accept arguments")]
+fn unexpected_args_are_rejected() {
+    call_context(br#"{"by": 2}"#);
+    increment();
+}
@@ -1,4 +1,4 @@
-use near_sdk::test_utils::get_logs;
+use near_sdk::test_utils::{get_events, get_logs, EventLog};
 use near_sdk::{near_bindgen, AccountId};
 
 #[near_bindgen(event_json(standard = "test_standard", random = "random"), other_random)]
@@ -32,6 +32,30 @@ mod private {
     }
 }
 
+#[near_bindgen(event_json(standard = "versioned_standard", version = "1.2.0"))]
+pub enum VersionedEvents {
+    DefaultVersion,
+
+    #[event_version("2.0.0")]
+    OverriddenVersion {
+        value: u64,
+    },
+}
+
+#[near_bindgen(event_json(standard = "js_standard", version = "1.0.0"))]
+#[serde(rename_all = "camelCase")]
+pub enum CamelCaseEvents {
+    #[serde(rename_all = "camelCase")]
+    TokensMinted { owner_id: AccountId, token_ids: Vec<String> },
+}
+
+#[near_bindgen(event_json(standard = "derived_standard", version = "1.0.0"))]
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DerivedEvents {
+    CustomName,
+}
+
 #[test]
 fn test_json_emit() {
     let token_in: AccountId = "wrap.near".parse().unwrap();
@@ -78,3 +102,109 @@ fn test_json_emit() {
         r#"EVENT_JSON:{"standard":"another_standard","version":"1.0.0","event":"test"}"#
     );
 }
+
+#[test]
+fn test_enum_level_version() {
+    VersionedEvents::DefaultVersion.emit();
+    VersionedEvents::OverriddenVersion { value: 1 }.emit();
+
+    let logs = get_logs();
+
+    assert_eq!(
+        logs[0],
+        r#"EVENT_JSON:{"standard":"versioned_standard","version":"1.2.0","event":"default_version"}"#
+    );
+    // The variant level version takes precedence over the enum level one.
+    assert_eq!(
+        logs[1],
+        r#"EVENT_JSON:{"standard":"versioned_standard","version":"2.0.0","event":"overridden_version","data":{"value":1}}"#
+    );
+}
+
+#[test]
+fn test_json_field_order() {
+    VersionedEvents::OverriddenVersion { value: 1 }.emit();
+
+    let logs = get_logs();
+    let json = logs[0].strip_prefix("EVENT_JSON:").unwrap();
+    let positions: Vec<usize> = ["\"standard\"", "\"version\"", "\"event\"", "\"data\""]
+        .iter()
+        .map(|field| json.find(field).unwrap())
+        .collect();
+
+    // NEP-297 requires the fields in this order.
+    assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[test]
+fn test_serde_container_attributes() {
+    CamelCaseEvents::TokensMinted {
+        owner_id: "alice.near".parse().unwrap(),
+        token_ids: vec!["1".to_string()],
+    }
+    .emit();
+    DerivedEvents::CustomName.emit();
+
+    let logs = get_logs();
+
+    assert_eq!(
+        logs[0],
+        r#"EVENT_JSON:{"standard":"js_standard","version":"1.0.0","event":"tokensMinted","data":{"ownerId":"alice.near","tokenIds":["1"]}}"#
+    );
+    assert_eq!(
+        logs[1],
+        r#"EVENT_JSON:{"standard":"derived_standard","version":"1.0.0","event":"CUSTOM_NAME"}"#
+    );
+}
+
+#[test]
+fn test_get_events() {
+    #[derive(near_sdk::serde::Deserialize, Debug, PartialEq)]
+    #[serde(crate = "near_sdk::serde")]
+    struct Swap {
+        token_in: AccountId,
+        token_out: AccountId,
+        amount_in: u128,
+        amount_out: u128,
+        test: String,
+    }
+
+    let swap = Swap {
+        token_in: "wrap.near".parse().unwrap(),
+        token_out: "test.near".parse().unwrap(),
+        amount_in: 100,
+        amount_out: 200,
+        test: "tst".to_string(),
+    };
+    TestEvents::Swap {
+        token_in: swap.token_in.clone(),
+        token_out: swap.token_out.clone(),
+        amount_in: swap.amount_in,
+        amount_out: swap.amount_out,
+        test: swap.test.clone(),
+    }
+    .emit();
+    near_sdk::env::log_str("not an event");
+    VersionedEvents::OverriddenVersion { value: 7 }.emit();
+    TestEvents::EmptyEvent::<String>.emit();
+
+    let events = get_events();
+    assert_eq!(events.len(), 3);
+    assert_eq!(
+        (events[0].standard.as_str(), events[0].version.as_str(), events[0].event.as_str()),
+        ("test_standard", "1.0.0", "swap")
+    );
+    assert_eq!(events[0].parse_data::<Swap>().unwrap(), swap);
+    assert_eq!(
+        events[1],
+        EventLog {
+            standard: "versioned_standard".to_string(),
+            version: "2.0.0".to_string(),
+            event: "overridden_version".to_string(),
+            data: Some(near_sdk::serde_json::json!({ "value": 7 })),
+        }
+    );
+    assert_eq!(events[2].event, "empty_event");
+    assert_eq!(events[2].data, None);
+    assert!(events[2].parse_data::<()>().is_ok());
+}
@@ -0,0 +1,74 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::serde_json::{self, json};
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::{env, near_bindgen, testing_env, AccountId};
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldCounter {
+    count: u32,
+}
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize, Debug, PartialEq, Eq)]
+pub struct Counter {
+    owner: AccountId,
+    count: u64,
+    label: String,
+}
+
+impl Default for Counter {
+    fn default() -> Self {
+        Self { owner: env::current_account_id(), count: 0, label: String::new() }
+    }
+}
+
+#[near_bindgen]
+impl Counter {
+    #[migrate(from = OldCounter)]
+    pub fn migrate(old: OldCounter, label: String) -> Self {
+        Self { owner: env::current_account_id(), count: u64::from(old.count), label }
+    }
+}
+
+/// Sets up a context where the contract calls its `migrate` method with the given label.
+fn migration_context(label: &str) {
+    let account_id: AccountId = "counter.near".parse().unwrap();
+    let mut context = VMContextBuilder::new()
+        .current_account_id(account_id.clone())
+        .predecessor_account_id(account_id)
+        .build();
+    context.input = serde_json::to_vec(&json!({ "label": label })).unwrap();
+    testing_env!(context);
+}
+
+#[test]
+fn migrate_changed_layout() {
+    migration_context("visits");
+    env::state_write(&OldCounter { count: 7 });
+
+    migrate();
+
+    let state = env::storage_read(b"STATE").unwrap();
+    assert_eq!(
+        Counter::try_from_slice(&state).unwrap(),
+        Counter { owner: "counter.near".parse().unwrap(), count: 7, label: "visits".to_string() }
+    );
+}
+
+#[test]
+#[should_panic(expected = "The contract has no state to migrate")]
+fn migrate_without_state() {
+    migration_context("visits");
+    migrate();
+}
+
+#[test]
+#[should_panic(
+    expected = "The contract state is not the state to migrate from, it may have been migrated already"
+)]
+fn migrate_twice() {
+    migration_context("visits");
+    env::state_write(&OldCounter { count: 7 });
+    migrate();
+    migrate();
+}
@@ -0,0 +1,78 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::serde_json::{self, json};
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::{env, near_bindgen, testing_env, AccountId};
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Incrementer {
+    value: u32,
+    owner: AccountId,
+}
+
+impl Default for Incrementer {
+    fn default() -> Self {
+        Self { value: 0, owner: "owner.near".parse().unwrap() }
+    }
+}
+
+#[near_bindgen]
+impl Incrementer {
+    #[private(allow = ["alice.near", "bob.near"])]
+    pub fn inc_by_allowed(&mut self, by: u32) {
+        self.value += by;
+    }
+
+    #[private(allow = "owner")]
+    pub fn inc_by_owner(&mut self, by: u32) {
+        self.value += by;
+    }
+}
+
+/// Sets up a context where `predecessor` calls the contract with `{ "by": 1 }`.
+fn call_context(predecessor: &str) {
+    let mut context = VMContextBuilder::new()
+        .current_account_id("incrementer.near".parse().unwrap())
+        .predecessor_account_id(predecessor.parse().unwrap())
+        .build();
+    context.input = serde_json::to_vec(&json!({ "by": 1 })).unwrap();
+    testing_env!(context);
+}
+
+fn value() -> u32 {
+    env::state_read::<Incrementer>().unwrap().value
+}
+
+#[test]
+fn allowed_accounts_can_call() {
+    call_context("alice.near");
+    inc_by_allowed();
+    call_context("bob.near");
+    inc_by_allowed();
+    call_context("incrementer.near");
+    inc_by_allowed();
+    assert_eq!(value(), 3);
+}
+
+#[test]
+#[should_panic(expected = "Method inc_by_allowed is private")]
+fn other_accounts_are_rejected() {
+    call_context("stranger.near");
+    inc_by_allowed();
+}
+
+#[test]
+fn allowed_field_can_call() {
+    call_context("owner.near");
+    inc_by_owner();
+    call_context("incrementer.near");
+    inc_by_owner();
+    assert_eq!(value(), 2);
+}
+
+#[test]
+#[should_panic(expected = "Method inc_by_owner is private")]
+fn other_accounts_than_field_are_rejected() {
+    call_context("stranger.near");
+    inc_by_owner();
+}
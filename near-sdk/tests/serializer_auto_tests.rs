@@ -0,0 +1,50 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::serde_json::{self, json};
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::{env, near_bindgen, testing_env};
+
+#[near_bindgen]
+#[derive(Default, BorshDeserialize, BorshSerialize)]
+pub struct Greeter {
+    greeting: String,
+    count: u32,
+}
+
+#[near_bindgen]
+impl Greeter {
+    #[serializer(auto)]
+    pub fn set_greeting(&mut self, greeting: String, count: u32) {
+        self.greeting = greeting;
+        self.count = count;
+    }
+}
+
+/// Sets up a context where the contract is called with the given input.
+fn call_context(input: Vec<u8>) {
+    let mut context = VMContextBuilder::new().build();
+    context.input = input;
+    testing_env!(context);
+}
+
+fn state() -> (String, u32) {
+    let Greeter { greeting, count } = env::state_read().unwrap();
+    (greeting, count)
+}
+
+#[test]
+fn json_and_borsh_inputs() {
+    call_context(serde_json::to_vec(&json!({ "greeting": "hello", "count": 1 })).unwrap());
+    set_greeting();
+    assert_eq!(state(), ("hello".to_string(), 1));
+
+    call_context(("hi".to_string(), 2u32).try_to_vec().unwrap());
+    set_greeting();
+    assert_eq!(state(), ("hi".to_string(), 2));
+}
+
+#[test]
+#[should_panic(expected = "Failed to deserialize input from Borsh or JSON.")]
+fn invalid_input() {
+    call_context(b"{\"greeting\": 1}".to_vec());
+    set_greeting();
+}
@@ -0,0 +1,44 @@
+use near_sdk::borsh::BorshSerialize;
+use near_sdk::store::LookupMap;
+use near_sdk::{BorshStorageKey, IntoStorageKey};
+
+#[derive(IntoStorageKey)]
+enum StorageKey {
+    Accounts,
+    Balances,
+    Metadata,
+}
+
+#[derive(BorshSerialize, BorshStorageKey)]
+enum BorshKey {
+    Accounts,
+    Balances,
+    Metadata,
+}
+
+#[test]
+fn single_byte_prefixes() {
+    let keys = [StorageKey::Accounts, StorageKey::Balances, StorageKey::Metadata]
+        .map(IntoStorageKey::into_storage_key);
+    assert_eq!(keys, [vec![0], vec![1], vec![2]]);
+}
+
+#[test]
+fn same_keys_as_borsh() {
+    assert_eq!(StorageKey::Accounts.into_storage_key(), BorshKey::Accounts.into_storage_key());
+    assert_eq!(StorageKey::Balances.into_storage_key(), BorshKey::Balances.into_storage_key());
+    assert_eq!(StorageKey::Metadata.into_storage_key(), BorshKey::Metadata.into_storage_key());
+}
+
+#[test]
+fn distinct_collections() {
+    let mut accounts = LookupMap::new(StorageKey::Accounts);
+    let mut balances = LookupMap::new(StorageKey::Balances);
+    accounts.insert(1u8, "alice".to_string());
+    balances.insert(1u8, 100u128);
+    accounts.flush();
+    balances.flush();
+
+    assert_eq!(accounts.get(&1).map(String::as_str), Some("alice"));
+    assert_eq!(balances.get(&1), Some(&100));
+}